@@ -1,15 +1,21 @@
+use std::sync::Arc;
+
 use clap::Parser;
 use comfy_table::{Cell, ContentArrangement, presets};
 use miden_client::{
-    Client, ZERO,
-    account::{Account, AccountId, AccountType, StorageSlot},
+    Client, Felt, Word, ZERO,
+    account::{Account, AccountId, AccountStateSelector, AccountType, RelatedData, StorageSlot},
     asset::Asset,
-    rpc::{NodeRpcClient, TonicRpcClient},
+    rpc::{
+        NodeRpcClient, TonicRpcClient,
+        middleware::{MiddlewareRpcClient, RetryPolicy},
+    },
 };
 use miden_objects::PrettyPrint;
 
 use crate::{
     CLIENT_BINARY_NAME,
+    commands::new_transactions::print_account_delta,
     config::CliConfig,
     create_dynamic_table,
     errors::CliError,
@@ -39,10 +45,32 @@ pub struct AccountCmd {
     /// account to the provided ID.
     #[arg(short, long, group = "action", value_name = "ID")]
     default: Option<Option<String>>,
+    /// List every stored state of the account for the specified ID or hex prefix.
+    #[arg(long, group = "action", value_name = "ID")]
+    history: Option<String>,
+    /// Show the changes between two stored states of the account for the specified ID or hex
+    /// prefix.
+    #[arg(long, group = "action", value_name = "ID", requires = "from")]
+    diff: Option<String>,
+    /// When using --diff, nonce or commitment of the state to diff from.
+    #[arg(long, requires = "diff", value_name = "NONCE_OR_COMMITMENT")]
+    from: Option<String>,
+    /// When using --diff, nonce or commitment of the state to diff to. Defaults to the latest
+    /// state.
+    #[arg(long, requires = "diff", value_name = "NONCE_OR_COMMITMENT")]
+    to: Option<String>,
+    /// Stop tracking the account for the specified ID or hex prefix, removing its states from the
+    /// store.
+    #[arg(long, group = "action", value_name = "ID")]
+    remove: Option<String>,
+    /// When using --remove, also remove the transactions executed by the account and the notes
+    /// that only relate to them.
+    #[arg(long, requires = "remove")]
+    with_related: bool,
 }
 
 impl AccountCmd {
-    pub async fn execute(&self, client: Client) -> Result<(), CliError> {
+    pub async fn execute(&self, mut client: Client) -> Result<(), CliError> {
         let (cli_config, _) = load_config_file()?;
         match self {
             AccountCmd {
@@ -87,6 +115,18 @@ impl AccountCmd {
                     },
                 }
             },
+            AccountCmd { history: Some(id), .. } => {
+                let account_id = parse_account_id(&client, id).await?;
+                show_account_history(&client, account_id).await?;
+            },
+            AccountCmd { diff: Some(id), from: Some(from), .. } => {
+                let account_id = parse_account_id(&client, id).await?;
+                show_account_diff(&client, account_id, from, self.to.as_deref()).await?;
+            },
+            AccountCmd { remove: Some(id), .. } => {
+                let account_id = parse_account_id(&client, id).await?;
+                remove_account(&mut client, account_id, &cli_config, self.with_related).await?;
+            },
             _ => {
                 list_accounts(client, &cli_config).await?;
             },
@@ -125,6 +165,85 @@ async fn list_accounts(client: Client, cli_config: &CliConfig) -> Result<(), Cli
     Ok(())
 }
 
+// ACCOUNT HISTORY
+// ================================================================================================
+
+async fn show_account_history(client: &Client, account_id: AccountId) -> Result<(), CliError> {
+    let history = client.get_account_history(account_id).await?;
+    if history.is_empty() {
+        return Err(CliError::Input(format!("Account {account_id} is not tracked")));
+    }
+
+    let mut table = create_dynamic_table(&["Nonce", "Commitment", "Vault Root", "Storage Root"]);
+    for account_header in history {
+        table.add_row(vec![
+            account_header.nonce().as_int().to_string(),
+            account_header.commitment().to_hex(),
+            account_header.vault_root().to_hex(),
+            account_header.storage_commitment().to_hex(),
+        ]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+async fn show_account_diff(
+    client: &Client,
+    account_id: AccountId,
+    from: &str,
+    to: Option<&str>,
+) -> Result<(), CliError> {
+    let from = parse_account_state(from)?;
+    let to = match to {
+        Some(to) => parse_account_state(to)?,
+        None => AccountStateSelector::Commitment(
+            client.try_get_account_header(account_id).await?.0.commitment(),
+        ),
+    };
+
+    let account_delta = client.get_account_state_diff(account_id, from, to).await?;
+
+    println!("Changes to account {account_id} between the states with {from} and {to}:");
+    print_account_delta(&account_delta)
+}
+
+/// Parses a hex commitment (starting with `0x`) or a nonce into an [`AccountStateSelector`].
+fn parse_account_state(arg: &str) -> Result<AccountStateSelector, CliError> {
+    if arg.starts_with("0x") {
+        let commitment = Word::try_from(arg).map_err(|err| {
+            CliError::Parse(err.into(), "failed to parse account state commitment".to_string())
+        })?;
+        return Ok(AccountStateSelector::Commitment(commitment));
+    }
+
+    let nonce = arg.parse::<u64>().map_err(|err| {
+        CliError::Parse(err.into(), "failed to parse account state nonce".to_string())
+    })?;
+    Ok(AccountStateSelector::Nonce(Felt::new(nonce)))
+}
+
+// REMOVE ACCOUNT
+// ================================================================================================
+
+async fn remove_account(
+    client: &mut Client,
+    account_id: AccountId,
+    cli_config: &CliConfig,
+    with_related: bool,
+) -> Result<(), CliError> {
+    client.remove_account(account_id, RelatedData::from(with_related)).await?;
+    let removed_entries = client.garbage_collect().await?;
+
+    if cli_config.default_account_id.as_deref() == Some(account_id.to_hex().as_str()) {
+        set_default_account(None)?;
+        println!("Removing default account...");
+    }
+
+    println!("Account {account_id} removed. Cleaned up {removed_entries} unused store entries.");
+    Ok(())
+}
+
 // SHOW ACCOUNT
 // ================================================================================================
 
@@ -140,8 +259,11 @@ pub async fn show_account(
         let bech32_id = account_id.to_bech32(cli_config.rpc.endpoint.0.to_network_id()?);
         println!("Account {bech32_id} is not tracked by the client. Fetching from the network...",);
 
-        let rpc_client =
-            TonicRpcClient::new(&cli_config.rpc.endpoint.clone().into(), cli_config.rpc.timeout_ms);
+        let rpc_client = MiddlewareRpcClient::new(Arc::new(TonicRpcClient::new(
+            &cli_config.rpc.endpoint.clone().into(),
+            cli_config.rpc.timeout_ms,
+        )))
+        .with_middleware(Arc::new(RetryPolicy::new(cli_config.rpc.max_retries)));
 
         let fetched_account = rpc_client.get_account_details(account_id).await.map_err(|_| {
             CliError::Input(format!(
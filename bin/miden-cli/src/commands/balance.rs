@@ -0,0 +1,76 @@
+use clap::Parser;
+use miden_client::{Client, account::AccountBalance};
+
+use crate::{
+    create_dynamic_table,
+    errors::CliError,
+    faucet_details_map::FaucetDetailsMap,
+    utils::{get_input_acc_id_by_prefix_or_default, load_faucet_details_map},
+};
+
+// BALANCE COMMAND
+// ================================================================================================
+
+#[derive(Debug, Clone, Parser)]
+#[command(
+    about = "View the fungible balances of an account, including the amounts in notes that are \
+pending to be consumed by the account or to be committed after being sent by it"
+)]
+pub struct BalanceCmd {
+    /// Account ID or a prefix of it. If not provided, the default account is used.
+    #[arg(short = 'a', long = "account")]
+    account_id: Option<String>,
+
+    /// Only show the balance for this faucet, given as its account ID, its address or a token
+    /// symbol tracked in the token symbol map file.
+    #[arg(short, long)]
+    faucet: Option<String>,
+}
+
+impl BalanceCmd {
+    pub async fn execute(&self, client: Client) -> Result<(), CliError> {
+        let account_id =
+            get_input_acc_id_by_prefix_or_default(&client, self.account_id.clone()).await?;
+        let faucet_details_map = load_faucet_details_map()?;
+
+        let balances = if let Some(faucet) = &self.faucet {
+            let faucet_id = faucet_details_map.parse_faucet_id(&client, faucet).await?;
+            vec![client.get_balance(account_id, faucet_id).await?]
+        } else {
+            client.get_balances(account_id).await?
+        };
+
+        if balances.is_empty() {
+            println!("Account {account_id} holds no fungible assets.");
+            return Ok(());
+        }
+
+        let mut table =
+            create_dynamic_table(&["Faucet", "Amount", "Pending Incoming", "Pending Outgoing"]);
+        for balance in &balances {
+            table.add_row(balance_row(&faucet_details_map, balance)?);
+        }
+
+        println!("{table}");
+        Ok(())
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the table row for the balance, with the amounts formatted using the token's decimals
+/// if the faucet is tracked by the token symbol map file.
+fn balance_row(
+    faucet_details_map: &FaucetDetailsMap,
+    balance: &AccountBalance,
+) -> Result<Vec<String>, CliError> {
+    let faucet_id = balance.faucet_id();
+    let (faucet, amount) = faucet_details_map.format_amount(&faucet_id, balance.amount())?;
+    let (_, pending_incoming) =
+        faucet_details_map.format_amount(&faucet_id, balance.pending_incoming())?;
+    let (_, pending_outgoing) =
+        faucet_details_map.format_amount(&faucet_id, balance.pending_outgoing())?;
+
+    Ok(vec![faucet, amount, pending_incoming, pending_outgoing])
+}
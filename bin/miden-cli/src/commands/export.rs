@@ -3,6 +3,7 @@ use std::{fs::File, io::Write, path::PathBuf};
 use miden_client::{
     Client, Word,
     account::{Account, AccountFile},
+    note::{EncryptedNoteFile, NoteDecryptionKey, NoteEncryptionKey},
     store::NoteExportType,
     transaction::AccountInterface,
     utils::Serializable,
@@ -36,6 +37,12 @@ pub struct ExportCmd {
     /// Exported note type.
     #[arg(short, long, value_enum, conflicts_with = "account")]
     export_type: Option<ExportType>,
+
+    /// Encrypt the exported note so that only its recipient can import it. Takes either the note
+    /// encryption key of the recipient (as listed by `keys --list`) or the ID of a tracked account
+    /// whose keys are in the keystore.
+    #[arg(long, value_name = "account/pubkey", conflicts_with = "account")]
+    encrypt_to: Option<String>,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -60,7 +67,19 @@ impl ExportCmd {
         if self.account {
             export_account(&client, &keystore, self.id.as_str(), self.filename.clone()).await?;
         } else if let Some(export_type) = &self.export_type {
-            export_note(&mut client, self.id.as_str(), self.filename.clone(), export_type).await?;
+            let recipient = match &self.encrypt_to {
+                Some(recipient) => Some(parse_encryption_key(&client, &keystore, recipient).await?),
+                None => None,
+            };
+
+            export_note(
+                &mut client,
+                self.id.as_str(),
+                self.filename.clone(),
+                export_type,
+                recipient,
+            )
+            .await?;
         } else {
             return Err(CliError::Export(
                 "Export type is required when exporting a note".to_string(),
@@ -95,6 +114,7 @@ async fn export_account(
         key_pairs.push(
             keystore
                 .get_key(pub_key)
+                .await
                 .map_err(CliError::KeyStore)?
                 .ok_or(CliError::Export("Auth not found for account".to_string()))?,
         );
@@ -125,6 +145,7 @@ async fn export_note(
     note_id: &str,
     filename: Option<PathBuf>,
     export_type: &ExportType,
+    recipient: Option<NoteEncryptionKey>,
 ) -> Result<File, CliError> {
     let note_id = get_output_note_with_id_prefix(client, note_id)
         .await
@@ -150,12 +171,51 @@ async fn export_note(
 
     info!("Writing file to {}", file_path.to_string_lossy());
     let mut file = File::create(file_path)?;
-    file.write_all(&note_file.to_bytes()).map_err(CliError::IO)?;
+    if let Some(recipient) = recipient {
+        let encrypted_file = EncryptedNoteFile::encrypt(&note_file, recipient, client.rng())
+            .map_err(|err| CliError::Export(err.to_string()))?;
+        file.write_all(&encrypted_file.to_bytes()).map_err(CliError::IO)?;
+
+        println!("Successfully exported note {note_id}, encrypted to {recipient}");
+    } else {
+        file.write_all(&note_file.to_bytes()).map_err(CliError::IO)?;
+
+        println!("Successfully exported note {note_id}");
+    }
 
-    println!("Successfully exported note {note_id}");
     Ok(file)
 }
 
+/// Parses the recipient of an encrypted note file, given either as a note encryption key or as
+/// the ID (or a valid prefix) of a tracked account. In the latter case, the encryption key is
+/// derived from the first of the account's keys found in the keystore.
+async fn parse_encryption_key(
+    client: &Client,
+    keystore: &CliKeyStore,
+    recipient: &str,
+) -> Result<NoteEncryptionKey, CliError> {
+    if let Ok(encryption_key) = NoteEncryptionKey::from_hex(recipient) {
+        return Ok(encryption_key);
+    }
+
+    let account_id = parse_account_id(client, recipient).await?;
+    let account = client
+        .get_account(account_id)
+        .await?
+        .ok_or(CliError::Export(format!("Account with ID {account_id} not found")))?;
+
+    for pub_key in get_public_keys_from_account(account.account()) {
+        if let Some(key) = keystore.get_key(pub_key).await.map_err(CliError::KeyStore)? {
+            return Ok(NoteDecryptionKey::from_auth_secret_key(&key).encryption_key());
+        }
+    }
+
+    Err(CliError::Export(format!(
+        "None of the keys of account {account_id} are in the keystore, use the note encryption key \
+        of the recipient instead"
+    )))
+}
+
 /// Gets the public key from the storage of an account. This will only work if the account is
 /// created by the CLI as it expects the account to have the `RpoFalcon512` authentication scheme.
 pub fn get_public_keys_from_account(account: &Account) -> Vec<Word> {
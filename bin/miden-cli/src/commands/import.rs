@@ -7,7 +7,7 @@ use std::{
 use miden_client::{
     Client, ClientError,
     account::{AccountFile, AccountId},
-    note::NoteFile,
+    note::{EncryptedNoteFile, NoteFile},
     utils::Deserializable,
 };
 use tracing::info;
@@ -18,7 +18,10 @@ use crate::{
 };
 
 #[derive(Debug, Parser, Clone)]
-#[command(about = "Import notes or accounts")]
+#[command(
+    about = "Import notes or accounts. Encrypted note files are decrypted with the keys in the \
+keystore"
+)]
 pub struct ImportCmd {
     /// Paths to the files that contains the account/note data.
     #[arg()]
@@ -33,7 +36,10 @@ impl ImportCmd {
         validate_paths(&self.filenames)?;
         let (mut current_config, _) = load_config_file()?;
         for filename in &self.filenames {
-            let note_file = read_note_file(filename.clone());
+            let note_file = match read_encrypted_note_file(&keystore, filename).await? {
+                Some(note_file) => Ok(note_file),
+                None => read_note_file(filename.clone()),
+            };
 
             if let Ok(note_file) = note_file {
                 let note_id = client.import_note(note_file).await?;
@@ -78,7 +84,7 @@ async fn import_account(
     let account_id = account_data.account.id();
 
     for key in account_data.auth_secret_keys {
-        keystore.add_key(&key).map_err(CliError::KeyStore)?;
+        keystore.add_key(&key).await.map_err(CliError::KeyStore)?;
     }
 
     client
@@ -99,6 +105,37 @@ fn read_note_file(filename: PathBuf) -> Result<NoteFile, CliError> {
         .map_err(|err| CliError::Client(ClientError::DataDeserializationError(err)))
 }
 
+/// Reads and decrypts an [`EncryptedNoteFile`] with the note decryption key derived from the
+/// keystore key it's addressed to. Returns `None` if the file isn't an encrypted note file.
+async fn read_encrypted_note_file(
+    keystore: &CliKeyStore,
+    filename: &PathBuf,
+) -> Result<Option<NoteFile>, CliError> {
+    let contents = fs::read(filename)?;
+    let Ok(encrypted_file) = EncryptedNoteFile::read_from_bytes(&contents) else {
+        return Ok(None);
+    };
+
+    let recipient = encrypted_file.recipient();
+    let decryption_key = keystore
+        .get_note_decryption_key(&recipient)
+        .await
+        .map_err(CliError::KeyStore)?
+        .ok_or_else(|| {
+            CliError::Import(format!(
+                "The note file `{}` is encrypted to {recipient}, which doesn't match any key in \
+                the keystore",
+                filename.display()
+            ))
+        })?;
+
+    let note_file = encrypted_file
+        .decrypt(&decryption_key)
+        .map_err(|err| CliError::Import(err.to_string()))?;
+
+    Ok(Some(note_file))
+}
+
 // HELPERS
 // ================================================================================================
 
@@ -0,0 +1,197 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use miden_client::{
+    Client, Word,
+    account::AccountId,
+    keystore::{KeyStoreError, decrypt_exported_keys, encrypt_exported_keys},
+    note::NoteDecryptionKey,
+};
+use tracing::info;
+
+use crate::{
+    CliKeyStore, Parser, commands::export::get_public_keys_from_account, create_dynamic_table,
+    errors::CliError, keystore::KEYS_EXPORT_PASSPHRASE_ENV_VAR, load_config_file,
+};
+
+#[derive(Default, Debug, Parser, Clone)]
+#[command(about = "View and manage the keys in the keystore. Defaults to `list` command")]
+pub struct KeysCmd {
+    /// List all keys in the keystore along with the tracked accounts that use them and the note
+    /// encryption keys derived from them.
+    #[arg(short, long, group = "action")]
+    list: bool,
+
+    /// Remove the key with the provided public key (as a hex string) from the keystore.
+    #[arg(short, long, group = "action", value_name = "pub_key")]
+    remove: Option<String>,
+
+    /// Export all keys in the keystore to the provided file, encrypted with the passphrase in the
+    /// `MIDEN_KEYS_EXPORT_PASSPHRASE` environment variable.
+    #[arg(short, long, group = "action", value_name = "file")]
+    export: Option<PathBuf>,
+
+    /// Import the keys from a file created with `--export`, decrypted with the passphrase in the
+    /// `MIDEN_KEYS_EXPORT_PASSPHRASE` environment variable.
+    #[arg(short, long, group = "action", value_name = "file")]
+    import: Option<PathBuf>,
+
+    /// Remove the key even if it's used by a tracked account.
+    #[arg(long, requires = "remove")]
+    force: bool,
+}
+
+impl KeysCmd {
+    pub async fn execute(&self, client: Client, keystore: CliKeyStore) -> Result<(), CliError> {
+        match self {
+            KeysCmd { remove: Some(pub_key), force, .. } => {
+                remove_key(&client, &keystore, pub_key, *force).await?;
+            },
+            KeysCmd { export: Some(filename), .. } => {
+                export_keys(&keystore, filename).await?;
+            },
+            KeysCmd { import: Some(filename), .. } => {
+                import_keys(&keystore, filename).await?;
+            },
+            _ => {
+                list_keys(&client, &keystore).await?;
+            },
+        }
+        Ok(())
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+async fn list_keys(client: &Client, keystore: &CliKeyStore) -> Result<(), CliError> {
+    let (cli_config, _) = load_config_file()?;
+
+    let accounts_by_key = accounts_by_public_key(client).await?;
+    let mut table = create_dynamic_table(&["Public Key", "Note Encryption Key", "Accounts"]);
+
+    for pub_key in keystore.list_keys().await.map_err(CliError::KeyStore)? {
+        let mut accounts = vec![];
+        for account_id in accounts_by_key.get(&pub_key).into_iter().flatten() {
+            accounts.push(account_id.to_bech32(cli_config.rpc.endpoint.0.to_network_id()?));
+        }
+
+        // Keystores that don't expose their secret keys, such as external signers, can't derive
+        // note encryption keys
+        let encryption_key = match keystore.get_key(pub_key).await {
+            Ok(Some(key)) => {
+                NoteDecryptionKey::from_auth_secret_key(&key).encryption_key().to_hex()
+            },
+            Ok(None) | Err(KeyStoreError::UnsupportedOperation(_)) => "-".to_string(),
+            Err(err) => return Err(CliError::KeyStore(err)),
+        };
+
+        let accounts = if accounts.is_empty() {
+            "-".to_string()
+        } else {
+            accounts.join(", ")
+        };
+        table.add_row(vec![pub_key.to_hex(), encryption_key, accounts]);
+    }
+
+    println!("\n{table}");
+
+    Ok(())
+}
+
+async fn remove_key(
+    client: &Client,
+    keystore: &CliKeyStore,
+    pub_key: &str,
+    force: bool,
+) -> Result<(), CliError> {
+    let pub_key = Word::try_from(pub_key)
+        .map_err(|err| CliError::Parse(err.into(), "failed to parse public key".to_string()))?;
+
+    if !force {
+        if let Some(accounts) = accounts_by_public_key(client).await?.get(&pub_key) {
+            return Err(CliError::InvalidArgument(format!(
+                "key is used by {} tracked account(s), use --force to remove it anyway",
+                accounts.len()
+            )));
+        }
+    }
+
+    keystore.remove_key(pub_key).await.map_err(CliError::KeyStore)?;
+    println!("Key {} removed", pub_key.to_hex());
+
+    Ok(())
+}
+
+async fn export_keys(keystore: &CliKeyStore, filename: &PathBuf) -> Result<(), CliError> {
+    let keys = keystore.export_keys().await.map_err(CliError::KeyStore)?;
+    if keys.is_empty() {
+        // Keystores backed by an external signer never export their keys
+        println!("The keystore has no keys that can be exported");
+        return Ok(());
+    }
+
+    let passphrase = export_passphrase()?;
+    let contents =
+        encrypt_exported_keys(&keys, &passphrase, &mut rand::rng()).map_err(CliError::KeyStore)?;
+
+    info!("Writing file to {}", filename.to_string_lossy());
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // The file contains secret keys, so only its owner should be able to read it
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(filename)?.write_all(&contents)?;
+
+    println!("Exported {} keys to {}", keys.len(), filename.to_string_lossy());
+
+    Ok(())
+}
+
+async fn import_keys(keystore: &CliKeyStore, filename: &PathBuf) -> Result<(), CliError> {
+    let passphrase = export_passphrase()?;
+    let contents = fs::read(filename)?;
+    let keys = decrypt_exported_keys(&contents, &passphrase).map_err(CliError::KeyStore)?;
+
+    keystore.import_keys(&keys).await.map_err(CliError::KeyStore)?;
+    println!("Imported {} keys", keys.len());
+
+    Ok(())
+}
+
+/// Returns the passphrase used to encrypt and decrypt exported keys.
+fn export_passphrase() -> Result<String, CliError> {
+    env::var(KEYS_EXPORT_PASSPHRASE_ENV_VAR).map_err(|_| {
+        CliError::Config(
+            "Exported keys".to_string().into(),
+            format!(
+                "Exported keys are encrypted, set the {KEYS_EXPORT_PASSPHRASE_ENV_VAR} environment variable to the passphrase to use"
+            ),
+        )
+    })
+}
+
+/// Maps the public keys of the auth components of every tracked account to the accounts that
+/// use them.
+async fn accounts_by_public_key(
+    client: &Client,
+) -> Result<BTreeMap<Word, Vec<AccountId>>, CliError> {
+    let mut accounts_by_key: BTreeMap<Word, Vec<AccountId>> = BTreeMap::new();
+
+    for (header, _) in client.get_account_headers().await? {
+        let Some(record) = client.get_account(header.id()).await? else {
+            continue;
+        };
+
+        for pub_key in get_public_keys_from_account(record.account()) {
+            accounts_by_key.entry(pub_key).or_default().push(header.id());
+        }
+    }
+
+    Ok(accounts_by_key)
+}
@@ -0,0 +1,47 @@
+use clap::{ArgGroup, Parser};
+
+use crate::{
+    errors::CliError,
+    keystore::{initialize_encrypted_keystore, migrate_plaintext_keys},
+    utils::load_config_file,
+};
+
+// KEYSTORE COMMAND
+// ================================================================================================
+
+#[derive(Debug, Clone, Parser)]
+#[command(
+    about = "Set up the encrypted keystore, using the passphrase in the \
+`MIDEN_KEYSTORE_PASSPHRASE` environment variable",
+    group = ArgGroup::new("action").required(true)
+)]
+pub struct KeystoreCmd {
+    /// Initialize the encrypted keystore with the passphrase. This must be done once before the
+    /// keystore can be used.
+    #[arg(long, group = "action")]
+    init: bool,
+
+    /// Encrypt the plaintext keys left in the keystore directory, such as the ones created before
+    /// encryption was enabled.
+    #[arg(long, group = "action")]
+    migrate: bool,
+}
+
+impl KeystoreCmd {
+    pub fn execute(&self) -> Result<(), CliError> {
+        let (cli_config, _) = load_config_file()?;
+
+        if self.init {
+            initialize_encrypted_keystore(&cli_config)?;
+            println!(
+                "Initialized the encrypted keystore at {}.",
+                cli_config.secret_keys_directory.display()
+            );
+        } else if self.migrate {
+            let migrated = migrate_plaintext_keys(&cli_config)?;
+            println!("Encrypted {migrated} plaintext keys.");
+        }
+
+        Ok(())
+    }
+}
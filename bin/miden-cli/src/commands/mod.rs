@@ -1,11 +1,16 @@
 pub mod account;
+pub mod balance;
 pub mod exec;
 pub mod export;
 pub mod import;
 pub mod init;
+pub mod keys;
+pub mod keystore;
 pub mod new_account;
 pub mod new_transactions;
 pub mod notes;
+pub mod sign;
+pub mod snapshot;
 pub mod sync;
 pub mod tags;
 pub mod transactions;
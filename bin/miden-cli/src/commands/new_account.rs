@@ -285,6 +285,7 @@ async fn create_client_account(
 
     keystore
         .add_key(&AuthSecretKey::RpoFalcon512(key_pair))
+        .await
         .map_err(CliError::KeyStore)?;
 
     client.add_account(&account, Some(seed), false).await?;
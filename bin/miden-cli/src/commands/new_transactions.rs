@@ -1,16 +1,22 @@
-use std::{io, sync::Arc};
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use clap::{Parser, ValueEnum};
 use miden_client::{
     Client, RemoteTransactionProver,
-    account::AccountId,
+    account::{AccountDelta, AccountId},
     asset::{FungibleAsset, NonFungibleDeltaAction},
     note::{BlockNumber, NoteType as MidenNoteType, build_swap_tag, get_input_note_with_id_prefix},
     store::NoteRecordError,
     transaction::{
-        InputNote, OutputNote, PaymentNoteDescription, SwapTransactionData, TransactionRequest,
-        TransactionRequestBuilder, TransactionResult,
+        InputNote, OutputNote, PaymentNoteDescription, SwapTransactionData, TransactionId,
+        TransactionRequest, TransactionRequestBuilder, TransactionResult,
     },
+    utils::Serializable,
 };
 use tracing::info;
 
@@ -58,6 +64,11 @@ pub struct MintCmd {
     /// Flag to delegate proving to the remote prover specified in the config file.
     #[arg(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Instead of executing the transaction, write everything needed to execute and sign it on an
+    /// offline machine to this file. The file can then be signed with the `sign` command.
+    #[arg(long, value_name = "file", conflicts_with_all = ["force", "delegate_proving"])]
+    offline: Option<PathBuf>,
 }
 
 impl MintCmd {
@@ -86,6 +97,7 @@ impl MintCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.offline.as_ref(),
         )
         .await
     }
@@ -126,6 +138,11 @@ pub struct SendCmd {
     /// Flag to delegate proving to the remote prover specified in the config file
     #[arg(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Instead of executing the transaction, write everything needed to execute and sign it on an
+    /// offline machine to this file. The file can then be signed with the `sign` command.
+    #[arg(long, value_name = "file", conflicts_with_all = ["force", "delegate_proving"])]
+    offline: Option<PathBuf>,
 }
 
 impl SendCmd {
@@ -169,6 +186,7 @@ impl SendCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.offline.as_ref(),
         )
         .await
     }
@@ -199,6 +217,11 @@ pub struct SwapCmd {
     /// Flag to delegate proving to the remote prover specified in the config file.
     #[arg(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Instead of executing the transaction, write everything needed to execute and sign it on an
+    /// offline machine to this file. The file can then be signed with the `sign` command.
+    #[arg(long, value_name = "file", conflicts_with_all = ["force", "delegate_proving"])]
+    offline: Option<PathBuf>,
 }
 
 impl SwapCmd {
@@ -234,6 +257,7 @@ impl SwapCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.offline.as_ref(),
         )
         .await?;
 
@@ -270,6 +294,11 @@ pub struct ConsumeNotesCmd {
     /// Flag to delegate proving to the remote prover specified in the config file.
     #[arg(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Instead of executing the transaction, write everything needed to execute and sign it on an
+    /// offline machine to this file. The file can then be signed with the `sign` command.
+    #[arg(long, value_name = "file", conflicts_with_all = ["force", "delegate_proving"])]
+    offline: Option<PathBuf>,
 }
 
 impl ConsumeNotesCmd {
@@ -333,6 +362,7 @@ impl ConsumeNotesCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.offline.as_ref(),
         )
         .await
     }
@@ -347,7 +377,12 @@ async fn execute_transaction(
     transaction_request: TransactionRequest,
     force: bool,
     delegated_proving: bool,
+    offline_file: Option<&PathBuf>,
 ) -> Result<(), CliError> {
+    if let Some(filename) = offline_file {
+        return export_offline_transaction(client, account_id, transaction_request, filename).await;
+    }
+
     println!("Executing transaction...");
     let transaction_execution_result =
         client.new_transaction(account_id, transaction_request).await?;
@@ -376,21 +411,15 @@ async fn execute_transaction(
         .map(OutputNote::id)
         .collect::<Vec<_>>();
 
-    if delegated_proving {
-        let (cli_config, _) = load_config_file()?;
-        let remote_prover_endpoint =
-            cli_config.remote_prover_endpoint.as_ref().ok_or(CliError::Config(
-                "Remote prover endpoint".to_string().into(),
-                "remote prover endpoint is not set in the configuration file".to_string(),
-            ))?;
-
-        let remote_prover =
-            Arc::new(RemoteTransactionProver::new(remote_prover_endpoint.to_string()));
-        client
-            .submit_transaction_with_prover(transaction_execution_result, remote_prover)
-            .await?;
-    } else {
-        client.submit_transaction(transaction_execution_result).await?;
+    // Store the transaction before proving it, so that it can be resumed with the `tx` command
+    // if proving or submitting fails
+    client.store_pending_transaction(transaction_execution_result).await?;
+
+    if let Err(err) = prove_and_submit(client, transaction_id, delegated_proving).await {
+        println!(
+            "The transaction was stored as pending, resume it with `miden-client tx --submit {transaction_id}`"
+        );
+        return Err(err);
     }
 
     println!("Successfully created transaction.");
@@ -408,6 +437,55 @@ async fn execute_transaction(
     Ok(())
 }
 
+async fn prove_and_submit(
+    client: &mut Client,
+    transaction_id: TransactionId,
+    delegated_proving: bool,
+) -> Result<(), CliError> {
+    if delegated_proving {
+        client
+            .prove_pending_transaction_with_prover(transaction_id, load_remote_prover()?)
+            .await?;
+    }
+
+    client.submit_pending_transaction(transaction_id).await?;
+
+    Ok(())
+}
+
+async fn export_offline_transaction(
+    client: &mut Client,
+    account_id: AccountId,
+    transaction_request: TransactionRequest,
+    filename: &PathBuf,
+) -> Result<(), CliError> {
+    let offline_transaction =
+        client.export_offline_transaction(account_id, transaction_request).await?;
+
+    info!("Writing file to {}", filename.to_string_lossy());
+    let mut file = File::create(filename)?;
+    file.write_all(&offline_transaction.to_bytes())?;
+
+    println!("Exported offline transaction to {}", filename.to_string_lossy());
+    println!(
+        "Sign it on the offline machine with `miden-client sign {}`",
+        filename.to_string_lossy()
+    );
+    Ok(())
+}
+
+/// Instantiates the remote prover specified in the config file.
+pub(crate) fn load_remote_prover() -> Result<Arc<RemoteTransactionProver>, CliError> {
+    let (cli_config, _) = load_config_file()?;
+    let remote_prover_endpoint =
+        cli_config.remote_prover_endpoint.as_ref().ok_or(CliError::Config(
+            "Remote prover endpoint".to_string().into(),
+            "remote prover endpoint is not set in the configuration file".to_string(),
+        ))?;
+
+    Ok(Arc::new(RemoteTransactionProver::new(remote_prover_endpoint.to_string())))
+}
+
 fn print_transaction_details(transaction_result: &TransactionResult) -> Result<(), CliError> {
     println!("The transaction will have the following effects:\n");
 
@@ -443,8 +521,11 @@ fn print_transaction_details(transaction_result: &TransactionResult) -> Result<(
         transaction_result.executed_transaction().account_id()
     );
 
-    let account_delta = transaction_result.account_delta();
+    print_account_delta(transaction_result.account_delta())
+}
 
+/// Prints the storage, vault and nonce changes of an account delta.
+pub(crate) fn print_account_delta(account_delta: &AccountDelta) -> Result<(), CliError> {
     let has_storage_changes = !account_delta.storage().is_empty();
     if has_storage_changes {
         let mut table = create_dynamic_table(&["Storage Slot", "Effect"]);
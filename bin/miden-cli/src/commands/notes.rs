@@ -1,12 +1,16 @@
 use clap::ValueEnum;
 use comfy_table::{Attribute, Cell, ContentArrangement, Table, presets};
 use miden_client::{
-    Client, ClientError, IdPrefixFetchError,
+    Client, ClientError, IdPrefixFetchError, Word,
     asset::Asset,
     note::{
-        NoteConsumability, NoteInputs, NoteMetadata, WellKnownNote, get_input_note_with_id_prefix,
+        NoteConsumability, NoteInputs, NoteMetadata, NoteTag, WellKnownNote,
+        get_input_note_with_id_prefix,
+    },
+    store::{
+        InputNoteRecord, NoteFilter as ClientNoteFilter, NoteSort, NoteSortKey, OutputNoteRecord,
+        Page,
     },
-    store::{InputNoteRecord, NoteFilter as ClientNoteFilter, OutputNoteRecord},
 };
 use miden_objects::PrettyPrint;
 
@@ -42,6 +46,24 @@ impl TryInto<ClientNoteFilter> for NoteFilter {
     }
 }
 
+/// Field by which the listed notes are sorted.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum NoteSortField {
+    Id,
+    Block,
+    CreatedAt,
+}
+
+impl From<&NoteSortField> for NoteSortKey {
+    fn from(field: &NoteSortField) -> Self {
+        match field {
+            NoteSortField::Id => NoteSortKey::Id,
+            NoteSortField::Block => NoteSortKey::BlockNum,
+            NoteSortField::CreatedAt => NoteSortKey::CreatedAt,
+        }
+    }
+}
+
 #[derive(Debug, Parser, Clone)]
 #[command(about = "View and manage notes")]
 pub struct NotesCmd {
@@ -58,6 +80,65 @@ pub struct NotesCmd {
     /// consumable by this account will be shown.
     #[arg(short, long, value_name = "account_id")]
     account_id: Option<String>,
+
+    /// When listing, only show the input notes consumed by this account.
+    #[arg(long, value_name = "account_id")]
+    consumer: Option<String>,
+
+    /// When listing, only show the P2ID and P2IDE notes targeting this account.
+    #[arg(long, value_name = "account_id")]
+    target: Option<String>,
+
+    /// When listing, only show the notes sent by this account.
+    #[arg(long, value_name = "account_id")]
+    sender: Option<String>,
+
+    /// When listing, only show the notes with this tag.
+    #[arg(long, value_name = "tag")]
+    tag: Option<u32>,
+
+    /// When listing, only show the notes holding assets from this faucet, given as its account
+    /// ID, its address or a token symbol tracked in the token symbol map file.
+    #[arg(long, value_name = "faucet")]
+    faucet: Option<String>,
+
+    /// When listing, only show the notes whose script has this root.
+    #[arg(long, value_name = "script_root")]
+    script_root: Option<String>,
+
+    /// When listing, only show the input notes created or imported at this time or later, given
+    /// as a Unix timestamp in seconds.
+    #[arg(long, value_name = "timestamp")]
+    created_after: Option<u64>,
+
+    /// When listing, only show the input notes created or imported at this time or earlier,
+    /// given as a Unix timestamp in seconds.
+    #[arg(long, value_name = "timestamp")]
+    created_before: Option<u64>,
+
+    /// When listing, only show the notes included in this block or a later one.
+    #[arg(long, value_name = "block_num")]
+    from_block: Option<u32>,
+
+    /// When listing, only show the notes included in this block or an earlier one.
+    #[arg(long, value_name = "block_num")]
+    to_block: Option<u32>,
+
+    /// When listing, sort the notes by this field.
+    #[arg(long, value_name = "field")]
+    sort: Option<NoteSortField>,
+
+    /// When listing, sort the notes in descending order.
+    #[arg(long)]
+    desc: bool,
+
+    /// When listing, show at most this number of input notes and of output notes.
+    #[arg(long, value_name = "limit")]
+    limit: Option<usize>,
+
+    /// When listing, skip this number of input notes and of output notes.
+    #[arg(long, value_name = "offset")]
+    offset: Option<usize>,
 }
 
 impl NotesCmd {
@@ -69,6 +150,7 @@ impl NotesCmd {
             NotesCmd { list: Some(filter), .. } => {
                 list_notes(
                     client,
+                    self,
                     filter.clone().try_into().expect("Filter shouldn't be consumable"),
                 )
                 .await?;
@@ -77,11 +159,89 @@ impl NotesCmd {
                 show_note(client, id.to_owned(), self.with_code).await?;
             },
             _ => {
-                list_notes(client, ClientNoteFilter::All).await?;
+                list_notes(client, self, ClientNoteFilter::All).await?;
             },
         }
         Ok(())
     }
+
+    /// Builds the filter for the notes to list out of the state filter and the provided options.
+    async fn note_filter(
+        &self,
+        client: &Client,
+        state_filter: ClientNoteFilter,
+    ) -> Result<ClientNoteFilter, CliError> {
+        let mut filters = Vec::new();
+        if !matches!(state_filter, ClientNoteFilter::All) {
+            filters.push(state_filter);
+        }
+
+        if let Some(account_id) = &self.consumer {
+            filters.push(ClientNoteFilter::Consumer(parse_account_id(client, account_id).await?));
+        }
+
+        if let Some(account_id) = &self.target {
+            filters.push(ClientNoteFilter::Target(parse_account_id(client, account_id).await?));
+        }
+
+        if let Some(account_id) = &self.sender {
+            filters.push(ClientNoteFilter::Sender(parse_account_id(client, account_id).await?));
+        }
+
+        if let Some(tag) = self.tag {
+            filters.push(ClientNoteFilter::Tag(NoteTag::from(tag)));
+        }
+
+        if let Some(faucet) = &self.faucet {
+            let faucet_id = load_faucet_details_map()?.parse_faucet_id(client, faucet).await?;
+            filters.push(ClientNoteFilter::Faucet(faucet_id));
+        }
+
+        if let Some(script_root) = &self.script_root {
+            let script_root = Word::try_from(script_root.as_str()).map_err(|err| {
+                CliError::Parse(err.into(), "failed to parse script root".to_string())
+            })?;
+            filters.push(ClientNoteFilter::ScriptRoot(script_root));
+        }
+
+        if self.created_after.is_some() || self.created_before.is_some() {
+            filters.push(ClientNoteFilter::CreatedBetween {
+                from: self.created_after.unwrap_or(0),
+                to: self.created_before.unwrap_or(u64::MAX),
+            });
+        }
+
+        if self.from_block.is_some() || self.to_block.is_some() {
+            filters.push(ClientNoteFilter::BlockRange {
+                from: self.from_block.unwrap_or(0).into(),
+                to: self.to_block.unwrap_or(u32::MAX).into(),
+            });
+        }
+
+        Ok(match filters.len() {
+            0 => ClientNoteFilter::All,
+            1 => filters.remove(0),
+            _ => ClientNoteFilter::And(filters),
+        })
+    }
+
+    /// Returns the page of notes to list, or `None` if neither sorting nor pagination options
+    /// were provided.
+    fn note_page(&self) -> Option<(NoteSort, Page)> {
+        if self.sort.is_none() && !self.desc && self.limit.is_none() && self.offset.is_none() {
+            return None;
+        }
+
+        let key = self.sort.as_ref().map(NoteSortKey::from).unwrap_or_default();
+        let sort = if self.desc {
+            NoteSort::descending(key)
+        } else {
+            NoteSort::ascending(key)
+        };
+        let page = Page::new(self.offset.unwrap_or(0), self.limit.unwrap_or(usize::MAX));
+
+        Some((sort, page))
+    }
 }
 
 struct CliNoteSummary {
@@ -99,16 +259,30 @@ struct CliNoteSummary {
 
 // LIST NOTES
 // ================================================================================================
-async fn list_notes(client: Client, filter: ClientNoteFilter) -> Result<(), CliError> {
-    let input_notes = client
-        .get_input_notes(filter.clone())
-        .await?
+async fn list_notes(
+    client: Client,
+    cmd: &NotesCmd,
+    state_filter: ClientNoteFilter,
+) -> Result<(), CliError> {
+    let filter = cmd.note_filter(&client, state_filter).await?;
+
+    let (input_notes, output_notes) = if let Some((sort, page)) = cmd.note_page() {
+        (
+            client.get_input_notes_page(filter.clone(), sort, page).await?,
+            client.get_output_notes_page(filter, sort, page).await?,
+        )
+    } else {
+        (
+            client.get_input_notes(filter.clone()).await?,
+            client.get_output_notes(filter).await?,
+        )
+    };
+
+    let input_notes = input_notes
         .into_iter()
         .map(|input_note_record| note_summary(Some(&input_note_record), None))
         .collect::<Vec<CliNoteSummary>>();
-    let output_notes = client
-        .get_output_notes(filter.clone())
-        .await?
+    let output_notes = output_notes
         .into_iter()
         .map(|output_note_record| note_summary(None, Some(&output_note_record)))
         .collect::<Vec<CliNoteSummary>>();
@@ -0,0 +1,80 @@
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::PathBuf,
+    sync::Arc,
+};
+
+use clap::Parser;
+use miden_client::{
+    DebugMode,
+    transaction::{
+        LocalTransactionProver, OfflineTransaction, OfflineTransactionExecutor,
+        PendingTransactionRecord, PendingTransactionStage, ProvingOptions,
+    },
+    utils::{Deserializable, Serializable},
+};
+use tracing::info;
+
+use crate::{CliKeyStore, errors::CliError, utils::load_config_file};
+
+// SIGN COMMAND
+// ================================================================================================
+
+#[derive(Debug, Clone, Parser)]
+#[command(
+    about = "Execute and sign a transaction exported with the `--offline` flag, without connecting \
+to the network or opening the store. The resulting file can be imported with `tx --import` and \
+submitted with `tx --submit`"
+)]
+pub struct SignCmd {
+    /// File created by a transaction command with the `--offline` flag.
+    filename: PathBuf,
+
+    /// File to write the signed transaction to.
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Only execute and sign the transaction, leaving the proving to the client that submits it.
+    #[arg(long, default_value_t = false)]
+    skip_proving: bool,
+}
+
+impl SignCmd {
+    pub async fn execute(&self, in_debug_mode: DebugMode) -> Result<(), CliError> {
+        let contents = fs::read(&self.filename)?;
+        let offline_transaction =
+            OfflineTransaction::read_from_bytes(&contents).map_err(|err| {
+                CliError::Parse(
+                    err.into(),
+                    format!("failed to read offline transaction from {}", self.filename.display()),
+                )
+            })?;
+
+        let (cli_config, _) = load_config_file()?;
+        let keystore = CliKeyStore::from_config(&cli_config)?;
+        let executor =
+            OfflineTransactionExecutor::new(keystore.inner()).in_debug_mode(in_debug_mode);
+
+        println!("Executing transaction...");
+        let record = if self.skip_proving {
+            let tx_result = executor.execute(offline_transaction).await?;
+            PendingTransactionRecord::new(tx_result, PendingTransactionStage::Executed)
+        } else {
+            println!("Proving transaction...");
+            let tx_prover = Arc::new(LocalTransactionProver::new(ProvingOptions::default()));
+            executor.execute_and_prove(offline_transaction, tx_prover).await?
+        };
+
+        info!("Writing file to {}", self.output.to_string_lossy());
+        let mut file = File::create(&self.output)?;
+        file.write_all(&record.to_bytes())?;
+
+        println!(
+            "Signed transaction {} written to {}",
+            record.id(),
+            self.output.to_string_lossy()
+        );
+        Ok(())
+    }
+}
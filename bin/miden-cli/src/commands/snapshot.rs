@@ -0,0 +1,86 @@
+use std::{fs, path::PathBuf};
+
+use miden_client::{
+    Client, ClientError,
+    store::StoreSnapshot,
+    utils::{Deserializable, Serializable},
+};
+use tracing::info;
+
+use crate::{Parser, errors::CliError};
+
+// BACKUP COMMAND
+// ================================================================================================
+
+#[derive(Debug, Parser, Clone)]
+#[command(
+    about = "Write a snapshot of the store (accounts, notes, transactions, tags and chain data) to \
+a file. Keys in the keystore are not included and need to be exported separately"
+)]
+pub struct BackupCmd {
+    /// Path of the file the snapshot is written to.
+    filename: PathBuf,
+}
+
+impl BackupCmd {
+    pub async fn execute(&self, client: Client) -> Result<(), CliError> {
+        if self.filename.exists() {
+            return Err(CliError::Export(format!(
+                "The file `{}` already exists",
+                self.filename.display()
+            )));
+        }
+
+        let snapshot = client.export_snapshot().await?;
+        fs::write(&self.filename, snapshot.to_bytes())?;
+
+        info!("Store snapshot written to {}", self.filename.display());
+        println!(
+            "Backed up {} accounts, {} input notes, {} output notes and {} transactions at block {} to {}",
+            snapshot.accounts().len(),
+            snapshot.input_notes().len(),
+            snapshot.output_notes().len(),
+            snapshot.transactions().len(),
+            snapshot.sync_height(),
+            self.filename.display()
+        );
+
+        Ok(())
+    }
+}
+
+// RESTORE COMMAND
+// ================================================================================================
+
+#[derive(Debug, Parser, Clone)]
+#[command(
+    about = "Restore a store snapshot, written by the `backup` command or exported by the web \
+client, into an empty store"
+)]
+pub struct RestoreCmd {
+    /// Path of the snapshot file.
+    filename: PathBuf,
+}
+
+impl RestoreCmd {
+    pub async fn execute(&self, mut client: Client) -> Result<(), CliError> {
+        let contents = fs::read(&self.filename).map_err(|err| {
+            CliError::Import(format!("Failed to read `{}`: {err}", self.filename.display()))
+        })?;
+        let snapshot = StoreSnapshot::read_from_bytes(&contents)
+            .map_err(|err| CliError::Client(ClientError::DataDeserializationError(err)))?;
+
+        client.import_snapshot(&snapshot).await?;
+
+        println!(
+            "Restored {} accounts, {} input notes, {} output notes and {} transactions at block {}",
+            snapshot.accounts().len(),
+            snapshot.input_notes().len(),
+            snapshot.output_notes().len(),
+            snapshot.transactions().len(),
+            snapshot.sync_height()
+        );
+
+        Ok(())
+    }
+}
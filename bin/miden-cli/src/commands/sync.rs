@@ -1,14 +1,28 @@
+use std::sync::Arc;
+
 use clap::Parser;
-use miden_client::Client;
+use miden_client::{
+    Client,
+    sync::{SyncEvent, SyncListener},
+};
 
 use crate::errors::CliError;
 
 #[derive(Debug, Parser, Clone)]
 #[command(about = "Sync this client with the latest state of the Miden network")]
-pub struct SyncCmd {}
+pub struct SyncCmd {
+    /// Print the changes detected while syncing as they happen.
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
 
 impl SyncCmd {
     pub async fn execute(&self, mut client: Client) -> Result<(), CliError> {
+        if self.verbose {
+            let listener: Arc<dyn SyncListener> = Arc::new(print_sync_event);
+            client.add_sync_listener(listener);
+        }
+
         let new_details = client.sync_state().await?;
 
         println!("State synced to block {}", new_details.block_num);
@@ -21,3 +35,37 @@ impl SyncCmd {
         Ok(())
     }
 }
+
+// HELPERS
+// ================================================================================================
+
+/// Prints a single line describing the provided [`SyncEvent`].
+fn print_sync_event(event: &SyncEvent) {
+    match event {
+        SyncEvent::StepStarted { block_num } => {
+            println!("Requesting updates from block {block_num}");
+        },
+        SyncEvent::StepCompleted { block_num, chain_tip } => {
+            println!("Synced block {block_num} (chain tip: {chain_tip})");
+        },
+        SyncEvent::NoteCommitted { note_id, block_num } => {
+            println!("  Note {note_id} committed in block {block_num}");
+        },
+        SyncEvent::NullifierConsumed { nullifier, block_num } => {
+            println!("  Nullifier {} consumed in block {block_num}", nullifier.to_hex());
+        },
+        SyncEvent::AccountUpdated { account_id } => {
+            println!("  Account {account_id} updated");
+        },
+        SyncEvent::AccountLocked { account_id } => {
+            println!("  Account {account_id} has a mismatched commitment and may be locked");
+        },
+        SyncEvent::TransactionCommitted { transaction_id, block_num } => {
+            println!("  Transaction {transaction_id} committed in block {block_num}");
+        },
+        SyncEvent::TransactionDiscarded { transaction_id, cause } => {
+            println!("  Transaction {transaction_id} discarded ({cause})");
+        },
+        SyncEvent::MmrPeaksAdvanced { .. } => {},
+    }
+}
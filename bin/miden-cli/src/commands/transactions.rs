@@ -1,6 +1,54 @@
-use miden_client::{Client, store::TransactionFilter, transaction::TransactionRecord};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::PathBuf,
+};
 
-use crate::{Parser, create_dynamic_table, errors::CliError};
+use clap::ValueEnum;
+use miden_client::{
+    Client, Word,
+    note::{NoteId, get_input_note_with_id_prefix},
+    store::{Page, TransactionFilter},
+    transaction::{
+        DiscardCause, PendingTransactionRecord, PendingTransactionStage, TransactionId,
+        TransactionRecord,
+    },
+    utils::{Deserializable, Serializable},
+};
+use tracing::info;
+
+use crate::{
+    Parser, commands::new_transactions::load_remote_prover, create_dynamic_table, errors::CliError,
+    get_output_note_with_id_prefix, utils::parse_account_id,
+};
+
+/// Status of the transactions to list.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum TransactionStatusFilter {
+    Pending,
+    Committed,
+    Discarded,
+}
+
+/// Cause for which the listed transactions were discarded.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum DiscardCauseFilter {
+    Expired,
+    InputConsumed,
+    DiscardedInitialState,
+    Stale,
+}
+
+impl From<&DiscardCauseFilter> for DiscardCause {
+    fn from(cause: &DiscardCauseFilter) -> Self {
+        match cause {
+            DiscardCauseFilter::Expired => DiscardCause::Expired,
+            DiscardCauseFilter::InputConsumed => DiscardCause::InputConsumed,
+            DiscardCauseFilter::DiscardedInitialState => DiscardCause::DiscardedInitialState,
+            DiscardCauseFilter::Stale => DiscardCause::Stale,
+        }
+    }
+}
 
 #[derive(Default, Debug, Parser, Clone)]
 #[command(about = "Manage and view transactions. Defaults to `list` command")]
@@ -8,25 +56,291 @@ pub struct TransactionCmd {
     /// List currently tracked transactions.
     #[arg(short, long, group = "action")]
     list: bool,
+
+    /// List the transactions that were stored before being fully proven and submitted.
+    #[arg(long, group = "action")]
+    pending: bool,
+
+    /// Prove the pending transaction with the provided ID and store the proof.
+    #[arg(long, group = "action", value_name = "tx_id")]
+    prove: Option<String>,
+
+    /// Submit the pending transaction with the provided ID, proving it first if needed.
+    #[arg(long, group = "action", value_name = "tx_id")]
+    submit: Option<String>,
+
+    /// Discard the pending transaction with the provided ID without submitting it.
+    #[arg(long, group = "action", value_name = "tx_id")]
+    discard: Option<String>,
+
+    /// Export the pending transaction with the provided ID, so it can be proven or submitted by
+    /// another client.
+    #[arg(long, group = "action", value_name = "tx_id", requires = "filename")]
+    export: Option<String>,
+
+    /// Import a pending transaction from a file created with `--export`.
+    #[arg(long, group = "action", value_name = "file")]
+    import: Option<PathBuf>,
+
+    /// File to write the exported pending transaction to.
+    #[arg(short, long)]
+    filename: Option<PathBuf>,
+
+    /// Delegate proving to the remote prover specified in the config file.
+    #[arg(long, default_value_t = false)]
+    delegate_proving: bool,
+
+    /// When listing, only show the transactions executed by this account.
+    #[arg(long, value_name = "account_id")]
+    account: Option<String>,
+
+    /// When listing, only show the transactions with this status.
+    #[arg(long, value_name = "status")]
+    status: Option<TransactionStatusFilter>,
+
+    /// When listing, only show the transactions discarded for this cause.
+    #[arg(long, value_name = "cause", conflicts_with = "status")]
+    discard_cause: Option<DiscardCauseFilter>,
+
+    /// When listing, only show the transactions executed against this block or a later one.
+    #[arg(long, value_name = "block_num")]
+    from_block: Option<u32>,
+
+    /// When listing, only show the transactions executed against this block or an earlier one.
+    #[arg(long, value_name = "block_num")]
+    to_block: Option<u32>,
+
+    /// When listing, only show the transactions that consumed or created this note. Accepts a
+    /// prefix of the note ID.
+    #[arg(long, value_name = "note_id")]
+    note: Option<String>,
+
+    /// When listing, only show the transactions whose script has this root.
+    #[arg(long, value_name = "script_root")]
+    script_root: Option<String>,
+
+    /// When listing, show at most this number of transactions, sorted by execution block.
+    #[arg(long, value_name = "limit")]
+    limit: Option<usize>,
+
+    /// When listing, skip this number of transactions, sorted by execution block.
+    #[arg(long, value_name = "offset")]
+    offset: Option<usize>,
 }
 
 impl TransactionCmd {
-    pub async fn execute(&self, client: Client) -> Result<(), CliError> {
-        list_transactions(client).await?;
+    pub async fn execute(&self, mut client: Client) -> Result<(), CliError> {
+        match self {
+            TransactionCmd { pending: true, .. } => {
+                list_pending_transactions(&client).await?;
+            },
+            TransactionCmd { prove: Some(tx_id), .. } => {
+                let tx_id = parse_transaction_id(tx_id)?;
+                prove_pending_transaction(&mut client, tx_id, self.delegate_proving).await?;
+                println!("Transaction {tx_id} proven");
+            },
+            TransactionCmd { submit: Some(tx_id), .. } => {
+                let tx_id = parse_transaction_id(tx_id)?;
+                submit_pending_transaction(&mut client, tx_id, self.delegate_proving).await?;
+                println!("Transaction {tx_id} submitted");
+            },
+            TransactionCmd { discard: Some(tx_id), .. } => {
+                let tx_id = parse_transaction_id(tx_id)?;
+                if client.discard_pending_transaction(tx_id).await? {
+                    println!("Transaction {tx_id} discarded");
+                } else {
+                    println!("Transaction {tx_id} isn't pending");
+                }
+            },
+            TransactionCmd {
+                export: Some(tx_id),
+                filename: Some(filename),
+                ..
+            } => {
+                export_pending_transaction(&client, tx_id, filename).await?;
+            },
+            TransactionCmd { import: Some(filename), .. } => {
+                import_pending_transaction(&client, filename).await?;
+            },
+            _ => {
+                list_transactions(client, self).await?;
+            },
+        }
         Ok(())
     }
+
+    /// Builds the filter for the transactions to list out of the provided options.
+    async fn transaction_filter(&self, client: &Client) -> Result<TransactionFilter, CliError> {
+        let mut filters = Vec::new();
+
+        if let Some(account_id) = &self.account {
+            filters.push(TransactionFilter::Account(parse_account_id(client, account_id).await?));
+        }
+
+        match (&self.status, &self.discard_cause) {
+            (Some(TransactionStatusFilter::Pending), _) => {
+                filters.push(TransactionFilter::InFlight);
+            },
+            (Some(TransactionStatusFilter::Committed), _) => {
+                filters.push(TransactionFilter::Committed);
+            },
+            (Some(TransactionStatusFilter::Discarded), _) => {
+                filters.push(TransactionFilter::Discarded(None));
+            },
+            (None, Some(cause)) => filters.push(TransactionFilter::Discarded(Some(cause.into()))),
+            (None, None) => {},
+        }
+
+        if self.from_block.is_some() || self.to_block.is_some() {
+            filters.push(TransactionFilter::BlockRange {
+                from: self.from_block.unwrap_or(0).into(),
+                to: self.to_block.unwrap_or(u32::MAX).into(),
+            });
+        }
+
+        if let Some(note_id) = &self.note {
+            filters.push(TransactionFilter::Note(parse_note_id(client, note_id).await?));
+        }
+
+        if let Some(script_root) = &self.script_root {
+            let script_root = Word::try_from(script_root.as_str()).map_err(|err| {
+                CliError::Parse(err.into(), "failed to parse script root".to_string())
+            })?;
+            filters.push(TransactionFilter::ScriptRoot(script_root));
+        }
+
+        Ok(match filters.len() {
+            0 => TransactionFilter::All,
+            1 => filters.remove(0),
+            _ => TransactionFilter::And(filters),
+        })
+    }
 }
 
 // LIST TRANSACTIONS
 // ================================================================================================
-async fn list_transactions(client: Client) -> Result<(), CliError> {
-    let transactions = client.get_transactions(TransactionFilter::All).await?;
+async fn list_transactions(client: Client, cmd: &TransactionCmd) -> Result<(), CliError> {
+    let filter = cmd.transaction_filter(&client).await?;
+
+    let transactions = if cmd.limit.is_some() || cmd.offset.is_some() {
+        let page = Page::new(cmd.offset.unwrap_or(0), cmd.limit.unwrap_or(usize::MAX));
+        client.get_transactions_page(filter, page).await?
+    } else {
+        client.get_transactions(filter).await?
+    };
+
     print_transactions_summary(&transactions);
     Ok(())
 }
 
+// PENDING TRANSACTIONS
+// ================================================================================================
+async fn list_pending_transactions(client: &Client) -> Result<(), CliError> {
+    let pending_transactions = client.get_pending_transactions().await?;
+
+    let mut table = create_dynamic_table(&["ID", "Stage", "Account ID"]);
+    for record in pending_transactions {
+        table.add_row(vec![
+            record.id().to_string(),
+            record.stage().to_string(),
+            record.account_id().to_string(),
+        ]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+async fn prove_pending_transaction(
+    client: &mut Client,
+    tx_id: TransactionId,
+    delegate_proving: bool,
+) -> Result<(), CliError> {
+    if delegate_proving {
+        client
+            .prove_pending_transaction_with_prover(tx_id, load_remote_prover()?)
+            .await?;
+    } else {
+        client.prove_pending_transaction(tx_id).await?;
+    }
+
+    Ok(())
+}
+
+async fn submit_pending_transaction(
+    client: &mut Client,
+    tx_id: TransactionId,
+    delegate_proving: bool,
+) -> Result<(), CliError> {
+    let record = client
+        .get_pending_transaction(tx_id)
+        .await?
+        .ok_or_else(|| CliError::InvalidArgument(format!("transaction {tx_id} isn't pending")))?;
+
+    // Transactions that weren't proven yet are otherwise proven locally when submitted
+    if delegate_proving && *record.stage() == PendingTransactionStage::Executed {
+        prove_pending_transaction(client, tx_id, true).await?;
+    }
+
+    client.submit_pending_transaction(tx_id).await?;
+
+    Ok(())
+}
+
+async fn export_pending_transaction(
+    client: &Client,
+    tx_id: &str,
+    filename: &PathBuf,
+) -> Result<(), CliError> {
+    let tx_id = parse_transaction_id(tx_id)?;
+    let record = client
+        .get_pending_transaction(tx_id)
+        .await?
+        .ok_or_else(|| CliError::InvalidArgument(format!("transaction {tx_id} isn't pending")))?;
+
+    info!("Writing file to {}", filename.to_string_lossy());
+    let mut file = File::create(filename)?;
+    file.write_all(&record.to_bytes())?;
+
+    println!("Exported transaction {tx_id} to {}", filename.to_string_lossy());
+    Ok(())
+}
+
+async fn import_pending_transaction(client: &Client, filename: &PathBuf) -> Result<(), CliError> {
+    let contents = fs::read(filename)?;
+    let record = PendingTransactionRecord::read_from_bytes(&contents).map_err(|err| {
+        CliError::Parse(
+            err.into(),
+            format!("failed to read transaction from {}", filename.display()),
+        )
+    })?;
+
+    let tx_id = client.import_pending_transaction(record).await?;
+    println!("Imported pending transaction {tx_id}");
+    Ok(())
+}
+
 // HELPERS
 // ================================================================================================
+fn parse_transaction_id(tx_id: &str) -> Result<TransactionId, CliError> {
+    let tx_id = Word::try_from(tx_id)
+        .map_err(|err| CliError::Parse(err.into(), "failed to parse transaction ID".to_string()))?;
+
+    Ok(tx_id.into())
+}
+
+/// Returns the ID of the input or output note whose ID starts with `note_id_prefix`.
+async fn parse_note_id(client: &Client, note_id_prefix: &str) -> Result<NoteId, CliError> {
+    if let Ok(note) = get_input_note_with_id_prefix(client, note_id_prefix).await {
+        return Ok(note.id());
+    }
+
+    get_output_note_with_id_prefix(client, note_id_prefix)
+        .await
+        .map(|note| note.id())
+        .map_err(|err| CliError::Input(err.to_string()))
+}
+
 fn print_transactions_summary<'a, I>(executed_transactions: I)
 where
     I: IntoIterator<Item = &'a TransactionRecord>,
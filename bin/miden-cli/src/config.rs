@@ -1,6 +1,7 @@
 use core::fmt::Debug;
 use std::{
     fmt::Display,
+    num::NonZeroU32,
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -39,6 +40,17 @@ pub struct CliConfig {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     pub max_block_number_delta: Option<u32>,
+    /// Whether the secret keys are encrypted at rest. The passphrase is read from the
+    /// `MIDEN_KEYSTORE_PASSPHRASE` environment variable.
+    #[serde(default)]
+    pub encrypted_keystore: bool,
+    /// Path to the Unix socket of an external signer. If set, transactions are signed by the
+    /// external signer and the secret keys directory isn't used.
+    #[serde(default)]
+    pub external_signer_socket: Option<PathBuf>,
+    /// Timeout for the requests sent to the external signer, in milliseconds.
+    #[serde(default = "default_external_signer_timeout_ms")]
+    pub external_signer_timeout_ms: u64,
 }
 
 // Make `ClientConfig` a provider itself for composability.
@@ -74,6 +86,9 @@ impl Default for CliConfig {
             remote_prover_endpoint: None,
             component_template_directory: Path::new(DEFAULT_COMPONENT_TEMPLATE_DIR).to_path_buf(),
             max_block_number_delta: None,
+            encrypted_keystore: false,
+            external_signer_socket: None,
+            external_signer_timeout_ms: default_external_signer_timeout_ms(),
         }
     }
 }
@@ -88,6 +103,18 @@ pub struct RpcConfig {
     pub endpoint: CliEndpoint,
     /// Timeout for the RPC api requests, in milliseconds.
     pub timeout_ms: u64,
+    /// Maximum number of times a request that fails with a transient error (such as the node
+    /// being unavailable) is retried.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Maximum number of requests sent to the node per second. If not set, requests aren't rate
+    /// limited.
+    #[serde(default)]
+    pub max_requests_per_second: Option<NonZeroU32>,
+    /// Whether to print the number of requests sent to each endpoint and their timing after every
+    /// command.
+    #[serde(default)]
+    pub print_metrics: bool,
 }
 
 impl Default for RpcConfig {
@@ -95,10 +122,21 @@ impl Default for RpcConfig {
         Self {
             endpoint: Endpoint::default().into(),
             timeout_ms: 10000,
+            max_retries: default_max_retries(),
+            max_requests_per_second: None,
+            print_metrics: false,
         }
     }
 }
 
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_external_signer_timeout_ms() -> u64 {
+    30_000
+}
+
 // CLI ENDPOINT
 // ================================================================================================
 
@@ -26,7 +26,7 @@ pub enum CliError {
     Asset(#[source] AssetError),
     #[error("client error")]
     #[diagnostic(code(cli::client_error))]
-    Client(#[from] ClientError),
+    Client(#[source] ClientError),
     #[error("config error: {1}")]
     #[diagnostic(
         code(cli::config_error),
@@ -74,4 +74,29 @@ pub enum CliError {
     #[error("transaction error: {1}")]
     #[diagnostic(code(cli::transaction_error))]
     Transaction(#[source] SourceError, String),
+    #[error("transaction rejected by the node")]
+    #[diagnostic(code(cli::transaction_rejected))]
+    TransactionRejected(#[source] ClientError, #[help] String),
+}
+
+impl From<ClientError> for CliError {
+    fn from(err: ClientError) -> Self {
+        let help = match &err {
+            ClientError::TransactionInputNotesAlreadyConsumed(_) => format!(
+                "Another transaction consumed some of the input notes first. Run `{CLIENT_BINARY_NAME} sync` to update the state of the notes and consume other notes."
+            ),
+            ClientError::TransactionAccountStateStale(_) => format!(
+                "The account changed after the transaction was executed. Run `{CLIENT_BINARY_NAME} sync` and execute the transaction again."
+            ),
+            ClientError::TransactionExpired(_) => format!(
+                "The transaction wasn't submitted before its expiration block or its reference block is too old. Run `{CLIENT_BINARY_NAME} sync` and execute the transaction again."
+            ),
+            ClientError::TransactionRejected(_) => {
+                "The node considers the transaction invalid, check the error details.".to_string()
+            },
+            _ => return Self::Client(err),
+        };
+
+        Self::TransactionRejected(err, help)
+    }
 }
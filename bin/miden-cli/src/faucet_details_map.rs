@@ -72,6 +72,26 @@ impl FaucetDetailsMap {
         self.get_token_symbol(faucet_id).unwrap_or("Unknown".to_string())
     }
 
+    /// Parses a string representing a faucet, which can be its account ID, its address or a token
+    /// symbol tracked in the token symbol map file.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if `arg` isn't a valid faucet ID or address and the token symbol isn't
+    /// present in the token symbol map file.
+    pub async fn parse_faucet_id(&self, client: &Client, arg: &str) -> Result<AccountId, CliError> {
+        if let Ok(id) = parse_account_id(client, arg).await {
+            return Ok(id);
+        }
+
+        let FaucetDetails { id, .. } = self.0.get(arg).ok_or(CliError::Config(
+            "Token symbol not found in the map file".to_string().into(),
+            arg.to_string(),
+        ))?;
+
+        parse_account_id(client, id).await
+    }
+
     /// Parses a string representing a [`FungibleAsset`]. There are two accepted formats for the
     /// string:
     /// - `<AMOUNT>::<FAUCET_ID>` where `<AMOUNT>` is in the faucet base units and `<FAUCET_ID>` is
@@ -132,7 +152,17 @@ impl FaucetDetailsMap {
         &self,
         asset: &FungibleAsset,
     ) -> Result<(String, String), CliError> {
-        if let Some(token_symbol) = self.get_token_symbol(&asset.faucet_id()) {
+        self.format_amount(&asset.faucet_id(), asset.amount())
+    }
+
+    /// Formats an amount of the faucet's asset in the same way as
+    /// [`FaucetDetailsMap::format_fungible_asset`].
+    pub fn format_amount(
+        &self,
+        faucet_id: &AccountId,
+        amount: u64,
+    ) -> Result<(String, String), CliError> {
+        if let Some(token_symbol) = self.get_token_symbol(faucet_id) {
             let decimals = self
                 .0
                 .get(&token_symbol)
@@ -141,15 +171,15 @@ impl FaucetDetailsMap {
                     token_symbol.clone(),
                 ))?
                 .decimals;
-            let amount = format_amount_from_faucet_units(asset.amount(), decimals);
+            let amount = format_amount_from_faucet_units(amount, decimals);
 
             Ok((token_symbol, amount))
         } else {
             let (cli_config, _) = load_config_file()?;
 
             Ok((
-                asset.faucet_id().to_bech32(cli_config.rpc.endpoint.0.to_network_id()?),
-                asset.amount().to_string(),
+                faucet_id.to_bech32(cli_config.rpc.endpoint.0.to_network_id()?),
+                amount.to_string(),
             ))
         }
     }
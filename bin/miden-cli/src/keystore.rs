@@ -0,0 +1,158 @@
+use std::{env, ops::Deref, path::PathBuf, sync::Arc};
+
+use miden_client::keystore::{EncryptedFilesystemKeyStore, FilesystemKeyStore, KeyStore};
+#[cfg(unix)]
+use miden_client::keystore::{ExternalSignerKeyStore, UnixSocketTransport};
+
+use crate::{config::CliConfig, errors::CliError};
+
+/// Environment variable that holds the passphrase used to unlock an encrypted keystore.
+pub const KEYSTORE_PASSPHRASE_ENV_VAR: &str = "MIDEN_KEYSTORE_PASSPHRASE";
+
+/// Environment variable that holds the passphrase used to encrypt and decrypt exported keys.
+pub const KEYS_EXPORT_PASSPHRASE_ENV_VAR: &str = "MIDEN_KEYS_EXPORT_PASSPHRASE";
+
+// CLI KEYSTORE
+// ================================================================================================
+
+/// The keystore used by the CLI. The commands program against the [`KeyStore`] trait, and the
+/// actual implementation is picked from the configuration file:
+///
+/// - If `external_signer_socket` is set, signing is delegated to the signer listening on that
+///   socket.
+/// - If `encrypted_keystore` is set, keys are encrypted at rest.
+/// - Otherwise, keys are stored in plaintext.
+#[derive(Clone)]
+pub struct CliKeyStore(Arc<dyn KeyStore>);
+
+impl CliKeyStore {
+    /// Opens the keystore described by the CLI configuration.
+    pub fn from_config(cli_config: &CliConfig) -> Result<Self, CliError> {
+        if let Some(socket_path) = &cli_config.external_signer_socket {
+            #[cfg(unix)]
+            {
+                let transport = UnixSocketTransport::new(socket_path.clone()).timeout(
+                    std::time::Duration::from_millis(cli_config.external_signer_timeout_ms),
+                );
+                return Ok(Self(Arc::new(ExternalSignerKeyStore::new(transport))));
+            }
+
+            #[cfg(not(unix))]
+            return Err(CliError::Config(
+                "External signer".to_string().into(),
+                format!(
+                    "Unix sockets aren't supported on this platform, can't connect to {}",
+                    socket_path.display()
+                ),
+            ));
+        }
+
+        Self::new(cli_config.secret_keys_directory.clone(), cli_config.encrypted_keystore)
+    }
+
+    /// Opens the keystore stored in `keys_directory`.
+    ///
+    /// If `encrypted` is set, the keystore gets unlocked with the passphrase in the
+    /// [`KEYSTORE_PASSPHRASE_ENV_VAR`] environment variable. It must have been initialized with
+    /// `keystore --init` beforehand.
+    pub fn new(keys_directory: PathBuf, encrypted: bool) -> Result<Self, CliError> {
+        if !encrypted {
+            let keystore = FilesystemKeyStore::new(keys_directory).map_err(CliError::KeyStore)?;
+            return Ok(Self(Arc::new(keystore)));
+        }
+
+        let keystore = unlock_encrypted_keystore(keys_directory)?;
+        Ok(Self(Arc::new(keystore)))
+    }
+
+    /// Returns the keystore as a trait object, to be used as the client's authenticator.
+    pub fn inner(&self) -> Arc<dyn KeyStore> {
+        self.0.clone()
+    }
+}
+
+impl Deref for CliKeyStore {
+    type Target = dyn KeyStore;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+// ENCRYPTED KEYSTORE SETUP
+// ================================================================================================
+
+/// Initializes the encrypted keystore described by the CLI configuration with the passphrase in
+/// the [`KEYSTORE_PASSPHRASE_ENV_VAR`] environment variable.
+pub fn initialize_encrypted_keystore(cli_config: &CliConfig) -> Result<(), CliError> {
+    let keys_directory = encrypted_keys_directory(cli_config)?;
+    let passphrase = keystore_passphrase()?;
+
+    let keystore =
+        EncryptedFilesystemKeyStore::new(keys_directory.clone()).map_err(CliError::KeyStore)?;
+    if keystore.is_initialized() {
+        return Err(CliError::Config(
+            "Encrypted keystore".to_string().into(),
+            format!("The keystore at {} is already initialized", keys_directory.display()),
+        ));
+    }
+
+    keystore.initialize(&passphrase).map_err(CliError::KeyStore)
+}
+
+/// Encrypts the plaintext keys left in the directory of the encrypted keystore described by the
+/// CLI configuration. Returns the number of keys that were encrypted.
+pub fn migrate_plaintext_keys(cli_config: &CliConfig) -> Result<usize, CliError> {
+    let keys_directory = encrypted_keys_directory(cli_config)?;
+    let keystore = unlock_encrypted_keystore(keys_directory.clone())?;
+
+    keystore.migrate_plaintext_keys(&keys_directory).map_err(CliError::KeyStore)
+}
+
+/// Returns the keys directory, or an error if the configuration doesn't enable the encrypted
+/// keystore.
+fn encrypted_keys_directory(cli_config: &CliConfig) -> Result<PathBuf, CliError> {
+    if !cli_config.encrypted_keystore {
+        return Err(CliError::Config(
+            "Encrypted keystore".to_string().into(),
+            "The keystore isn't encrypted, set `encrypted_keystore = true` in the configuration file first"
+                .to_string(),
+        ));
+    }
+
+    Ok(cli_config.secret_keys_directory.clone())
+}
+
+/// Opens the encrypted keystore in `keys_directory` and unlocks it.
+fn unlock_encrypted_keystore(
+    keys_directory: PathBuf,
+) -> Result<EncryptedFilesystemKeyStore, CliError> {
+    let passphrase = keystore_passphrase()?;
+
+    let keystore =
+        EncryptedFilesystemKeyStore::new(keys_directory.clone()).map_err(CliError::KeyStore)?;
+    if !keystore.is_initialized() {
+        return Err(CliError::Config(
+            "Encrypted keystore".to_string().into(),
+            format!(
+                "The keystore at {} isn't initialized, run `miden-client keystore --init` to initialize it",
+                keys_directory.display()
+            ),
+        ));
+    }
+
+    keystore.unlock(&passphrase).map_err(CliError::KeyStore)?;
+    Ok(keystore)
+}
+
+/// Reads the keystore passphrase from the [`KEYSTORE_PASSPHRASE_ENV_VAR`] environment variable.
+fn keystore_passphrase() -> Result<String, CliError> {
+    env::var(KEYSTORE_PASSPHRASE_ENV_VAR).map_err(|_| {
+        CliError::Config(
+            "Encrypted keystore".to_string().into(),
+            format!(
+                "The keystore is encrypted, set the {KEYSTORE_PASSPHRASE_ENV_VAR} environment variable to unlock it"
+            ),
+        )
+    })
+}
@@ -7,33 +7,37 @@ use miden_client::{
     Client, DebugMode, IdPrefixFetchError,
     account::AccountHeader,
     builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
+    rpc::middleware::{RateLimiter, RetryPolicy, RpcMetrics},
     store::{NoteFilter as ClientNoteFilter, OutputNoteRecord},
 };
-use rand::rngs::StdRng;
 mod commands;
 use commands::{
     account::AccountCmd,
+    balance::BalanceCmd,
     exec::ExecCmd,
     export::ExportCmd,
     import::ImportCmd,
     init::InitCmd,
+    keys::KeysCmd,
+    keystore::KeystoreCmd,
     new_account::{NewAccountCmd, NewWalletCmd},
     new_transactions::{ConsumeNotesCmd, MintCmd, SendCmd, SwapCmd},
     notes::NotesCmd,
+    sign::SignCmd,
+    snapshot::{BackupCmd, RestoreCmd},
     sync::SyncCmd,
     tags::TagsCmd,
     transactions::TransactionCmd,
 };
 
+pub use self::keystore::CliKeyStore;
 use self::utils::load_config_file;
 
-pub type CliKeyStore = FilesystemKeyStore<StdRng>;
-
 mod config;
 mod errors;
 mod faucet_details_map;
 mod info;
+mod keystore;
 mod utils;
 
 /// Config file name.
@@ -68,6 +72,7 @@ pub struct Cli {
 #[derive(Debug, Parser)]
 pub enum Command {
     Account(AccountCmd),
+    Balance(BalanceCmd),
     NewAccount(NewAccountCmd),
     NewWallet(NewWalletCmd),
     Import(ImportCmd),
@@ -78,6 +83,8 @@ pub enum Command {
     /// View a summary of the current client state.
     Info,
     Tags(TagsCmd),
+    Keys(KeysCmd),
+    Keystore(KeystoreCmd),
     #[command(name = "tx")]
     Transaction(TransactionCmd),
     Mint(MintCmd),
@@ -85,6 +92,9 @@ pub enum Command {
     Swap(SwapCmd),
     ConsumeNotes(ConsumeNotesCmd),
     Exec(ExecCmd),
+    Sign(SignCmd),
+    Backup(BackupCmd),
+    Restore(RestoreCmd),
 }
 
 /// CLI entry point.
@@ -108,11 +118,21 @@ impl Cli {
             _ => DebugMode::Disabled,
         };
 
+        // Signing offline transactions must work on machines without network access, so it's
+        // handled before creating the client
+        if let Command::Sign(sign_cmd) = &self.action {
+            return sign_cmd.execute(in_debug_mode).await;
+        }
+
+        // The encrypted keystore has to be set up before it can be opened to create the client
+        if let Command::Keystore(keystore_cmd) = &self.action {
+            return keystore_cmd.execute();
+        }
+
         // Create the client
         let (cli_config, _config_path) = load_config_file()?;
 
-        let keystore = CliKeyStore::new(cli_config.secret_keys_directory.clone())
-            .map_err(CliError::KeyStore)?;
+        let keystore = CliKeyStore::from_config(&cli_config)?;
 
         let mut builder = ClientBuilder::new()
             .sqlite_store(cli_config.store_filepath.to_str().expect("Store path should be valid"))
@@ -120,10 +140,20 @@ impl Cli {
                 &cli_config.rpc.endpoint.clone().into(),
                 Some(cli_config.rpc.timeout_ms),
             )
-            .authenticator(Arc::new(keystore.clone()))
+            .rpc_middleware(Arc::new(RetryPolicy::new(cli_config.rpc.max_retries)))
+            .keystore(keystore.inner())
             .in_debug_mode(in_debug_mode)
             .tx_graceful_blocks(Some(TX_GRACEFUL_BLOCK_DELTA));
 
+        if let Some(max_requests_per_second) = cli_config.rpc.max_requests_per_second {
+            builder = builder.rpc_middleware(Arc::new(RateLimiter::new(max_requests_per_second)));
+        }
+
+        let rpc_metrics = Arc::new(RpcMetrics::new());
+        if cli_config.rpc.print_metrics {
+            builder = builder.rpc_middleware(rpc_metrics.clone());
+        }
+
         if let Some(delta) = cli_config.max_block_number_delta {
             builder = builder.max_block_number_delta(delta);
         }
@@ -132,20 +162,33 @@ impl Cli {
 
         client.ensure_genesis_in_place().await?;
 
-        // Execute CLI command
+        let result = self.execute_command(client, keystore).await;
+
+        if cli_config.rpc.print_metrics {
+            print_rpc_metrics(&rpc_metrics);
+        }
+
+        result
+    }
+
+    async fn execute_command(&self, client: Client, keystore: CliKeyStore) -> Result<(), CliError> {
         match &self.action {
             Command::Account(account) => account.execute(client).await,
+            Command::Balance(balance) => balance.execute(client).await,
             Command::NewWallet(new_wallet) => new_wallet.execute(client, keystore).await,
             Command::NewAccount(new_account) => new_account.execute(client, keystore).await,
             Command::Import(import) => import.execute(client, keystore).await,
-            Command::Init(_) => Ok(()),
+            Command::Init(_) | Command::Sign(_) | Command::Keystore(_) => Ok(()),
             Command::Info => info::print_client_info(&client).await,
             Command::Notes(notes) => notes.execute(client).await,
             Command::Sync(sync) => sync.execute(client).await,
             Command::Tags(tags) => tags.execute(client).await,
+            Command::Keys(keys) => keys.execute(client, keystore).await,
             Command::Transaction(transaction) => transaction.execute(client).await,
             Command::Exec(execute_program) => execute_program.execute(client).await,
             Command::Export(cmd) => cmd.execute(client, keystore).await,
+            Command::Backup(backup) => backup.execute(client).await,
+            Command::Restore(restore) => restore.execute(client).await,
             Command::Mint(mint) => mint.execute(client).await,
             Command::Send(send) => send.execute(client).await,
             Command::Swap(swap) => swap.execute(client).await,
@@ -154,6 +197,29 @@ impl Cli {
     }
 }
 
+/// Prints the requests sent to each endpoint of the node along with their timing.
+fn print_rpc_metrics(rpc_metrics: &RpcMetrics) {
+    let mut table = create_dynamic_table(&[
+        "Endpoint",
+        "Requests",
+        "Failures",
+        "Average Time (ms)",
+        "Max Time (ms)",
+    ]);
+
+    for (endpoint, metrics) in rpc_metrics.endpoint_metrics() {
+        table.add_row(vec![
+            endpoint.to_string(),
+            metrics.requests.to_string(),
+            metrics.failures.to_string(),
+            metrics.average_time().as_millis().to_string(),
+            metrics.max_time.as_millis().to_string(),
+        ]);
+    }
+
+    println!("\nRPC requests:\n{table}");
+}
+
 pub fn create_dynamic_table(headers: &[&str]) -> Table {
     let header_cells = headers
         .iter()
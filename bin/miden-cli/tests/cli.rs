@@ -84,6 +84,51 @@ fn init_with_params() {
     init_cmd.current_dir(&temp_dir).assert().failure();
 }
 
+// KEYSTORE TESTS
+// ================================================================================================
+
+#[test]
+fn encrypted_keystore_requires_explicit_initialization() {
+    let temp_dir = init_cli().1;
+
+    let config_path = temp_dir.join("miden-client.toml");
+    let mut config: Table = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    config.insert("encrypted_keystore".to_string(), true.into());
+    fs::write(&config_path, toml::to_string(&config).unwrap()).unwrap();
+
+    // Opening the keystore doesn't initialize it
+    let mut keys_cmd = Command::cargo_bin("miden-client").unwrap();
+    keys_cmd.args(["keys", "--list"]).env("MIDEN_KEYSTORE_PASSPHRASE", "passphrase");
+    keys_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .failure()
+        .stderr(contains("initialized"));
+
+    let mut init_cmd = Command::cargo_bin("miden-client").unwrap();
+    init_cmd
+        .args(["keystore", "--init"])
+        .env("MIDEN_KEYSTORE_PASSPHRASE", "passphrase");
+    init_cmd.current_dir(&temp_dir).assert().success();
+
+    // Trying to initialize twice should result in an error
+    let mut init_cmd = Command::cargo_bin("miden-client").unwrap();
+    init_cmd
+        .args(["keystore", "--init"])
+        .env("MIDEN_KEYSTORE_PASSPHRASE", "passphrase");
+    init_cmd.current_dir(&temp_dir).assert().failure();
+
+    let mut migrate_cmd = Command::cargo_bin("miden-client").unwrap();
+    migrate_cmd
+        .args(["keystore", "--migrate"])
+        .env("MIDEN_KEYSTORE_PASSPHRASE", "passphrase");
+    migrate_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .success()
+        .stdout(contains("Encrypted 0 plaintext keys"));
+}
+
 // TX TESTS
 // ================================================================================================
 
@@ -764,14 +809,14 @@ async fn create_rust_client_with_store_path(store_path: &Path) -> (TestClient, C
 
     let rng = Box::new(RpoRandomCoin::new(coin_seed.map(Felt::new).into()));
 
-    let keystore = CliKeyStore::new(temp_dir()).unwrap();
+    let keystore = CliKeyStore::new(temp_dir(), false).unwrap();
 
     (
         TestClient::new(
             Arc::new(TonicRpcClient::new(&endpoint, 10_000)),
             rng,
             store,
-            std::sync::Arc::new(keystore.clone()),
+            keystore.inner(),
             ExecutionOptions::new(
                 Some(MAX_TX_EXECUTION_CYCLES),
                 MIN_TX_EXECUTION_CYCLES,
@@ -0,0 +1,204 @@
+//! Compares the cost of updating an account with a large storage map in the `SQLite` store against
+//! the storage and vault blobs that were written on every update before the account state was
+//! normalized.
+//!
+//! Every update changes a single entry of the map. The `normalized` rows go through the store,
+//! which only writes the storage slots, map entries and assets that changed, so the time and space
+//! taken by an update shouldn't grow with the size of the map. The `blobs` rows replay the writes
+//! of the released 0.10 schema, which serialized the whole storage and vault of every state.
+//!
+//! Run with `make bench`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use miden_client::store::{Store, sqlite_store::SqliteStore};
+use miden_lib::{account::auth::RpoFalcon512, transaction::TransactionKernel};
+use miden_objects::{
+    EMPTY_WORD, Felt, Word,
+    account::{
+        Account, AccountBuilder, AccountComponent, AccountDelta, AccountStorageDelta,
+        AccountVaultDelta, StorageMap, StorageSlot,
+    },
+    asset::{Asset, FungibleAsset},
+    crypto::dsa::rpo_falcon512::PublicKey,
+    testing::account_component::BASIC_WALLET_CODE,
+    utils::Serializable,
+};
+use rusqlite::{Connection, params};
+
+/// Number of updates applied to the account for each map size.
+const UPDATES: u32 = 100;
+
+/// Number of entries in the storage map of the benchmarked accounts.
+const MAP_SIZES: [u32; 3] = [100, 1_000, 10_000];
+
+/// Schema of the store as released in 0.10, which kept the storage and vault of each account state
+/// as blobs.
+const BLOB_SCHEMA: &str =
+    include_str!("../src/store/sqlite_store/db_management/fixtures/store_v0.10.0.sql");
+
+#[tokio::main]
+async fn main() {
+    println!(
+        "{:>10} {:>12} {:>18} {:>18}",
+        "Map size", "Layout", "Time per update", "Bytes per update"
+    );
+
+    for map_size in MAP_SIZES {
+        bench_normalized_updates(map_size).await;
+        bench_blob_updates(map_size);
+    }
+}
+
+/// Measures the updates written by the `SqliteStore`.
+async fn bench_normalized_updates(map_size: u32) {
+    let store_path = store_path("normalized", map_size);
+    let store = SqliteStore::new(store_path.clone()).await.unwrap();
+
+    let (mut account, map_index) = account_with_storage_map(map_size);
+    store.insert_account(&account, None).await.unwrap();
+    let initial_size = fs::metadata(&store_path).unwrap().len();
+
+    let start = Instant::now();
+    for update in 0..UPDATES {
+        apply_update(&mut account, map_index, update % map_size);
+        store.update_account(&account).await.unwrap();
+    }
+
+    print_results("normalized", map_size, start, &store_path, initial_size);
+}
+
+/// Measures the updates written as they were before the account state was normalized: the whole
+/// storage and vault blobs of every state, along with its account record.
+fn bench_blob_updates(map_size: u32) {
+    const STORAGE_QUERY: &str = "INSERT OR IGNORE INTO account_storage (root, slots) VALUES (?, ?)";
+    const VAULT_QUERY: &str = "INSERT OR IGNORE INTO account_vaults (root, assets) VALUES (?, ?)";
+    const ACCOUNT_QUERY: &str = "INSERT OR REPLACE INTO accounts \
+        (id, code_root, storage_root, vault_root, nonce, committed, account_seed, account_commitment, locked) \
+        VALUES (?, ?, ?, ?, ?, ?, NULL, ?, false)";
+
+    let store_path = store_path("blobs", map_size);
+    let mut conn = Connection::open(&store_path).unwrap();
+    conn.execute_batch(BLOB_SCHEMA).unwrap();
+
+    let write_state = |conn: &mut Connection, account: &Account| {
+        let tx = conn.transaction().unwrap();
+        tx.execute(
+            STORAGE_QUERY,
+            params![account.storage().commitment().to_string(), account.storage().to_bytes()],
+        )
+        .unwrap();
+        tx.execute(
+            VAULT_QUERY,
+            params![
+                account.vault().root().to_string(),
+                account.vault().assets().collect::<Vec<Asset>>().to_bytes()
+            ],
+        )
+        .unwrap();
+        tx.execute(
+            ACCOUNT_QUERY,
+            params![
+                account.id().to_hex(),
+                account.code().commitment().to_string(),
+                account.storage().commitment().to_string(),
+                account.vault().root().to_string(),
+                i64::try_from(account.nonce().as_int()).unwrap(),
+                account.is_public(),
+                account.commitment().to_string(),
+            ],
+        )
+        .unwrap();
+        tx.commit().unwrap();
+    };
+
+    let (mut account, map_index) = account_with_storage_map(map_size);
+    write_state(&mut conn, &account);
+    let initial_size = fs::metadata(&store_path).unwrap().len();
+
+    let start = Instant::now();
+    for update in 0..UPDATES {
+        apply_update(&mut account, map_index, update % map_size);
+        write_state(&mut conn, &account);
+    }
+
+    print_results("blobs", map_size, start, &store_path, initial_size);
+}
+
+/// Changes the value of the specified entry of the storage map and increments the nonce.
+fn apply_update(account: &mut Account, map_index: u8, entry: u32) {
+    let mut storage_delta = AccountStorageDelta::default();
+    storage_delta.set_map_item(map_index, map_key(entry), Word::from([Felt::from(entry + 2); 4]));
+
+    let delta =
+        AccountDelta::new(account.id(), storage_delta, AccountVaultDelta::default(), Felt::new(1))
+            .unwrap();
+    account.apply_delta(&delta).unwrap();
+}
+
+fn print_results(
+    layout: &str,
+    map_size: u32,
+    start: Instant,
+    store_path: &Path,
+    initial_size: u64,
+) {
+    let time_per_update = start.elapsed() / UPDATES;
+    let bytes_per_update =
+        (fs::metadata(store_path).unwrap().len() - initial_size) / u64::from(UPDATES);
+
+    println!(
+        "{map_size:>10} {layout:>12} {:>18} {bytes_per_update:>18}",
+        format!("{time_per_update:?}")
+    );
+
+    fs::remove_file(store_path).unwrap();
+}
+
+/// Returns an existing account with a storage map of the specified size, along with the index of
+/// the map slot.
+fn account_with_storage_map(map_size: u32) -> (Account, u8) {
+    let storage_map = StorageMap::with_entries(
+        (0..map_size).map(|index| (map_key(index), Word::from([Felt::new(1); 4]))),
+    )
+    .unwrap();
+
+    let component = AccountComponent::compile(
+        BASIC_WALLET_CODE,
+        TransactionKernel::assembler(),
+        vec![StorageSlot::Map(storage_map)],
+    )
+    .unwrap()
+    .with_supports_all_types();
+
+    let account = AccountBuilder::new([0; 32])
+        .with_auth_component(RpoFalcon512::new(PublicKey::new(EMPTY_WORD)))
+        .with_component(component)
+        .with_assets([FungibleAsset::mock(100)])
+        .build_existing()
+        .unwrap();
+
+    let map_index = account
+        .storage()
+        .slots()
+        .iter()
+        .position(|slot| matches!(slot, StorageSlot::Map(_)))
+        .map(|index| u8::try_from(index).unwrap())
+        .unwrap();
+
+    (account, map_index)
+}
+
+fn map_key(index: u32) -> Word {
+    Word::from([Felt::from(index); 4])
+}
+
+fn store_path(layout: &str, map_size: u32) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("miden-client-bench-{layout}-{map_size}.sqlite3"));
+    let _ = fs::remove_file(&path);
+    path
+}
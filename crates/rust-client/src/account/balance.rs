@@ -0,0 +1,214 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use miden_objects::{
+    account::AccountId,
+    asset::{Asset, FungibleAsset},
+    note::{Note, NoteAssets},
+};
+
+use crate::{
+    Client, ClientError,
+    store::{InputNoteState, NoteFilter, OutputNoteState},
+};
+
+// ACCOUNT BALANCE
+// ================================================================================================
+
+/// The balance of an account for a fungible faucet.
+///
+/// Besides the amount held in the account vault, the balance reports the amounts that are on their
+/// way in or out of the account and not yet reflected by the network:
+///
+/// - **Pending incoming:** assets in expected or committed P2ID and P2IDE notes targeting the
+///   account that it can consume but hasn't consumed yet.
+/// - **Pending outgoing:** assets in notes created by the account that are still waiting to be
+///   committed. These were already removed from the vault.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountBalance {
+    /// ID of the faucet that issued the asset.
+    faucet_id: AccountId,
+    /// Amount held in the account vault.
+    amount: u64,
+    /// Amount in notes that the account can consume.
+    pending_incoming: u64,
+    /// Amount in notes created by the account that aren't committed yet.
+    pending_outgoing: u64,
+}
+
+impl AccountBalance {
+    fn new(faucet_id: AccountId) -> Self {
+        Self {
+            faucet_id,
+            amount: 0,
+            pending_incoming: 0,
+            pending_outgoing: 0,
+        }
+    }
+
+    /// Returns the ID of the faucet that issued the asset.
+    pub fn faucet_id(&self) -> AccountId {
+        self.faucet_id
+    }
+
+    /// Returns the amount held in the account vault.
+    pub fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    /// Returns the amount in expected or committed notes that the account can consume.
+    pub fn pending_incoming(&self) -> u64 {
+        self.pending_incoming
+    }
+
+    /// Returns the amount in notes created by the account that aren't committed yet.
+    pub fn pending_outgoing(&self) -> u64 {
+        self.pending_outgoing
+    }
+}
+
+/// Account balance methods
+impl Client {
+    /// Returns the balance of the account for the specified fungible faucet, including the
+    /// pending amounts (see [`AccountBalance`]).
+    ///
+    /// The amount in the vault is read without loading the whole account. Finding the incoming
+    /// notes requires screening the expected or committed notes targeting the account that hold
+    /// an asset from the faucet. Expected notes whose metadata isn't known can't be screened and
+    /// are left out.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked.
+    /// - Returns [`ClientError::StoreError`] if `faucet_id` isn't the ID of a fungible faucet.
+    pub async fn get_balance(
+        &self,
+        account_id: AccountId,
+        faucet_id: AccountId,
+    ) -> Result<AccountBalance, ClientError> {
+        self.try_get_account_header(account_id).await?;
+
+        let mut balance = AccountBalance::new(faucet_id);
+        balance.amount = self.store.get_account_balance(account_id, faucet_id).await?;
+
+        let pending = self.get_pending_amounts(account_id, Some(faucet_id)).await?;
+        if let Some(pending) = pending.get(&faucet_id) {
+            balance.pending_incoming = pending.pending_incoming;
+            balance.pending_outgoing = pending.pending_outgoing;
+        }
+
+        Ok(balance)
+    }
+
+    /// Returns the balances of the account for every fungible faucet it holds assets from or has
+    /// pending amounts of, sorted by faucet ID. Non-fungible assets aren't included.
+    ///
+    /// See [`Client::get_balance`] for how pending amounts are computed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked.
+    pub async fn get_balances(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountBalance>, ClientError> {
+        let account = self.try_get_account(account_id).await?;
+
+        let mut balances = self.get_pending_amounts(account_id, None).await?;
+        for asset in account.account().vault().assets() {
+            if let Asset::Fungible(asset) = asset {
+                balances
+                    .entry(asset.faucet_id())
+                    .or_insert_with(|| AccountBalance::new(asset.faucet_id()))
+                    .amount = asset.amount();
+            }
+        }
+
+        Ok(balances.into_values().collect())
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the pending incoming and outgoing amounts of the account, keyed by faucet ID. If
+    /// `faucet_id` is provided, only notes with assets from that faucet are taken into account.
+    async fn get_pending_amounts(
+        &self,
+        account_id: AccountId,
+        faucet_id: Option<AccountId>,
+    ) -> Result<BTreeMap<AccountId, AccountBalance>, ClientError> {
+        let mut balances = BTreeMap::new();
+        let holds_faucet_assets = |assets: &NoteAssets| {
+            faucet_id.is_none_or(|faucet_id| {
+                fungible_assets(assets).any(|asset| asset.faucet_id() == faucet_id)
+            })
+        };
+
+        // Only the notes targeting the account are screened, as screening every tracked note
+        // would execute a transaction for each of them
+        let input_notes = self.store.get_input_notes(NoteFilter::Target(account_id)).await?;
+
+        let note_screener = self.note_screener();
+        for input_note in input_notes {
+            let pending = input_note.is_committed()
+                || matches!(input_note.state(), InputNoteState::Expected(_));
+            if !pending || !holds_faucet_assets(input_note.assets()) {
+                continue;
+            }
+
+            // Notes without metadata can't be screened
+            let Ok(note) = Note::try_from(input_note) else {
+                continue;
+            };
+
+            let consumable = note_screener
+                .check_relevance(&note)
+                .await?
+                .iter()
+                .any(|(id, _)| *id == account_id);
+
+            if consumable {
+                for asset in fungible_assets(note.assets()) {
+                    let balance = balances
+                        .entry(asset.faucet_id())
+                        .or_insert_with(|| AccountBalance::new(asset.faucet_id()));
+                    balance.pending_incoming =
+                        balance.pending_incoming.saturating_add(asset.amount());
+                }
+            }
+        }
+
+        for output_note in self.store.get_output_notes(NoteFilter::Sender(account_id)).await? {
+            let pending = matches!(
+                output_note.state(),
+                OutputNoteState::ExpectedPartial | OutputNoteState::ExpectedFull { .. }
+            );
+            if !pending || !holds_faucet_assets(output_note.assets()) {
+                continue;
+            }
+
+            for asset in fungible_assets(output_note.assets()) {
+                let balance = balances
+                    .entry(asset.faucet_id())
+                    .or_insert_with(|| AccountBalance::new(asset.faucet_id()));
+                balance.pending_outgoing = balance.pending_outgoing.saturating_add(asset.amount());
+            }
+        }
+
+        if let Some(faucet_id) = faucet_id {
+            balances.retain(|id, _| *id == faucet_id);
+        }
+
+        Ok(balances)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns an iterator over the fungible assets in the provided note assets.
+fn fungible_assets(assets: &NoteAssets) -> impl Iterator<Item = &FungibleAsset> {
+    assets.iter().filter_map(|asset| match asset {
+        Asset::Fungible(asset) => Some(asset),
+        Asset::NonFungible(_) => None,
+    })
+}
@@ -35,23 +35,34 @@
 //!
 //! For more details on accounts, refer to the [Account] documentation.
 
-use alloc::vec::Vec;
+use alloc::{collections::BTreeSet, vec::Vec};
+use core::fmt;
 
 use miden_lib::account::{auth::RpoFalcon512, wallets::BasicWallet};
-use miden_objects::{Word, crypto::dsa::rpo_falcon512::PublicKey};
+use miden_objects::{
+    EMPTY_WORD, Felt, Word,
+    account::delta::{AccountStorageDelta, AccountVaultDelta},
+    asset::{Asset, FungibleAsset},
+    crypto::{dsa::rpo_falcon512::PublicKey, merkle::SmtProof},
+    note::{NoteId, Nullifier},
+    transaction::{OutputNote, TransactionId},
+};
 
 use super::Client;
 use crate::{
     errors::ClientError,
     rpc::domain::account::FetchedAccount,
-    store::{AccountRecord, AccountStatus},
+    store::{AccountRecord, AccountStatus, InputNoteRecord, NoteFilter, TransactionFilter},
+    transaction::PendingTransactionRecord,
 };
 
+mod balance;
 pub mod procedure_roots;
 
 // RE-EXPORTS
 // ================================================================================================
 
+pub use balance::AccountBalance;
 pub use miden_objects::{
     AccountIdError, NetworkIdError,
     account::{
@@ -87,6 +98,15 @@ pub mod component {
 ///   with the network.
 ///
 /// - **Data retrieval:** The module also provides methods to fetch account-related data.
+///
+/// - **Balances:** The balance of an account for a fungible faucet can be retrieved along with the
+///   amounts in notes that are on their way in or out of the account.
+///
+/// - **Account history:** Every state of a tracked account is kept in the store, so past states can
+///   be listed, retrieved and compared.
+///
+/// - **Account removal:** Accounts can stop being tracked, optionally removing their transactions
+///   and notes from the store.
 impl Client {
     // ACCOUNT CREATION
     // --------------------------------------------------------------------------------------------
@@ -129,7 +149,7 @@ impl Client {
             None
         };
 
-        let tracked_account = self.store.get_account(account.id()).await?;
+        let tracked_account = self.store.get_account_header(account.id()).await?;
 
         match tracked_account {
             None => {
@@ -142,18 +162,18 @@ impl Client {
                     .await
                     .map_err(ClientError::StoreError)
             },
-            Some(tracked_account) => {
+            Some((tracked_account, tracked_status)) => {
                 if !overwrite {
                     // Only overwrite the account if the flag is set to `true`
                     return Err(ClientError::AccountAlreadyTracked(account.id()));
                 }
 
-                if tracked_account.account().nonce().as_int() > account.nonce().as_int() {
+                if tracked_account.nonce().as_int() > account.nonce().as_int() {
                     // If the new account is older than the one being tracked, return an error
                     return Err(ClientError::AccountNonceTooLow);
                 }
 
-                if tracked_account.is_locked() {
+                if tracked_status.is_locked() {
                     // If the tracked account is locked, check that the account commitment matches
                     // the one in the network
                     let network_account_commitment =
@@ -254,6 +274,326 @@ impl Client {
             .await?
             .ok_or(ClientError::AccountDataNotFound(account_id))
     }
+
+    /// Retrieves the value of the storage slot with the specified index in the latest state of the
+    /// account, without loading the rest of the account. For storage map slots, the value is the
+    /// root of the map.
+    ///
+    /// # Errors
+    ///
+    /// - If the account isn't tracked or has no slot with the provided index.
+    /// - If the underlying store operation fails.
+    pub async fn get_account_storage_item(
+        &self,
+        account_id: AccountId,
+        index: u8,
+    ) -> Result<Word, ClientError> {
+        self.store.get_account_storage_item(account_id, index).await.map_err(Into::into)
+    }
+
+    /// Retrieves the value of the entry with the specified key in the storage map at the specified
+    /// slot index, along with a Merkle proof of the entry against the root of the map, without
+    /// loading the rest of the account.
+    ///
+    /// # Errors
+    ///
+    /// - If the account isn't tracked, has no slot with the provided index or the slot isn't a
+    ///   storage map.
+    /// - If the underlying store operation fails.
+    pub async fn get_account_map_item(
+        &self,
+        account_id: AccountId,
+        index: u8,
+        key: Word,
+    ) -> Result<(Word, SmtProof), ClientError> {
+        self.store
+            .get_account_map_item(account_id, index, key)
+            .await
+            .map_err(Into::into)
+    }
+
+    // ACCOUNT HISTORY
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the [`AccountHeader`] of every stored state of the specified account, ordered by
+    /// nonce from oldest to newest. Returns an empty list if the account isn't tracked.
+    pub async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountHeader>, ClientError> {
+        self.store.get_account_history(account_id).await.map_err(Into::into)
+    }
+
+    /// Retrieves the full [`AccountRecord`] of the stored state of the account with the specified
+    /// nonce. If several stored states share the nonce, the latest one is returned. Returns `None`
+    /// if no stored state of the account has that nonce.
+    pub async fn get_account_at_nonce(
+        &self,
+        account_id: AccountId,
+        nonce: Felt,
+    ) -> Result<Option<AccountRecord>, ClientError> {
+        self.store.get_account_at_nonce(account_id, nonce).await.map_err(Into::into)
+    }
+
+    /// Retrieves the full [`AccountRecord`] of the stored account state that matches the given
+    /// commitment. Returns `None` if no stored account state matches the commitment.
+    pub async fn get_account_by_commitment(
+        &self,
+        account_commitment: Word,
+    ) -> Result<Option<AccountRecord>, ClientError> {
+        self.store
+            .get_account_by_commitment(account_commitment)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Computes the [`AccountDelta`] that takes the stored state of the account selected by `from`
+    /// to the stored state selected by `to`.
+    ///
+    /// States selected by nonce resolve to the latest stored state with that nonce. As several
+    /// states of an account may share a nonce, they can be selected by commitment instead.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountStateNotFound`] if either state isn't stored.
+    /// - Returns [`ClientError::InvalidAccountStateRange`] if the nonce of the `to` state is lower
+    ///   than the nonce of the `from` state.
+    /// - Returns [`ClientError::AccountStorageLayoutMismatch`] if the storage slots of the states
+    ///   don't match.
+    pub async fn get_account_state_diff(
+        &self,
+        account_id: AccountId,
+        from: AccountStateSelector,
+        to: AccountStateSelector,
+    ) -> Result<AccountDelta, ClientError> {
+        let from = self.get_selected_account_state(account_id, from).await?;
+        let to = self.get_selected_account_state(account_id, to).await?;
+
+        if to.nonce().as_int() < from.nonce().as_int() {
+            return Err(ClientError::InvalidAccountStateRange {
+                from: from.nonce(),
+                to: to.nonce(),
+            });
+        }
+
+        diff_account_states(&from, &to)
+    }
+
+    /// Retrieves the stored state of the account selected by `selector`.
+    async fn get_selected_account_state(
+        &self,
+        account_id: AccountId,
+        selector: AccountStateSelector,
+    ) -> Result<Account, ClientError> {
+        let record = match selector {
+            AccountStateSelector::Nonce(nonce) => {
+                self.get_account_at_nonce(account_id, nonce).await?
+            },
+            AccountStateSelector::Commitment(commitment) => self
+                .get_account_by_commitment(commitment)
+                .await?
+                .filter(|record| record.account().id() == account_id),
+        };
+
+        record
+            .map(Into::into)
+            .ok_or(ClientError::AccountStateNotFound(account_id, selector))
+    }
+
+    // ACCOUNT REMOVAL
+    // --------------------------------------------------------------------------------------------
+
+    /// Stops tracking the specified account, removing all of its states from the store along with
+    /// the note tags that were tracked for it and its pending transactions.
+    ///
+    /// If `related_data` is [`RelatedData::Remove`], the transactions executed by the account are
+    /// removed too, along with the notes they consumed or created. Notes that are also consumed
+    /// or created by transactions of other tracked accounts are kept.
+    ///
+    /// All of the data is removed in a single store operation, so a failure leaves the account
+    /// tracked along with its data. The account code, scripts and other data that may be shared
+    /// with other accounts are kept until [`Client::garbage_collect`] is called.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked.
+    /// - If the underlying store operation fails.
+    pub async fn remove_account(
+        &mut self,
+        account_id: AccountId,
+        related_data: RelatedData,
+    ) -> Result<(), ClientError> {
+        self.try_get_account_header(account_id).await?;
+
+        let pending_transaction_ids = self
+            .store
+            .get_pending_transactions()
+            .await?
+            .iter()
+            .filter(|pending_transaction| pending_transaction.account_id() == account_id)
+            .map(PendingTransactionRecord::id)
+            .collect();
+        let mut removal = AccountRemoval::new(account_id, pending_transaction_ids);
+
+        if related_data == RelatedData::Remove {
+            self.collect_account_related_data(&mut removal).await?;
+        }
+
+        self.store.remove_account(&removal).await.map_err(Into::into)
+    }
+
+    /// Removes the data that is no longer referenced by any stored account, note or transaction,
+    /// such as the code of removed accounts and the scripts of removed notes and transactions.
+    /// Returns the number of removed entries.
+    pub async fn garbage_collect(&mut self) -> Result<usize, ClientError> {
+        self.store.garbage_collect().await.map_err(Into::into)
+    }
+
+    /// Adds the transactions executed by the removed account to `removal`, along with the notes
+    /// they consumed or created that aren't related to transactions of other tracked accounts.
+    async fn collect_account_related_data(
+        &self,
+        removal: &mut AccountRemoval,
+    ) -> Result<(), ClientError> {
+        let account_id = removal.account_id;
+        let (account_transactions, other_transactions): (Vec<_>, Vec<_>) = self
+            .store
+            .get_transactions(TransactionFilter::All)
+            .await?
+            .into_iter()
+            .partition(|tx| tx.details.account_id == account_id);
+
+        let shared_nullifiers = other_transactions
+            .iter()
+            .flat_map(|tx| tx.details.input_note_nullifiers.iter().copied())
+            .collect::<Vec<Word>>();
+        let shared_note_ids = other_transactions
+            .iter()
+            .flat_map(|tx| tx.details.output_notes.iter().map(OutputNote::id))
+            .collect::<BTreeSet<NoteId>>();
+
+        let nullifiers = account_transactions
+            .iter()
+            .flat_map(|tx| tx.details.input_note_nullifiers.iter().copied())
+            .filter(|nullifier| !shared_nullifiers.contains(nullifier))
+            .map(Nullifier::from)
+            .collect::<Vec<_>>();
+        removal.input_note_ids = self
+            .store
+            .get_input_notes(NoteFilter::Nullifiers(nullifiers))
+            .await?
+            .iter()
+            .map(InputNoteRecord::id)
+            .filter(|note_id| !shared_note_ids.contains(note_id))
+            .collect::<Vec<_>>();
+        removal.output_note_ids = account_transactions
+            .iter()
+            .flat_map(|tx| tx.details.output_notes.iter().map(OutputNote::id))
+            .collect::<Vec<_>>();
+        removal.transaction_ids = account_transactions.iter().map(|tx| tx.id).collect();
+
+        Ok(())
+    }
+}
+
+// RELATED DATA
+// ================================================================================================
+
+/// Indicates whether the transactions and notes related to an account are removed along with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelatedData {
+    /// The transactions and notes of the account are kept in the store.
+    Keep,
+    /// The transactions executed by the account are removed, along with the notes they consumed
+    /// or created that aren't related to other tracked accounts.
+    Remove,
+}
+
+impl From<bool> for RelatedData {
+    fn from(remove: bool) -> RelatedData {
+        if remove { RelatedData::Remove } else { RelatedData::Keep }
+    }
+}
+
+// ACCOUNT REMOVAL
+// ================================================================================================
+
+/// Represents the data that needs to be removed from the client store, in a single operation, to
+/// stop tracking an account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountRemoval {
+    /// ID of the removed account.
+    account_id: AccountId,
+    /// IDs of the pending transactions of the account.
+    pending_transaction_ids: Vec<TransactionId>,
+    /// IDs of the transactions executed by the account.
+    transaction_ids: Vec<TransactionId>,
+    /// IDs of the input notes consumed by the removed transactions.
+    input_note_ids: Vec<NoteId>,
+    /// IDs of the output notes created by the removed transactions.
+    output_note_ids: Vec<NoteId>,
+}
+
+impl AccountRemoval {
+    /// Creates a new [`AccountRemoval`] instance that removes the account along with its pending
+    /// transactions, keeping its transactions and notes.
+    pub fn new(account_id: AccountId, pending_transaction_ids: Vec<TransactionId>) -> Self {
+        Self {
+            account_id,
+            pending_transaction_ids,
+            transaction_ids: Vec::new(),
+            input_note_ids: Vec::new(),
+            output_note_ids: Vec::new(),
+        }
+    }
+
+    /// Returns the ID of the removed account.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the IDs of the pending transactions of the account.
+    pub fn pending_transaction_ids(&self) -> &[TransactionId] {
+        &self.pending_transaction_ids
+    }
+
+    /// Returns the IDs of the transactions executed by the account.
+    pub fn transaction_ids(&self) -> &[TransactionId] {
+        &self.transaction_ids
+    }
+
+    /// Returns the IDs of the input notes consumed by the removed transactions.
+    pub fn input_note_ids(&self) -> &[NoteId] {
+        &self.input_note_ids
+    }
+
+    /// Returns the IDs of the output notes created by the removed transactions.
+    pub fn output_note_ids(&self) -> &[NoteId] {
+        &self.output_note_ids
+    }
+}
+
+// ACCOUNT STATE SELECTOR
+// ================================================================================================
+
+/// Selects one of the stored states of an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountStateSelector {
+    /// The latest stored state with the specified nonce.
+    Nonce(Felt),
+    /// The stored state with the specified commitment.
+    Commitment(Word),
+}
+
+impl fmt::Display for AccountStateSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountStateSelector::Nonce(nonce) => write!(f, "nonce {nonce}"),
+            AccountStateSelector::Commitment(commitment) => {
+                write!(f, "commitment {}", commitment.to_hex())
+            },
+        }
+    }
 }
 
 // UTILITY FUNCTIONS
@@ -297,6 +637,89 @@ pub fn build_wallet_id(
     Ok(account.id())
 }
 
+/// Computes the [`AccountDelta`] that takes the `from` state of an account to the `to` state.
+fn diff_account_states(from: &Account, to: &Account) -> Result<AccountDelta, ClientError> {
+    // STORAGE
+    if from.storage().slots().len() != to.storage().slots().len() {
+        return Err(ClientError::AccountStorageLayoutMismatch(to.id()));
+    }
+
+    let mut storage_delta = AccountStorageDelta::default();
+    for (index, (from_slot, to_slot)) in
+        from.storage().slots().iter().zip(to.storage().slots()).enumerate()
+    {
+        let index = u8::try_from(index).expect("accounts have at most 255 storage slots");
+
+        match (from_slot, to_slot) {
+            (StorageSlot::Value(from_value), StorageSlot::Value(to_value)) => {
+                if from_value != to_value {
+                    storage_delta.set_item(index, *to_value);
+                }
+            },
+            (StorageSlot::Map(from_map), StorageSlot::Map(to_map)) => {
+                for (key, value) in to_map.entries() {
+                    if from_map.get(key) != *value {
+                        storage_delta.set_map_item(index, *key, *value);
+                    }
+                }
+                // Entries that were removed are set to the empty word
+                for (key, _) in from_map.entries() {
+                    if to_map.get(key) == EMPTY_WORD {
+                        storage_delta.set_map_item(index, *key, EMPTY_WORD);
+                    }
+                }
+            },
+            _ => return Err(ClientError::AccountStorageLayoutMismatch(to.id())),
+        }
+    }
+
+    // VAULT
+    let mut vault_delta = AccountVaultDelta::default();
+    for asset in to.vault().assets() {
+        match asset {
+            Asset::Fungible(fungible) => {
+                let from_balance = from.vault().get_balance(fungible.faucet_id()).unwrap_or(0);
+                if fungible.amount() > from_balance {
+                    vault_delta.add_asset(
+                        FungibleAsset::new(fungible.faucet_id(), fungible.amount() - from_balance)?
+                            .into(),
+                    )?;
+                }
+            },
+            Asset::NonFungible(non_fungible) => {
+                if !from.vault().has_non_fungible_asset(non_fungible).unwrap_or(false) {
+                    vault_delta.add_asset(asset)?;
+                }
+            },
+        }
+    }
+    for asset in from.vault().assets() {
+        match asset {
+            Asset::Fungible(fungible) => {
+                let to_balance = to.vault().get_balance(fungible.faucet_id()).unwrap_or(0);
+                if fungible.amount() > to_balance {
+                    vault_delta.remove_asset(
+                        FungibleAsset::new(fungible.faucet_id(), fungible.amount() - to_balance)?
+                            .into(),
+                    )?;
+                }
+            },
+            Asset::NonFungible(non_fungible) => {
+                if !to.vault().has_non_fungible_asset(non_fungible).unwrap_or(false) {
+                    vault_delta.remove_asset(asset)?;
+                }
+            },
+        }
+    }
+
+    Ok(AccountDelta::new(
+        to.id(),
+        storage_delta,
+        vault_delta,
+        to.nonce() - from.nonce(),
+    )?)
+}
+
 // TESTS
 // ================================================================================================
 
@@ -307,14 +730,15 @@ pub mod tests {
     use miden_lib::{account::auth::RpoFalcon512, transaction::TransactionKernel};
     use miden_objects::{
         EMPTY_WORD, Felt, Word,
-        account::{Account, AccountFile, AuthSecretKey},
+        account::{Account, AccountFile, AccountStorage, AuthSecretKey, StorageMap, StorageSlot},
         crypto::dsa::rpo_falcon512::{PublicKey, SecretKey},
         testing::account_id::{
             ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
         },
     };
 
-    use crate::tests::create_test_client;
+    use super::diff_account_states;
+    use crate::{errors::ClientError, tests::create_test_client};
 
     fn create_account_data(account_id: u128) -> AccountFile {
         let account = Account::mock(
@@ -383,4 +807,32 @@ pub mod tests {
             assert_eq!(client_acc.0.commitment(), expected_acc.commitment());
         }
     }
+
+    #[test]
+    fn diff_of_states_with_different_storage_layouts_fails() {
+        let account = create_account_data(ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET).account;
+
+        // Every slot changes its type, so both states have the same number of slots
+        let slots = account
+            .storage()
+            .slots()
+            .iter()
+            .map(|slot| match slot {
+                StorageSlot::Value(_) => StorageSlot::Map(StorageMap::new()),
+                StorageSlot::Map(_) => StorageSlot::Value(EMPTY_WORD),
+            })
+            .collect();
+        let changed_account = Account::from_parts(
+            account.id(),
+            account.vault().clone(),
+            AccountStorage::new(slots).unwrap(),
+            account.code().clone(),
+            account.nonce(),
+        );
+
+        assert!(matches!(
+            diff_account_states(&account, &changed_account),
+            Err(ClientError::AccountStorageLayoutMismatch(account_id)) if account_id == account.id()
+        ));
+    }
 }
@@ -1,22 +1,35 @@
+#[cfg(feature = "tonic")]
+use alloc::vec::Vec;
 use alloc::{
     string::{String, ToString},
     sync::Arc,
 };
+use core::num::NonZeroUsize;
 use std::boxed::Box;
 
 use miden_objects::{
-    Felt, MAX_TX_EXECUTION_CYCLES, MIN_TX_EXECUTION_CYCLES,
+    Felt,
     crypto::rand::{FeltRng, RpoRandomCoin},
 };
-use miden_tx::{ExecutionOptions, auth::TransactionAuthenticator};
+use miden_tx::auth::TransactionAuthenticator;
 use rand::Rng;
 
 #[cfg(feature = "tonic")]
-use crate::rpc::{Endpoint, TonicRpcClient};
+use crate::rpc::{
+    Endpoint, TonicRpcClient,
+    middleware::{MiddlewareRpcClient, RpcMiddleware},
+};
+#[cfg(feature = "memory-store")]
+use crate::store::memory_store::MemoryStore;
 #[cfg(feature = "sqlite")]
 use crate::store::sqlite_store::SqliteStore;
 use crate::{
-    Client, ClientError, DebugMode, keystore::FilesystemKeyStore, rpc::NodeRpcClient, store::Store,
+    Client, ClientError, DebugMode,
+    keystore::{FilesystemKeyStore, KeyStore},
+    note_transport::NoteTransport,
+    rpc::NodeRpcClient,
+    store::Store,
+    transaction::default_exec_options,
 };
 
 // CONSTANTS
@@ -68,6 +81,14 @@ pub struct ClientBuilder {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     max_block_number_delta: Option<u32>,
+    /// The number of sync steps after which the sync updates are applied to the store. If `None`,
+    /// the updates are applied once the chain tip is reached.
+    sync_chunk_size: Option<NonZeroUsize>,
+    /// An optional transport used to deliver private notes to their recipients.
+    note_transport: Option<Arc<dyn NoteTransport>>,
+    /// Middlewares that wrap the RPC client, in the order they were added.
+    #[cfg(feature = "tonic")]
+    rpc_middlewares: Vec<Arc<dyn RpcMiddleware>>,
 }
 
 impl Default for ClientBuilder {
@@ -82,6 +103,10 @@ impl Default for ClientBuilder {
             in_debug_mode: DebugMode::Disabled,
             tx_graceful_blocks: Some(TX_GRACEFUL_BLOCKS),
             max_block_number_delta: None,
+            sync_chunk_size: None,
+            note_transport: None,
+            #[cfg(feature = "tonic")]
+            rpc_middlewares: Vec::new(),
         }
     }
 }
@@ -115,6 +140,17 @@ impl ClientBuilder {
         self
     }
 
+    /// Adds a middleware that runs around every request sent by the RPC client, such as a
+    /// [`RetryPolicy`](crate::rpc::middleware::RetryPolicy) or a
+    /// [`RateLimiter`](crate::rpc::middleware::RateLimiter). Middlewares run in the order they
+    /// were added.
+    #[cfg(feature = "tonic")]
+    #[must_use]
+    pub fn rpc_middleware(mut self, middleware: Arc<dyn RpcMiddleware>) -> Self {
+        self.rpc_middlewares.push(middleware);
+        self
+    }
+
     /// Optionally set a custom store path.
     #[cfg(feature = "sqlite")]
     #[must_use]
@@ -123,6 +159,15 @@ impl ClientBuilder {
         self
     }
 
+    /// Uses a [`MemoryStore`], which keeps the client's data in memory until the client is
+    /// dropped.
+    #[cfg(feature = "memory-store")]
+    #[must_use]
+    pub fn in_memory_store(mut self) -> Self {
+        self.store = Some(Arc::new(MemoryStore::new()));
+        self
+    }
+
     /// Optionally provide a store directly.
     #[must_use]
     pub fn store(mut self, store: Arc<dyn Store>) -> Self {
@@ -144,6 +189,13 @@ impl ClientBuilder {
         self
     }
 
+    /// Optionally provide a [`KeyStore`] instance, which is used as the client's authenticator.
+    #[must_use]
+    pub fn keystore(mut self, keystore: Arc<dyn KeyStore>) -> Self {
+        self.keystore = Some(AuthenticatorConfig::Instance(keystore));
+        self
+    }
+
     /// Optionally set a maximum number of blocks that the client can be behind the network.
     /// By default, there's no maximum.
     #[must_use]
@@ -161,6 +213,25 @@ impl ClientBuilder {
         self
     }
 
+    /// Optionally set the number of sync steps after which the sync updates are applied to the
+    /// store. Applying the updates in chunks allows an interrupted sync to resume from the last
+    /// applied chunk instead of starting over.
+    /// By default, the updates are applied once the chain tip is reached.
+    #[must_use]
+    pub fn sync_chunk_size(mut self, sync_chunk_size: NonZeroUsize) -> Self {
+        self.sync_chunk_size = Some(sync_chunk_size);
+        self
+    }
+
+    /// Optionally provide a [`NoteTransport`] to send and fetch private notes. Channel keys for the
+    /// tags of the notes are registered on the built client through
+    /// [`Client::add_note_channel`].
+    #[must_use]
+    pub fn note_transport(mut self, transport: Arc<dyn NoteTransport>) -> Self {
+        self.note_transport = Some(transport);
+        self
+    }
+
     /// **Required:** Provide the keystore path as a string.
     ///
     /// This stores the keystore path as a configuration option so that actual keystore
@@ -190,6 +261,17 @@ impl ClientBuilder {
             ));
         };
 
+        #[cfg(feature = "tonic")]
+        let rpc_api: Arc<dyn NodeRpcClient + Send> = if self.rpc_middlewares.is_empty() {
+            rpc_api
+        } else {
+            let client = self
+                .rpc_middlewares
+                .into_iter()
+                .fold(MiddlewareRpcClient::new(rpc_api), MiddlewareRpcClient::with_middleware);
+            Arc::new(client)
+        };
+
         #[cfg(feature = "sqlite")]
         if self.store.is_none() {
             let store = SqliteStore::new(self.store_path.into())
@@ -233,20 +315,20 @@ impl ClientBuilder {
             }
         };
 
-        Ok(Client::new(
+        let mut client = Client::new(
             rpc_api,
             rng,
             arc_store,
             authenticator,
-            ExecutionOptions::new(
-                Some(MAX_TX_EXECUTION_CYCLES),
-                MIN_TX_EXECUTION_CYCLES,
-                false,
-                self.in_debug_mode.into(),
-            )
-            .expect("Default executor's options should always be valid"),
+            default_exec_options(self.in_debug_mode),
             self.tx_graceful_blocks,
             self.max_block_number_delta,
-        ))
+        );
+        client.set_sync_chunk_size(self.sync_chunk_size);
+        if let Some(transport) = self.note_transport {
+            client.set_note_transport(transport);
+        }
+
+        Ok(client)
     }
 }
@@ -1,25 +1,29 @@
 use alloc::{
+    boxed::Box,
     string::{String, ToString},
     vec::Vec,
 };
 
 use miden_lib::account::interface::AccountInterfaceError;
 use miden_objects::{
-    AccountError, AssetError, NoteError, PartialBlockchainError, TransactionInputError,
-    TransactionScriptError, Word, account::AccountId, crypto::merkle::MerkleError, note::NoteId,
+    AccountDeltaError, AccountError, AssetError, Felt, NoteError, PartialBlockchainError,
+    TransactionInputError, TransactionScriptError, Word, account::AccountId,
+    crypto::merkle::MerkleError, note::NoteId, transaction::TransactionId,
 };
 // RE-EXPORTS
 // ================================================================================================
 pub use miden_tx::AuthenticationError;
 use miden_tx::{
-    TransactionExecutorError, TransactionProverError,
+    DataStoreError, TransactionExecutorError, TransactionProverError,
     utils::{DeserializationError, HexParseError},
 };
 use thiserror::Error;
 
 use crate::{
+    account::AccountStateSelector,
     note::NoteScreenerError,
-    rpc::RpcError,
+    note_transport::NoteTransportError,
+    rpc::{RpcError, SubmissionRejection},
     store::{NoteRecordError, StoreError},
     transaction::TransactionRequestError,
 };
@@ -32,6 +36,8 @@ use crate::{
 pub enum ClientError {
     #[error("account with id {0} is already being tracked")]
     AccountAlreadyTracked(AccountId),
+    #[error("account delta error")]
+    AccountDeltaError(#[from] AccountDeltaError),
     #[error("account error")]
     AccountError(#[from] AccountError),
     #[error("account with id {0} is locked")]
@@ -46,12 +52,20 @@ pub enum ClientError {
     AssetError(#[from] AssetError),
     #[error("account data wasn't found for account id {0}")]
     AccountDataNotFound(AccountId),
+    #[error("account with id {0} has no stored state with {1}")]
+    AccountStateNotFound(AccountId, AccountStateSelector),
+    #[error("can't diff account state with nonce {from} against older state with nonce {to}")]
+    InvalidAccountStateRange { from: Felt, to: Felt },
+    #[error("the diffed states of account with id {0} have different storage layouts")]
+    AccountStorageLayoutMismatch(AccountId),
     #[error("error creating the partial blockchain")]
     PartialBlockchainError(#[from] PartialBlockchainError),
     #[error("data deserialization error")]
     DataDeserializationError(#[from] DeserializationError),
     #[error("note with id {0} not found on chain")]
     NoteNotFoundOnChain(NoteId),
+    #[error("data store error")]
+    DataStoreError(#[from] DataStoreError),
     #[error("error parsing hex")]
     HexParseError(#[from] HexParseError),
     #[error("can't add new account without seed")]
@@ -64,22 +78,42 @@ pub enum ClientError {
     MissingOutputRecipients(Vec<Word>),
     #[error("note error")]
     NoteError(#[from] NoteError),
+    #[error("note {0} is already consumed by a transaction in the chain")]
+    NoteAlreadyConsumedInChain(NoteId),
     #[error("note import error: {0}")]
     NoteImportError(String),
     #[error("error while converting input note")]
     NoteRecordConversionError(#[from] NoteRecordError),
     #[error("no consumable note for account {0}")]
     NoConsumableNoteForAccount(AccountId),
+    #[error("pending transaction {0} was already submitted")]
+    PendingTransactionAlreadySubmitted(TransactionId),
+    #[error("pending transaction {0} not found")]
+    PendingTransactionNotFound(TransactionId),
+    #[error("pending transaction {0} holds a proof for transaction {1}")]
+    PendingTransactionProofMismatch(TransactionId, TransactionId),
     #[error("rpc api error")]
     RpcError(#[from] RpcError),
     #[error("recency condition error: {0}")]
     RecencyConditionError(String),
     #[error("note screener error")]
     NoteScreenerError(#[from] NoteScreenerError),
+    #[error("note transport error")]
+    NoteTransportError(#[from] NoteTransportError),
     #[error("store error")]
     StoreError(#[from] StoreError),
     #[error("transaction executor error: {0}")]
     TransactionExecutorError(#[from] TransactionExecutorError),
+    #[error("transaction was rejected because some of its input notes were already consumed")]
+    TransactionInputNotesAlreadyConsumed(#[source] RpcError),
+    #[error("transaction was rejected because the account state it was executed against is stale")]
+    TransactionAccountStateStale(#[source] RpcError),
+    #[error("transaction was rejected because it expired")]
+    TransactionExpired(#[source] RpcError),
+    #[error("transaction was rejected by the node")]
+    TransactionRejected(#[source] RpcError),
+    #[error("transaction chain submission failed after {0} transactions were submitted")]
+    TransactionChainSubmissionFailed(usize, #[source] Box<ClientError>),
     #[error("transaction input error")]
     TransactionInputError(#[source] TransactionInputError),
     #[error("transaction prover error")]
@@ -103,6 +137,26 @@ impl From<ClientError> for String {
     }
 }
 
+impl ClientError {
+    /// Maps the error returned by the node when submitting a transaction to the error that
+    /// explains why the transaction was rejected.
+    ///
+    /// Rejections whose reason isn't known are returned as [`ClientError::TransactionRejected`].
+    /// Errors that aren't rejections, such as the node being unavailable, are returned as
+    /// [`ClientError::RpcError`].
+    pub(crate) fn from_submission_error(err: RpcError) -> Self {
+        match err.submission_rejection() {
+            Some(SubmissionRejection::InputNotesAlreadyConsumed) => {
+                Self::TransactionInputNotesAlreadyConsumed(err)
+            },
+            Some(SubmissionRejection::AccountStateStale) => Self::TransactionAccountStateStale(err),
+            Some(SubmissionRejection::Expired) => Self::TransactionExpired(err),
+            Some(SubmissionRejection::Other) => Self::TransactionRejected(err),
+            None => Self::RpcError(err),
+        }
+    }
+}
+
 // ID PREFIX FETCH ERROR
 // ================================================================================================
 
@@ -116,3 +170,111 @@ pub enum IdPrefixFetchError {
     #[error("found more than one element for the provided {0} and only one match is expected")]
     MultipleMatches(String),
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use tonic::Code;
+
+    use super::ClientError;
+    use crate::rpc::{NodeRpcClientEndpoint, RpcError};
+
+    fn submission_error(code: Code, message: &str, details: &[u8]) -> ClientError {
+        ClientError::from_submission_error(RpcError::GrpcError {
+            endpoint: NodeRpcClientEndpoint::SubmitProvenTx,
+            code,
+            message: message.into(),
+            details: details.to_vec(),
+        })
+    }
+
+    #[test]
+    fn submission_rejections_are_explained_by_error_code() {
+        assert!(matches!(
+            submission_error(Code::InvalidArgument, "transaction rejected", &[4]),
+            ClientError::TransactionInputNotesAlreadyConsumed(_)
+        ));
+        assert!(matches!(
+            submission_error(Code::InvalidArgument, "transaction rejected", &[3]),
+            ClientError::TransactionAccountStateStale(_)
+        ));
+        assert!(matches!(
+            submission_error(Code::InvalidArgument, "transaction rejected", &[7]),
+            ClientError::TransactionExpired(_)
+        ));
+        assert!(matches!(
+            submission_error(Code::InvalidArgument, "transaction rejected", &[2]),
+            ClientError::TransactionRejected(_)
+        ));
+
+        // The error code takes precedence over the message
+        assert!(matches!(
+            submission_error(
+                Code::InvalidArgument,
+                "transaction expired at block height 10 but the block height limit was 12",
+                &[4]
+            ),
+            ClientError::TransactionInputNotesAlreadyConsumed(_)
+        ));
+
+        // Internal node errors aren't rejections of the transaction
+        assert!(matches!(
+            submission_error(Code::InvalidArgument, "internal error", &[0]),
+            ClientError::RpcError(_)
+        ));
+    }
+
+    #[test]
+    fn submission_rejections_are_explained_by_message() {
+        assert!(matches!(
+            submission_error(
+                Code::InvalidArgument,
+                "input notes with given nullifiers were already consumed by another transaction",
+                &[]
+            ),
+            ClientError::TransactionInputNotesAlreadyConsumed(_)
+        ));
+        assert!(matches!(
+            submission_error(
+                Code::InvalidArgument,
+                "incorrect account's initial commitment (0x01, current: 0x02)",
+                &[]
+            ),
+            ClientError::TransactionAccountStateStale(_)
+        ));
+        assert!(matches!(
+            submission_error(
+                Code::InvalidArgument,
+                "transaction expired at block height 10 but the block height limit was 12",
+                &[]
+            ),
+            ClientError::TransactionExpired(_)
+        ));
+        assert!(matches!(
+            submission_error(
+                Code::InvalidArgument,
+                "transaction input data from block 5 is rejected as stale because it is older \
+                 than the limit of 8",
+                &[]
+            ),
+            ClientError::TransactionExpired(_)
+        ));
+        assert!(matches!(
+            submission_error(Code::InvalidArgument, "invalid transaction proof", &[]),
+            ClientError::TransactionRejected(_)
+        ));
+
+        // Errors that don't come from the transaction being rejected are kept as RPC errors
+        let error = submission_error(
+            Code::Unavailable,
+            "input notes with given nullifiers were already consumed by another transaction",
+            &[],
+        );
+        assert!(matches!(
+            error,
+            ClientError::RpcError(RpcError::GrpcError { code: Code::Unavailable, .. })
+        ));
+    }
+}
@@ -0,0 +1,721 @@
+use alloc::{boxed::Box, string::String};
+use std::{
+    fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    string::ToString,
+    sync::Arc,
+    vec::Vec,
+};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    KeyInit, XChaCha20Poly1305, XNonce,
+    aead::{Aead, Payload},
+};
+use miden_objects::{
+    Felt, Word,
+    account::{AccountDelta, AuthSecretKey},
+    utils::SliceReader,
+};
+use miden_tx::{
+    AuthenticationError,
+    auth::TransactionAuthenticator,
+    utils::{
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, sync::RwLock,
+    },
+};
+use rand::{Rng, SeedableRng};
+use zeroize::Zeroizing;
+
+use super::{
+    KeyStore, KeyStoreError,
+    fs_keystore::{hash_pub_key, key_file_paths, remove_key_file},
+    public_key_of, sign_with_secret_key,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Name of the file that holds the keystore's key derivation parameters.
+const METADATA_FILE_NAME: &str = "keystore.meta";
+
+/// Extension of the files staged while changing the passphrase. They replace the current files
+/// once the staged metadata file is renamed over the current one.
+const STAGED_FILE_EXTENSION: &str = "next";
+
+/// Current version of the encrypted keystore format.
+const KEYSTORE_VERSION: u8 = 1;
+
+/// Length in bytes of the salt used to derive the encryption key.
+const SALT_LEN: usize = 16;
+
+/// Length in bytes of the derived encryption key.
+const KEY_LEN: usize = 32;
+
+/// Length in bytes of the `XChaCha20Poly1305` nonce.
+const NONCE_LEN: usize = 24;
+
+/// Plaintext sealed into the metadata file, used to verify the passphrase on unlock.
+const PASSPHRASE_CHECK: &[u8] = b"miden-client-keystore";
+
+/// Associated data used when sealing exported keys.
+const EXPORTED_KEYS_AAD: &[u8] = b"miden-client-exported-keys";
+
+// ENCRYPTED FILESYSTEM KEYSTORE
+// ================================================================================================
+
+/// A filesystem-based keystore that encrypts the stored keys at rest.
+///
+/// Keys are laid out like in [`FilesystemKeyStore`](super::FilesystemKeyStore): each key is
+/// stored in a separate file named after the hash of its public key. The contents of each file are
+/// sealed with `XChaCha20Poly1305` using a key derived from a passphrase with Argon2id. The salt
+/// and key derivation parameters are kept in a metadata file in the same directory.
+///
+/// A new keystore must be initialized with a passphrase through
+/// [`EncryptedFilesystemKeyStore::initialize`]. After that, it starts out locked:
+/// [`EncryptedFilesystemKeyStore::unlock`] derives the encryption key from the passphrase and
+/// keeps it in memory until [`EncryptedFilesystemKeyStore::lock`] is called. Reading or writing
+/// keys while the keystore is locked results in [`KeyStoreError::Locked`].
+#[derive(Clone)]
+pub struct EncryptedFilesystemKeyStore<R: Rng> {
+    /// The random number generator used to generate signatures, salts and nonces.
+    rng: Arc<RwLock<R>>,
+    /// The directory where the keys are stored and read from.
+    keys_directory: PathBuf,
+    /// The key used to seal the stored keys. `None` while the keystore is locked.
+    ///
+    /// The lock is also held while writing or removing key files, so that they can't change while
+    /// the passphrase is being changed.
+    encryption_key: Arc<RwLock<Option<Zeroizing<[u8; KEY_LEN]>>>>,
+}
+
+impl<R: Rng> EncryptedFilesystemKeyStore<R> {
+    pub fn with_rng(keys_directory: PathBuf, rng: R) -> Result<Self, KeyStoreError> {
+        if !keys_directory.exists() {
+            fs::create_dir_all(&keys_directory).map_err(|err| {
+                KeyStoreError::StorageError(format!("error creating keys directory: {err:?}"))
+            })?;
+        }
+
+        recover_passphrase_change(&keys_directory)?;
+
+        Ok(EncryptedFilesystemKeyStore {
+            rng: Arc::new(RwLock::new(rng)),
+            keys_directory,
+            encryption_key: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    // LOCK LIFECYCLE
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns `true` if the keystore's passphrase has already been set.
+    pub fn is_initialized(&self) -> bool {
+        self.metadata_path().exists()
+    }
+
+    /// Returns `true` if the keystore is unlocked and keys can be read and written.
+    pub fn is_unlocked(&self) -> bool {
+        self.encryption_key.read().is_some()
+    }
+
+    /// Sets the passphrase of a new keystore by creating its metadata file. The keystore is left
+    /// unlocked.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::AlreadyInitialized`] if the keystore's passphrase was already set.
+    pub fn initialize(&self, passphrase: &str) -> Result<(), KeyStoreError> {
+        let mut encryption_key = self.encryption_key.write();
+        if self.is_initialized() {
+            return Err(KeyStoreError::AlreadyInitialized);
+        }
+
+        let metadata = KeyStoreMetadata::new(&mut *self.rng.write());
+        let new_key = metadata.derive_key_unchecked(passphrase)?;
+        let metadata = metadata.seal_check(&new_key, &mut *self.rng.write())?;
+        write_file(&self.metadata_path(), &metadata.to_bytes())?;
+
+        *encryption_key = Some(new_key);
+        Ok(())
+    }
+
+    /// Unlocks the keystore with the provided passphrase.
+    ///
+    /// # Errors
+    /// - Returns [`KeyStoreError::NotInitialized`] if the keystore's passphrase wasn't set yet.
+    /// - Returns [`KeyStoreError::InvalidPassphrase`] if the passphrase doesn't match the one the
+    ///   keystore was initialized with.
+    pub fn unlock(&self, passphrase: &str) -> Result<(), KeyStoreError> {
+        if !self.is_initialized() {
+            return Err(KeyStoreError::NotInitialized);
+        }
+
+        let encryption_key = self.read_metadata()?.derive_key(passphrase)?;
+        *self.encryption_key.write() = Some(encryption_key);
+        Ok(())
+    }
+
+    /// Locks the keystore, dropping the encryption key from memory.
+    pub fn lock(&self) {
+        *self.encryption_key.write() = None;
+    }
+
+    /// Changes the keystore's passphrase, re-encrypting every stored key.
+    ///
+    /// The new metadata and the re-encrypted keys are first written to staged files next to the
+    /// current ones. Renaming the staged metadata file over the current one commits the change,
+    /// after which the staged keys replace the current ones. If the process stops before the
+    /// change is committed, the staged files are discarded the next time the keystore is opened.
+    /// If it stops afterwards, the remaining staged keys are moved into place instead. Keys can't
+    /// be added or removed while the passphrase is being changed.
+    ///
+    /// The keystore is left unlocked with the new passphrase.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::InvalidPassphrase`] if `current_passphrase` is incorrect.
+    pub fn change_passphrase(
+        &self,
+        current_passphrase: &str,
+        new_passphrase: &str,
+    ) -> Result<(), KeyStoreError> {
+        let mut encryption_key = self.encryption_key.write();
+
+        let current_key = self.read_metadata()?.derive_key(current_passphrase)?;
+
+        let new_metadata = KeyStoreMetadata::new(&mut *self.rng.write());
+        let new_key = new_metadata.derive_key_unchecked(new_passphrase)?;
+        let new_metadata = new_metadata.seal_check(&new_key, &mut *self.rng.write())?;
+
+        if let Err(err) = self.stage_passphrase_change(&current_key, &new_key, &new_metadata) {
+            // Best effort, any file left behind is discarded when the keystore is opened again
+            let _ = discard_staged_files(&self.keys_directory);
+            return Err(err);
+        }
+
+        rename_file(&staged_path(&self.metadata_path()), &self.metadata_path())?;
+        *encryption_key = Some(new_key);
+
+        sync_directory(&self.keys_directory)?;
+        apply_staged_key_files(&self.keys_directory)
+    }
+
+    // KEY MANAGEMENT
+    // --------------------------------------------------------------------------------------------
+
+    /// Encrypts the plaintext keys found in `directory`, which is expected to be the keys
+    /// directory of a [`FilesystemKeyStore`](super::FilesystemKeyStore).
+    ///
+    /// The directory can be the same one used by this keystore, in which case the plaintext files
+    /// get replaced by their encrypted counterparts. Files that are already encrypted with this
+    /// keystore's passphrase are skipped. Plaintext files in other directories are left in place
+    /// and should be removed by the caller once the migration succeeds.
+    ///
+    /// Returns the number of migrated keys.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::Locked`] if the keystore is locked.
+    pub fn migrate_plaintext_keys(&self, directory: &Path) -> Result<usize, KeyStoreError> {
+        if !self.is_unlocked() {
+            return Err(KeyStoreError::Locked);
+        }
+
+        let mut migrated = 0;
+        for path in key_file_paths(directory)? {
+            let key_name = file_name(&path);
+            let contents = read_file(&path)?;
+
+            let already_encrypted = self
+                .encryption_key
+                .read()
+                .as_ref()
+                .is_some_and(|key| open(key, &contents, key_name.as_bytes()).is_ok());
+            if already_encrypted {
+                continue;
+            }
+
+            let key_bytes = Zeroizing::new(hex::decode(contents.trim_ascii()).map_err(|err| {
+                KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
+            })?);
+            let secret_key = AuthSecretKey::read_from_bytes(&key_bytes).map_err(|err| {
+                KeyStoreError::DecodingError(format!(
+                    "error reading secret key from bytes: {err:?}"
+                ))
+            })?;
+
+            self.write_key(&secret_key)?;
+            migrated += 1;
+        }
+
+        Ok(migrated)
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes the staged metadata file and the staged copy of every key file, re-encrypted with
+    /// `new_key`.
+    fn stage_passphrase_change(
+        &self,
+        current_key: &[u8; KEY_LEN],
+        new_key: &[u8; KEY_LEN],
+        new_metadata: &KeyStoreMetadata,
+    ) -> Result<(), KeyStoreError> {
+        // The staged metadata file must exist before any staged key file, as it tells whether
+        // the staged key files belong to a committed change
+        write_file(&staged_path(&self.metadata_path()), &new_metadata.to_bytes())?;
+        sync_directory(&self.keys_directory)?;
+
+        for path in key_file_paths(&self.keys_directory)? {
+            let key_name = file_name(&path);
+            let sealed = read_file(&path)?;
+            let key_bytes = Zeroizing::new(open(current_key, &sealed, key_name.as_bytes())?);
+            let resealed = seal(new_key, &key_bytes, key_name.as_bytes(), &mut *self.rng.write())?;
+
+            write_file(&staged_path(&path), &resealed)?;
+        }
+
+        sync_directory(&self.keys_directory)
+    }
+
+    fn metadata_path(&self) -> PathBuf {
+        self.keys_directory.join(METADATA_FILE_NAME)
+    }
+
+    fn read_metadata(&self) -> Result<KeyStoreMetadata, KeyStoreError> {
+        let bytes = read_file(&self.metadata_path())?;
+        KeyStoreMetadata::read_from_bytes(&bytes).map_err(|err| {
+            KeyStoreError::DecodingError(format!("error reading keystore metadata: {err:?}"))
+        })
+    }
+
+    /// Seals a secret key and writes it to the file named after the hash of its public key.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::Locked`] if the keystore is locked.
+    fn write_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        let key_name = hash_pub_key(public_key_of(key));
+        let key_bytes = Zeroizing::new(key.to_bytes());
+
+        let encryption_key = self.encryption_key.read();
+        let sealed = seal(
+            encryption_key.as_ref().ok_or(KeyStoreError::Locked)?,
+            &key_bytes,
+            key_name.as_bytes(),
+            &mut *self.rng.write(),
+        )?;
+
+        write_file(&self.keys_directory.join(key_name), &sealed)
+    }
+
+    /// Reads and opens the secret key that corresponds to the provided public key, if it exists.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::Locked`] if the keystore is locked.
+    fn read_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        let file_path = self.keys_directory.join(hash_pub_key(pub_key));
+        if !file_path.exists() {
+            if !self.is_unlocked() {
+                return Err(KeyStoreError::Locked);
+            }
+            return Ok(None);
+        }
+
+        self.read_key_file(&file_path).map(Some)
+    }
+
+    /// Reads and opens a sealed secret key file.
+    fn read_key_file(&self, file_path: &Path) -> Result<AuthSecretKey, KeyStoreError> {
+        let encryption_key = self.encryption_key.read();
+        let encryption_key = encryption_key.as_ref().ok_or(KeyStoreError::Locked)?;
+
+        let sealed = read_file(file_path)?;
+        let key_bytes =
+            Zeroizing::new(open(encryption_key, &sealed, file_name(file_path).as_bytes())?);
+
+        AuthSecretKey::read_from_bytes(&key_bytes).map_err(|err| {
+            KeyStoreError::DecodingError(format!("error reading secret key from bytes: {err:?}"))
+        })
+    }
+}
+
+// Provide a default implementation for `StdRng` so you can call
+// EncryptedFilesystemKeyStore::new() without type annotations.
+impl EncryptedFilesystemKeyStore<rand::rngs::StdRng> {
+    /// Creates a new [`EncryptedFilesystemKeyStore`] using [`rand::rngs::StdRng`] as the RNG.
+    pub fn new(keys_directory: PathBuf) -> Result<Self, KeyStoreError> {
+        use rand::rngs::StdRng;
+        let rng = StdRng::from_os_rng();
+
+        EncryptedFilesystemKeyStore::with_rng(keys_directory, rng)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl<R: Rng + Send + Sync> KeyStore for EncryptedFilesystemKeyStore<R> {
+    /// Adds a secret key to the keystore.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::Locked`] if the keystore is locked.
+    async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        self.write_key(key)
+    }
+
+    /// Retrieves a secret key from the keystore given its public key.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::Locked`] if the keystore is locked.
+    async fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        self.read_key(pub_key)
+    }
+
+    /// Returns the public keys of all the stored secret keys.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::Locked`] if the keystore is locked.
+    async fn list_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        key_file_paths(&self.keys_directory)?
+            .iter()
+            .map(|path| self.read_key_file(path).map(|key| public_key_of(&key)))
+            .collect()
+    }
+
+    async fn remove_key(&self, pub_key: Word) -> Result<(), KeyStoreError> {
+        let _guard = self.encryption_key.read();
+        remove_key_file(&self.keys_directory.join(hash_pub_key(pub_key)))
+    }
+}
+
+impl<R: Rng> fmt::Debug for EncryptedFilesystemKeyStore<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedFilesystemKeyStore")
+            .field("keys_directory", &self.keys_directory)
+            .field("unlocked", &self.is_unlocked())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: Rng> TransactionAuthenticator for EncryptedFilesystemKeyStore<R> {
+    /// Gets a signature over a message, given a public key.
+    ///
+    /// The public key should correspond to one of the keys tracked by the keystore, and the
+    /// keystore must be unlocked.
+    ///
+    /// # Errors
+    /// If the public key isn't found in the store, [`AuthenticationError::UnknownPublicKey`] is
+    /// returned.
+    fn get_signature(
+        &self,
+        pub_key: Word,
+        message: Word,
+        _account_delta: &AccountDelta,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        let secret_key = self.read_key(pub_key);
+
+        let mut rng = self.rng.write();
+        sign_with_secret_key(secret_key, pub_key, message, &mut *rng)
+    }
+}
+
+// KEY EXPORT
+// ================================================================================================
+
+/// Encrypts secret keys with a passphrase, so that they can be exported to a file.
+///
+/// The keys are sealed with the same scheme used by [`EncryptedFilesystemKeyStore`]: a key is
+/// derived from the passphrase with Argon2id and a fresh salt, and used to seal the keys with
+/// `XChaCha20Poly1305`. The returned bytes can be decrypted with [`decrypt_exported_keys`].
+pub fn encrypt_exported_keys<R: Rng>(
+    keys: &[AuthSecretKey],
+    passphrase: &str,
+    rng: &mut R,
+) -> Result<Vec<u8>, KeyStoreError> {
+    let metadata = KeyStoreMetadata::new(rng);
+    let encryption_key = metadata.derive_key_unchecked(passphrase)?;
+    let metadata = metadata.seal_check(&encryption_key, rng)?;
+
+    let keys_bytes = Zeroizing::new(keys.to_vec().to_bytes());
+    let sealed_keys = seal(&encryption_key, &keys_bytes, EXPORTED_KEYS_AAD, rng)?;
+
+    let mut bytes = metadata.to_bytes();
+    sealed_keys.write_into(&mut bytes);
+    Ok(bytes)
+}
+
+/// Decrypts secret keys encrypted with [`encrypt_exported_keys`].
+///
+/// # Errors
+/// Returns [`KeyStoreError::InvalidPassphrase`] if the passphrase doesn't match the one the keys
+/// were encrypted with.
+pub fn decrypt_exported_keys(
+    bytes: &[u8],
+    passphrase: &str,
+) -> Result<Vec<AuthSecretKey>, KeyStoreError> {
+    let decoding_error = |err: DeserializationError| {
+        KeyStoreError::DecodingError(format!("error reading exported keys: {err:?}"))
+    };
+
+    let mut reader = SliceReader::new(bytes);
+    let metadata = KeyStoreMetadata::read_from(&mut reader).map_err(decoding_error)?;
+    let sealed_keys = Vec::<u8>::read_from(&mut reader).map_err(decoding_error)?;
+
+    let encryption_key = metadata.derive_key(passphrase)?;
+    let keys_bytes = Zeroizing::new(open(&encryption_key, &sealed_keys, EXPORTED_KEYS_AAD)?);
+
+    Vec::<AuthSecretKey>::read_from_bytes(&keys_bytes).map_err(decoding_error)
+}
+
+// KEYSTORE METADATA
+// ================================================================================================
+
+/// Parameters used to derive the encryption key from the passphrase, along with a sealed check
+/// value used to verify the passphrase.
+struct KeyStoreMetadata {
+    version: u8,
+    salt: [u8; SALT_LEN],
+    memory_cost: u32,
+    time_cost: u32,
+    parallelism: u32,
+    passphrase_check: Vec<u8>,
+}
+
+impl KeyStoreMetadata {
+    /// Creates new metadata with a random salt and the default Argon2id parameters. The
+    /// passphrase check needs to be set through [`KeyStoreMetadata::seal_check`].
+    fn new<R: Rng>(rng: &mut R) -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        rng.fill(&mut salt[..]);
+
+        Self {
+            version: KEYSTORE_VERSION,
+            salt,
+            memory_cost: Params::DEFAULT_M_COST,
+            time_cost: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+            passphrase_check: Vec::new(),
+        }
+    }
+
+    /// Seals the passphrase check value with the provided key.
+    fn seal_check<R: Rng>(
+        mut self,
+        encryption_key: &[u8; KEY_LEN],
+        rng: &mut R,
+    ) -> Result<Self, KeyStoreError> {
+        self.passphrase_check =
+            seal(encryption_key, PASSPHRASE_CHECK, METADATA_FILE_NAME.as_bytes(), rng)?;
+        Ok(self)
+    }
+
+    /// Derives the encryption key from the passphrase and verifies it against the sealed check
+    /// value.
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; KEY_LEN]>, KeyStoreError> {
+        let encryption_key = self.derive_key_unchecked(passphrase)?;
+
+        let check = open(&encryption_key, &self.passphrase_check, METADATA_FILE_NAME.as_bytes())
+            .map_err(|_| KeyStoreError::InvalidPassphrase)?;
+        if check != PASSPHRASE_CHECK {
+            return Err(KeyStoreError::InvalidPassphrase);
+        }
+
+        Ok(encryption_key)
+    }
+
+    /// Derives the encryption key from the passphrase without verifying it.
+    fn derive_key_unchecked(
+        &self,
+        passphrase: &str,
+    ) -> Result<Zeroizing<[u8; KEY_LEN]>, KeyStoreError> {
+        let params = Params::new(self.memory_cost, self.time_cost, self.parallelism, Some(KEY_LEN))
+            .map_err(|err| KeyStoreError::EncryptionError(err.to_string()))?;
+
+        let mut encryption_key = Zeroizing::new([0u8; KEY_LEN]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &self.salt, encryption_key.as_mut())
+            .map_err(|err| KeyStoreError::EncryptionError(err.to_string()))?;
+
+        Ok(encryption_key)
+    }
+}
+
+impl Serializable for KeyStoreMetadata {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.version);
+        target.write_bytes(&self.salt);
+        target.write_u32(self.memory_cost);
+        target.write_u32(self.time_cost);
+        target.write_u32(self.parallelism);
+        self.passphrase_check.write_into(target);
+    }
+}
+
+impl Deserializable for KeyStoreMetadata {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = source.read_u8()?;
+        if version != KEYSTORE_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported keystore version {version}"
+            )));
+        }
+
+        Ok(Self {
+            version,
+            salt: source.read_array()?,
+            memory_cost: source.read_u32()?,
+            time_cost: source.read_u32()?,
+            parallelism: source.read_u32()?,
+            passphrase_check: Vec::<u8>::read_from(source)?,
+        })
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Encrypts `plaintext` with a fresh random nonce. The returned bytes contain the nonce followed by
+/// the ciphertext.
+fn seal<R: Rng>(
+    encryption_key: &[u8; KEY_LEN],
+    plaintext: &[u8],
+    associated_data: &[u8],
+    rng: &mut R,
+) -> Result<Vec<u8>, KeyStoreError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut nonce[..]);
+
+    let cipher = XChaCha20Poly1305::new(encryption_key.into());
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce), Payload { msg: plaintext, aad: associated_data })
+        .map_err(|err| KeyStoreError::EncryptionError(err.to_string()))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend(ciphertext);
+    Ok(sealed)
+}
+
+/// Decrypts bytes produced by [`seal`].
+fn open(
+    encryption_key: &[u8; KEY_LEN],
+    sealed: &[u8],
+    associated_data: &[u8],
+) -> Result<Vec<u8>, KeyStoreError> {
+    if sealed.len() < NONCE_LEN {
+        return Err(KeyStoreError::DecodingError("sealed key is too short".to_string()));
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+    let cipher = XChaCha20Poly1305::new(encryption_key.into());
+    cipher
+        .decrypt(XNonce::from_slice(nonce), Payload { msg: ciphertext, aad: associated_data })
+        .map_err(|err| KeyStoreError::EncryptionError(err.to_string()))
+}
+
+/// Returns the name of a key file, which is used as associated data when sealing the key.
+fn file_name(path: &Path) -> String {
+    path.file_name().and_then(|name| name.to_str()).unwrap_or_default().to_string()
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, KeyStoreError> {
+    fs::read(path)
+        .map_err(|err| KeyStoreError::StorageError(format!("error reading key file: {err:?}")))
+}
+
+/// Writes a file and flushes it to disk.
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), KeyStoreError> {
+    let mut file = fs::File::create(path)
+        .map_err(|err| KeyStoreError::StorageError(format!("error writing key file: {err:?}")))?;
+    file.write_all(contents)
+        .and_then(|()| file.sync_all())
+        .map_err(|err| KeyStoreError::StorageError(format!("error writing key file: {err:?}")))
+}
+
+fn rename_file(from: &Path, to: &Path) -> Result<(), KeyStoreError> {
+    fs::rename(from, to)
+        .map_err(|err| KeyStoreError::StorageError(format!("error replacing key file: {err:?}")))
+}
+
+/// Flushes the entries of a directory to disk, so that the files created and renamed in it
+/// survive a crash. Directories can't be opened on Windows, where this is a no-op.
+fn sync_directory(directory: &Path) -> Result<(), KeyStoreError> {
+    #[cfg(unix)]
+    fs::File::open(directory).and_then(|dir| dir.sync_all()).map_err(|err| {
+        KeyStoreError::StorageError(format!("error syncing keys directory: {err:?}"))
+    })?;
+    #[cfg(not(unix))]
+    let _ = directory;
+
+    Ok(())
+}
+
+// STAGED FILES
+// ================================================================================================
+
+/// Returns the path of the staged copy of a file.
+fn staged_path(path: &Path) -> PathBuf {
+    let mut staged = path.as_os_str().to_owned();
+    staged.push(".");
+    staged.push(STAGED_FILE_EXTENSION);
+    PathBuf::from(staged)
+}
+
+/// Returns the paths of the staged key files in `directory`, along with the path of the key file
+/// each of them replaces.
+fn staged_key_file_paths(directory: &Path) -> Result<Vec<(PathBuf, PathBuf)>, KeyStoreError> {
+    let entries = fs::read_dir(directory).map_err(|err| {
+        KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
+    })?;
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| {
+                KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
+            })?
+            .path();
+
+        let is_staged_key_file = path.extension().is_some_and(|ext| ext == STAGED_FILE_EXTENSION)
+            && path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| stem.parse::<u64>().is_ok());
+        if path.is_file() && is_staged_key_file {
+            let key_path = path.with_extension("");
+            paths.push((path, key_path));
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Replaces every key file with its staged copy.
+fn apply_staged_key_files(directory: &Path) -> Result<(), KeyStoreError> {
+    for (staged, key_path) in staged_key_file_paths(directory)? {
+        rename_file(&staged, &key_path)?;
+    }
+
+    sync_directory(directory)
+}
+
+/// Removes the staged metadata file and every staged key file.
+fn discard_staged_files(directory: &Path) -> Result<(), KeyStoreError> {
+    for (staged, _) in staged_key_file_paths(directory)? {
+        remove_key_file(&staged)?;
+    }
+    remove_key_file(&staged_path(&directory.join(METADATA_FILE_NAME)))?;
+
+    sync_directory(directory)
+}
+
+/// Completes or rolls back a passphrase change that was interrupted.
+///
+/// If the staged metadata file still exists, the change wasn't committed and the staged files are
+/// discarded. Otherwise, the staged key files are already encrypted with the current passphrase
+/// and replace the current key files.
+fn recover_passphrase_change(directory: &Path) -> Result<(), KeyStoreError> {
+    if staged_path(&directory.join(METADATA_FILE_NAME)).exists() {
+        discard_staged_files(directory)
+    } else {
+        apply_staged_key_files(directory)
+    }
+}
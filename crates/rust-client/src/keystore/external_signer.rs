@@ -0,0 +1,338 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+
+use miden_objects::{
+    Felt, Word,
+    account::{AccountDelta, AuthSecretKey},
+};
+use miden_tx::{
+    AuthenticationError,
+    auth::TransactionAuthenticator,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+
+use super::{KeyStore, KeyStoreError};
+use crate::note::{NoteDecryptionKey, NoteEncryptionKey};
+
+// SIGNER MESSAGES
+// ================================================================================================
+
+/// A request sent to an external signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerRequest {
+    /// Requests the public keys of all the secret keys held by the signer.
+    ListKeys,
+    /// Requests a signature over `message` with the secret key that corresponds to `pub_key`. The
+    /// account delta of the transaction being signed is included so the signer can apply its own
+    /// signing policies.
+    Sign {
+        pub_key: Word,
+        message: Word,
+        account_delta: AccountDelta,
+    },
+}
+
+/// A response sent back by an external signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerResponse {
+    /// The public keys of all the secret keys held by the signer.
+    Keys(Vec<Word>),
+    /// The requested signature.
+    Signature(Vec<Felt>),
+    /// The signer doesn't hold the secret key for the requested public key.
+    UnknownPublicKey,
+    /// The signer failed to process the request.
+    Error(String),
+}
+
+const LIST_KEYS_REQUEST: u8 = 0;
+const SIGN_REQUEST: u8 = 1;
+
+const KEYS_RESPONSE: u8 = 0;
+const SIGNATURE_RESPONSE: u8 = 1;
+const UNKNOWN_PUBLIC_KEY_RESPONSE: u8 = 2;
+const ERROR_RESPONSE: u8 = 3;
+
+impl Serializable for SignerRequest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            SignerRequest::ListKeys => target.write_u8(LIST_KEYS_REQUEST),
+            SignerRequest::Sign { pub_key, message, account_delta } => {
+                target.write_u8(SIGN_REQUEST);
+                pub_key.write_into(target);
+                message.write_into(target);
+                account_delta.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for SignerRequest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            LIST_KEYS_REQUEST => Ok(SignerRequest::ListKeys),
+            SIGN_REQUEST => Ok(SignerRequest::Sign {
+                pub_key: Word::read_from(source)?,
+                message: Word::read_from(source)?,
+                account_delta: AccountDelta::read_from(source)?,
+            }),
+            tag => {
+                Err(DeserializationError::InvalidValue(format!("invalid signer request tag {tag}")))
+            },
+        }
+    }
+}
+
+impl Serializable for SignerResponse {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            SignerResponse::Keys(keys) => {
+                target.write_u8(KEYS_RESPONSE);
+                keys.write_into(target);
+            },
+            SignerResponse::Signature(signature) => {
+                target.write_u8(SIGNATURE_RESPONSE);
+                signature.write_into(target);
+            },
+            SignerResponse::UnknownPublicKey => target.write_u8(UNKNOWN_PUBLIC_KEY_RESPONSE),
+            SignerResponse::Error(message) => {
+                target.write_u8(ERROR_RESPONSE);
+                message.as_bytes().to_vec().write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for SignerResponse {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            KEYS_RESPONSE => Ok(SignerResponse::Keys(Vec::<Word>::read_from(source)?)),
+            SIGNATURE_RESPONSE => Ok(SignerResponse::Signature(Vec::<Felt>::read_from(source)?)),
+            UNKNOWN_PUBLIC_KEY_RESPONSE => Ok(SignerResponse::UnknownPublicKey),
+            ERROR_RESPONSE => {
+                let message = String::from_utf8(Vec::<u8>::read_from(source)?)
+                    .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+                Ok(SignerResponse::Error(message))
+            },
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid signer response tag {tag}"
+            ))),
+        }
+    }
+}
+
+// SIGNER TRANSPORT
+// ================================================================================================
+
+/// Sends requests to an external signer and waits for its responses.
+///
+/// Requests are sent synchronously because signatures are requested from within transaction
+/// execution. Implementations are expected to apply their own timeouts.
+pub trait SignerTransport: Send + Sync {
+    /// Sends a request to the signer and returns its response.
+    fn send(&self, request: &SignerRequest) -> Result<SignerResponse, KeyStoreError>;
+}
+
+// EXTERNAL SIGNER KEYSTORE
+// ================================================================================================
+
+/// A keystore that delegates signing to an out-of-process signer, such as a local signing daemon,
+/// so that secret keys never live in the client process.
+///
+/// Communication with the signer goes through a [`SignerTransport`]. Since the secret keys are
+/// managed by the signer, [`KeyStore::add_key`], [`KeyStore::get_key`] and
+/// [`KeyStore::remove_key`] return [`KeyStoreError::UnsupportedOperation`]. For the same reason,
+/// [`KeyStore::export_keys`] fails with the same error and [`KeyStore::get_note_decryption_key`]
+/// never finds a matching key.
+pub struct ExternalSignerKeyStore<T: SignerTransport> {
+    transport: Arc<T>,
+}
+
+impl<T: SignerTransport> ExternalSignerKeyStore<T> {
+    /// Creates a new keystore that sends its requests through the provided transport.
+    pub fn new(transport: T) -> Self {
+        Self { transport: Arc::new(transport) }
+    }
+}
+
+impl<T: SignerTransport> Clone for ExternalSignerKeyStore<T> {
+    fn clone(&self) -> Self {
+        Self { transport: self.transport.clone() }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl<T: SignerTransport> KeyStore for ExternalSignerKeyStore<T> {
+    async fn add_key(&self, _key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        Err(KeyStoreError::UnsupportedOperation(
+            "secret keys must be added directly to the external signer".to_string(),
+        ))
+    }
+
+    async fn get_key(&self, _pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        Err(KeyStoreError::UnsupportedOperation(
+            "secret keys can't be retrieved from the external signer".to_string(),
+        ))
+    }
+
+    async fn list_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        match self.transport.send(&SignerRequest::ListKeys)? {
+            SignerResponse::Keys(keys) => Ok(keys),
+            SignerResponse::Error(message) => Err(KeyStoreError::SignerError(message)),
+            response => Err(KeyStoreError::SignerError(format!(
+                "unexpected response to list keys request: {response:?}"
+            ))),
+        }
+    }
+
+    async fn remove_key(&self, _pub_key: Word) -> Result<(), KeyStoreError> {
+        Err(KeyStoreError::UnsupportedOperation(
+            "secret keys must be removed directly from the external signer".to_string(),
+        ))
+    }
+
+    async fn export_keys(&self) -> Result<Vec<AuthSecretKey>, KeyStoreError> {
+        Err(KeyStoreError::UnsupportedOperation(
+            "secret keys never leave the external signer, back them up through the signer itself"
+                .to_string(),
+        ))
+    }
+
+    /// Returns `None`, as note decryption keys can't be derived from keys held by the external
+    /// signer.
+    async fn get_note_decryption_key(
+        &self,
+        _encryption_key: &NoteEncryptionKey,
+    ) -> Result<Option<NoteDecryptionKey>, KeyStoreError> {
+        Ok(None)
+    }
+}
+
+impl<T: SignerTransport> TransactionAuthenticator for ExternalSignerKeyStore<T> {
+    /// Requests a signature over a message from the external signer, given a public key.
+    ///
+    /// # Errors
+    /// If the signer doesn't hold the secret key for the public key,
+    /// [`AuthenticationError::UnknownPublicKey`] is returned.
+    fn get_signature(
+        &self,
+        pub_key: Word,
+        message: Word,
+        account_delta: &AccountDelta,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        let request = SignerRequest::Sign {
+            pub_key,
+            message,
+            account_delta: account_delta.clone(),
+        };
+
+        match self.transport.send(&request) {
+            Ok(SignerResponse::Signature(signature)) => Ok(signature),
+            Ok(SignerResponse::UnknownPublicKey) => {
+                Err(AuthenticationError::UnknownPublicKey(pub_key.to_hex()))
+            },
+            Ok(SignerResponse::Error(message)) => Err(AuthenticationError::other(message)),
+            Ok(response) => Err(AuthenticationError::other(format!(
+                "unexpected response to sign request: {response:?}"
+            ))),
+            Err(err) => Err(AuthenticationError::other(err.to_string())),
+        }
+    }
+}
+
+// UNIX SOCKET TRANSPORT
+// ================================================================================================
+
+#[cfg(all(feature = "std", unix))]
+mod unix_socket {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+        path::PathBuf,
+        time::Duration,
+        vec,
+    };
+
+    use miden_tx::utils::{Deserializable, Serializable};
+
+    use super::{SignerRequest, SignerResponse, SignerTransport};
+    use crate::keystore::KeyStoreError;
+
+    /// Maximum size in bytes of a response read from the signer's socket.
+    const MAX_RESPONSE_LEN: usize = 1 << 20;
+
+    /// A [`SignerTransport`] that talks to a signing daemon listening on a Unix socket.
+    ///
+    /// A new connection is opened for every request. Each message is framed as a little-endian
+    /// `u32` length followed by the serialized [`SignerRequest`] or [`SignerResponse`]. Responses
+    /// larger than 1 MiB are rejected.
+    #[derive(Debug, Clone)]
+    pub struct UnixSocketTransport {
+        /// Path to the signer's socket.
+        socket_path: PathBuf,
+        /// Read and write timeout for each request.
+        timeout: Duration,
+    }
+
+    impl UnixSocketTransport {
+        /// Default read and write timeout for each request.
+        pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+        /// Creates a new transport that connects to the socket at the provided path, with a
+        /// timeout of [`Self::DEFAULT_TIMEOUT`] for each request.
+        pub fn new(socket_path: PathBuf) -> Self {
+            Self {
+                socket_path,
+                timeout: Self::DEFAULT_TIMEOUT,
+            }
+        }
+
+        /// Sets the read and write timeout for each request.
+        #[must_use]
+        pub fn timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = timeout;
+            self
+        }
+    }
+
+    impl SignerTransport for UnixSocketTransport {
+        fn send(&self, request: &SignerRequest) -> Result<SignerResponse, KeyStoreError> {
+            let io_error =
+                |err: std::io::Error| KeyStoreError::SignerError(format!("socket error: {err}"));
+
+            let mut stream = UnixStream::connect(&self.socket_path).map_err(io_error)?;
+            stream.set_read_timeout(Some(self.timeout)).map_err(io_error)?;
+            stream.set_write_timeout(Some(self.timeout)).map_err(io_error)?;
+
+            let request = request.to_bytes();
+            let request_len = u32::try_from(request.len())
+                .map_err(|_| KeyStoreError::SignerError("request is too large".into()))?;
+            stream.write_all(&request_len.to_le_bytes()).map_err(io_error)?;
+            stream.write_all(&request).map_err(io_error)?;
+
+            let mut response_len = [0u8; 4];
+            stream.read_exact(&mut response_len).map_err(io_error)?;
+            let response_len = u32::from_le_bytes(response_len) as usize;
+            if response_len > MAX_RESPONSE_LEN {
+                return Err(KeyStoreError::SignerError(format!(
+                    "response of {response_len} bytes exceeds the maximum of {MAX_RESPONSE_LEN} bytes"
+                )));
+            }
+
+            let mut response = vec![0u8; response_len];
+            stream.read_exact(&mut response).map_err(io_error)?;
+
+            SignerResponse::read_from_bytes(&response).map_err(|err| {
+                KeyStoreError::DecodingError(format!("error reading signer response: {err:?}"))
+            })
+        }
+    }
+}
+
+#[cfg(all(feature = "std", unix))]
+pub use unix_socket::UnixSocketTransport;
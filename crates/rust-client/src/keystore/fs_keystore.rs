@@ -1,9 +1,9 @@
-use alloc::string::String;
+use alloc::{boxed::Box, string::String};
 use std::{
-    fs::OpenOptions,
+    fs::{self, OpenOptions},
     hash::{DefaultHasher, Hash, Hasher},
     io::{BufRead, BufReader, BufWriter, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     string::ToString,
     sync::Arc,
     vec::Vec,
@@ -20,7 +20,7 @@ use miden_tx::{
 };
 use rand::{Rng, SeedableRng};
 
-use super::KeyStoreError;
+use super::{KeyStore, KeyStoreError, public_key_of, sign_with_secret_key};
 
 /// A filesystem-based keystore that stores keys in separate files and provides transaction
 /// authentication functionality. The public key is hashed and the result is used as the filename
@@ -50,13 +50,9 @@ impl<R: Rng> FilesystemKeyStore<R> {
         })
     }
 
-    /// Adds a secret key to the keystore.
-    pub fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
-        let pub_key = match key {
-            AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
-        };
-
-        let filename = hash_pub_key(pub_key);
+    /// Writes a secret key to the file named after the hash of its public key.
+    fn write_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        let filename = hash_pub_key(public_key_of(key));
 
         let file_path = self.keys_directory.join(filename);
         let file = OpenOptions::new()
@@ -77,35 +73,14 @@ impl<R: Rng> FilesystemKeyStore<R> {
         Ok(())
     }
 
-    /// Retrieves a secret key from the keystore given its public key.
-    pub fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
-        let filename = hash_pub_key(pub_key);
-
-        let file_path = self.keys_directory.join(filename);
+    /// Reads the secret key that corresponds to the provided public key, if it exists.
+    fn read_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        let file_path = self.keys_directory.join(hash_pub_key(pub_key));
         if !file_path.exists() {
             return Ok(None);
         }
 
-        let file = OpenOptions::new().read(true).open(file_path).map_err(|err| {
-            KeyStoreError::StorageError(format!("error opening secret key file: {err:?}"))
-        })?;
-        let mut reader = BufReader::new(file);
-        let mut key_pair_hex = String::new();
-        reader.read_line(&mut key_pair_hex).map_err(|err| {
-            KeyStoreError::StorageError(format!("error reading secret key file: {err:?}"))
-        })?;
-
-        let secret_key_bytes = hex::decode(key_pair_hex.trim()).map_err(|err| {
-            KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
-        })?;
-        let secret_key =
-            AuthSecretKey::read_from_bytes(secret_key_bytes.as_slice()).map_err(|err| {
-                KeyStoreError::DecodingError(format!(
-                    "error reading secret key from bytes: {err:?}"
-                ))
-            })?;
-
-        Ok(Some(secret_key))
+        read_key_file(&file_path).map(Some)
     }
 }
 
@@ -121,6 +96,29 @@ impl FilesystemKeyStore<rand::rngs::StdRng> {
     }
 }
 
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl<R: Rng + Send + Sync> KeyStore for FilesystemKeyStore<R> {
+    async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        self.write_key(key)
+    }
+
+    async fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        self.read_key(pub_key)
+    }
+
+    async fn list_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        key_file_paths(&self.keys_directory)?
+            .iter()
+            .map(|path| read_key_file(path).map(|key| public_key_of(&key)))
+            .collect()
+    }
+
+    async fn remove_key(&self, pub_key: Word) -> Result<(), KeyStoreError> {
+        remove_key_file(&self.keys_directory.join(hash_pub_key(pub_key)))
+    }
+}
+
 impl<R: Rng> TransactionAuthenticator for FilesystemKeyStore<R> {
     /// Gets a signature over a message, given a public key.
     ///
@@ -136,22 +134,75 @@ impl<R: Rng> TransactionAuthenticator for FilesystemKeyStore<R> {
         _account_delta: &AccountDelta,
     ) -> Result<Vec<Felt>, AuthenticationError> {
         let mut rng = self.rng.write();
-
-        let secret_key = self
-            .get_key(pub_key)
-            .map_err(|err| AuthenticationError::other(err.to_string()))?;
-
-        let AuthSecretKey::RpoFalcon512(k) =
-            secret_key.ok_or(AuthenticationError::UnknownPublicKey(pub_key.to_hex()))?;
-
-        miden_tx::auth::signatures::get_falcon_signature(&k, message, &mut *rng)
+        sign_with_secret_key(self.read_key(pub_key), pub_key, message, &mut *rng)
     }
 }
 
+// HELPERS
+// ================================================================================================
+
 /// Hashes a public key to a string representation.
-fn hash_pub_key(pub_key: Word) -> String {
+pub(super) fn hash_pub_key(pub_key: Word) -> String {
     let pub_key = pub_key.to_hex();
     let mut hasher = DefaultHasher::new();
     pub_key.hash(&mut hasher);
     hasher.finish().to_string()
 }
+
+/// Returns the paths of the key files stored in `directory`. Files that aren't named after a public
+/// key hash (such as the metadata of an encrypted keystore) are skipped.
+pub(super) fn key_file_paths(directory: &Path) -> Result<Vec<PathBuf>, KeyStoreError> {
+    let entries = fs::read_dir(directory).map_err(|err| {
+        KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
+    })?;
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| {
+                KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
+            })?
+            .path();
+
+        let is_key_file = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.parse::<u64>().is_ok());
+        if path.is_file() && is_key_file {
+            paths.push(path);
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Removes a key file, if it exists.
+pub(super) fn remove_key_file(file_path: &Path) -> Result<(), KeyStoreError> {
+    if !file_path.exists() {
+        return Ok(());
+    }
+
+    fs::remove_file(file_path).map_err(|err| {
+        KeyStoreError::StorageError(format!("error removing secret key file: {err:?}"))
+    })
+}
+
+/// Reads a plaintext secret key file.
+fn read_key_file(file_path: &Path) -> Result<AuthSecretKey, KeyStoreError> {
+    let file = OpenOptions::new().read(true).open(file_path).map_err(|err| {
+        KeyStoreError::StorageError(format!("error opening secret key file: {err:?}"))
+    })?;
+    let mut reader = BufReader::new(file);
+    let mut key_pair_hex = String::new();
+    reader.read_line(&mut key_pair_hex).map_err(|err| {
+        KeyStoreError::StorageError(format!("error reading secret key file: {err:?}"))
+    })?;
+
+    let secret_key_bytes = hex::decode(key_pair_hex.trim()).map_err(|err| {
+        KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
+    })?;
+
+    AuthSecretKey::read_from_bytes(secret_key_bytes.as_slice()).map_err(|err| {
+        KeyStoreError::DecodingError(format!("error reading secret key from bytes: {err:?}"))
+    })
+}
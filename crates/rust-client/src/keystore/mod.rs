@@ -1,21 +1,182 @@
-use alloc::string::String;
+//! Provides the [`KeyStore`] trait and its implementations, used to manage the secret keys of the
+//! accounts tracked by the client and to sign transactions with them.
+//!
+//! The following implementations are provided:
+//!
+//! - [`FilesystemKeyStore`]: stores each key in plaintext in a separate file (requires `std`).
+//! - [`EncryptedFilesystemKeyStore`]: like the filesystem keystore, but keys are encrypted at rest
+//!   with a passphrase (requires `std`).
+//! - [`ExternalSignerKeyStore`]: forwards signing requests to an out-of-process signer through a
+//!   [`SignerTransport`], so secret keys never live in the client process.
+//! - [`WebKeyStore`]: stores keys in the browser (requires `idxdb`).
 
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use miden_objects::{Word, account::AuthSecretKey};
+use miden_tx::auth::TransactionAuthenticator;
 use thiserror::Error;
 
-#[derive(Debug, Error)]
-pub enum KeyStoreError {
-    #[error("storage error: {0}")]
-    StorageError(String),
-    #[error("decoding error: {0}")]
-    DecodingError(String),
-}
+use crate::note::{NoteDecryptionKey, NoteEncryptionKey};
+
+mod external_signer;
+#[cfg(all(feature = "std", unix))]
+pub use external_signer::UnixSocketTransport;
+pub use external_signer::{ExternalSignerKeyStore, SignerRequest, SignerResponse, SignerTransport};
 
 #[cfg(feature = "std")]
 mod fs_keystore;
 #[cfg(feature = "std")]
 pub use fs_keystore::FilesystemKeyStore;
 
+#[cfg(feature = "std")]
+mod encrypted_fs_keystore;
+#[cfg(feature = "std")]
+pub use encrypted_fs_keystore::{
+    EncryptedFilesystemKeyStore, decrypt_exported_keys, encrypt_exported_keys,
+};
+
 #[cfg(feature = "idxdb")]
 mod web_keystore;
 #[cfg(feature = "idxdb")]
 pub use web_keystore::WebKeyStore;
+
+// KEYSTORE
+// ================================================================================================
+
+/// Manages the secret keys used to authenticate the transactions of the accounts tracked by the
+/// client.
+///
+/// Signing is provided through the [`TransactionAuthenticator`] supertrait, so any keystore can be
+/// passed to the client as its authenticator. Implementations that don't hold the secret keys
+/// themselves (such as [`ExternalSignerKeyStore`]) may return
+/// [`KeyStoreError::UnsupportedOperation`] for the operations that would expose them.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+pub trait KeyStore: TransactionAuthenticator + Send + Sync {
+    /// Adds a secret key to the keystore.
+    async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError>;
+
+    /// Retrieves a secret key from the keystore given its public key. Returns `None` if the key
+    /// isn't tracked by the keystore.
+    async fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError>;
+
+    /// Returns the public keys of all the secret keys tracked by the keystore.
+    async fn list_keys(&self) -> Result<Vec<Word>, KeyStoreError>;
+
+    /// Removes the secret key that corresponds to the provided public key from the keystore.
+    /// Removing a key that isn't tracked is not an error.
+    async fn remove_key(&self, pub_key: Word) -> Result<(), KeyStoreError>;
+
+    /// Returns all the secret keys tracked by the keystore, so they can be backed up and later
+    /// restored through [`KeyStore::import_keys`].
+    async fn export_keys(&self) -> Result<Vec<AuthSecretKey>, KeyStoreError> {
+        let mut keys = Vec::new();
+        for pub_key in self.list_keys().await? {
+            let key = self.get_key(pub_key).await?.ok_or_else(|| {
+                KeyStoreError::StorageError(format!(
+                    "key for public key {} was removed while exporting",
+                    pub_key.to_hex()
+                ))
+            })?;
+            keys.push(key);
+        }
+
+        Ok(keys)
+    }
+
+    /// Adds all the provided secret keys to the keystore. Keys that are already tracked get
+    /// overwritten.
+    async fn import_keys(&self, keys: &[AuthSecretKey]) -> Result<(), KeyStoreError> {
+        for key in keys {
+            self.add_key(key).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the note decryption key, derived from one of the tracked secret keys, that
+    /// corresponds to `encryption_key`. Returns `None` if none of the keys tracked by the keystore
+    /// match it.
+    ///
+    /// Used to decrypt the [`EncryptedNoteFile`](crate::note::EncryptedNoteFile)s addressed to
+    /// the accounts whose keys are tracked by the keystore.
+    async fn get_note_decryption_key(
+        &self,
+        encryption_key: &NoteEncryptionKey,
+    ) -> Result<Option<NoteDecryptionKey>, KeyStoreError> {
+        for pub_key in self.list_keys().await? {
+            let Some(key) = self.get_key(pub_key).await? else {
+                continue;
+            };
+
+            let decryption_key = NoteDecryptionKey::from_auth_secret_key(&key);
+            if decryption_key.encryption_key() == *encryption_key {
+                return Ok(Some(decryption_key));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+// KEYSTORE ERROR
+// ================================================================================================
+
+#[derive(Debug, Error)]
+pub enum KeyStoreError {
+    #[error("storage error: {0}")]
+    StorageError(String),
+    #[error("decoding error: {0}")]
+    DecodingError(String),
+    #[error("encryption error: {0}")]
+    EncryptionError(String),
+    #[error("invalid passphrase")]
+    InvalidPassphrase,
+    #[error("keystore passphrase was already set")]
+    AlreadyInitialized,
+    #[error("keystore passphrase wasn't set yet")]
+    NotInitialized,
+    #[error("keystore is locked")]
+    Locked,
+    #[error("external signer error: {0}")]
+    SignerError(String),
+    #[error("unsupported operation: {0}")]
+    UnsupportedOperation(String),
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the public key that corresponds to the provided secret key.
+#[cfg(any(feature = "std", feature = "idxdb"))]
+pub(crate) fn public_key_of(key: &AuthSecretKey) -> Word {
+    match key {
+        AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
+    }
+}
+
+/// Signs `message` with the secret key retrieved from a keystore for `pub_key`. Shared by the
+/// keystores that hold their secret keys in order to implement [`TransactionAuthenticator`].
+///
+/// # Errors
+/// If `secret_key` is `None`, [`AuthenticationError::UnknownPublicKey`] is returned.
+///
+/// [`AuthenticationError::UnknownPublicKey`]: miden_tx::AuthenticationError::UnknownPublicKey
+#[cfg(any(feature = "std", feature = "idxdb"))]
+pub(crate) fn sign_with_secret_key<R: rand::Rng>(
+    secret_key: Result<Option<AuthSecretKey>, KeyStoreError>,
+    pub_key: Word,
+    message: Word,
+    rng: &mut R,
+) -> Result<Vec<miden_objects::Felt>, miden_tx::AuthenticationError> {
+    use alloc::string::ToString;
+
+    use miden_tx::AuthenticationError;
+
+    let secret_key = secret_key.map_err(|err| AuthenticationError::other(err.to_string()))?;
+
+    let AuthSecretKey::RpoFalcon512(k) =
+        secret_key.ok_or(AuthenticationError::UnknownPublicKey(pub_key.to_hex()))?;
+
+    miden_tx::auth::signatures::get_falcon_signature(&k, message, rng)
+}
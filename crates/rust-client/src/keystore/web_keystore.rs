@@ -1,14 +1,17 @@
-use alloc::{string::ToString, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
 
 use miden_lib::utils::{Deserializable, Serializable};
 use rand::Rng;
 
-use super::KeyStoreError;
+use super::{KeyStore, KeyStoreError, public_key_of, sign_with_secret_key};
 use crate::{
     AuthenticationError, Felt, Word,
     account::AccountDelta,
     auth::{AuthSecretKey, TransactionAuthenticator},
-    store::web_store::account::utils::{get_account_auth_by_pub_key, insert_account_auth},
+    store::web_store::account::utils::{
+        fetch_account_auth_by_pub_key, get_account_auth_by_pub_key,
+        get_all_account_auth_secret_keys, insert_account_auth, remove_account_auth,
+    },
     utils::RwLock,
 };
 
@@ -26,10 +29,23 @@ impl<R: Rng> WebKeyStore<R> {
         WebKeyStore { rng: Arc::new(RwLock::new(rng)) }
     }
 
-    pub async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
-        let pub_key = match &key {
-            AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()).to_hex(),
-        };
+    /// Retrieves a secret key from the keys cached in memory. Keys get cached when fetched through
+    /// [`KeyStore::get_key`].
+    fn get_cached_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        let pub_key_str = pub_key.to_hex();
+        let secret_key_hex = get_account_auth_by_pub_key(pub_key_str).map_err(|_| {
+            KeyStoreError::StorageError("Failed to get item from local storage".to_string())
+        })?;
+
+        decode_secret_key(&secret_key_hex).map(Some)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl<R: Rng + Send + Sync> KeyStore for WebKeyStore<R> {
+    async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
+        let pub_key = public_key_of(key).to_hex();
         let secret_key_hex = hex::encode(key.to_bytes());
 
         insert_account_auth(pub_key, secret_key_hex).await.map_err(|_| {
@@ -39,21 +55,30 @@ impl<R: Rng> WebKeyStore<R> {
         Ok(())
     }
 
-    pub fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
-        let pub_key_str = pub_key.to_hex();
-        let secret_key_hex = get_account_auth_by_pub_key(pub_key_str).map_err(|_| {
-            KeyStoreError::StorageError("Failed to get item from local storage".to_string())
-        })?;
+    async fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        let secret_key_hex =
+            fetch_account_auth_by_pub_key(pub_key.to_hex()).await.map_err(|_| {
+                KeyStoreError::StorageError("Failed to get item from local storage".to_string())
+            })?;
 
-        let secret_key_bytes = hex::decode(secret_key_hex).map_err(|err| {
-            KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
-        })?;
+        secret_key_hex.as_deref().map(decode_secret_key).transpose()
+    }
 
-        let secret_key = AuthSecretKey::read_from_bytes(&secret_key_bytes).map_err(|err| {
-            KeyStoreError::DecodingError(format!("error reading secret key: {err:?}"))
+    async fn list_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        let secret_keys = get_all_account_auth_secret_keys().await.map_err(|_| {
+            KeyStoreError::StorageError("Failed to get items from local storage".to_string())
         })?;
 
-        Ok(Some(secret_key))
+        secret_keys
+            .iter()
+            .map(|secret_key_hex| decode_secret_key(secret_key_hex).map(|key| public_key_of(&key)))
+            .collect()
+    }
+
+    async fn remove_key(&self, pub_key: Word) -> Result<(), KeyStoreError> {
+        remove_account_auth(pub_key.to_hex()).await.map_err(|_| {
+            KeyStoreError::StorageError("Failed to remove item from local storage".to_string())
+        })
     }
 }
 
@@ -72,11 +97,15 @@ impl<R: Rng> TransactionAuthenticator for WebKeyStore<R> {
         _account_delta: &AccountDelta,
     ) -> Result<Vec<Felt>, AuthenticationError> {
         let mut rng = self.rng.write();
-        let secret_key = self
-            .get_key(pub_key)
-            .map_err(|err| AuthenticationError::other(err.to_string()))?;
-        let AuthSecretKey::RpoFalcon512(k) =
-            secret_key.ok_or(AuthenticationError::UnknownPublicKey(pub_key.to_hex()))?;
-        miden_tx::auth::signatures::get_falcon_signature(&k, message, &mut *rng)
+        sign_with_secret_key(self.get_cached_key(pub_key), pub_key, message, &mut *rng)
     }
 }
+
+fn decode_secret_key(secret_key_hex: &str) -> Result<AuthSecretKey, KeyStoreError> {
+    let secret_key_bytes = hex::decode(secret_key_hex).map_err(|err| {
+        KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
+    })?;
+
+    AuthSecretKey::read_from_bytes(&secret_key_bytes)
+        .map_err(|err| KeyStoreError::DecodingError(format!("error reading secret key: {err:?}")))
+}
@@ -15,6 +15,9 @@
 //!
 //! - **Notes:** Contains types and utilities for working with notes in the Miden client.
 //!
+//! - **Note transport:** Delivers private notes to their recipients through a pluggable transport,
+//!   such as a relay server, with their details encrypted.
+//!
 //! - **RPC:** Facilitates communication with Miden node, exposing RPC methods for syncing state,
 //!   fetching block headers, and submitting transactions.
 //!
@@ -120,6 +123,7 @@ extern crate std;
 pub mod account;
 pub mod keystore;
 pub mod note;
+pub mod note_transport;
 pub mod rpc;
 pub mod store;
 pub mod sync;
@@ -154,7 +158,10 @@ pub mod asset {
 pub mod auth {
     pub use miden_lib::AuthScheme;
     pub use miden_objects::account::AuthSecretKey;
-    pub use miden_tx::auth::{BasicAuthenticator, TransactionAuthenticator};
+    pub use miden_tx::{
+        AuthenticationError,
+        auth::{BasicAuthenticator, TransactionAuthenticator},
+    };
 }
 
 /// Provides types for working with blocks within the Miden network.
@@ -200,14 +207,18 @@ pub mod testing {
     pub use crate::test_utils::*;
 }
 
-use alloc::sync::Arc;
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use core::num::NonZeroUsize;
 
 use miden_lib::utils::ScriptBuilder;
-use miden_objects::crypto::rand::FeltRng;
+use miden_objects::{crypto::rand::FeltRng, note::NoteTag};
 use miden_tx::{LocalTransactionProver, auth::TransactionAuthenticator};
+use note::NoteRelevanceChecker;
+use note_transport::{NoteChannelKey, NoteTransport};
 use rand::RngCore;
 use rpc::NodeRpcClient;
 use store::Store;
+use sync::SyncListener;
 
 // MIDEN CLIENT
 // ================================================================================================
@@ -241,6 +252,18 @@ pub struct Client {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     max_block_number_delta: Option<u32>,
+    /// The number of sync steps after which the sync updates are applied to the store. If `None`,
+    /// the updates are applied once the chain tip is reached.
+    sync_chunk_size: Option<NonZeroUsize>,
+    /// Listeners that get notified of the changes detected while syncing the client's state.
+    sync_listeners: Vec<Arc<dyn SyncListener>>,
+    /// Custom note relevance checkers used by the note screener, keyed by note script root.
+    note_relevance_checkers: BTreeMap<Word, Arc<dyn NoteRelevanceChecker>>,
+    /// An optional [`NoteTransport`] used to deliver private notes to their recipients.
+    note_transport: Option<Arc<dyn NoteTransport>>,
+    /// The keys used to encrypt and decrypt the private notes sent through the note transport,
+    /// keyed by the tag the notes are addressed to.
+    note_channels: BTreeMap<NoteTag, NoteChannelKey>,
 }
 
 /// Construction and access methods.
@@ -291,6 +314,11 @@ impl Client {
             exec_options,
             tx_graceful_blocks,
             max_block_number_delta,
+            sync_chunk_size: None,
+            sync_listeners: Vec::new(),
+            note_relevance_checkers: BTreeMap::new(),
+            note_transport: None,
+            note_channels: BTreeMap::new(),
         }
     }
 
@@ -0,0 +1,379 @@
+//! Provides an encrypted envelope for [`NoteFile`]s, so that exported notes can only be imported
+//! by their intended recipient.
+//!
+//! Note files contain the secrets needed to consume a note (its serial number and inputs), so
+//! anyone who gets hold of one can consume the note. An [`EncryptedNoteFile`] is addressed to a
+//! [`NoteEncryptionKey`], an X25519 public key, and can only be decrypted with the matching
+//! [`NoteDecryptionKey`].
+//!
+//! Decryption keys are derived from the secret keys that authenticate accounts, so they don't need
+//! to be stored separately: a recipient shares the encryption key derived from one of its account
+//! keys, and decrypts the files addressed to it with the keys in its keystore (see
+//! [`KeyStore::get_note_decryption_key`](crate::keystore::KeyStore::get_note_decryption_key)).
+//!
+//! Files are encrypted with an ephemeral X25519 key exchange: the shared secret is turned into a
+//! key with BLAKE3 and the serialized note file is encrypted with XChaCha20-Poly1305.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use chacha20poly1305::{
+    KeyInit, XChaCha20Poly1305, XNonce,
+    aead::{Aead, Payload},
+};
+use miden_objects::{account::AuthSecretKey, note::NoteFile};
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use rand::RngCore;
+use thiserror::Error;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// Magic bytes at the start of every serialized [`EncryptedNoteFile`].
+const MAGIC: &[u8; 4] = b"mnef";
+
+/// Version of the [`EncryptedNoteFile`] format.
+const VERSION: u8 = 1;
+
+/// Length in bytes of the nonce used to encrypt the note file.
+const NONCE_LEN: usize = 24;
+
+/// Context used to derive note decryption keys from account secret keys.
+const DECRYPTION_KEY_CONTEXT: &str = "miden-client 2025-08 note decryption key";
+
+/// Context used to derive the encryption key of a file from the key exchange.
+const FILE_KEY_CONTEXT: &str = "miden-client 2025-08 encrypted note file";
+
+// NOTE ENCRYPTION KEY
+// ================================================================================================
+
+/// The public key that [`EncryptedNoteFile`]s are addressed to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct NoteEncryptionKey(PublicKey);
+
+impl NoteEncryptionKey {
+    /// Returns a new [`NoteEncryptionKey`] from its raw bytes.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(PublicKey::from(bytes))
+    }
+
+    /// Returns the raw bytes of the key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        self.0.as_bytes()
+    }
+
+    /// Returns the key as a `0x`-prefixed hex string.
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.as_bytes()))
+    }
+
+    /// Parses a key from a hex string, with or without the `0x` prefix.
+    pub fn from_hex(hex_key: &str) -> Result<Self, NoteEncryptionError> {
+        let hex_key = hex_key.strip_prefix("0x").unwrap_or(hex_key);
+
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(hex_key, &mut bytes)
+            .map_err(|err| NoteEncryptionError::InvalidKey(err.to_string()))?;
+
+        Ok(Self::new(bytes))
+    }
+}
+
+impl FromStr for NoteEncryptionKey {
+    type Err = NoteEncryptionError;
+
+    fn from_str(hex_key: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(hex_key)
+    }
+}
+
+impl fmt::Display for NoteEncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl fmt::Debug for NoteEncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NoteEncryptionKey({})", self.to_hex())
+    }
+}
+
+impl Serializable for NoteEncryptionKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(self.as_bytes());
+    }
+}
+
+impl Deserializable for NoteEncryptionKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self::new(source.read_array()?))
+    }
+}
+
+// NOTE DECRYPTION KEY
+// ================================================================================================
+
+/// The secret key used to decrypt the [`EncryptedNoteFile`]s addressed to its
+/// [`NoteEncryptionKey`].
+#[derive(Clone)]
+pub struct NoteDecryptionKey(StaticSecret);
+
+impl NoteDecryptionKey {
+    /// Generates a random [`NoteDecryptionKey`].
+    pub fn with_rng<R: RngCore>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        Self(StaticSecret::from(bytes))
+    }
+
+    /// Derives the [`NoteDecryptionKey`] of an account secret key. The same secret key always
+    /// derives the same decryption key.
+    pub fn from_auth_secret_key(secret_key: &AuthSecretKey) -> Self {
+        let bytes = blake3::derive_key(DECRYPTION_KEY_CONTEXT, &secret_key.to_bytes());
+        Self(StaticSecret::from(bytes))
+    }
+
+    /// Returns the [`NoteEncryptionKey`] that files decrypted by this key are addressed to.
+    pub fn encryption_key(&self) -> NoteEncryptionKey {
+        NoteEncryptionKey(PublicKey::from(&self.0))
+    }
+}
+
+impl fmt::Debug for NoteDecryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NoteDecryptionKey({})", self.encryption_key())
+    }
+}
+
+// ENCRYPTED NOTE FILE
+// ================================================================================================
+
+/// A [`NoteFile`] encrypted for the holder of the [`NoteDecryptionKey`] of `recipient`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedNoteFile {
+    /// The key the file is addressed to.
+    recipient: NoteEncryptionKey,
+    /// The ephemeral public key of the key exchange.
+    ephemeral_key: NoteEncryptionKey,
+    /// The nonce used to encrypt the note file.
+    nonce: [u8; NONCE_LEN],
+    /// The encrypted note file.
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedNoteFile {
+    /// Encrypts the note file for the holder of the decryption key of `recipient`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NoteEncryptionError::InvalidKey`] if the recipient key is a low-order point, which
+    /// wouldn't keep the note file secret.
+    pub fn encrypt<R: RngCore>(
+        note_file: &NoteFile,
+        recipient: NoteEncryptionKey,
+        rng: &mut R,
+    ) -> Result<Self, NoteEncryptionError> {
+        let ephemeral_secret = NoteDecryptionKey::with_rng(rng);
+        let ephemeral_key = ephemeral_secret.encryption_key();
+
+        let shared_secret = ephemeral_secret.0.diffie_hellman(&recipient.0);
+        if !shared_secret.was_contributory() {
+            return Err(NoteEncryptionError::InvalidKey(
+                "the recipient key is a low-order point".to_string(),
+            ));
+        }
+
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+
+        let mut encrypted_file = Self {
+            recipient,
+            ephemeral_key,
+            nonce,
+            ciphertext: Vec::new(),
+        };
+        let file_key = encrypted_file.file_key(shared_secret.as_bytes());
+        encrypted_file.ciphertext = XChaCha20Poly1305::new((&file_key).into())
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &note_file.to_bytes(),
+                    aad: &encrypted_file.header_bytes(),
+                },
+            )
+            .map_err(|_| NoteEncryptionError::EncryptionFailed)?;
+
+        Ok(encrypted_file)
+    }
+
+    /// Decrypts the note file.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`NoteEncryptionError::WrongRecipient`] if the file isn't addressed to the
+    ///   decryption key.
+    /// - Returns [`NoteEncryptionError::DecryptionFailed`] if the file was tampered with.
+    pub fn decrypt(&self, key: &NoteDecryptionKey) -> Result<NoteFile, NoteEncryptionError> {
+        if key.encryption_key() != self.recipient {
+            return Err(NoteEncryptionError::WrongRecipient(self.recipient));
+        }
+
+        let shared_secret = key.0.diffie_hellman(&self.ephemeral_key.0);
+        let file_key = self.file_key(shared_secret.as_bytes());
+        let plaintext = XChaCha20Poly1305::new((&file_key).into())
+            .decrypt(
+                XNonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &self.header_bytes(),
+                },
+            )
+            .map_err(|_| NoteEncryptionError::DecryptionFailed)?;
+
+        NoteFile::read_from_bytes(&plaintext).map_err(NoteEncryptionError::InvalidNoteFile)
+    }
+
+    /// Returns the key the file is addressed to.
+    pub fn recipient(&self) -> NoteEncryptionKey {
+        self.recipient
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the serialized fields that precede the ciphertext, which are authenticated along
+    /// with it.
+    fn header_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        self.recipient.write_into(&mut bytes);
+        self.ephemeral_key.write_into(&mut bytes);
+        bytes.extend_from_slice(&self.nonce);
+        bytes
+    }
+
+    /// Derives the key used to encrypt the file from the shared secret of the key exchange.
+    fn file_key(&self, shared_secret: &[u8; 32]) -> [u8; 32] {
+        let mut key_material = Vec::with_capacity(96);
+        key_material.extend_from_slice(shared_secret);
+        key_material.extend_from_slice(self.ephemeral_key.as_bytes());
+        key_material.extend_from_slice(self.recipient.as_bytes());
+
+        blake3::derive_key(FILE_KEY_CONTEXT, &key_material)
+    }
+}
+
+impl Serializable for EncryptedNoteFile {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.header_bytes());
+        self.ciphertext.write_into(target);
+    }
+}
+
+impl Deserializable for EncryptedNoteFile {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let magic: [u8; 4] = source.read_array()?;
+        if &magic != MAGIC {
+            return Err(DeserializationError::InvalidValue(
+                "not an encrypted note file".to_string(),
+            ));
+        }
+
+        let version = source.read_u8()?;
+        if version != VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported encrypted note file version {version}"
+            )));
+        }
+
+        Ok(Self {
+            recipient: NoteEncryptionKey::read_from(source)?,
+            ephemeral_key: NoteEncryptionKey::read_from(source)?,
+            nonce: source.read_array()?,
+            ciphertext: Vec::<u8>::read_from(source)?,
+        })
+    }
+}
+
+// NOTE ENCRYPTION ERROR
+// ================================================================================================
+
+/// Errors generated while encrypting or decrypting note files.
+#[derive(Debug, Error)]
+pub enum NoteEncryptionError {
+    #[error("invalid note encryption key: {0}")]
+    InvalidKey(String),
+    #[error("failed to encrypt note file")]
+    EncryptionFailed,
+    #[error("note file is addressed to note encryption key {0}")]
+    WrongRecipient(NoteEncryptionKey),
+    #[error("failed to decrypt note file, it may have been tampered with")]
+    DecryptionFailed,
+    #[error("decrypted note file is invalid")]
+    InvalidNoteFile(#[source] DeserializationError),
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{
+        Felt, Word,
+        account::AuthSecretKey,
+        crypto::dsa::rpo_falcon512::SecretKey,
+        note::{NoteFile, NoteId},
+    };
+    use miden_tx::utils::{Deserializable, Serializable};
+
+    use super::{EncryptedNoteFile, NoteDecryptionKey, NoteEncryptionError, NoteEncryptionKey};
+
+    #[test]
+    fn note_files_are_decrypted_by_their_recipient() {
+        let mut rng = rand::rng();
+        let secret_key = AuthSecretKey::RpoFalcon512(SecretKey::new());
+        let decryption_key = NoteDecryptionKey::from_auth_secret_key(&secret_key);
+        let encryption_key = decryption_key.encryption_key();
+
+        // Keys are derived deterministically and survive a hex round trip
+        assert_eq!(
+            NoteDecryptionKey::from_auth_secret_key(&secret_key).encryption_key(),
+            encryption_key
+        );
+        assert_eq!(NoteEncryptionKey::from_hex(&encryption_key.to_hex()).unwrap(), encryption_key);
+
+        let note_file = NoteFile::NoteId(NoteId::from(Word::from([Felt::new(7); 4])));
+        let encrypted_file =
+            EncryptedNoteFile::encrypt(&note_file, encryption_key, &mut rng).unwrap();
+        let encrypted_file =
+            EncryptedNoteFile::read_from_bytes(&encrypted_file.to_bytes()).unwrap();
+        assert_eq!(encrypted_file.recipient(), encryption_key);
+
+        let NoteFile::NoteId(note_id) = encrypted_file.decrypt(&decryption_key).unwrap() else {
+            panic!("the decrypted note file should contain a note ID");
+        };
+        assert_eq!(note_id, NoteId::from(Word::from([Felt::new(7); 4])));
+
+        // Other keys can't decrypt the file
+        assert!(matches!(
+            encrypted_file.decrypt(&NoteDecryptionKey::with_rng(&mut rng)),
+            Err(NoteEncryptionError::WrongRecipient(_))
+        ));
+
+        // Tampered files are rejected
+        let mut tampered_bytes = encrypted_file.to_bytes();
+        let last_byte = tampered_bytes.len() - 1;
+        tampered_bytes[last_byte] ^= 1;
+        let tampered_file = EncryptedNoteFile::read_from_bytes(&tampered_bytes).unwrap();
+        assert!(matches!(
+            tampered_file.decrypt(&decryption_key),
+            Err(NoteEncryptionError::DecryptionFailed)
+        ));
+
+        // Plain note files aren't mistaken for encrypted ones
+        assert!(EncryptedNoteFile::read_from_bytes(&note_file.to_bytes()).is_err());
+    }
+}
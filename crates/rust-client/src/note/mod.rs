@@ -56,15 +56,16 @@
 //! For more details on the API and error handling, see the documentation for the specific functions
 //! and types in this module.
 
-use alloc::{string::ToString, vec::Vec};
+use alloc::{string::ToString, sync::Arc, vec::Vec};
 
-use miden_objects::account::AccountId;
+use miden_objects::{Word, account::AccountId};
 
 use crate::{
     Client, ClientError, IdPrefixFetchError,
-    store::{InputNoteRecord, NoteFilter, OutputNoteRecord},
+    store::{InputNoteRecord, NoteFilter, NoteSort, OutputNoteRecord, Page},
 };
 
+mod encrypted_note_file;
 mod import;
 mod note_screener;
 mod note_update_tracker;
@@ -72,6 +73,9 @@ mod note_update_tracker;
 // RE-EXPORTS
 // ================================================================================================
 
+pub use encrypted_note_file::{
+    EncryptedNoteFile, NoteDecryptionKey, NoteEncryptionError, NoteEncryptionKey,
+};
 pub use miden_lib::note::{
     create_p2id_note, create_swap_note,
     utils::{build_p2id_recipient, build_swap_tag},
@@ -81,12 +85,14 @@ pub use miden_objects::{
     NoteError,
     block::BlockNumber,
     note::{
-        Note, NoteAssets, NoteExecutionHint, NoteExecutionMode, NoteFile, NoteId,
+        Note, NoteAssets, NoteExecutionHint, NoteExecutionMode, NoteFile, NoteHeader, NoteId,
         NoteInclusionProof, NoteInputs, NoteMetadata, NoteRecipient, NoteScript, NoteTag, NoteType,
         Nullifier,
     },
 };
-pub use note_screener::{NoteConsumability, NoteRelevance, NoteScreener, NoteScreenerError};
+pub use note_screener::{
+    NoteConsumability, NoteRelevance, NoteRelevanceChecker, NoteScreener, NoteScreenerError,
+};
 pub use note_update_tracker::{
     InputNoteUpdate, NoteUpdateTracker, NoteUpdateType, OutputNoteUpdate,
 };
@@ -109,6 +115,17 @@ impl Client {
         self.store.get_input_notes(filter).await.map_err(Into::into)
     }
 
+    /// Retrieves a [`Page`] of the input notes managed by the client, filtered by [`NoteFilter`]
+    /// and sorted as specified by the [`NoteSort`].
+    pub async fn get_input_notes_page(
+        &self,
+        filter: NoteFilter,
+        sort: NoteSort,
+        page: Page,
+    ) -> Result<Vec<InputNoteRecord>, ClientError> {
+        self.store.get_input_notes_page(filter, sort, page).await.map_err(Into::into)
+    }
+
     /// Returns the input notes and their consumability. Assuming the notes will be consumed by a
     /// normal consume transaction. If `account_id` is None then all consumable input notes are
     /// returned.
@@ -122,7 +139,7 @@ impl Client {
     ) -> Result<Vec<(InputNoteRecord, Vec<NoteConsumability>)>, ClientError> {
         let committed_notes = self.store.get_input_notes(NoteFilter::Committed).await?;
 
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        let note_screener = self.note_screener();
 
         let mut relevant_notes = Vec::new();
         for input_note in committed_notes {
@@ -152,7 +169,7 @@ impl Client {
         &self,
         note: InputNoteRecord,
     ) -> Result<Vec<NoteConsumability>, ClientError> {
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        let note_screener = self.note_screener();
         note_screener
             .check_relevance(&note.clone().try_into()?)
             .await
@@ -178,6 +195,17 @@ impl Client {
         self.store.get_output_notes(filter).await.map_err(Into::into)
     }
 
+    /// Retrieves a [`Page`] of the output notes managed by the client, filtered by [`NoteFilter`]
+    /// and sorted as specified by the [`NoteSort`].
+    pub async fn get_output_notes_page(
+        &self,
+        filter: NoteFilter,
+        sort: NoteSort,
+        page: Page,
+    ) -> Result<Vec<OutputNoteRecord>, ClientError> {
+        self.store.get_output_notes_page(filter, sort, page).await.map_err(Into::into)
+    }
+
     /// Retrieves the output note given a [`NoteId`]. Returns `None` if the note is not found.
     pub async fn get_output_note(
         &self,
@@ -185,6 +213,33 @@ impl Client {
     ) -> Result<Option<OutputNoteRecord>, ClientError> {
         Ok(self.store.get_output_notes(NoteFilter::Unique(note_id)).await?.pop())
     }
+
+    // NOTE SCREENING
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers a [`NoteRelevanceChecker`] for notes with the provided script root. The checker
+    /// will be used to determine the relevance of such notes when syncing, executing transactions
+    /// and checking note consumability, instead of the default checks for well-known notes.
+    ///
+    /// If a checker was already registered for the script root, it gets replaced.
+    pub fn register_note_relevance_checker(
+        &mut self,
+        script_root: Word,
+        checker: Arc<dyn NoteRelevanceChecker>,
+    ) {
+        self.note_relevance_checkers.insert(script_root, checker);
+    }
+
+    /// Returns a [`NoteScreener`] that uses the client's store, authenticator and registered
+    /// [`NoteRelevanceChecker`]s.
+    pub(crate) fn note_screener(&self) -> NoteScreener {
+        let mut note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        for (script_root, checker) in &self.note_relevance_checkers {
+            note_screener.add_relevance_checker(*script_root, checker.clone());
+        }
+
+        note_screener
+    }
 }
 
 /// Returns the client input note whose ID starts with `note_id_prefix`.
@@ -232,3 +287,24 @@ pub async fn get_input_note_with_id_prefix(
         .pop()
         .expect("input_note_records should always have one element"))
 }
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the ID of the account targeted by a P2ID or P2IDE note with the provided recipient, or
+/// `None` if the recipient doesn't belong to one of these notes.
+pub(crate) fn well_known_note_target(recipient: &NoteRecipient) -> Option<AccountId> {
+    let script_root = recipient.script().root();
+    if script_root != WellKnownNote::P2ID.script_root()
+        && script_root != WellKnownNote::P2IDE.script_root()
+    {
+        return None;
+    }
+
+    // The first two inputs of P2ID and P2IDE notes are the suffix and prefix of the target
+    recipient
+        .inputs()
+        .values()
+        .get(..2)
+        .and_then(|target| AccountId::try_from([target[1], target[0]]).ok())
+}
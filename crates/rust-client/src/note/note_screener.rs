@@ -1,9 +1,9 @@
-use alloc::{sync::Arc, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
 use core::fmt;
 
 use miden_lib::{account::interface::AccountInterface, note::well_known_note::WellKnownNote};
 use miden_objects::{
-    AccountError, AssetError,
+    AccountError, AssetError, Word,
     account::{Account, AccountId},
     assembly::DefaultSourceManager,
     note::{Note, NoteId},
@@ -15,8 +15,9 @@ use miden_tx::{
 };
 use thiserror::Error;
 
+use super::well_known_note_target;
 use crate::{
-    store::{Store, StoreError, data_store::ClientDataStore},
+    store::{AccountRecord, Store, StoreError, data_store::ClientDataStore},
     transaction::{TransactionRequestBuilder, TransactionRequestError},
 };
 
@@ -44,17 +45,46 @@ impl fmt::Display for NoteRelevance {
     }
 }
 
+// NOTE RELEVANCE CHECKER
+// ================================================================================================
+
+/// Determines whether notes with a specific script are relevant to a given account.
+///
+/// Checkers are registered in the [`NoteScreener`] for a note script root (see
+/// [`Client::register_note_relevance_checker`](crate::Client::register_note_relevance_checker)).
+/// When a note with that script root is screened, the checker is used instead of the default
+/// consumability checks, which only know about the well-known notes.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+pub trait NoteRelevanceChecker: Send + Sync {
+    /// Returns the relevance of the note for the provided account, or `None` if the account can't
+    /// consume the note.
+    async fn check_relevance(
+        &self,
+        note: &Note,
+        account: &Account,
+    ) -> Result<Option<NoteRelevance>, NoteScreenerError>;
+}
+
+// NOTE SCREENER
+// ================================================================================================
+
 /// Provides functionality for testing whether a note is relevant to the client or not.
 ///
 /// Here, relevance is based on whether the note is able to be consumed by an account that is
 /// tracked in the provided `store`. This can be derived in a number of ways, such as looking
 /// at the combination of script root and note inputs. For example, a P2ID note is relevant
 /// for a specific account ID if this ID is its first note input.
+///
+/// Custom [`NoteRelevanceChecker`]s can be registered for specific note script roots to decide
+/// the relevance of notes that the screener doesn't know about.
 pub struct NoteScreener {
     /// A reference to the client's store, used to fetch necessary data to check consumability.
     store: Arc<dyn Store>,
     /// A reference to the transaction authenticator
     authenticator: Option<Arc<dyn TransactionAuthenticator>>,
+    /// Custom relevance checkers, keyed by the root of the note script they apply to.
+    relevance_checkers: BTreeMap<Word, Arc<dyn NoteRelevanceChecker>>,
 }
 
 impl NoteScreener {
@@ -62,30 +92,63 @@ impl NoteScreener {
         store: Arc<dyn Store>,
         authenticator: Option<Arc<dyn TransactionAuthenticator>>,
     ) -> Self {
-        Self { store, authenticator }
+        Self {
+            store,
+            authenticator,
+            relevance_checkers: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a [`NoteRelevanceChecker`] for notes with the provided script root. If a checker
+    /// was already registered for the script root, it gets replaced.
+    pub fn add_relevance_checker(
+        &mut self,
+        script_root: Word,
+        checker: Arc<dyn NoteRelevanceChecker>,
+    ) {
+        self.relevance_checkers.insert(script_root, checker);
     }
 
     /// Returns a vector of tuples describing the relevance of the provided note to the
     /// accounts monitored by this screener.
     ///
-    /// Does a fast check for known scripts (P2ID, P2IDE, SWAP). We're currently
-    /// unable to execute notes that aren't committed so a slow check for other scripts is
-    /// currently not available.
+    /// If a [`NoteRelevanceChecker`] was registered for the note's script root, it's used to
+    /// determine the relevance for each account. Otherwise, does a fast check for known scripts
+    /// (P2ID, P2IDE, SWAP). We're currently unable to execute notes that aren't committed so a
+    /// slow check for other scripts is currently not available.
     ///
     /// If relevance can't be determined, the screener defaults to setting the note as consumable.
     pub async fn check_relevance(
         &self,
         note: &Note,
     ) -> Result<Vec<NoteConsumability>, NoteScreenerError> {
+        let relevance_checker = self.relevance_checkers.get(&note.script().root());
+
         let mut note_relevances = vec![];
         for id in self.store.get_account_ids().await? {
+            // Accounts that can't consume the note are skipped without loading them
+            if relevance_checker.is_none() && Self::is_unrelated_account(note, id) {
+                continue;
+            }
+
             let account_record = self
                 .store
                 .get_account(id)
                 .await?
                 .ok_or(NoteScreenerError::AccountDataNotFound(id))?;
 
-            match self.check_standard_consumability(account_record.account(), note).await {
+            if let Some(relevance_checker) = relevance_checker {
+                // If an error occurs while checking relevance, we count it as not relevant for
+                // that account
+                if let Ok(Some(relevance)) =
+                    relevance_checker.check_relevance(note, account_record.account()).await
+                {
+                    note_relevances.push((id, relevance));
+                }
+                continue;
+            }
+
+            match self.check_standard_consumability(&account_record, note).await {
                 Ok(Some(relevance)) => {
                     note_relevances.push((id, relevance));
                 },
@@ -114,9 +177,10 @@ impl NoteScreener {
     /// account.
     async fn check_standard_consumability(
         &self,
-        account: &Account,
+        account_record: &AccountRecord,
         note: &Note,
     ) -> Result<Option<NoteRelevance>, NoteScreenerError> {
+        let account = account_record.account();
         let transaction_request =
             TransactionRequestBuilder::new().build_consume_notes(vec![note.id()])?;
 
@@ -129,7 +193,9 @@ impl NoteScreener {
         let input_notes = InputNotes::new(vec![InputNote::unauthenticated(note.clone())])
             .expect("Single note should be valid");
 
-        let data_store = ClientDataStore::new(self.store.clone());
+        // The account was already loaded, so the data store doesn't need to load it again
+        let data_store = ClientDataStore::new(self.store.clone())
+            .with_account_state(account.clone(), account_record.seed().copied());
         let transaction_executor =
             TransactionExecutor::new(&data_store, self.authenticator.as_deref());
         let consumption_checker = NoteConsumptionChecker::new(&transaction_executor);
@@ -152,6 +218,17 @@ impl NoteScreener {
         Ok(None)
     }
 
+    /// Returns `true` if the note is a P2ID or P2IDE note that can't be consumed by the account, as
+    /// the account is neither its target nor, for P2IDE notes, its sender.
+    fn is_unrelated_account(note: &Note, account_id: AccountId) -> bool {
+        let Some(target) = well_known_note_target(note.recipient()) else {
+            return false;
+        };
+        let is_p2ide = note.script().root() == WellKnownNote::P2IDE.script_root();
+
+        target != account_id && !(is_p2ide && note.metadata().sender() == account_id)
+    }
+
     /// Special relevance check for P2IDE notes. It checks if the sender account can consume and
     /// recall the note.
     fn check_p2ide_recall_consumability(
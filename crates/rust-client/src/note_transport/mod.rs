@@ -0,0 +1,579 @@
+//! Provides a pluggable transport to deliver private notes between clients.
+//!
+//! The details of private notes never reach the network, so they have to be handed to their
+//! recipients out-of-band. Instead of exporting and importing a [`NoteFile`] manually, clients
+//! can exchange them through a [`NoteTransport`], such as a relay server that both the sender and
+//! the recipient connect to.
+//!
+//! Notes are addressed by their [`NoteTag`] and travel as [`EncryptedNote`]s: the public
+//! [`NoteHeader`] along with the note details encrypted with a [`NoteChannelKey`]. Channel keys
+//! are symmetric keys that the sender and the recipient agree upon beforehand for a tag (for
+//! example, the tag derived from the recipient's account ID), and are registered in both clients
+//! through [`Client::add_note_channel`]. Since tags aren't unique, the transport may return notes
+//! that can't be decrypted with the client's keys, which are ignored.
+//!
+//! Once a transport is set with [`Client::set_note_transport`]:
+//!
+//! - [`Client::submit_transaction`] sends the private notes created by the transaction to the
+//!   transport, as long as a channel key is registered for their tag.
+//! - [`Client::sync_state`] fetches the notes sent to the tracked tags that have a channel key and
+//!   imports them as expected notes before syncing with the node. The position up to which the
+//!   notes of each tag were fetched is kept in the store, so that later fetches only request the
+//!   new ones.
+//!
+//! With the `std` feature, [`TcpRelayTransport`] connects to a simple relay server over TCP.
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::fmt;
+
+use chacha20poly1305::{
+    KeyInit, XChaCha20Poly1305, XNonce,
+    aead::{Aead, Payload},
+};
+use miden_objects::{
+    block::BlockNumber,
+    note::{Note, NoteFile, NoteHeader, NoteId, NoteTag, NoteType},
+    transaction::OutputNote,
+};
+use miden_tx::utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+use rand::RngCore;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::{Client, ClientError};
+
+#[cfg(feature = "std")]
+mod tcp_relay;
+#[cfg(feature = "std")]
+pub use tcp_relay::{RelayRequest, RelayResponse, TcpRelayTransport};
+
+/// Name of the store setting that keeps the note transport cursor of each tag.
+const NOTE_TRANSPORT_CURSORS_SETTING: &str = "note_transport_cursors";
+
+/// Length in bytes of the nonce prepended to the ciphertext of an [`EncryptedNote`].
+const NONCE_LEN: usize = 24;
+
+// NOTE TRANSPORT
+// ================================================================================================
+
+/// Sends and fetches [`EncryptedNote`]s addressed by [`NoteTag`].
+///
+/// Transports only see the public header of the notes, so they can be operated by untrusted
+/// parties.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+pub trait NoteTransport: Send + Sync {
+    /// Sends a note to the recipients that fetch notes for its tag.
+    async fn send_note(&self, note: EncryptedNote) -> Result<(), NoteTransportError>;
+
+    /// Fetches the notes sent to any of the specified tags after the position given by `cursor`.
+    /// Returns the notes along with the cursor to use in the next request.
+    ///
+    /// A cursor of `0` fetches all the notes held by the transport.
+    async fn fetch_notes(
+        &self,
+        tags: &[NoteTag],
+        cursor: u64,
+    ) -> Result<(Vec<EncryptedNote>, u64), NoteTransportError>;
+}
+
+// ENCRYPTED NOTE
+// ================================================================================================
+
+/// A private note as it travels through a [`NoteTransport`].
+///
+/// The header of the note is public, while its details (and the block after which the note is
+/// expected to be committed) are encrypted with the [`NoteChannelKey`] of its tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedNote {
+    header: NoteHeader,
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedNote {
+    /// Returns a new [`EncryptedNote`] with the provided header and ciphertext.
+    pub fn new(header: NoteHeader, ciphertext: Vec<u8>) -> Self {
+        Self { header, ciphertext }
+    }
+
+    /// Returns the public header of the note.
+    pub fn header(&self) -> &NoteHeader {
+        &self.header
+    }
+
+    /// Returns the ID of the note.
+    pub fn id(&self) -> NoteId {
+        self.header.id()
+    }
+
+    /// Returns the tag the note is addressed to.
+    pub fn tag(&self) -> NoteTag {
+        self.header.metadata().tag()
+    }
+
+    /// Returns the encrypted details of the note.
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+}
+
+impl Serializable for EncryptedNote {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.header.write_into(target);
+        self.ciphertext.write_into(target);
+    }
+}
+
+impl Deserializable for EncryptedNote {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let header = NoteHeader::read_from(source)?;
+        let ciphertext = Vec::<u8>::read_from(source)?;
+
+        Ok(Self { header, ciphertext })
+    }
+}
+
+// NOTE CHANNEL KEY
+// ================================================================================================
+
+/// A symmetric key shared by the senders and the recipient of the private notes addressed to a
+/// tag.
+///
+/// Notes are encrypted with XChaCha20-Poly1305, using the serialized note header as associated
+/// data so that a transport can't swap the header of a note.
+#[derive(Clone, PartialEq, Eq)]
+pub struct NoteChannelKey([u8; 32]);
+
+impl NoteChannelKey {
+    /// Returns a new [`NoteChannelKey`] from its raw bytes.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Generates a random [`NoteChannelKey`].
+    pub fn with_rng<R: RngCore>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Returns the raw bytes of the key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Encrypts the details of the note so that it can be sent through a [`NoteTransport`]. The
+    /// recipient imports it as an expected note committed after `after_block_num`.
+    pub fn encrypt<R: RngCore>(
+        &self,
+        note: &Note,
+        after_block_num: BlockNumber,
+        rng: &mut R,
+    ) -> Result<EncryptedNote, NoteTransportError> {
+        let header = NoteHeader::from(note);
+        let note_file = NoteFile::NoteDetails {
+            details: note.into(),
+            after_block_num,
+            tag: Some(note.metadata().tag()),
+        };
+
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+
+        let ciphertext = XChaCha20Poly1305::new((&self.0).into())
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &note_file.to_bytes(),
+                    aad: &header.to_bytes(),
+                },
+            )
+            .map_err(|_| NoteTransportError::EncryptionFailed(header.id()))?;
+
+        let mut payload = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        payload.extend_from_slice(&nonce);
+        payload.extend_from_slice(&ciphertext);
+
+        Ok(EncryptedNote::new(header, payload))
+    }
+
+    /// Decrypts the note and returns it as a [`NoteFile::NoteDetails`].
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`NoteTransportError::DecryptionFailed`] if the note wasn't encrypted with this
+    ///   key.
+    /// - Returns [`NoteTransportError::InvalidNote`] if the decrypted details don't match the
+    ///   header of the note.
+    pub fn decrypt(&self, note: &EncryptedNote) -> Result<NoteFile, NoteTransportError> {
+        if note.ciphertext.len() < NONCE_LEN {
+            return Err(NoteTransportError::DecryptionFailed(note.id()));
+        }
+        let (nonce, ciphertext) = note.ciphertext.split_at(NONCE_LEN);
+
+        let plaintext = XChaCha20Poly1305::new((&self.0).into())
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &note.header.to_bytes(),
+                },
+            )
+            .map_err(|_| NoteTransportError::DecryptionFailed(note.id()))?;
+
+        let note_file = NoteFile::read_from_bytes(&plaintext)
+            .map_err(|err| NoteTransportError::DecodingError(err.to_string()))?;
+        match &note_file {
+            NoteFile::NoteDetails { details, tag: Some(tag), .. }
+                if details.id() == note.id() && *tag == note.tag() =>
+            {
+                Ok(note_file)
+            },
+            _ => Err(NoteTransportError::InvalidNote(note.id())),
+        }
+    }
+}
+
+impl fmt::Debug for NoteChannelKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NoteChannelKey(..)")
+    }
+}
+
+// NOTE TRANSPORT ERROR
+// ================================================================================================
+
+/// Errors generated while delivering private notes.
+#[derive(Debug, Error)]
+pub enum NoteTransportError {
+    #[error("no note transport is set")]
+    NotConfigured,
+    #[error("no channel key is registered for note tag {0}")]
+    MissingChannelKey(NoteTag),
+    #[error("note {0} is public")]
+    PublicNote(NoteId),
+    #[error("note transport connection error: {0}")]
+    ConnectionError(String),
+    #[error("note transport error: {0}")]
+    TransportError(String),
+    #[error("error decoding note transport message: {0}")]
+    DecodingError(String),
+    #[error("failed to encrypt note {0}")]
+    EncryptionFailed(NoteId),
+    #[error("failed to decrypt note {0}")]
+    DecryptionFailed(NoteId),
+    #[error("decrypted details don't match the header of note {0}")]
+    InvalidNote(NoteId),
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+/// Private note delivery methods.
+impl Client {
+    /// Sets the [`NoteTransport`] used to deliver private notes. Only the notes addressed to tags
+    /// registered through [`Client::add_note_channel`] are sent and fetched.
+    pub fn set_note_transport(&mut self, transport: Arc<dyn NoteTransport>) {
+        self.note_transport = Some(transport);
+    }
+
+    /// Registers the key used to encrypt and decrypt the private notes addressed to `tag`,
+    /// replacing the previous key for the tag, if any.
+    ///
+    /// Keys are kept in memory only, so they need to be registered again every time the client
+    /// is created.
+    pub fn add_note_channel(&mut self, tag: NoteTag, key: NoteChannelKey) {
+        self.note_channels.insert(tag, key);
+    }
+
+    /// Stops sending and fetching the private notes addressed to `tag`. Returns the removed key,
+    /// if any.
+    pub fn remove_note_channel(&mut self, tag: NoteTag) -> Option<NoteChannelKey> {
+        self.note_channels.remove(&tag)
+    }
+
+    /// Sends a private note to its recipient through the note transport. The recipient imports it
+    /// as an expected note committed after the current sync height.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`NoteTransportError::NotConfigured`] if no transport is set.
+    /// - Returns [`NoteTransportError::PublicNote`] if the note is public.
+    /// - Returns [`NoteTransportError::MissingChannelKey`] if no channel key is registered for the
+    ///   tag of the note.
+    pub async fn send_private_note(&mut self, note: &Note) -> Result<(), ClientError> {
+        let after_block_num = self.get_sync_height().await?;
+        self.send_private_note_after(note, after_block_num).await
+    }
+
+    /// Fetches the private notes sent to the tracked tags that have a channel key and imports them
+    /// as expected notes. Notes that can't be decrypted or that are already tracked are ignored,
+    /// while notes that can't be decoded or imported are logged and skipped. Returns the IDs of
+    /// the imported notes.
+    ///
+    /// Consecutive calls only fetch the notes sent since the previous call, as the position up to
+    /// which the notes of each tag were fetched is kept in the store. The positions reached are
+    /// stored even if fetching some of the tags fails, and skipped notes aren't fetched again.
+    /// Tags that get a channel key later start from the first note held by the transport. This is
+    /// done automatically at the start of [`Client::sync_state`].
+    pub async fn fetch_private_notes(&mut self) -> Result<Vec<NoteId>, ClientError> {
+        let transport = self.note_transport.clone().ok_or(NoteTransportError::NotConfigured)?;
+
+        let tags: Vec<NoteTag> = self
+            .store
+            .get_unique_note_tags()
+            .await?
+            .into_iter()
+            .filter(|tag| self.note_channels.contains_key(tag))
+            .collect();
+        if tags.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Tags that were fetched up to the same position are requested together
+        let mut cursors = self.get_note_transport_cursors().await?;
+        let mut tags_by_cursor: BTreeMap<u64, Vec<NoteTag>> = BTreeMap::new();
+        for tag in tags {
+            tags_by_cursor
+                .entry(cursors.get(&tag).copied().unwrap_or(0))
+                .or_default()
+                .push(tag);
+        }
+
+        let mut imported_notes = Vec::new();
+        let mut seen_notes = BTreeSet::new();
+        let mut fetch_error = None;
+        for (cursor, tags) in tags_by_cursor {
+            let (notes, next_cursor) = match transport.fetch_notes(&tags, cursor).await {
+                Ok(fetched) => fetched,
+                Err(err) => {
+                    fetch_error = Some(err);
+                    break;
+                },
+            };
+
+            for note in notes {
+                let Some(key) = self.note_channels.get(&note.tag()) else {
+                    continue;
+                };
+                let note_file = match key.decrypt(&note) {
+                    Ok(note_file) => note_file,
+                    // Tags aren't unique, so notes encrypted for other recipients are expected
+                    Err(NoteTransportError::DecryptionFailed(_)) => continue,
+                    Err(err) => {
+                        warn!("Skipping private note {} from the note transport: {err}", note.id());
+                        continue;
+                    },
+                };
+
+                if !seen_notes.insert(note.id()) {
+                    continue;
+                }
+
+                match self.import_private_note(note_file, note.id()).await {
+                    Ok(Some(note_id)) => imported_notes.push(note_id),
+                    Ok(None) => {},
+                    Err(err) => {
+                        warn!(
+                            "Failed to import private note {} from the note transport: {err}",
+                            note.id()
+                        );
+                    },
+                }
+            }
+
+            cursors.extend(tags.into_iter().map(|tag| (tag, next_cursor)));
+        }
+        self.set_note_transport_cursors(&cursors).await?;
+
+        match fetch_error {
+            Some(err) => Err(err.into()),
+            None => Ok(imported_notes),
+        }
+    }
+
+    /// Imports a private note fetched from the note transport, unless it's already tracked.
+    async fn import_private_note(
+        &mut self,
+        note_file: NoteFile,
+        note_id: NoteId,
+    ) -> Result<Option<NoteId>, ClientError> {
+        if self.get_input_note(note_id).await?.is_some() {
+            return Ok(None);
+        }
+
+        self.import_note(note_file).await.map(Some)
+    }
+
+    /// Returns the position in the note transport up to which the private notes of each tag were
+    /// fetched.
+    async fn get_note_transport_cursors(&self) -> Result<BTreeMap<NoteTag, u64>, ClientError> {
+        let Some(bytes) = self.store.get_setting(NOTE_TRANSPORT_CURSORS_SETTING.into()).await?
+        else {
+            return Ok(BTreeMap::new());
+        };
+
+        let mut reader = SliceReader::new(&bytes);
+        let cursors = (0..reader.read_usize()?)
+            .map(|_| Ok((NoteTag::read_from(&mut reader)?, reader.read_u64()?)))
+            .collect::<Result<_, DeserializationError>>()?;
+        Ok(cursors)
+    }
+
+    /// Stores the position in the note transport up to which the private notes of each tag were
+    /// fetched.
+    async fn set_note_transport_cursors(
+        &self,
+        cursors: &BTreeMap<NoteTag, u64>,
+    ) -> Result<(), ClientError> {
+        let mut bytes = Vec::new();
+        bytes.write_usize(cursors.len());
+        for (tag, cursor) in cursors {
+            tag.write_into(&mut bytes);
+            bytes.write_u64(*cursor);
+        }
+
+        self.store.set_setting(NOTE_TRANSPORT_CURSORS_SETTING.into(), bytes).await?;
+        Ok(())
+    }
+
+    /// Sends the private notes created by a submitted transaction to their recipients. Failures
+    /// are logged instead of returned, since the transaction was already submitted.
+    pub(crate) async fn send_created_private_notes<'a>(
+        &mut self,
+        notes: impl IntoIterator<Item = &'a OutputNote>,
+        submission_height: BlockNumber,
+    ) {
+        if self.note_transport.is_none() {
+            return;
+        }
+
+        let notes: Vec<Note> = notes
+            .into_iter()
+            .filter_map(|note| match note {
+                OutputNote::Full(note)
+                    if note.metadata().note_type() == NoteType::Private
+                        && self.note_channels.contains_key(&note.metadata().tag()) =>
+                {
+                    Some(note.clone())
+                },
+                _ => None,
+            })
+            .collect();
+
+        for note in notes {
+            if let Err(err) = self.send_private_note_after(&note, submission_height).await {
+                warn!("Failed to send private note {}: {err}", note.id());
+            }
+        }
+    }
+
+    async fn send_private_note_after(
+        &mut self,
+        note: &Note,
+        after_block_num: BlockNumber,
+    ) -> Result<(), ClientError> {
+        let transport = self.note_transport.clone().ok_or(NoteTransportError::NotConfigured)?;
+
+        if note.metadata().note_type() == NoteType::Public {
+            return Err(NoteTransportError::PublicNote(note.id()).into());
+        }
+
+        let tag = note.metadata().tag();
+        let key = self
+            .note_channels
+            .get(&tag)
+            .ok_or(NoteTransportError::MissingChannelKey(tag))?
+            .clone();
+
+        let encrypted_note = key.encrypt(note, after_block_num, &mut self.rng)?;
+        transport.send_note(encrypted_note).await?;
+
+        Ok(())
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_lib::note::create_p2id_note;
+    use miden_objects::{
+        Felt, Word,
+        account::AccountId,
+        block::BlockNumber,
+        crypto::rand::RpoRandomCoin,
+        note::{NoteFile, NoteType},
+        testing::account_id::{
+            ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        },
+    };
+    use miden_tx::utils::{Deserializable, Serializable};
+
+    use super::{EncryptedNote, NoteChannelKey, NoteTransportError};
+
+    #[test]
+    fn encrypted_notes_are_decrypted_with_their_channel_key() {
+        let sender = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let target =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE).unwrap();
+        let note = create_p2id_note(
+            sender,
+            target,
+            vec![],
+            NoteType::Private,
+            Felt::ZERO,
+            &mut RpoRandomCoin::new(Word::default()),
+        )
+        .unwrap();
+
+        let mut rng = rand::rng();
+        let key = NoteChannelKey::with_rng(&mut rng);
+        let encrypted_note = key.encrypt(&note, BlockNumber::from(3), &mut rng).unwrap();
+        assert_eq!(encrypted_note.id(), note.id());
+        assert_eq!(encrypted_note.tag(), note.metadata().tag());
+
+        let encrypted_note = EncryptedNote::read_from_bytes(&encrypted_note.to_bytes()).unwrap();
+        let NoteFile::NoteDetails { details, after_block_num, tag } =
+            key.decrypt(&encrypted_note).unwrap()
+        else {
+            panic!("notes should be decrypted as note details");
+        };
+        assert_eq!(details.id(), note.id());
+        assert_eq!(after_block_num, BlockNumber::from(3));
+        assert_eq!(tag, Some(note.metadata().tag()));
+
+        // Other keys can't decrypt the note
+        assert!(matches!(
+            NoteChannelKey::with_rng(&mut rng).decrypt(&encrypted_note),
+            Err(NoteTransportError::DecryptionFailed(_))
+        ));
+
+        // The header of the note can't be swapped
+        let other_note = create_p2id_note(
+            sender,
+            target,
+            vec![],
+            NoteType::Private,
+            Felt::ONE,
+            &mut RpoRandomCoin::new(Word::default()),
+        )
+        .unwrap();
+        let swapped_note =
+            EncryptedNote::new((&other_note).into(), encrypted_note.ciphertext().to_vec());
+        assert!(matches!(
+            key.decrypt(&swapped_note),
+            Err(NoteTransportError::DecryptionFailed(_))
+        ));
+    }
+}
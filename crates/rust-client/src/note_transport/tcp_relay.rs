@@ -0,0 +1,229 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use miden_objects::note::NoteTag;
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{EncryptedNote, NoteTransport, NoteTransportError};
+
+// RELAY MESSAGES
+// ================================================================================================
+
+/// A request sent to a note relay server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelayRequest {
+    /// Stores a note so that it can be fetched by its recipients.
+    Send(EncryptedNote),
+    /// Requests the notes sent to any of `tags` after the position given by `cursor`.
+    Fetch { tags: Vec<NoteTag>, cursor: u64 },
+}
+
+/// A response sent back by a note relay server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelayResponse {
+    /// The note was stored.
+    Stored,
+    /// The requested notes along with the cursor to use in the next fetch request.
+    Notes { notes: Vec<EncryptedNote>, cursor: u64 },
+    /// The relay failed to process the request.
+    Error(String),
+}
+
+const SEND_REQUEST: u8 = 0;
+const FETCH_REQUEST: u8 = 1;
+
+const STORED_RESPONSE: u8 = 0;
+const NOTES_RESPONSE: u8 = 1;
+const ERROR_RESPONSE: u8 = 2;
+
+impl Serializable for RelayRequest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            RelayRequest::Send(note) => {
+                target.write_u8(SEND_REQUEST);
+                note.write_into(target);
+            },
+            RelayRequest::Fetch { tags, cursor } => {
+                target.write_u8(FETCH_REQUEST);
+                tags.write_into(target);
+                target.write_u64(*cursor);
+            },
+        }
+    }
+}
+
+impl Deserializable for RelayRequest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            SEND_REQUEST => Ok(RelayRequest::Send(EncryptedNote::read_from(source)?)),
+            FETCH_REQUEST => {
+                let tags = Vec::<NoteTag>::read_from(source)?;
+                let cursor = source.read_u64()?;
+                Ok(RelayRequest::Fetch { tags, cursor })
+            },
+            tag => {
+                Err(DeserializationError::InvalidValue(format!("invalid relay request tag {tag}")))
+            },
+        }
+    }
+}
+
+impl Serializable for RelayResponse {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            RelayResponse::Stored => target.write_u8(STORED_RESPONSE),
+            RelayResponse::Notes { notes, cursor } => {
+                target.write_u8(NOTES_RESPONSE);
+                notes.write_into(target);
+                target.write_u64(*cursor);
+            },
+            RelayResponse::Error(message) => {
+                target.write_u8(ERROR_RESPONSE);
+                message.as_bytes().to_vec().write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for RelayResponse {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            STORED_RESPONSE => Ok(RelayResponse::Stored),
+            NOTES_RESPONSE => {
+                let notes = Vec::<EncryptedNote>::read_from(source)?;
+                let cursor = source.read_u64()?;
+                Ok(RelayResponse::Notes { notes, cursor })
+            },
+            ERROR_RESPONSE => {
+                let message = String::from_utf8(Vec::<u8>::read_from(source)?)
+                    .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+                Ok(RelayResponse::Error(message))
+            },
+            tag => {
+                Err(DeserializationError::InvalidValue(format!("invalid relay response tag {tag}")))
+            },
+        }
+    }
+}
+
+// TCP RELAY TRANSPORT
+// ================================================================================================
+
+/// A [`NoteTransport`] that talks to a note relay server over TCP.
+///
+/// A new connection is opened for every request. Each message is framed as a little-endian `u32`
+/// length followed by the serialized [`RelayRequest`] or [`RelayResponse`]. Requests are blocking,
+/// so they should only be sent to relays that are close to the client, such as a local relay used
+/// in tests.
+///
+/// Responses larger than the maximum response size are rejected before being read, so that a
+/// misbehaving relay can't make the client allocate an arbitrary amount of memory.
+#[derive(Debug, Clone)]
+pub struct TcpRelayTransport {
+    /// Address of the relay, such as `127.0.0.1:57292`.
+    address: String,
+    /// Read and write timeout for each request.
+    timeout: Duration,
+    /// Maximum size of a response, in bytes.
+    max_response_size: usize,
+}
+
+impl TcpRelayTransport {
+    /// Default read and write timeout for each request.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Default maximum size of a response, in bytes.
+    pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 16 * 1024 * 1024;
+
+    /// Creates a new transport that connects to the relay at the provided address, with a timeout
+    /// of [`Self::DEFAULT_TIMEOUT`] for each request and a maximum response size of
+    /// [`Self::DEFAULT_MAX_RESPONSE_SIZE`].
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            timeout: Self::DEFAULT_TIMEOUT,
+            max_response_size: Self::DEFAULT_MAX_RESPONSE_SIZE,
+        }
+    }
+
+    /// Sets the read and write timeout for each request.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum size of a response, in bytes.
+    #[must_use]
+    pub fn max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
+    fn send_request(&self, request: &RelayRequest) -> Result<RelayResponse, NoteTransportError> {
+        let io_error = |err: std::io::Error| NoteTransportError::ConnectionError(err.to_string());
+
+        let mut stream = TcpStream::connect(&self.address).map_err(io_error)?;
+        stream.set_read_timeout(Some(self.timeout)).map_err(io_error)?;
+        stream.set_write_timeout(Some(self.timeout)).map_err(io_error)?;
+
+        let request = request.to_bytes();
+        let request_len = u32::try_from(request.len())
+            .map_err(|_| NoteTransportError::TransportError("request is too large".into()))?;
+        stream.write_all(&request_len.to_le_bytes()).map_err(io_error)?;
+        stream.write_all(&request).map_err(io_error)?;
+
+        let mut response_len = [0u8; 4];
+        stream.read_exact(&mut response_len).map_err(io_error)?;
+        let response_len = u32::from_le_bytes(response_len) as usize;
+        if response_len > self.max_response_size {
+            return Err(NoteTransportError::TransportError(format!(
+                "response of {response_len} bytes exceeds the maximum of {} bytes",
+                self.max_response_size
+            )));
+        }
+
+        let mut response = vec![0u8; response_len];
+        stream.read_exact(&mut response).map_err(io_error)?;
+
+        RelayResponse::read_from_bytes(&response)
+            .map_err(|err| NoteTransportError::DecodingError(err.to_string()))
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl NoteTransport for TcpRelayTransport {
+    async fn send_note(&self, note: EncryptedNote) -> Result<(), NoteTransportError> {
+        match self.send_request(&RelayRequest::Send(note))? {
+            RelayResponse::Stored => Ok(()),
+            RelayResponse::Error(message) => Err(NoteTransportError::TransportError(message)),
+            response => Err(NoteTransportError::TransportError(format!(
+                "unexpected response to send request: {response:?}"
+            ))),
+        }
+    }
+
+    async fn fetch_notes(
+        &self,
+        tags: &[NoteTag],
+        cursor: u64,
+    ) -> Result<(Vec<EncryptedNote>, u64), NoteTransportError> {
+        let request = RelayRequest::Fetch { tags: tags.to_vec(), cursor };
+        match self.send_request(&request)? {
+            RelayResponse::Notes { notes, cursor } => Ok((notes, cursor)),
+            RelayResponse::Error(message) => Err(NoteTransportError::TransportError(message)),
+            response => Err(NoteTransportError::TransportError(format!(
+                "unexpected response to fetch request: {response:?}"
+            ))),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use alloc::{
     boxed::Box,
     string::{String, ToString},
+    vec::Vec,
 };
 use core::{error::Error, num::TryFromIntError};
 
@@ -9,6 +10,9 @@ use miden_objects::{
     utils::DeserializationError,
 };
 use thiserror::Error;
+use tonic::{Code, Status};
+
+use super::NodeRpcClientEndpoint;
 
 // RPC ERROR
 // ================================================================================================
@@ -21,14 +25,94 @@ pub enum RpcError {
     ConnectionError(#[source] Box<dyn Error + Send + Sync + 'static>),
     #[error("failed to deserialize rpc data: {0}")]
     DeserializationError(String),
+    /// The node answered a request with an error status.
+    #[error("rpc request {endpoint} failed with status `{code}`: {message}")]
+    GrpcError {
+        /// The endpoint the request was sent to.
+        endpoint: NodeRpcClientEndpoint,
+        /// The gRPC status code returned by the node.
+        code: Code,
+        /// The error message returned by the node.
+        message: String,
+        /// The binary error details returned by the node, if any. Their encoding is defined by
+        /// the node.
+        details: Vec<u8>,
+    },
     #[error("rpc api response missing an expected field: {0}")]
     ExpectedDataMissing(String),
     #[error("rpc api response is invalid: {0}")]
     InvalidResponse(String),
     #[error("note with id {0} was not found")]
     NoteNotFound(NoteId),
-    #[error("rpc request failed for {0}: {1}")]
-    RequestError(String, String),
+}
+
+impl RpcError {
+    /// Creates an error from the status returned by the node for a request to `endpoint`.
+    pub(crate) fn from_status(endpoint: NodeRpcClientEndpoint, status: &Status) -> Self {
+        Self::GrpcError {
+            endpoint,
+            code: status.code(),
+            message: status.message().to_string(),
+            details: status.details().to_vec(),
+        }
+    }
+
+    /// Returns the endpoint of the request that failed, if the node answered it with an error
+    /// status.
+    pub fn endpoint(&self) -> Option<NodeRpcClientEndpoint> {
+        match self {
+            Self::GrpcError { endpoint, .. } => Some(*endpoint),
+            _ => None,
+        }
+    }
+
+    /// Returns the gRPC status code returned by the node, if the node answered the request with
+    /// an error status.
+    pub fn status_code(&self) -> Option<Code> {
+        match self {
+            Self::GrpcError { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Returns the reason the node gave for rejecting a submitted transaction, or `None` if the
+    /// error isn't a rejection, such as when the node is unavailable.
+    ///
+    /// The reason is read from the error code the node sends in the status details. Nodes that
+    /// don't send one are matched by their error message, see [`SUBMISSION_REJECTION_MESSAGES`].
+    pub(crate) fn submission_rejection(&self) -> Option<SubmissionRejection> {
+        let Self::GrpcError {
+            code: Code::InvalidArgument | Code::FailedPrecondition | Code::AlreadyExists,
+            message,
+            details,
+            ..
+        } = self
+        else {
+            return None;
+        };
+
+        match details.first() {
+            Some(error_code) => SubmissionRejection::from_error_code(*error_code),
+            None => Some(SubmissionRejection::from_message(message)),
+        }
+    }
+
+    /// Returns whether the error is likely to be temporary, in which case the request can be
+    /// retried. This is the case for connection errors and for the gRPC statuses that signal an
+    /// unavailable or overloaded node.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::ConnectionError(_) => true,
+            Self::GrpcError { code, .. } => matches!(
+                code,
+                Code::Unavailable
+                    | Code::DeadlineExceeded
+                    | Code::ResourceExhausted
+                    | Code::Aborted
+            ),
+            _ => false,
+        }
+    }
 }
 
 impl From<DeserializationError> for RpcError {
@@ -49,6 +133,79 @@ impl From<RpcConversionError> for RpcError {
     }
 }
 
+// SUBMISSION REJECTION
+// ================================================================================================
+
+/// Reason given by the node for rejecting a submitted transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SubmissionRejection {
+    /// Some of the input notes of the transaction were already consumed.
+    InputNotesAlreadyConsumed,
+    /// The initial account commitment of the transaction doesn't match the account state known
+    /// by the node.
+    AccountStateStale,
+    /// The transaction expired, or its reference block is too old to be accepted.
+    Expired,
+    /// The transaction was rejected for any other reason, such as an invalid proof.
+    Other,
+}
+
+/// Messages of the transaction rejections sent by nodes that don't include an error code in the
+/// status details, as defined by the node's block producer errors. Messages are matched
+/// case-insensitively, in order, against the status message.
+const SUBMISSION_REJECTION_MESSAGES: &[(&str, SubmissionRejection)] = &[
+    (
+        "input notes with given nullifiers were already consumed",
+        SubmissionRejection::InputNotesAlreadyConsumed,
+    ),
+    ("incorrect account's initial commitment", SubmissionRejection::AccountStateStale),
+    ("transaction expired at block height", SubmissionRejection::Expired),
+    ("is rejected as stale", SubmissionRejection::Expired),
+];
+
+/// Error codes sent by the node in the status details of a rejected submission, as defined by the
+/// node's `SubmitProvenTransactionGrpcError`. Codes not listed here are mapped to
+/// [`SubmissionRejection::Other`].
+mod submission_error_code {
+    /// The node failed while processing the transaction.
+    pub const INTERNAL_ERROR: u8 = 0;
+    /// The initial account commitment of the transaction doesn't match the one in the node.
+    pub const INCORRECT_ACCOUNT_INITIAL_COMMITMENT: u8 = 3;
+    /// Some of the input notes of the transaction were already consumed.
+    pub const INPUT_NOTES_ALREADY_CONSUMED: u8 = 4;
+    /// The transaction expired.
+    pub const TRANSACTION_EXPIRED: u8 = 7;
+}
+
+impl SubmissionRejection {
+    /// Maps the error code sent by the node in the status details of a rejected submission.
+    /// Returns `None` for internal node errors, which aren't caused by the transaction.
+    fn from_error_code(error_code: u8) -> Option<Self> {
+        use submission_error_code::{
+            INCORRECT_ACCOUNT_INITIAL_COMMITMENT, INPUT_NOTES_ALREADY_CONSUMED, INTERNAL_ERROR,
+            TRANSACTION_EXPIRED,
+        };
+
+        match error_code {
+            INTERNAL_ERROR => None,
+            INCORRECT_ACCOUNT_INITIAL_COMMITMENT => Some(Self::AccountStateStale),
+            INPUT_NOTES_ALREADY_CONSUMED => Some(Self::InputNotesAlreadyConsumed),
+            TRANSACTION_EXPIRED => Some(Self::Expired),
+            _ => Some(Self::Other),
+        }
+    }
+
+    /// Maps the status message of a rejected submission using
+    /// [`SUBMISSION_REJECTION_MESSAGES`].
+    fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+        SUBMISSION_REJECTION_MESSAGES
+            .iter()
+            .find(|(pattern, _)| message.contains(pattern))
+            .map_or(Self::Other, |(_, rejection)| *rejection)
+    }
+}
+
 // RPC CONVERSION ERROR
 // ================================================================================================
 
@@ -0,0 +1,561 @@
+//! Provides a middleware layer that can wrap any [`NodeRpcClient`] to observe and control the
+//! requests sent to the node.
+//!
+//! A [`MiddlewareRpcClient`] forwards every request to the wrapped client and runs the hooks of its
+//! [`RpcMiddleware`]s around it. The following middlewares are provided:
+//!
+//! - [`RetryPolicy`]: retries the requests that fail with a transient error, with exponential
+//!   backoff.
+//! - [`RateLimiter`]: limits the number of requests sent per second.
+//! - [`RpcMetrics`]: records the number of requests, failures and their timing per endpoint.
+//!
+//! Middlewares can be combined, and custom ones can be added by implementing [`RpcMiddleware`].
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{future::Future, num::NonZeroU32, time::Duration};
+use std::{sync::Mutex, time::Instant};
+
+use miden_objects::{
+    account::{AccountCode, AccountDelta, AccountId},
+    block::{BlockHeader, BlockNumber, ProvenBlock},
+    crypto::merkle::{MmrProof, SmtProof},
+    note::{NoteId, NoteTag, Nullifier},
+    transaction::ProvenTransaction,
+};
+use tracing::debug;
+
+use super::{
+    NodeRpcClient, NodeRpcClientEndpoint, RpcError,
+    domain::{
+        account::{AccountProofs, FetchedAccount},
+        note::{FetchedNote, NoteSyncInfo},
+        nullifier::NullifierUpdate,
+        sync::StateSyncInfo,
+    },
+};
+use crate::transaction::ForeignAccount;
+
+// RPC MIDDLEWARE
+// ================================================================================================
+
+/// Hooks that run around every request sent through a [`MiddlewareRpcClient`].
+///
+/// All the hooks have a default implementation that does nothing, so middlewares only need to
+/// implement the ones they use.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+pub trait RpcMiddleware: Send + Sync {
+    /// Called before every attempt of a request to `endpoint`. The request waits until the
+    /// returned future completes, and fails with the returned error if there is one.
+    async fn before_request(&self, _endpoint: NodeRpcClientEndpoint) -> Result<(), RpcError> {
+        Ok(())
+    }
+
+    /// Called after every attempt of a request to `endpoint`, with the time it took and its error
+    /// if it failed.
+    fn after_response(
+        &self,
+        _endpoint: NodeRpcClientEndpoint,
+        _elapsed: Duration,
+        _error: Option<&RpcError>,
+    ) {
+    }
+
+    /// Called when the attempt number `attempt` (starting at 1) of a request to `endpoint`
+    /// fails. Returns the time to wait before retrying the request, or `None` if the error should
+    /// be returned.
+    fn retry_delay(
+        &self,
+        _endpoint: NodeRpcClientEndpoint,
+        _attempt: u32,
+        _error: &RpcError,
+    ) -> Option<Duration> {
+        None
+    }
+}
+
+// MIDDLEWARE RPC CLIENT
+// ================================================================================================
+
+/// A [`NodeRpcClient`] that forwards requests to another client and runs the hooks of its
+/// [`RpcMiddleware`]s around them.
+///
+/// The hooks of the middlewares run in the order the middlewares were added. A failed request is
+/// retried after the delay returned by the first middleware that asks for a retry.
+///
+/// Only the endpoints of the node are forwarded: the methods of [`NodeRpcClient`] that have a
+/// default implementation use the default implementation, so each of the requests they send goes
+/// through the middlewares.
+pub struct MiddlewareRpcClient {
+    /// The client the requests are forwarded to.
+    inner: Arc<dyn NodeRpcClient + Send>,
+    /// The middlewares that run around every request.
+    middlewares: Vec<Arc<dyn RpcMiddleware>>,
+}
+
+impl MiddlewareRpcClient {
+    /// Creates a new client that forwards requests to `inner`, without any middleware.
+    pub fn new(inner: Arc<dyn NodeRpcClient + Send>) -> Self {
+        Self { inner, middlewares: Vec::new() }
+    }
+
+    /// Adds a middleware that runs around every request, after the ones already added.
+    #[must_use]
+    pub fn with_middleware(mut self, middleware: Arc<dyn RpcMiddleware>) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// Sends a request to `endpoint` by calling `request`, running the middlewares around every
+    /// attempt.
+    async fn call<T, F, Fut>(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        request: F,
+    ) -> Result<T, RpcError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            for middleware in &self.middlewares {
+                middleware.before_request(endpoint).await?;
+            }
+
+            let start = Instant::now();
+            let result = request().await;
+            let elapsed = start.elapsed();
+
+            for middleware in &self.middlewares {
+                middleware.after_response(endpoint, elapsed, result.as_ref().err());
+            }
+
+            let error = match result {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+
+            let Some(delay) = self
+                .middlewares
+                .iter()
+                .find_map(|middleware| middleware.retry_delay(endpoint, attempt, &error))
+            else {
+                return Err(error);
+            };
+
+            debug!("Request to {endpoint} failed ({error}), retrying in {delay:?}");
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl NodeRpcClient for MiddlewareRpcClient {
+    async fn submit_proven_transaction(
+        &self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<BlockNumber, RpcError> {
+        self.call(NodeRpcClientEndpoint::SubmitProvenTx, || {
+            self.inner.submit_proven_transaction(proven_transaction.clone())
+        })
+        .await
+    }
+
+    async fn get_block_header_by_number(
+        &self,
+        block_num: Option<BlockNumber>,
+        include_mmr_proof: bool,
+    ) -> Result<(BlockHeader, Option<MmrProof>), RpcError> {
+        self.call(NodeRpcClientEndpoint::GetBlockHeaderByNumber, || {
+            self.inner.get_block_header_by_number(block_num, include_mmr_proof)
+        })
+        .await
+    }
+
+    async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
+        self.call(NodeRpcClientEndpoint::GetBlockByNumber, || {
+            self.inner.get_block_by_number(block_num)
+        })
+        .await
+    }
+
+    async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
+        self.call(NodeRpcClientEndpoint::GetNotesById, || self.inner.get_notes_by_id(note_ids))
+            .await
+    }
+
+    async fn sync_state(
+        &self,
+        block_num: BlockNumber,
+        account_ids: &[AccountId],
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<StateSyncInfo, RpcError> {
+        self.call(NodeRpcClientEndpoint::SyncState, || {
+            self.inner.sync_state(block_num, account_ids, note_tags)
+        })
+        .await
+    }
+
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        self.call(NodeRpcClientEndpoint::GetAccountDetails, || {
+            self.inner.get_account_details(account_id)
+        })
+        .await
+    }
+
+    async fn sync_notes(
+        &self,
+        block_num: BlockNumber,
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<NoteSyncInfo, RpcError> {
+        self.call(NodeRpcClientEndpoint::SyncNotes, || self.inner.sync_notes(block_num, note_tags))
+            .await
+    }
+
+    async fn check_nullifiers_by_prefix(
+        &self,
+        prefix: &[u16],
+        block_num: BlockNumber,
+    ) -> Result<Vec<NullifierUpdate>, RpcError> {
+        self.call(NodeRpcClientEndpoint::CheckNullifiersByPrefix, || {
+            self.inner.check_nullifiers_by_prefix(prefix, block_num)
+        })
+        .await
+    }
+
+    async fn check_nullifiers(&self, nullifiers: &[Nullifier]) -> Result<Vec<SmtProof>, RpcError> {
+        self.call(NodeRpcClientEndpoint::CheckNullifiers, || {
+            self.inner.check_nullifiers(nullifiers)
+        })
+        .await
+    }
+
+    async fn get_account_proofs(
+        &self,
+        account_storage_requests: &BTreeSet<ForeignAccount>,
+        known_account_codes: Vec<AccountCode>,
+    ) -> Result<AccountProofs, RpcError> {
+        self.call(NodeRpcClientEndpoint::GetAccountProofs, || {
+            self.inner
+                .get_account_proofs(account_storage_requests, known_account_codes.clone())
+        })
+        .await
+    }
+
+    async fn get_account_state_delta(
+        &self,
+        account_id: AccountId,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<AccountDelta, RpcError> {
+        self.call(NodeRpcClientEndpoint::GetAccountStateDelta, || {
+            self.inner.get_account_state_delta(account_id, from_block, to_block)
+        })
+        .await
+    }
+}
+
+// RETRY POLICY
+// ================================================================================================
+
+/// A [`RpcMiddleware`] that retries the requests that fail with a transient error (see
+/// [`RpcError::is_transient`]), waiting longer after every failed attempt.
+///
+/// Transaction submissions are only retried when the connection to the node couldn't be
+/// established, as the node may have received the transaction before failing otherwise.
+///
+/// The first retry happens after `initial_backoff`, and the delay is multiplied by
+/// `backoff_multiplier` on every retry, up to `max_backoff`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of times a request is retried.
+    max_retries: u32,
+    /// Delay before the first retry.
+    initial_backoff: Duration,
+    /// Maximum delay between retries.
+    max_backoff: Duration,
+    /// Factor the delay is multiplied by after every retry.
+    backoff_multiplier: u32,
+}
+
+impl RetryPolicy {
+    /// Creates a policy that retries a request up to `max_retries` times, waiting 100ms before the
+    /// first retry and doubling the delay on every retry, up to 5 seconds.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            backoff_multiplier: 2,
+        }
+    }
+
+    /// Sets the delay before the first retry.
+    #[must_use]
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum delay between retries.
+    #[must_use]
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the factor the delay is multiplied by after every retry.
+    #[must_use]
+    pub fn backoff_multiplier(mut self, backoff_multiplier: u32) -> Self {
+        self.backoff_multiplier = backoff_multiplier;
+        self
+    }
+
+    /// Returns the delay before the retry that follows the failed attempt number `attempt`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let multiplier = self.backoff_multiplier.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(multiplier).min(self.max_backoff)
+    }
+}
+
+impl RpcMiddleware for RetryPolicy {
+    fn retry_delay(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        attempt: u32,
+        error: &RpcError,
+    ) -> Option<Duration> {
+        let retryable = match endpoint {
+            NodeRpcClientEndpoint::SubmitProvenTx => matches!(error, RpcError::ConnectionError(_)),
+            _ => error.is_transient(),
+        };
+
+        (attempt <= self.max_retries && retryable).then(|| self.backoff(attempt))
+    }
+}
+
+// RATE LIMITER
+// ================================================================================================
+
+/// A [`RpcMiddleware`] that limits the number of requests sent per second, across all endpoints.
+///
+/// Requests are spaced evenly: when a request is sent too soon after the previous one, it waits
+/// until its turn comes. Retries count as new requests.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Minimum time between the start of two requests.
+    interval: Duration,
+    /// The time at which the next request can be sent.
+    next_request: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter that allows up to `max_requests_per_second` requests per second.
+    pub fn new(max_requests_per_second: NonZeroU32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / max_requests_per_second.get(),
+            next_request: Mutex::new(None),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl RpcMiddleware for RateLimiter {
+    async fn before_request(&self, _endpoint: NodeRpcClientEndpoint) -> Result<(), RpcError> {
+        let now = Instant::now();
+        let request_time = {
+            let mut next_request = self.next_request.lock().expect("lock should not be poisoned");
+            let request_time = next_request.map_or(now, |next_request| next_request.max(now));
+            *next_request = Some(request_time + self.interval);
+            request_time
+        };
+
+        if request_time > now {
+            tokio::time::sleep(request_time - now).await;
+        }
+
+        Ok(())
+    }
+}
+
+// RPC METRICS
+// ================================================================================================
+
+/// Metrics of the requests sent to a single endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EndpointMetrics {
+    /// Number of requests sent, including retries.
+    pub requests: u64,
+    /// Number of requests that failed.
+    pub failures: u64,
+    /// Total time spent waiting for responses.
+    pub total_time: Duration,
+    /// Time taken by the slowest request.
+    pub max_time: Duration,
+}
+
+impl EndpointMetrics {
+    /// Returns the average time taken by a request, or zero if no request was sent.
+    pub fn average_time(&self) -> Duration {
+        u32::try_from(self.requests)
+            .ok()
+            .and_then(|requests| self.total_time.checked_div(requests))
+            .unwrap_or_default()
+    }
+}
+
+/// A [`RpcMiddleware`] that records the number of requests, failures and their timing per
+/// endpoint.
+///
+/// The metrics middleware is usually shared with the code that reads the metrics, so it should be
+/// added to the client as an `Arc` that is kept around.
+#[derive(Debug, Default)]
+pub struct RpcMetrics {
+    /// The metrics of every endpoint that received at least one request.
+    endpoints: Mutex<BTreeMap<NodeRpcClientEndpoint, EndpointMetrics>>,
+}
+
+impl RpcMetrics {
+    /// Creates an empty set of metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the metrics of every endpoint that received at least one request.
+    pub fn endpoint_metrics(&self) -> BTreeMap<NodeRpcClientEndpoint, EndpointMetrics> {
+        self.endpoints.lock().expect("lock should not be poisoned").clone()
+    }
+
+    /// Clears all the recorded metrics.
+    pub fn reset(&self) {
+        self.endpoints.lock().expect("lock should not be poisoned").clear();
+    }
+}
+
+impl RpcMiddleware for RpcMetrics {
+    fn after_response(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        elapsed: Duration,
+        error: Option<&RpcError>,
+    ) {
+        let mut endpoints = self.endpoints.lock().expect("lock should not be poisoned");
+        let metrics = endpoints.entry(endpoint).or_default();
+
+        metrics.requests += 1;
+        if error.is_some() {
+            metrics.failures += 1;
+        }
+        metrics.total_time += elapsed;
+        metrics.max_time = metrics.max_time.max(elapsed);
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::{sync::Arc, vec::Vec};
+    use core::{num::NonZeroU32, time::Duration};
+    use std::time::Instant;
+
+    use miden_objects::block::BlockNumber;
+    use tonic::Code;
+
+    use super::{MiddlewareRpcClient, RateLimiter, RetryPolicy, RpcMetrics, RpcMiddleware};
+    use crate::{
+        rpc::{NodeRpcClient, NodeRpcClientEndpoint, RpcError},
+        testing::mock::MockRpcApi,
+    };
+
+    #[test]
+    fn retry_policy_backs_off_on_transient_errors() {
+        let policy = RetryPolicy::new(3).max_backoff(Duration::from_millis(300));
+        let unavailable = RpcError::GrpcError {
+            endpoint: NodeRpcClientEndpoint::SyncState,
+            code: Code::Unavailable,
+            message: "node is restarting".into(),
+            details: Vec::new(),
+        };
+        let invalid = RpcError::GrpcError {
+            endpoint: NodeRpcClientEndpoint::SubmitProvenTx,
+            code: Code::InvalidArgument,
+            message: "invalid transaction".into(),
+            details: Vec::new(),
+        };
+
+        let delays: Vec<_> = (1..=4)
+            .map(|attempt| {
+                policy.retry_delay(NodeRpcClientEndpoint::SyncState, attempt, &unavailable)
+            })
+            .collect();
+        assert_eq!(
+            delays,
+            [
+                Some(Duration::from_millis(100)),
+                Some(Duration::from_millis(200)),
+                Some(Duration::from_millis(300)),
+                None
+            ]
+        );
+
+        assert_eq!(policy.retry_delay(NodeRpcClientEndpoint::SubmitProvenTx, 1, &invalid), None);
+    }
+
+    #[test]
+    fn retry_policy_does_not_resubmit_transactions() {
+        let policy = RetryPolicy::new(3);
+        let unavailable = RpcError::GrpcError {
+            endpoint: NodeRpcClientEndpoint::SubmitProvenTx,
+            code: Code::Unavailable,
+            message: "node is restarting".into(),
+            details: Vec::new(),
+        };
+        let connection_error = RpcError::ConnectionError("connection refused".into());
+
+        assert_eq!(
+            policy.retry_delay(NodeRpcClientEndpoint::SubmitProvenTx, 1, &unavailable),
+            None
+        );
+        assert_eq!(
+            policy.retry_delay(NodeRpcClientEndpoint::SubmitProvenTx, 1, &connection_error),
+            Some(Duration::from_millis(100))
+        );
+    }
+
+    #[tokio::test]
+    async fn middlewares_record_and_limit_requests() {
+        let metrics = Arc::new(RpcMetrics::new());
+        let rpc_api = MiddlewareRpcClient::new(Arc::new(MockRpcApi::new()))
+            .with_middleware(Arc::new(RateLimiter::new(NonZeroU32::new(20).unwrap())))
+            .with_middleware(metrics.clone());
+
+        let start = Instant::now();
+        for block_num in 0..3 {
+            rpc_api
+                .get_block_header_by_number(Some(BlockNumber::from(block_num)), false)
+                .await
+                .unwrap();
+        }
+        // Requests are spaced 50ms apart, so the third one can't start before 100ms
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        let endpoint_metrics = metrics.endpoint_metrics();
+        let header_metrics = endpoint_metrics[&NodeRpcClientEndpoint::GetBlockHeaderByNumber];
+        assert_eq!(endpoint_metrics.len(), 1);
+        assert_eq!(header_metrics.requests, 3);
+        assert_eq!(header_metrics.failures, 0);
+        assert!(header_metrics.max_time <= header_metrics.total_time);
+    }
+}
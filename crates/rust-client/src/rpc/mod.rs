@@ -10,7 +10,9 @@
 //! - Fetch details for specific notes and accounts.
 //!
 //! In addition, the module provides implementations for different environments (e.g. tonic-based or
-//! web-based) via feature flags ( `tonic` and `web-tonic`).
+//! web-based) via feature flags ( `tonic` and `web-tonic`). With the `tonic` feature, any
+//! implementation can be wrapped in a [`middleware::MiddlewareRpcClient`] to retry failed requests,
+//! limit the request rate and record metrics.
 //!
 //! ## Example
 //!
@@ -48,13 +50,16 @@ use domain::{
     nullifier::NullifierUpdate,
     sync::StateSyncInfo,
 };
+use futures::{StreamExt, TryStreamExt, stream};
 use miden_objects::{
-    account::{Account, AccountCode, AccountDelta, AccountHeader, AccountId},
+    Word,
+    account::{Account, AccountCode, AccountDelta, AccountId},
     block::{BlockHeader, BlockNumber, ProvenBlock},
     crypto::merkle::{MmrProof, SmtProof},
     note::{NoteId, NoteTag, Nullifier},
     transaction::ProvenTransaction,
 };
+use tracing::debug;
 
 /// Contains domain types related to RPC requests and responses, as well as utility functions
 /// for dealing with them.
@@ -62,6 +67,7 @@ pub mod domain;
 
 mod errors;
 pub use errors::RpcError;
+pub(crate) use errors::SubmissionRejection;
 
 mod endpoint;
 pub use endpoint::Endpoint;
@@ -74,6 +80,9 @@ pub mod generated;
 #[cfg(all(feature = "tonic", feature = "web-tonic"))]
 compile_error!("features `tonic` and `web-tonic` are mutually exclusive");
 
+#[cfg(feature = "tonic")]
+pub mod middleware;
+
 #[cfg(any(feature = "tonic", feature = "web-tonic"))]
 mod tonic_client;
 #[cfg(any(feature = "tonic", feature = "web-tonic"))]
@@ -84,6 +93,10 @@ use crate::{
     transaction::ForeignAccount,
 };
 
+/// Maximum number of accounts requested concurrently by the default implementation of
+/// [`NodeRpcClient::get_updated_public_accounts`].
+pub const MAX_CONCURRENT_ACCOUNT_REQUESTS: usize = 8;
+
 // NODE RPC CLIENT TRAIT
 // ================================================================================================
 
@@ -244,31 +257,38 @@ pub trait NodeRpcClient: Send + Sync {
         Ok(public_notes)
     }
 
-    /// Fetches the public accounts that have been updated since the last known state of the
-    /// accounts.
+    /// Fetches the latest state of the public accounts that have been updated in the node.
+    ///
+    /// The `local_accounts` parameter contains the states of the accounts stored by the client,
+    /// which are up to date as of `from_block`, along with the commitment of each account in the
+    /// node at `to_block` (as returned by [NodeRpcClient::sync_state]). Accounts whose local
+    /// commitment already matches the one in the node aren't requested. If an account is private
+    /// or the node doesn't have a newer state, it is ignored and will not be included in the
+    /// returned list.
     ///
-    /// The `local_accounts` parameter is a list of account headers that the client has
-    /// stored locally and that it wants to check for updates. If an account is private or didn't
-    /// change, it is ignored and will not be included in the returned list.
-    /// The default implementation of this method uses [NodeRpcClient::get_account_details].
+    /// The default implementation of this method requests up to
+    /// [`MAX_CONCURRENT_ACCOUNT_REQUESTS`] accounts concurrently. Each account is updated by
+    /// applying the delta returned by [NodeRpcClient::get_account_state_delta], and is only
+    /// downloaded in full with [NodeRpcClient::get_account_details] if the delta can't be fetched
+    /// or doesn't result in the expected commitment.
     async fn get_updated_public_accounts(
         &self,
-        local_accounts: &[&AccountHeader],
+        local_accounts: Vec<(Account, Word)>,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
     ) -> Result<Vec<Account>, RpcError> {
-        let mut public_accounts = vec![];
-
-        for local_account in local_accounts {
-            let response = self.get_account_details(local_account.id()).await?;
-
-            if let FetchedAccount::Public(account, _) = response {
-                // We should only return an account if it's newer, otherwise we ignore it
-                if account.nonce().as_int() > local_account.nonce().as_int() {
-                    public_accounts.push(account);
-                }
-            }
-        }
-
-        Ok(public_accounts)
+        let updated_accounts: Vec<Option<Account>> = stream::iter(local_accounts)
+            .filter(|(account, commitment)| {
+                core::future::ready(account.commitment() != *commitment)
+            })
+            .map(|(account, commitment)| {
+                fetch_updated_public_account(self, account, commitment, from_block, to_block)
+            })
+            .buffered(MAX_CONCURRENT_ACCOUNT_REQUESTS)
+            .try_collect()
+            .await?;
+
+        Ok(updated_accounts.into_iter().flatten().collect())
     }
 
     /// Given a block number, fetches the block header corresponding to that height from the node
@@ -295,11 +315,56 @@ pub trait NodeRpcClient: Send + Sync {
     }
 }
 
+// HELPERS
+// ================================================================================================
+
+/// Fetches the state of a public account at `to_block`, given its local state at `from_block` and
+/// its commitment at `to_block`. Returns `None` if the account is private or the node doesn't have
+/// a newer state.
+///
+/// The account delta between both blocks is applied to the local state, and the whole account is
+/// only downloaded if the result doesn't match the expected commitment.
+async fn fetch_updated_public_account<R: NodeRpcClient + ?Sized>(
+    rpc_api: &R,
+    mut local_account: Account,
+    commitment: Word,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<Option<Account>, RpcError> {
+    let account_id = local_account.id();
+    let local_nonce = local_account.nonce().as_int();
+
+    match rpc_api.get_account_state_delta(account_id, from_block, to_block).await {
+        Ok(delta) => {
+            if local_account.apply_delta(&delta).is_ok() && local_account.commitment() == commitment
+            {
+                return Ok(Some(local_account));
+            }
+            debug!(
+                "Delta of account {account_id} doesn't match its commitment, fetching the account"
+            );
+        },
+        Err(err) => {
+            debug!(
+                "Failed to fetch the delta of account {account_id}, fetching the account: {err}"
+            );
+        },
+    }
+
+    match rpc_api.get_account_details(account_id).await? {
+        // We should only return an account if it's newer, otherwise we ignore it
+        FetchedAccount::Public(account, _) if account.nonce().as_int() > local_nonce => {
+            Ok(Some(account))
+        },
+        _ => Ok(None),
+    }
+}
+
 // RPC API ENDPOINT
 // ================================================================================================
 //
 /// RPC methods for the Miden protocol.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum NodeRpcClientEndpoint {
     CheckNullifiers,
     CheckNullifiersByPrefix,
@@ -308,6 +373,7 @@ pub enum NodeRpcClientEndpoint {
     GetAccountProofs,
     GetBlockByNumber,
     GetBlockHeaderByNumber,
+    GetNotesById,
     SyncState,
     SubmitProvenTx,
     SyncNotes,
@@ -327,6 +393,7 @@ impl fmt::Display for NodeRpcClientEndpoint {
             NodeRpcClientEndpoint::GetBlockHeaderByNumber => {
                 write!(f, "get_block_header_by_number")
             },
+            NodeRpcClientEndpoint::GetNotesById => write!(f, "get_notes_by_id"),
             NodeRpcClientEndpoint::SyncState => write!(f, "sync_state"),
             NodeRpcClientEndpoint::SubmitProvenTx => write!(f, "submit_proven_transaction"),
             NodeRpcClientEndpoint::SyncNotes => write!(f, "sync_notes"),
@@ -96,12 +96,10 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let api_response = rpc_api.submit_proven_transaction(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::SubmitProvenTx.to_string(),
-                err.to_string(),
-            )
-        })?;
+        let api_response = rpc_api
+            .submit_proven_transaction(request)
+            .await
+            .map_err(|err| RpcError::from_status(NodeRpcClientEndpoint::SubmitProvenTx, &err))?;
 
         Ok(BlockNumber::from(api_response.into_inner().block_height))
     }
@@ -121,10 +119,7 @@ impl NodeRpcClient for TonicRpcClient {
         let mut rpc_api = self.ensure_connected().await?;
 
         let api_response = rpc_api.get_block_header_by_number(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetBlockHeaderByNumber.to_string(),
-                err.to_string(),
-            )
+            RpcError::from_status(NodeRpcClientEndpoint::GetBlockHeaderByNumber, &err)
         })?;
 
         let response = api_response.into_inner();
@@ -162,12 +157,10 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let api_response = rpc_api.get_notes_by_id(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetBlockHeaderByNumber.to_string(),
-                err.to_string(),
-            )
-        })?;
+        let api_response = rpc_api
+            .get_notes_by_id(request)
+            .await
+            .map_err(|err| RpcError::from_status(NodeRpcClientEndpoint::GetNotesById, &err))?;
 
         let response_notes = api_response
             .into_inner()
@@ -199,9 +192,10 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.sync_state(request).await.map_err(|err| {
-            RpcError::RequestError(NodeRpcClientEndpoint::SyncState.to_string(), err.to_string())
-        })?;
+        let response = rpc_api
+            .sync_state(request)
+            .await
+            .map_err(|err| RpcError::from_status(NodeRpcClientEndpoint::SyncState, &err))?;
         response.into_inner().try_into()
     }
 
@@ -221,12 +215,10 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.get_account_details(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetAccountDetails.to_string(),
-                err.to_string(),
-            )
-        })?;
+        let response = rpc_api
+            .get_account_details(request)
+            .await
+            .map_err(|err| RpcError::from_status(NodeRpcClientEndpoint::GetAccountDetails, &err))?;
         let response = response.into_inner();
         let account_info = response.details.ok_or(RpcError::ExpectedDataMissing(
             "GetAccountDetails response should have an `account`".to_string(),
@@ -299,12 +291,7 @@ impl NodeRpcClient for TonicRpcClient {
         let response = rpc_api
             .get_account_proofs(request)
             .await
-            .map_err(|err| {
-                RpcError::RequestError(
-                    NodeRpcClientEndpoint::GetAccountProofs.to_string(),
-                    err.to_string(),
-                )
-            })?
+            .map_err(|err| RpcError::from_status(NodeRpcClientEndpoint::GetAccountProofs, &err))?
             .into_inner();
 
         let mut account_proofs = Vec::with_capacity(response.account_proofs.len());
@@ -357,9 +344,10 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.sync_notes(request).await.map_err(|err| {
-            RpcError::RequestError(NodeRpcClientEndpoint::SyncNotes.to_string(), err.to_string())
-        })?;
+        let response = rpc_api
+            .sync_notes(request)
+            .await
+            .map_err(|err| RpcError::from_status(NodeRpcClientEndpoint::SyncNotes, &err))?;
 
         response.into_inner().try_into()
     }
@@ -378,10 +366,7 @@ impl NodeRpcClient for TonicRpcClient {
         let mut rpc_api = self.ensure_connected().await?;
 
         let response = rpc_api.check_nullifiers_by_prefix(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::CheckNullifiersByPrefix.to_string(),
-                err.to_string(),
-            )
+            RpcError::from_status(NodeRpcClientEndpoint::CheckNullifiersByPrefix, &err)
         })?;
         let response = response.into_inner();
         let nullifiers = response
@@ -401,12 +386,10 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.check_nullifiers(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::CheckNullifiers.to_string(),
-                err.to_string(),
-            )
-        })?;
+        let response = rpc_api
+            .check_nullifiers(request)
+            .await
+            .map_err(|err| RpcError::from_status(NodeRpcClientEndpoint::CheckNullifiers, &err))?;
 
         let response = response.into_inner();
         let proofs = response.proofs.iter().map(TryInto::try_into).collect::<Result<_, _>>()?;
@@ -429,10 +412,7 @@ impl NodeRpcClient for TonicRpcClient {
         let mut rpc_api = self.ensure_connected().await?;
 
         let response = rpc_api.get_account_state_delta(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetAccountStateDelta.to_string(),
-                err.to_string(),
-            )
+            RpcError::from_status(NodeRpcClientEndpoint::GetAccountStateDelta, &err)
         })?;
 
         let response = response.into_inner();
@@ -448,12 +428,10 @@ impl NodeRpcClient for TonicRpcClient {
 
         let mut rpc_api = self.ensure_connected().await?;
 
-        let response = rpc_api.get_block_by_number(request).await.map_err(|err| {
-            RpcError::RequestError(
-                NodeRpcClientEndpoint::GetBlockByNumber.to_string(),
-                err.to_string(),
-            )
-        })?;
+        let response = rpc_api
+            .get_block_by_number(request)
+            .await
+            .map_err(|err| RpcError::from_status(NodeRpcClientEndpoint::GetBlockByNumber, &err))?;
 
         let response = response.into_inner();
         let block =
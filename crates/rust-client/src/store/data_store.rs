@@ -21,6 +21,10 @@ pub(crate) struct ClientDataStore {
     store: alloc::sync::Arc<dyn Store>,
     /// Store used to provide MAST nodes to the transaction executor.
     transaction_mast_store: Arc<TransactionMastStore>,
+    /// Account state (and seed) served instead of the one in the store, used to execute
+    /// transactions against state that wasn't persisted yet, or that was already loaded by the
+    /// caller.
+    account_state: Option<(Account, Option<Word>)>,
 }
 
 impl ClientDataStore {
@@ -28,9 +32,20 @@ impl ClientDataStore {
         Self {
             store,
             transaction_mast_store: Arc::new(TransactionMastStore::new()),
+            account_state: None,
         }
     }
 
+    /// Serves the provided account state instead of the one in the store for the account's ID.
+    ///
+    /// The executor needs the whole account state, so callers that already loaded it should
+    /// provide it here to avoid loading it again from the store.
+    #[must_use]
+    pub fn with_account_state(mut self, account: Account, seed: Option<Word>) -> Self {
+        self.account_state = Some((account, seed));
+        self
+    }
+
     pub fn mast_store(&self) -> Arc<TransactionMastStore> {
         self.transaction_mast_store.clone()
     }
@@ -47,14 +62,19 @@ impl DataStore for ClientDataStore {
         let ref_block = block_refs.pop_last().ok_or(DataStoreError::other("Block set is empty"))?;
 
         // Construct Account
-        let account_record = self
-            .store
-            .get_account(account_id)
-            .await?
-            .ok_or(DataStoreError::AccountNotFound(account_id))?;
-
-        let seed = account_record.seed().copied();
-        let account: Account = account_record.into();
+        let (account, seed) = match &self.account_state {
+            Some((account, seed)) if account.id() == account_id => (account.clone(), *seed),
+            _ => {
+                let account_record = self
+                    .store
+                    .get_account(account_id)
+                    .await?
+                    .ok_or(DataStoreError::AccountNotFound(account_id))?;
+
+                let seed = account_record.seed().copied();
+                (account_record.into(), seed)
+            },
+        };
 
         // Get header data
         let (block_header, _had_notes) = self
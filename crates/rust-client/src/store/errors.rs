@@ -38,6 +38,10 @@ pub enum StoreError {
     AccountKeyNotFound(String),
     #[error("account storage data with root {0} not found")]
     AccountStorageNotFound(Word),
+    #[error("storage slot {1} not found for account {0}")]
+    AccountStorageSlotNotFound(AccountId, u8),
+    #[error("storage slot {1} of account {0} is not a storage map")]
+    AccountStorageSlotNotMap(AccountId, u8),
     #[error("partial blockchain node at index {0} not found")]
     PartialBlockchainNodeNotFound(u64),
     #[error("error deserializing data from the store")]
@@ -48,6 +52,8 @@ pub enum StoreError {
     HexParseError(#[from] HexParseError),
     #[error("failed to convert int")]
     InvalidInt(#[from] TryFromIntError),
+    #[error("invalid store snapshot: {0}")]
+    InvalidSnapshot(String),
     #[error("note record error")]
     NoteRecordError(#[from] NoteRecordError),
     #[error("error constructing mmr")]
@@ -60,6 +66,10 @@ pub enum StoreError {
     ParsingError(String),
     #[error("failed to retrieve data from the database: {0}")]
     QueryError(String),
+    #[error("the snapshot has genesis block {0}, but the store has genesis block {1}")]
+    SnapshotGenesisMismatch(Word, Word),
+    #[error("the store isn't empty")]
+    StoreNotEmpty,
     #[error("error instantiating transaction script")]
     TransactionScriptError(#[from] TransactionScriptError),
     #[error("account vault data for root {0} not found")]
@@ -0,0 +1,1038 @@
+//! This module provides an in-memory implementation of the [Store] trait.
+//!
+//! [`MemoryStore`] keeps accounts, transactions, notes, block headers, and MMR nodes in memory, so
+//! the tracked data is lost once the store is dropped. It doesn't rely on the standard library,
+//! which makes it suitable for tests and short-lived clients in any environment.
+//! It is compiled only when the `memory-store` feature flag is enabled.
+
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+
+use miden_objects::{
+    Felt, Word,
+    account::{Account, AccountCode, AccountHeader, AccountId},
+    block::{BlockHeader, BlockNumber},
+    crypto::merkle::{Forest, InOrderIndex, MmrPeaks},
+    note::{NoteId, Nullifier},
+    transaction::{ToInputNoteCommitments, TransactionId},
+};
+use miden_tx::utils::sync::RwLock;
+
+use super::{
+    AccountRecord, AccountStatus, BlockRelevance, InputNoteRecord, NoteFilter, OutputNoteRecord,
+    PartialBlockchainFilter, Store, StoreError, StoreSnapshot, TransactionFilter,
+};
+use crate::{
+    account::AccountRemoval,
+    note::NoteUpdateTracker,
+    sync::{NoteTagRecord, NoteTagSource, StateSyncUpdate},
+    transaction::{
+        PendingTransactionRecord, TransactionDetails, TransactionRecord, TransactionStatus,
+        TransactionStoreUpdate,
+    },
+};
+
+// MEMORY STORE
+// ================================================================================================
+
+/// Keeps the data tracked by the client in memory.
+///
+/// Every update is applied while holding a write lock over the data, and the checks that may make
+/// it fail are performed before any change is made, so updates are atomic.
+pub struct MemoryStore {
+    state: RwLock<MemoryStoreState>,
+}
+
+impl MemoryStore {
+    /// Returns a new, empty [`MemoryStore`].
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(MemoryStoreState::default()),
+        }
+    }
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A stored state of an account.
+struct AccountState {
+    account: Account,
+    /// Seed used to create the account, only kept for the state it was inserted with.
+    seed: Option<Word>,
+    locked: bool,
+}
+
+impl AccountState {
+    fn status(&self) -> AccountStatus {
+        match (self.seed, self.locked) {
+            (_, true) => AccountStatus::Locked,
+            (Some(seed), _) => AccountStatus::New { seed },
+            _ => AccountStatus::Tracked,
+        }
+    }
+
+    fn record(&self) -> AccountRecord {
+        AccountRecord::new(self.account.clone(), self.status())
+    }
+
+    fn header(&self) -> (AccountHeader, AccountStatus) {
+        (AccountHeader::from(&self.account), self.status())
+    }
+}
+
+/// A stored block header, along with the peaks of the partial blockchain at its height.
+struct StoredBlockHeader {
+    header: BlockHeader,
+    partial_blockchain_peaks: MmrPeaks,
+    has_client_notes: bool,
+}
+
+/// The data tracked by a [`MemoryStore`].
+struct MemoryStoreState {
+    /// Stored states of each account, ordered by nonce.
+    accounts: BTreeMap<AccountId, Vec<AccountState>>,
+    foreign_account_code: BTreeMap<AccountId, AccountCode>,
+    input_notes: BTreeMap<NoteId, InputNoteRecord>,
+    output_notes: BTreeMap<NoteId, OutputNoteRecord>,
+    transactions: BTreeMap<TransactionId, TransactionRecord>,
+    pending_transactions: BTreeMap<TransactionId, PendingTransactionRecord>,
+    note_tags: Vec<NoteTagRecord>,
+    block_headers: BTreeMap<BlockNumber, StoredBlockHeader>,
+    partial_blockchain_nodes: BTreeMap<InOrderIndex, Word>,
+    sync_height: BlockNumber,
+    settings: BTreeMap<String, Vec<u8>>,
+}
+
+impl Default for MemoryStoreState {
+    fn default() -> Self {
+        Self {
+            accounts: BTreeMap::new(),
+            foreign_account_code: BTreeMap::new(),
+            input_notes: BTreeMap::new(),
+            output_notes: BTreeMap::new(),
+            transactions: BTreeMap::new(),
+            pending_transactions: BTreeMap::new(),
+            note_tags: Vec::new(),
+            block_headers: BTreeMap::new(),
+            partial_blockchain_nodes: BTreeMap::new(),
+            sync_height: BlockNumber::GENESIS,
+            settings: BTreeMap::new(),
+        }
+    }
+}
+
+impl MemoryStoreState {
+    // ACCOUNTS
+    // --------------------------------------------------------------------------------------------
+
+    fn latest_account_state(&self, account_id: AccountId) -> Option<&AccountState> {
+        self.accounts.get(&account_id).and_then(|states| states.last())
+    }
+
+    fn account_state_by_commitment(&self, account_commitment: Word) -> Option<&AccountState> {
+        self.accounts
+            .values()
+            .flatten()
+            .find(|state| state.account.commitment() == account_commitment)
+    }
+
+    /// Inserts a new state of the account, replacing the stored state with the same commitment.
+    fn insert_account_state(&mut self, account: &Account, seed: Option<Word>) {
+        let states = self.accounts.entry(account.id()).or_default();
+        let commitment = account.commitment();
+        states.retain(|state| state.account.commitment() != commitment);
+
+        let position = states.partition_point(|state| state.account.nonce() <= account.nonce());
+        states.insert(
+            position,
+            AccountState {
+                account: account.clone(),
+                seed,
+                locked: false,
+            },
+        );
+    }
+
+    /// Removes the account states with the specified commitments, which were created by discarded
+    /// transactions.
+    fn undo_account_states(&mut self, account_commitments: &[Word]) {
+        for states in self.accounts.values_mut() {
+            states.retain(|state| !account_commitments.contains(&state.account.commitment()));
+        }
+        self.accounts.retain(|_, states| !states.is_empty());
+    }
+
+    /// Locks the account if the mismatched commitment doesn't belong to one of its stored states,
+    /// as otherwise the mismatch is due to stale network data.
+    fn lock_account_on_unexpected_commitment(
+        &mut self,
+        account_id: AccountId,
+        mismatched_commitment: Word,
+    ) {
+        let Some(states) = self.accounts.get_mut(&account_id) else {
+            return;
+        };
+
+        if states.iter().all(|state| state.account.commitment() != mismatched_commitment) {
+            states.iter_mut().for_each(|state| state.locked = true);
+        }
+    }
+
+    // NOTES
+    // --------------------------------------------------------------------------------------------
+
+    fn apply_note_updates(&mut self, note_updates: &NoteUpdateTracker) {
+        for input_note in note_updates.updated_input_notes() {
+            let note = input_note.inner();
+            self.input_notes.insert(note.id(), note.clone());
+        }
+
+        for output_note in note_updates.updated_output_notes() {
+            let note = output_note.inner();
+            self.output_notes.insert(note.id(), note.clone());
+        }
+    }
+
+    /// Returns the nullifiers of the tracked input notes with the specified IDs.
+    fn input_note_nullifiers(&self, note_ids: &[NoteId]) -> BTreeMap<NoteId, Nullifier> {
+        note_ids
+            .iter()
+            .filter_map(|note_id| self.input_notes.get(note_id))
+            .map(|note| (note.id(), note.nullifier()))
+            .collect()
+    }
+
+    // CHAIN DATA
+    // --------------------------------------------------------------------------------------------
+
+    /// Inserts the block header if it isn't stored yet. If it's stored and `has_client_notes` is
+    /// `true`, the block is marked as relevant.
+    fn insert_block_header(
+        &mut self,
+        block_header: &BlockHeader,
+        partial_blockchain_peaks: MmrPeaks,
+        has_client_notes: bool,
+    ) {
+        self.block_headers
+            .entry(block_header.block_num())
+            .or_insert_with(|| StoredBlockHeader {
+                header: block_header.clone(),
+                partial_blockchain_peaks,
+                has_client_notes,
+            })
+            .has_client_notes |= has_client_notes;
+    }
+
+    /// Inserts the nodes whose index isn't stored yet.
+    fn insert_partial_blockchain_nodes(&mut self, nodes: &[(InOrderIndex, Word)]) {
+        for (index, node) in nodes {
+            self.partial_blockchain_nodes.entry(*index).or_insert(*node);
+        }
+    }
+
+    // SYNC
+    // --------------------------------------------------------------------------------------------
+
+    /// Removes the tag records equal to `tag`. Returns the number of removed records.
+    fn remove_note_tag(&mut self, tag: NoteTagRecord) -> usize {
+        let tags_count = self.note_tags.len();
+        self.note_tags.retain(|tag_record| *tag_record != tag);
+
+        tags_count - self.note_tags.len()
+    }
+
+    /// Removes the tags tracked for the specified source.
+    fn remove_note_tags_by_source(&mut self, source: NoteTagSource) {
+        self.note_tags.retain(|tag_record| tag_record.source != source);
+    }
+}
+
+// In-memory implementation of the Store trait
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl Store for MemoryStore {
+    #[cfg(feature = "std")]
+    fn get_current_timestamp(&self) -> Option<u64> {
+        let now = chrono::Utc::now();
+        Some(u64::try_from(now.timestamp()).expect("timestamp is always after epoch"))
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn get_current_timestamp(&self) -> Option<u64> {
+        None
+    }
+
+    // TRANSACTIONS
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_transactions(
+        &self,
+        filter: TransactionFilter,
+    ) -> Result<Vec<TransactionRecord>, StoreError> {
+        let state = self.state.read();
+
+        // Consumed notes are matched by nullifier, which is only known for tracked input notes
+        let nullifiers = state.input_note_nullifiers(&filter.note_ids());
+
+        Ok(state
+            .transactions
+            .values()
+            .filter(|record| filter.matches(record, &nullifiers))
+            .cloned()
+            .collect())
+    }
+
+    async fn apply_transaction(&self, tx_update: TransactionStoreUpdate) -> Result<(), StoreError> {
+        let executed_transaction = tx_update.executed_transaction();
+
+        let details = TransactionDetails {
+            account_id: executed_transaction.account_id(),
+            init_account_state: executed_transaction.initial_account().commitment(),
+            final_account_state: executed_transaction.final_account().commitment(),
+            input_note_nullifiers: executed_transaction
+                .input_notes()
+                .iter()
+                .map(|note| note.nullifier().as_word())
+                .collect(),
+            output_notes: executed_transaction.output_notes().clone(),
+            block_num: executed_transaction.block_header().block_num(),
+            submission_height: tx_update.submission_height(),
+            expiration_block_num: executed_transaction.expiration_block_num(),
+        };
+
+        let transaction_record = TransactionRecord::new(
+            executed_transaction.id(),
+            details,
+            executed_transaction.tx_args().tx_script().cloned(),
+            TransactionStatus::Pending,
+        );
+
+        let mut state = self.state.write();
+        state.transactions.insert(transaction_record.id, transaction_record);
+        state.insert_account_state(tx_update.updated_account(), None);
+        state.apply_note_updates(tx_update.note_updates());
+        state.note_tags.extend_from_slice(tx_update.new_tags());
+
+        // The transaction is no longer pending
+        state.pending_transactions.remove(&executed_transaction.id());
+
+        Ok(())
+    }
+
+    // PENDING TRANSACTIONS
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_pending_transactions(&self) -> Result<Vec<PendingTransactionRecord>, StoreError> {
+        Ok(self.state.read().pending_transactions.values().cloned().collect())
+    }
+
+    async fn get_pending_transaction(
+        &self,
+        tx_id: TransactionId,
+    ) -> Result<Option<PendingTransactionRecord>, StoreError> {
+        Ok(self.state.read().pending_transactions.get(&tx_id).cloned())
+    }
+
+    async fn upsert_pending_transaction(
+        &self,
+        record: &PendingTransactionRecord,
+    ) -> Result<(), StoreError> {
+        self.state.write().pending_transactions.insert(record.id(), record.clone());
+        Ok(())
+    }
+
+    async fn remove_pending_transaction(&self, tx_id: TransactionId) -> Result<bool, StoreError> {
+        Ok(self.state.write().pending_transactions.remove(&tx_id).is_some())
+    }
+
+    // NOTES
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_input_notes(
+        &self,
+        filter: NoteFilter,
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        Ok(self
+            .state
+            .read()
+            .input_notes
+            .values()
+            .filter(|note| filter.matches_input_note(note))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_output_notes(
+        &self,
+        filter: NoteFilter,
+    ) -> Result<Vec<OutputNoteRecord>, StoreError> {
+        Ok(self
+            .state
+            .read()
+            .output_notes
+            .values()
+            .filter(|note| filter.matches_output_note(note))
+            .cloned()
+            .collect())
+    }
+
+    async fn upsert_input_notes(&self, notes: &[InputNoteRecord]) -> Result<(), StoreError> {
+        let mut state = self.state.write();
+
+        for note in notes {
+            state.input_notes.insert(note.id(), note.clone());
+
+            // Whenever we insert a note, we also update block relevance
+            if let Some(inclusion_proof) = note.inclusion_proof() {
+                let block_num = inclusion_proof.location().block_num();
+                if let Some(block_header) = state.block_headers.get_mut(&block_num) {
+                    block_header.has_client_notes = true;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // CHAIN DATA
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_block_headers(
+        &self,
+        block_numbers: &BTreeSet<BlockNumber>,
+    ) -> Result<Vec<(BlockHeader, BlockRelevance)>, StoreError> {
+        let state = self.state.read();
+
+        Ok(block_numbers
+            .iter()
+            .filter_map(|block_num| state.block_headers.get(block_num))
+            .map(|stored| (stored.header.clone(), stored.has_client_notes.into()))
+            .collect())
+    }
+
+    async fn get_tracked_block_headers(&self) -> Result<Vec<BlockHeader>, StoreError> {
+        Ok(self
+            .state
+            .read()
+            .block_headers
+            .values()
+            .filter(|stored| stored.has_client_notes)
+            .map(|stored| stored.header.clone())
+            .collect())
+    }
+
+    async fn get_partial_blockchain_nodes(
+        &self,
+        filter: PartialBlockchainFilter,
+    ) -> Result<BTreeMap<InOrderIndex, Word>, StoreError> {
+        let state = self.state.read();
+
+        match filter {
+            PartialBlockchainFilter::All => Ok(state.partial_blockchain_nodes.clone()),
+            PartialBlockchainFilter::List(indices) => Ok(indices
+                .into_iter()
+                .filter_map(|index| {
+                    state.partial_blockchain_nodes.get(&index).map(|node| (index, *node))
+                })
+                .collect()),
+        }
+    }
+
+    async fn insert_partial_blockchain_nodes(
+        &self,
+        nodes: &[(InOrderIndex, Word)],
+    ) -> Result<(), StoreError> {
+        self.state.write().insert_partial_blockchain_nodes(nodes);
+        Ok(())
+    }
+
+    async fn get_partial_blockchain_peaks_by_block_num(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<MmrPeaks, StoreError> {
+        match self.state.read().block_headers.get(&block_num) {
+            Some(stored) => Ok(stored.partial_blockchain_peaks.clone()),
+            None => Ok(MmrPeaks::new(Forest::empty(), vec![])?),
+        }
+    }
+
+    async fn insert_block_header(
+        &self,
+        block_header: &BlockHeader,
+        partial_blockchain_peaks: MmrPeaks,
+        has_client_notes: bool,
+    ) -> Result<(), StoreError> {
+        self.state.write().insert_block_header(
+            block_header,
+            partial_blockchain_peaks,
+            has_client_notes,
+        );
+        Ok(())
+    }
+
+    async fn prune_irrelevant_blocks(&self) -> Result<(), StoreError> {
+        let mut state = self.state.write();
+        let sync_height = state.sync_height;
+
+        state.block_headers.retain(|block_num, stored| {
+            stored.has_client_notes
+                || *block_num == BlockNumber::GENESIS
+                || *block_num == sync_height
+        });
+
+        Ok(())
+    }
+
+    // ACCOUNTS
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_account_ids(&self) -> Result<Vec<AccountId>, StoreError> {
+        Ok(self.state.read().accounts.keys().copied().collect())
+    }
+
+    async fn get_account_headers(&self) -> Result<Vec<(AccountHeader, AccountStatus)>, StoreError> {
+        Ok(self
+            .state
+            .read()
+            .accounts
+            .values()
+            .filter_map(|states| states.last())
+            .map(AccountState::header)
+            .collect())
+    }
+
+    async fn get_account_header(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<(AccountHeader, AccountStatus)>, StoreError> {
+        Ok(self.state.read().latest_account_state(account_id).map(AccountState::header))
+    }
+
+    async fn get_account_header_by_commitment(
+        &self,
+        account_commitment: Word,
+    ) -> Result<Option<AccountHeader>, StoreError> {
+        Ok(self
+            .state
+            .read()
+            .account_state_by_commitment(account_commitment)
+            .map(|state| AccountHeader::from(&state.account)))
+    }
+
+    async fn get_account(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        Ok(self.state.read().latest_account_state(account_id).map(AccountState::record))
+    }
+
+    async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountHeader>, StoreError> {
+        Ok(self
+            .state
+            .read()
+            .accounts
+            .get(&account_id)
+            .map(|states| states.iter().map(|state| AccountHeader::from(&state.account)).collect())
+            .unwrap_or_default())
+    }
+
+    async fn get_account_at_nonce(
+        &self,
+        account_id: AccountId,
+        nonce: Felt,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        Ok(self.state.read().accounts.get(&account_id).and_then(|states| {
+            states
+                .iter()
+                .rev()
+                .find(|state| state.account.nonce() == nonce)
+                .map(AccountState::record)
+        }))
+    }
+
+    async fn get_account_by_commitment(
+        &self,
+        account_commitment: Word,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        Ok(self
+            .state
+            .read()
+            .account_state_by_commitment(account_commitment)
+            .map(AccountState::record))
+    }
+
+    async fn insert_account(
+        &self,
+        account: &Account,
+        account_seed: Option<Word>,
+    ) -> Result<(), StoreError> {
+        self.state.write().insert_account_state(account, account_seed);
+        Ok(())
+    }
+
+    async fn upsert_foreign_account_code(
+        &self,
+        account_id: AccountId,
+        code: AccountCode,
+    ) -> Result<(), StoreError> {
+        self.state.write().foreign_account_code.insert(account_id, code);
+        Ok(())
+    }
+
+    async fn get_foreign_account_code(
+        &self,
+        account_ids: Vec<AccountId>,
+    ) -> Result<BTreeMap<AccountId, AccountCode>, StoreError> {
+        let state = self.state.read();
+
+        Ok(account_ids
+            .into_iter()
+            .filter_map(|account_id| {
+                state
+                    .foreign_account_code
+                    .get(&account_id)
+                    .map(|code| (account_id, code.clone()))
+            })
+            .collect())
+    }
+
+    async fn update_account(&self, new_account_state: &Account) -> Result<(), StoreError> {
+        let mut state = self.state.write();
+        if !state.accounts.contains_key(&new_account_state.id()) {
+            return Err(StoreError::AccountDataNotFound(new_account_state.id()));
+        }
+
+        state.insert_account_state(new_account_state, None);
+        Ok(())
+    }
+
+    async fn remove_account(&self, removal: &AccountRemoval) -> Result<(), StoreError> {
+        let account_id = removal.account_id();
+        let mut state = self.state.write();
+        if state.accounts.remove(&account_id).is_none() {
+            return Err(StoreError::AccountDataNotFound(account_id));
+        }
+        state.remove_note_tags_by_source(NoteTagSource::Account(account_id));
+
+        for tx_id in removal.pending_transaction_ids() {
+            state.pending_transactions.remove(tx_id);
+        }
+        for tx_id in removal.transaction_ids() {
+            state.transactions.remove(tx_id);
+        }
+        for note_id in removal.input_note_ids() {
+            state.input_notes.remove(note_id);
+            state.remove_note_tags_by_source(NoteTagSource::Note(*note_id));
+        }
+        for note_id in removal.output_note_ids() {
+            state.output_notes.remove(note_id);
+        }
+
+        Ok(())
+    }
+
+    // SYNC
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_note_tags(&self) -> Result<Vec<NoteTagRecord>, StoreError> {
+        Ok(self.state.read().note_tags.clone())
+    }
+
+    async fn add_note_tag(&self, tag: NoteTagRecord) -> Result<bool, StoreError> {
+        let mut state = self.state.write();
+        if state.note_tags.contains(&tag) {
+            return Ok(false);
+        }
+
+        state.note_tags.push(tag);
+        Ok(true)
+    }
+
+    async fn remove_note_tag(&self, tag: NoteTagRecord) -> Result<usize, StoreError> {
+        Ok(self.state.write().remove_note_tag(tag))
+    }
+
+    async fn get_sync_height(&self) -> Result<BlockNumber, StoreError> {
+        Ok(self.state.read().sync_height)
+    }
+
+    async fn apply_state_sync(&self, state_sync_update: StateSyncUpdate) -> Result<(), StoreError> {
+        let StateSyncUpdate {
+            block_num,
+            block_updates,
+            note_updates,
+            transaction_updates,
+            account_updates,
+        } = state_sync_update;
+
+        let mut state = self.state.write();
+        state.sync_height = block_num;
+
+        for (block_header, block_has_relevant_notes, new_mmr_peaks) in block_updates.block_headers()
+        {
+            state.insert_block_header(
+                block_header,
+                new_mmr_peaks.clone(),
+                *block_has_relevant_notes,
+            );
+        }
+
+        // Insert new authentication nodes (inner nodes of the PartialBlockchain)
+        state.insert_partial_blockchain_nodes(block_updates.new_authentication_nodes());
+
+        // Update notes
+        state.apply_note_updates(&note_updates);
+
+        // Remove the tags of the notes that were committed
+        for note_update in note_updates.updated_input_notes() {
+            let note = note_update.inner();
+            if note.is_committed() {
+                state.remove_note_tag(NoteTagRecord {
+                    tag: note.metadata().expect("Committed notes should have metadata").tag(),
+                    source: NoteTagSource::Note(note.id()),
+                });
+            }
+        }
+
+        for transaction_record in transaction_updates
+            .committed_transactions()
+            .chain(transaction_updates.discarded_transactions())
+        {
+            state.transactions.insert(transaction_record.id, transaction_record.clone());
+        }
+
+        // Remove the account states originated from the discarded transactions
+        let account_commitments_to_remove: Vec<Word> = transaction_updates
+            .discarded_transactions()
+            .map(|tx| tx.details.final_account_state)
+            .collect();
+        state.undo_account_states(&account_commitments_to_remove);
+
+        // Update the public accounts that have been updated onchain
+        for account in account_updates.updated_public_accounts() {
+            state.insert_account_state(account, None);
+        }
+
+        for (account_id, commitment) in account_updates.mismatched_private_accounts() {
+            state.lock_account_on_unexpected_commitment(*account_id, *commitment);
+        }
+
+        Ok(())
+    }
+
+    // GARBAGE COLLECTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Account code, storage, vaults and scripts are owned by the records that reference them, so
+    /// there is never unreferenced data to remove.
+    async fn garbage_collect(&self) -> Result<usize, StoreError> {
+        Ok(0)
+    }
+
+    // SETTINGS
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError> {
+        Ok(self.state.read().settings.get(&name).cloned())
+    }
+
+    async fn set_setting(&self, name: String, value: Vec<u8>) -> Result<(), StoreError> {
+        self.state.write().settings.insert(name, value);
+        Ok(())
+    }
+
+    // SNAPSHOTS
+    // --------------------------------------------------------------------------------------------
+
+    async fn import_snapshot(&self, snapshot: &StoreSnapshot) -> Result<(), StoreError> {
+        // The peaks are built before making any change, as it's the only step that may fail
+        let block_headers = snapshot
+            .block_headers()
+            .iter()
+            .map(|block| Ok((block, block.partial_blockchain_peaks()?)))
+            .collect::<Result<Vec<_>, StoreError>>()?;
+
+        let mut state = self.state.write();
+
+        for account in snapshot.accounts() {
+            let Some((first_state, next_states)) = account.states().split_first() else {
+                return Err(StoreError::InvalidSnapshot("account without states".into()));
+            };
+
+            state.insert_account_state(first_state, account.seed());
+            for account_state in next_states {
+                state.insert_account_state(account_state, None);
+            }
+
+            if account.is_locked() {
+                for account_state in state.accounts.get_mut(&account.id()).into_iter().flatten() {
+                    account_state.locked = true;
+                }
+            }
+        }
+
+        for note in snapshot.input_notes() {
+            state.input_notes.insert(note.id(), note.clone());
+        }
+
+        for note in snapshot.output_notes() {
+            state.output_notes.insert(note.id(), note.clone());
+        }
+
+        for transaction in snapshot.transactions() {
+            state.transactions.insert(transaction.id, transaction.clone());
+        }
+
+        state.note_tags.extend_from_slice(snapshot.note_tags());
+
+        for (block, partial_blockchain_peaks) in block_headers {
+            state.insert_block_header(
+                block.header(),
+                partial_blockchain_peaks,
+                block.has_client_notes(),
+            );
+        }
+
+        state.insert_partial_blockchain_nodes(snapshot.partial_blockchain_nodes());
+        state.sync_height = snapshot.sync_height();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_lib::{note::create_p2id_note, transaction::TransactionKernel};
+    use miden_objects::{
+        Felt, Word,
+        account::{Account, AccountDelta, AccountId, AccountStorageDelta, AccountVaultDelta},
+        asset::FungibleAsset,
+        block::BlockHeader,
+        crypto::{
+            merkle::{InOrderIndex, Mmr},
+            rand::RpoRandomCoin,
+        },
+        note::{Note, NoteTag, NoteType},
+        testing::account_id::ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+        utils::Serializable,
+    };
+
+    use super::MemoryStore;
+    use crate::{
+        store::{
+            InputNoteRecord, NoteFilter, Store, StoreError,
+            sqlite_store::tests::{create_test_account, create_test_store},
+        },
+        sync::NoteTagRecord,
+    };
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn is_send_sync() {
+        assert_send_sync::<MemoryStore>();
+    }
+
+    /// Returns an account, a later state of it and a note sent to it.
+    fn test_data() -> (Account, Account, Note) {
+        let account = create_test_account();
+        let delta = AccountDelta::new(
+            account.id(),
+            AccountStorageDelta::default(),
+            AccountVaultDelta::default(),
+            Felt::new(1),
+        )
+        .unwrap();
+        let mut updated_account = account.clone();
+        updated_account.apply_delta(&delta).unwrap();
+
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+        let note = create_p2id_note(
+            account.id(),
+            account.id(),
+            vec![FungibleAsset::new(faucet_id, 10).unwrap().into()],
+            NoteType::Private,
+            Felt::ZERO,
+            &mut RpoRandomCoin::new(Word::default()),
+        )
+        .unwrap();
+
+        (account, updated_account, note)
+    }
+
+    /// Applies the same updates to the store, through the methods of the [`Store`] trait.
+    async fn populate(
+        store: &dyn Store,
+        account: &Account,
+        updated_account: &Account,
+        note: &Note,
+    ) {
+        store.insert_account(account, None).await.unwrap();
+        store.update_account(updated_account).await.unwrap();
+        store
+            .upsert_foreign_account_code(account.id(), account.code().clone())
+            .await
+            .unwrap();
+
+        store.upsert_input_notes(&[InputNoteRecord::from(note.clone())]).await.unwrap();
+        let tag = NoteTag::from(1);
+        assert!(
+            store
+                .add_note_tag(NoteTagRecord::with_account_source(tag, account.id()))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !store
+                .add_note_tag(NoteTagRecord::with_account_source(tag, account.id()))
+                .await
+                .unwrap()
+        );
+        assert!(
+            store
+                .add_note_tag(NoteTagRecord::with_note_source(tag, note.id()))
+                .await
+                .unwrap()
+        );
+
+        let genesis = BlockHeader::mock(0, None, None, &[], TransactionKernel::kernel_commitment());
+        let block = BlockHeader::mock(1, None, None, &[], TransactionKernel::kernel_commitment());
+        let irrelevant_block =
+            BlockHeader::mock(2, None, None, &[], TransactionKernel::kernel_commitment());
+        let mut mmr = Mmr::new();
+        store.insert_block_header(&genesis, mmr.peaks(), false).await.unwrap();
+        mmr.add(genesis.commitment());
+        store.insert_block_header(&block, mmr.peaks(), true).await.unwrap();
+        mmr.add(block.commitment());
+        store.insert_block_header(&irrelevant_block, mmr.peaks(), false).await.unwrap();
+        store
+            .insert_partial_blockchain_nodes(&[(
+                InOrderIndex::from_leaf_pos(0),
+                genesis.commitment(),
+            )])
+            .await
+            .unwrap();
+    }
+
+    /// Checks that both stores return the same data.
+    async fn assert_same_data(store: &dyn Store, expected_store: &dyn Store) {
+        assert_eq!(
+            store.export_snapshot().await.unwrap().to_bytes(),
+            expected_store.export_snapshot().await.unwrap().to_bytes()
+        );
+        assert_eq!(
+            store.get_note_tags().await.unwrap(),
+            expected_store.get_note_tags().await.unwrap()
+        );
+        assert_eq!(
+            store.get_tracked_block_headers().await.unwrap(),
+            expected_store.get_tracked_block_headers().await.unwrap()
+        );
+
+        let headers = |headers: Vec<_>| {
+            headers
+                .into_iter()
+                .map(|(header, status)| (header, format!("{status}")))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            headers(store.get_account_headers().await.unwrap()),
+            headers(expected_store.get_account_headers().await.unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn matches_the_sqlite_store() {
+        let (account, updated_account, note) = test_data();
+        let sqlite_store = create_test_store().await;
+        let memory_store = MemoryStore::new();
+        populate(&sqlite_store, &account, &updated_account, &note).await;
+        populate(&memory_store, &account, &updated_account, &note).await;
+
+        assert_same_data(&memory_store, &sqlite_store).await;
+
+        // Snapshots of the SQLite store are restored as they were exported
+        let restored_store = MemoryStore::new();
+        restored_store
+            .import_snapshot(&sqlite_store.export_snapshot().await.unwrap())
+            .await
+            .unwrap();
+        assert_same_data(&restored_store, &sqlite_store).await;
+
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+        for filter in [
+            NoteFilter::All,
+            NoteFilter::Expected,
+            NoteFilter::Committed,
+            NoteFilter::Unspent,
+            NoteFilter::Target(account.id()),
+            NoteFilter::Sender(account.id()),
+            NoteFilter::Faucet(faucet_id),
+            NoteFilter::And(vec![NoteFilter::Expected, NoteFilter::Target(account.id())]),
+        ] {
+            let note_ids = |notes: Vec<InputNoteRecord>| {
+                notes.iter().map(InputNoteRecord::id).collect::<Vec<_>>()
+            };
+            assert_eq!(
+                note_ids(memory_store.get_input_notes(filter.clone()).await.unwrap()),
+                note_ids(sqlite_store.get_input_notes(filter).await.unwrap())
+            );
+        }
+
+        assert_eq!(
+            memory_store.get_account_history(account.id()).await.unwrap(),
+            sqlite_store.get_account_history(account.id()).await.unwrap()
+        );
+        assert_eq!(
+            memory_store.get_foreign_account_code(vec![account.id()]).await.unwrap(),
+            sqlite_store.get_foreign_account_code(vec![account.id()]).await.unwrap()
+        );
+        assert_eq!(
+            memory_store.get_account_storage_item(account.id(), 1).await.unwrap(),
+            sqlite_store.get_account_storage_item(account.id(), 1).await.unwrap()
+        );
+        assert_eq!(
+            memory_store
+                .get_account_map_item(account.id(), 2, Word::default())
+                .await
+                .unwrap(),
+            sqlite_store
+                .get_account_map_item(account.id(), 2, Word::default())
+                .await
+                .unwrap()
+        );
+
+        // Removals
+        let tag = NoteTagRecord::with_account_source(NoteTag::from(1), account.id());
+        assert_eq!(memory_store.remove_note_tag(tag).await.unwrap(), 1);
+        assert_eq!(sqlite_store.remove_note_tag(tag).await.unwrap(), 1);
+        assert_eq!(memory_store.remove_input_notes(&[note.id()]).await.unwrap(), 1);
+        assert_eq!(sqlite_store.remove_input_notes(&[note.id()]).await.unwrap(), 1);
+        memory_store.prune_irrelevant_blocks().await.unwrap();
+        sqlite_store.prune_irrelevant_blocks().await.unwrap();
+        memory_store.remove_account(account.id()).await.unwrap();
+        sqlite_store.remove_account(account.id()).await.unwrap();
+
+        assert_same_data(&memory_store, &sqlite_store).await;
+        assert!(memory_store.get_note_tags().await.unwrap().is_empty());
+        assert!(matches!(
+            memory_store.update_account(&updated_account).await,
+            Err(StoreError::AccountDataNotFound(_))
+        ));
+        assert!(matches!(
+            memory_store.remove_account(account.id()).await,
+            Err(StoreError::AccountDataNotFound(_))
+        ));
+    }
+}
@@ -23,23 +23,29 @@
 use alloc::{
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
+    string::String,
     vec::Vec,
 };
 use core::fmt::Debug;
 
 use miden_objects::{
-    Word,
-    account::{Account, AccountCode, AccountHeader, AccountId},
+    Felt, Word,
+    account::{Account, AccountCode, AccountHeader, AccountId, StorageSlot},
     block::{BlockHeader, BlockNumber},
-    crypto::merkle::{InOrderIndex, MmrPeaks},
+    crypto::merkle::{InOrderIndex, MmrPeaks, SmtProof},
     note::{NoteId, NoteTag, Nullifier},
     transaction::TransactionId,
 };
 
 use crate::{
+    account::AccountRemoval,
     sync::{NoteTagRecord, StateSyncUpdate},
-    transaction::{TransactionRecord, TransactionStoreUpdate},
+    transaction::{
+        DiscardCause, PendingTransactionRecord, TransactionRecord, TransactionStoreUpdate,
+    },
 };
+#[cfg(any(feature = "idxdb", feature = "memory-store"))]
+use crate::{note::well_known_note_target, transaction::TransactionStatus};
 
 /// Contains [`ClientDataStore`] to automatically implement [`DataStore`] for anything that
 /// implements [`Store`]. This isn't public because it's an implementation detail to instantiate the
@@ -61,6 +67,9 @@ pub mod sqlite_store;
 #[cfg(feature = "idxdb")]
 pub mod web_store;
 
+#[cfg(feature = "memory-store")]
+pub mod memory_store;
+
 mod account;
 pub use account::{AccountRecord, AccountStatus, AccountUpdates};
 mod note_record;
@@ -68,6 +77,8 @@ pub use note_record::{
     InputNoteRecord, InputNoteState, NoteExportType, NoteRecordError, OutputNoteRecord,
     OutputNoteState, input_note_states,
 };
+mod snapshot;
+pub use snapshot::{AccountSnapshot, BlockHeaderSnapshot, SNAPSHOT_VERSION, StoreSnapshot};
 
 // STORE TRAIT
 // ================================================================================================
@@ -103,6 +114,22 @@ pub trait Store: Send + Sync {
         filter: TransactionFilter,
     ) -> Result<Vec<TransactionRecord>, StoreError>;
 
+    /// Retrieves a [`Page`] of the stored transactions matching the [`TransactionFilter`].
+    ///
+    /// Transactions are sorted by the number of the block they were executed against and then by
+    /// ID, so that consecutive pages don't overlap. The default implementation retrieves every
+    /// matching transaction and sorts them in memory.
+    async fn get_transactions_page(
+        &self,
+        filter: TransactionFilter,
+        page: Page,
+    ) -> Result<Vec<TransactionRecord>, StoreError> {
+        let mut transactions = self.get_transactions(filter).await?;
+        transactions.sort_by_cached_key(|tx| (tx.details.block_num, tx.id.to_hex()));
+
+        Ok(transactions.into_iter().skip(page.offset()).take(page.limit()).collect())
+    }
+
     /// Applies a transaction, atomically updating the current state based on the
     /// [`TransactionStoreUpdate`].
     ///
@@ -113,8 +140,34 @@ pub trait Store: Send + Sync {
     /// - Updating the input notes that are being processed by the transaction.
     /// - Inserting the new tracked tags into the store.
     /// - Inserting the transaction into the store to track.
+    /// - Removing the transaction from the pending transactions, if it was being tracked as one.
     async fn apply_transaction(&self, tx_update: TransactionStoreUpdate) -> Result<(), StoreError>;
 
+    // PENDING TRANSACTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Retrieves all the pending transactions, which are transactions that went through part of the
+    /// execute, prove and submit pipeline but haven't been applied yet.
+    async fn get_pending_transactions(&self) -> Result<Vec<PendingTransactionRecord>, StoreError>;
+
+    /// Retrieves the pending transaction with the specified ID. Returns `None` if the transaction
+    /// isn't pending.
+    async fn get_pending_transaction(
+        &self,
+        tx_id: TransactionId,
+    ) -> Result<Option<PendingTransactionRecord>, StoreError>;
+
+    /// Inserts a pending transaction into the store. If a pending transaction with the same ID
+    /// already exists, it will be replaced.
+    async fn upsert_pending_transaction(
+        &self,
+        record: &PendingTransactionRecord,
+    ) -> Result<(), StoreError>;
+
+    /// Removes the pending transaction with the specified ID from the store. Returns `true` if the
+    /// transaction was pending.
+    async fn remove_pending_transaction(&self, tx_id: TransactionId) -> Result<bool, StoreError>;
+
     // NOTES
     // --------------------------------------------------------------------------------------------
 
@@ -128,6 +181,44 @@ pub trait Store: Send + Sync {
         filter: NoteFilter,
     ) -> Result<Vec<OutputNoteRecord>, StoreError>;
 
+    /// Retrieves a [`Page`] of the input notes matching the [`NoteFilter`], sorted as specified by
+    /// the [`NoteSort`].
+    ///
+    /// The default implementation retrieves every matching note and sorts them in memory.
+    async fn get_input_notes_page(
+        &self,
+        filter: NoteFilter,
+        sort: NoteSort,
+        page: Page,
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        let mut notes = self.get_input_notes(filter).await?;
+        sort.sort_notes(&mut notes, |note| {
+            let block_num = note.inclusion_proof().map(|proof| proof.location().block_num());
+            (note.id(), block_num, note.created_at())
+        });
+
+        Ok(notes.into_iter().skip(page.offset()).take(page.limit()).collect())
+    }
+
+    /// Retrieves a [`Page`] of the output notes matching the [`NoteFilter`], sorted as specified
+    /// by the [`NoteSort`].
+    ///
+    /// The default implementation retrieves every matching note and sorts them in memory.
+    async fn get_output_notes_page(
+        &self,
+        filter: NoteFilter,
+        sort: NoteSort,
+        page: Page,
+    ) -> Result<Vec<OutputNoteRecord>, StoreError> {
+        let mut notes = self.get_output_notes(filter).await?;
+        sort.sort_notes(&mut notes, |note| {
+            let block_num = note.inclusion_proof().map(|proof| proof.location().block_num());
+            (note.id(), block_num, None)
+        });
+
+        Ok(notes.into_iter().skip(page.offset()).take(page.limit()).collect())
+    }
+
     /// Returns the nullifiers of all unspent input notes.
     ///
     /// The default implementation of this method uses [Store::get_input_notes].
@@ -249,6 +340,115 @@ pub trait Store: Send + Sync {
     async fn get_account(&self, account_id: AccountId)
     -> Result<Option<AccountRecord>, StoreError>;
 
+    /// Returns the [`AccountHeader`] of every stored state of the specified account, ordered by
+    /// nonce from oldest to newest.
+    async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountHeader>, StoreError>;
+
+    /// Retrieves the full [`AccountRecord`] of the stored state of the account with the specified
+    /// nonce. If several stored states share the nonce, the latest one is returned. Returns `None`
+    /// if no stored state of the account has that nonce.
+    async fn get_account_at_nonce(
+        &self,
+        account_id: AccountId,
+        nonce: Felt,
+    ) -> Result<Option<AccountRecord>, StoreError>;
+
+    /// Retrieves the full [`AccountRecord`] of the stored account state that matches the given
+    /// commitment. Returns `None` if no account state matches the provided commitment.
+    async fn get_account_by_commitment(
+        &self,
+        account_commitment: Word,
+    ) -> Result<Option<AccountRecord>, StoreError>;
+
+    /// Retrieves the value of the storage slot with the specified index in the latest state of the
+    /// account. For storage map slots, the value is the root of the map.
+    ///
+    /// The default implementation of this method uses [`Store::get_account`], stores should
+    /// override it to avoid loading the whole account.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a `StoreError::AccountDataNotFound` if there is no account for the provided ID.
+    /// - Returns a `StoreError::AccountStorageSlotNotFound` if the account has no slot with the
+    ///   provided index.
+    async fn get_account_storage_item(
+        &self,
+        account_id: AccountId,
+        index: u8,
+    ) -> Result<Word, StoreError> {
+        let account_record = self
+            .get_account(account_id)
+            .await?
+            .ok_or(StoreError::AccountDataNotFound(account_id))?;
+
+        account_record
+            .account()
+            .storage()
+            .slots()
+            .get(usize::from(index))
+            .map(StorageSlot::value)
+            .ok_or(StoreError::AccountStorageSlotNotFound(account_id, index))
+    }
+
+    /// Retrieves the value of the entry with the specified key in the storage map at the specified
+    /// slot index, along with a Merkle proof of the entry against the root of the map, in the
+    /// latest state of the account. The value is the empty word if the map has no such entry.
+    ///
+    /// The default implementation of this method uses [`Store::get_account`], stores should
+    /// override it to avoid loading the whole account.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a `StoreError::AccountDataNotFound` if there is no account for the provided ID.
+    /// - Returns a `StoreError::AccountStorageSlotNotFound` if the account has no slot with the
+    ///   provided index.
+    /// - Returns a `StoreError::AccountStorageSlotNotMap` if the slot isn't a storage map.
+    async fn get_account_map_item(
+        &self,
+        account_id: AccountId,
+        index: u8,
+        key: Word,
+    ) -> Result<(Word, SmtProof), StoreError> {
+        let account_record = self
+            .get_account(account_id)
+            .await?
+            .ok_or(StoreError::AccountDataNotFound(account_id))?;
+
+        match account_record.account().storage().slots().get(usize::from(index)) {
+            Some(StorageSlot::Map(map)) => Ok((map.get(&key), map.open(&key))),
+            Some(StorageSlot::Value(_)) => {
+                Err(StoreError::AccountStorageSlotNotMap(account_id, index))
+            },
+            None => Err(StoreError::AccountStorageSlotNotFound(account_id, index)),
+        }
+    }
+
+    /// Retrieves the balance of the fungible asset issued by the specified faucet in the vault of
+    /// the latest state of the account.
+    ///
+    /// The default implementation of this method uses [`Store::get_account`], stores should
+    /// override it to avoid loading the whole account.
+    ///
+    /// # Errors
+    ///
+    /// - Returns a `StoreError::AccountDataNotFound` if there is no account for the provided ID.
+    /// - Returns a `StoreError::AssetVaultError` if the faucet ID isn't a fungible faucet ID.
+    async fn get_account_balance(
+        &self,
+        account_id: AccountId,
+        faucet_id: AccountId,
+    ) -> Result<u64, StoreError> {
+        let account_record = self
+            .get_account(account_id)
+            .await?
+            .ok_or(StoreError::AccountDataNotFound(account_id))?;
+
+        Ok(account_record.account().vault().get_balance(faucet_id)?)
+    }
+
     /// Inserts an [`Account`] along with the seed used to create it.
     async fn insert_account(
         &self,
@@ -277,6 +477,22 @@ pub trait Store: Send + Sync {
     /// Returns a `StoreError::AccountDataNotFound` if there is no account for the provided ID.
     async fn update_account(&self, new_account_state: &Account) -> Result<(), StoreError>;
 
+    /// Applies the account removal to the store in a single operation. A removal involves:
+    ///
+    /// - Removing every stored state of the account, along with the note tags tracked for it.
+    /// - Removing the pending transactions of the account.
+    /// - Removing the specified transactions, input notes (along with the note tags tracked for
+    ///   them) and output notes.
+    ///
+    /// If any step fails, none of the data is removed. The account code is kept, as it may be
+    /// shared with other accounts. It's removed by [`Store::garbage_collect`] once no account
+    /// uses it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError::AccountDataNotFound` if there is no account for the provided ID.
+    async fn remove_account(&self, removal: &AccountRemoval) -> Result<(), StoreError>;
+
     // SYNC
     // --------------------------------------------------------------------------------------------
 
@@ -315,6 +531,38 @@ pub trait Store: Send + Sync {
     /// - Storing new MMR authentication nodes.
     /// - Updating the tracked public accounts.
     async fn apply_state_sync(&self, state_sync_update: StateSyncUpdate) -> Result<(), StoreError>;
+
+    // GARBAGE COLLECTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Removes the data that is no longer referenced by any stored account, note or transaction:
+    /// account code, account storage and vault data, note scripts and transaction scripts. Returns
+    /// the number of removed entries.
+    async fn garbage_collect(&self) -> Result<usize, StoreError>;
+
+    // SETTINGS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the value of the client setting with the specified name, or `None` if it isn't set.
+    ///
+    /// Settings keep small pieces of client state across sessions, such as the position up to
+    /// which private notes were fetched from the note transport.
+    async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError>;
+
+    /// Sets the value of the client setting with the specified name, replacing the previous one.
+    async fn set_setting(&self, name: String, value: Vec<u8>) -> Result<(), StoreError>;
+
+    // SNAPSHOTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a snapshot of the data tracked by the store.
+    async fn export_snapshot(&self) -> Result<StoreSnapshot, StoreError> {
+        StoreSnapshot::from_store(self).await
+    }
+
+    /// Inserts the data of the snapshot into the store, which is expected to be empty. The
+    /// snapshot is expected to be valid (see [`StoreSnapshot::validate`]).
+    async fn import_snapshot(&self, snapshot: &StoreSnapshot) -> Result<(), StoreError>;
 }
 
 // PARTIAL BLOCKCHAIN NODE FILTER
@@ -349,6 +597,129 @@ pub enum TransactionFilter {
     /// A transaction is considered expired if is uncommitted and the transaction's block number
     /// is less than the provided block number.
     ExpiredBefore(BlockNumber),
+    /// Return the submitted transactions that are neither committed nor discarded yet. Unlike
+    /// [`PendingTransactionRecord`]s, these were already submitted to the network.
+    InFlight,
+    /// Return the transactions that were committed to the blockchain as per the last sync.
+    Committed,
+    /// Return the discarded transactions. If a [`DiscardCause`] is provided, only the
+    /// transactions discarded for that cause are returned.
+    Discarded(Option<DiscardCause>),
+    /// Return the transactions executed by the account with the provided [`AccountId`].
+    Account(AccountId),
+    /// Return the transactions executed against a block whose number is within the provided
+    /// range, both ends included.
+    BlockRange { from: BlockNumber, to: BlockNumber },
+    /// Return the transactions that consumed or created the note with the provided [`NoteId`].
+    ///
+    /// Consumed notes are matched by nullifier, so they are only found if the note is tracked as
+    /// an input note.
+    Note(NoteId),
+    /// Return the transactions whose transaction script has the provided root.
+    ScriptRoot(Word),
+    /// Return the transactions that match all the provided filters.
+    And(Vec<TransactionFilter>),
+}
+
+#[cfg(any(feature = "idxdb", feature = "memory-store"))]
+impl TransactionFilter {
+    /// Returns the IDs of the notes that this filter matches transactions against.
+    pub(crate) fn note_ids(&self) -> Vec<NoteId> {
+        match self {
+            TransactionFilter::Note(note_id) => vec![*note_id],
+            TransactionFilter::And(filters) => {
+                filters.iter().flat_map(TransactionFilter::note_ids).collect()
+            },
+            _ => vec![],
+        }
+    }
+
+    /// Returns `true` if the transaction matches this filter. `nullifiers` must contain the
+    /// nullifiers of the tracked input notes returned by [`TransactionFilter::note_ids`].
+    pub(crate) fn matches(
+        &self,
+        record: &TransactionRecord,
+        nullifiers: &BTreeMap<NoteId, Nullifier>,
+    ) -> bool {
+        let details = &record.details;
+        match self {
+            TransactionFilter::All => true,
+            TransactionFilter::Uncommitted => {
+                !matches!(record.status, TransactionStatus::Committed(_))
+            },
+            TransactionFilter::Ids(ids) => ids.contains(&record.id),
+            TransactionFilter::ExpiredBefore(block_num) => {
+                matches!(record.status, TransactionStatus::Pending)
+                    && details.block_num < *block_num
+            },
+            TransactionFilter::InFlight => matches!(record.status, TransactionStatus::Pending),
+            TransactionFilter::Committed => {
+                matches!(record.status, TransactionStatus::Committed(_))
+            },
+            TransactionFilter::Discarded(cause) => match record.status {
+                TransactionStatus::Discarded(discard_cause) => {
+                    cause.is_none_or(|cause| cause == discard_cause)
+                },
+                _ => false,
+            },
+            TransactionFilter::Account(account_id) => details.account_id == *account_id,
+            TransactionFilter::BlockRange { from, to } => {
+                (*from..=*to).contains(&details.block_num)
+            },
+            TransactionFilter::Note(note_id) => {
+                details.output_notes.iter().any(|note| note.id() == *note_id)
+                    || nullifiers.get(note_id).is_some_and(|nullifier| {
+                        details.input_note_nullifiers.contains(&nullifier.as_word())
+                    })
+            },
+            TransactionFilter::ScriptRoot(script_root) => {
+                record.script.as_ref().is_some_and(|script| script.root() == *script_root)
+            },
+            TransactionFilter::And(filters) => {
+                filters.iter().all(|filter| filter.matches(record, nullifiers))
+            },
+        }
+    }
+}
+
+// PAGINATION
+// ================================================================================================
+
+/// Selects a range of the results of a query, to retrieve them in batches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    /// Number of results to skip.
+    offset: usize,
+    /// Maximum number of results to return.
+    limit: usize,
+}
+
+impl Page {
+    /// Returns a page with at most `limit` results, skipping the first `offset` ones.
+    pub fn new(offset: usize, limit: usize) -> Self {
+        Self { offset, limit }
+    }
+
+    /// Returns the first page with at most `limit` results.
+    pub fn first(limit: usize) -> Self {
+        Self::new(0, limit)
+    }
+
+    /// Returns the page that follows this one, with the same limit.
+    #[must_use]
+    pub fn next_page(&self) -> Self {
+        Self::new(self.offset.saturating_add(self.limit), self.limit)
+    }
+
+    /// Returns the number of results to skip.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the maximum number of results to return.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
 }
 
 // NOTE FILTER
@@ -384,6 +755,198 @@ pub enum NoteFilter {
     /// Return a list containing notes with unverified inclusion proofs. This filter doesn't apply
     /// to output notes.
     Unverified,
+    /// Return the input notes consumed, or being consumed, by a local transaction of the account
+    /// with the provided [`AccountId`]. This filter doesn't apply to output notes.
+    Consumer(AccountId),
+    /// Return the P2ID and P2IDE notes whose target is the account with the provided
+    /// [`AccountId`]. Output notes are only matched if their recipient is known.
+    Target(AccountId),
+    /// Return the notes sent by the account with the provided [`AccountId`]. Input notes are only
+    /// matched if their metadata is known.
+    Sender(AccountId),
+    /// Return the notes with the provided [`NoteTag`]. Input notes are only matched if their
+    /// metadata is known.
+    Tag(NoteTag),
+    /// Return the notes holding assets issued by the faucet with the provided [`AccountId`].
+    /// Faucets are matched by their ID prefix, as it's the only part of the ID that non-fungible
+    /// assets hold.
+    Faucet(AccountId),
+    /// Return the notes whose script has the provided root. Output notes are only matched if their
+    /// recipient is known.
+    ScriptRoot(Word),
+    /// Return the input notes created or imported between the provided timestamps (in seconds
+    /// since the Unix epoch), both ends included. This filter doesn't apply to output notes.
+    CreatedBetween { from: u64, to: u64 },
+    /// Return the notes included in a block whose number is within the provided range, both ends
+    /// included.
+    BlockRange { from: BlockNumber, to: BlockNumber },
+    /// Return the notes that match all the provided filters.
+    And(Vec<NoteFilter>),
+}
+
+#[cfg(any(feature = "idxdb", feature = "memory-store"))]
+impl NoteFilter {
+    /// Returns `true` if the input note matches this filter.
+    pub(crate) fn matches_input_note(&self, note: &InputNoteRecord) -> bool {
+        let state = note.state().discriminant();
+        match self {
+            NoteFilter::All => true,
+            NoteFilter::Committed => state == InputNoteState::STATE_COMMITTED,
+            NoteFilter::Consumed => note.is_consumed(),
+            NoteFilter::Expected => state == InputNoteState::STATE_EXPECTED,
+            NoteFilter::Processing => note.is_processing(),
+            NoteFilter::Unverified => state == InputNoteState::STATE_UNVERIFIED,
+            NoteFilter::Unspent => !note.is_consumed() && state != InputNoteState::STATE_INVALID,
+            NoteFilter::List(note_ids) => note_ids.contains(&note.id()),
+            NoteFilter::Unique(note_id) => note.id() == *note_id,
+            NoteFilter::Nullifiers(nullifiers) => nullifiers.contains(&note.nullifier()),
+            NoteFilter::Consumer(account_id) => note.consumer_account() == Some(*account_id),
+            NoteFilter::Target(account_id) => {
+                well_known_note_target(note.details().recipient()) == Some(*account_id)
+            },
+            NoteFilter::Sender(account_id) => {
+                note.metadata().is_some_and(|metadata| metadata.sender() == *account_id)
+            },
+            NoteFilter::Tag(tag) => note.metadata().is_some_and(|metadata| metadata.tag() == *tag),
+            NoteFilter::Faucet(faucet_id) => {
+                note.assets().iter().any(|asset| asset.faucet_id_prefix() == faucet_id.prefix())
+            },
+            NoteFilter::ScriptRoot(script_root) => {
+                note.details().recipient().script().root() == *script_root
+            },
+            NoteFilter::CreatedBetween { from, to } => {
+                (*from..=*to).contains(&note.created_at().unwrap_or(0))
+            },
+            NoteFilter::BlockRange { from, to } => note
+                .inclusion_proof()
+                .is_some_and(|proof| (*from..=*to).contains(&proof.location().block_num())),
+            NoteFilter::And(filters) => {
+                filters.iter().all(|filter| filter.matches_input_note(note))
+            },
+        }
+    }
+
+    /// Returns `true` if the output note matches this filter.
+    pub(crate) fn matches_output_note(&self, note: &OutputNoteRecord) -> bool {
+        let state = note.state().discriminant();
+        match self {
+            NoteFilter::All => true,
+            NoteFilter::Committed => note.is_committed(),
+            NoteFilter::Consumed => note.is_consumed(),
+            NoteFilter::Expected => {
+                [OutputNoteState::STATE_EXPECTED_FULL, OutputNoteState::STATE_EXPECTED_PARTIAL]
+                    .contains(&state)
+            },
+            NoteFilter::Unspent => {
+                [OutputNoteState::STATE_EXPECTED_FULL, OutputNoteState::STATE_COMMITTED_FULL]
+                    .contains(&state)
+            },
+            // There are no processing or unverified output notes, and there is no consumer
+            // account or creation time for them
+            NoteFilter::Processing
+            | NoteFilter::Unverified
+            | NoteFilter::Consumer(_)
+            | NoteFilter::CreatedBetween { .. } => false,
+            NoteFilter::List(note_ids) => note_ids.contains(&note.id()),
+            NoteFilter::Unique(note_id) => note.id() == *note_id,
+            NoteFilter::Nullifiers(nullifiers) => {
+                note.nullifier().is_some_and(|nullifier| nullifiers.contains(&nullifier))
+            },
+            NoteFilter::Target(account_id) => {
+                note.recipient().and_then(well_known_note_target) == Some(*account_id)
+            },
+            NoteFilter::Sender(account_id) => note.metadata().sender() == *account_id,
+            NoteFilter::Tag(tag) => note.metadata().tag() == *tag,
+            NoteFilter::Faucet(faucet_id) => {
+                note.assets().iter().any(|asset| asset.faucet_id_prefix() == faucet_id.prefix())
+            },
+            NoteFilter::ScriptRoot(script_root) => note
+                .recipient()
+                .is_some_and(|recipient| recipient.script().root() == *script_root),
+            NoteFilter::BlockRange { from, to } => note
+                .inclusion_proof()
+                .is_some_and(|proof| (*from..=*to).contains(&proof.location().block_num())),
+            NoteFilter::And(filters) => {
+                filters.iter().all(|filter| filter.matches_output_note(note))
+            },
+        }
+    }
+}
+
+// NOTE SORTING
+// ================================================================================================
+
+/// The fields by which paginated note queries can be sorted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NoteSortKey {
+    /// Sort by note ID.
+    #[default]
+    Id,
+    /// Sort by the number of the block the note was included in. Notes that weren't included in a
+    /// block yet come after the included ones.
+    BlockNum,
+    /// Sort by the time the note was created or imported. Output notes don't track their creation
+    /// time, so they are sorted by ID.
+    CreatedAt,
+}
+
+/// The order of the notes returned by paginated note queries.
+///
+/// Notes with the same value for the sort key are sorted by ID, so that consecutive pages don't
+/// overlap. Descending orders are the exact reverse of the ascending ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoteSort {
+    /// Field by which the notes are sorted.
+    key: NoteSortKey,
+    /// Whether the notes are sorted in descending order.
+    descending: bool,
+}
+
+impl NoteSort {
+    /// Returns an ascending order by the provided key.
+    pub fn ascending(key: NoteSortKey) -> Self {
+        Self { key, descending: false }
+    }
+
+    /// Returns a descending order by the provided key.
+    pub fn descending(key: NoteSortKey) -> Self {
+        Self { key, descending: true }
+    }
+
+    /// Returns the field by which the notes are sorted.
+    pub fn key(&self) -> NoteSortKey {
+        self.key
+    }
+
+    /// Returns `true` if the notes are sorted in descending order.
+    pub fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    /// Sorts the notes in place, using `values` to get the block number and creation time of each
+    /// note.
+    fn sort_notes<T>(
+        &self,
+        notes: &mut [T],
+        values: impl Fn(&T) -> (NoteId, Option<BlockNumber>, Option<u64>),
+    ) {
+        notes.sort_by_cached_key(|note| {
+            let (note_id, block_num, created_at) = values(note);
+            let value = match self.key {
+                NoteSortKey::Id => (false, 0),
+                NoteSortKey::BlockNum => {
+                    (block_num.is_none(), block_num.map_or(0, |block_num| block_num.as_u64()))
+                },
+                NoteSortKey::CreatedAt => (false, created_at.unwrap_or(0)),
+            };
+
+            (value, note_id.to_hex())
+        });
+
+        if self.descending {
+            notes.reverse();
+        }
+    }
 }
 
 // BLOCK RELEVANCE
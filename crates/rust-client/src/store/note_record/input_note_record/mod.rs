@@ -107,6 +107,12 @@ impl InputNoteRecord {
         self.state.consumer_transaction_id()
     }
 
+    /// If the note was consumed locally, or is being consumed, it returns the ID of the consumer
+    /// account. Otherwise, returns `None`.
+    pub fn consumer_account(&self) -> Option<AccountId> {
+        self.state.consumer_account()
+    }
+
     /// Returns true if the note is authenticated, meaning that it has the necessary inclusion
     /// proof and block header information to be considered valid.
     pub fn is_authenticated(&self) -> bool {
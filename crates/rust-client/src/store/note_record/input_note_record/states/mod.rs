@@ -97,6 +97,29 @@ impl InputNoteState {
         self.inner().consumer_transaction_id()
     }
 
+    /// Returns the ID of the account that consumed the note, or is consuming it, if it was
+    /// consumed by a local transaction.
+    pub(crate) fn consumer_account(&self) -> Option<AccountId> {
+        match self {
+            InputNoteState::ProcessingAuthenticated(ProcessingAuthenticatedNoteState {
+                submission_data,
+                ..
+            })
+            | InputNoteState::ProcessingUnauthenticated(ProcessingUnauthenticatedNoteState {
+                submission_data,
+                ..
+            })
+            | InputNoteState::ConsumedAuthenticatedLocal(ConsumedAuthenticatedLocalNoteState {
+                submission_data,
+                ..
+            })
+            | InputNoteState::ConsumedUnauthenticatedLocal(
+                ConsumedUnauthenticatedLocalNoteState { submission_data, .. },
+            ) => Some(submission_data.consumer_account),
+            _ => None,
+        }
+    }
+
     /// Returns a unique identifier for each note state.
     pub(crate) fn discriminant(&self) -> u8 {
         match self {
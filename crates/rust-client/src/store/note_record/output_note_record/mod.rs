@@ -152,6 +152,37 @@ impl OutputNoteRecord {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for OutputNoteRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.recipient_digest.write_into(target);
+        self.assets.write_into(target);
+        self.metadata.write_into(target);
+        self.state.write_into(target);
+        self.expected_height.write_into(target);
+    }
+}
+
+impl Deserializable for OutputNoteRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let recipient_digest = Word::read_from(source)?;
+        let assets = NoteAssets::read_from(source)?;
+        let metadata = NoteMetadata::read_from(source)?;
+        let state = OutputNoteState::read_from(source)?;
+        let expected_height = BlockNumber::read_from(source)?;
+
+        Ok(OutputNoteRecord {
+            assets,
+            metadata,
+            recipient_digest,
+            state,
+            expected_height,
+        })
+    }
+}
+
 // CONVERSIONS
 // ================================================================================================
 
@@ -0,0 +1,530 @@
+use alloc::{collections::BTreeSet, string::ToString, vec::Vec};
+use core::num::NonZeroUsize;
+
+use miden_objects::{
+    Word,
+    account::{Account, AccountId},
+    block::{BlockHeader, BlockNumber},
+    crypto::{
+        hash::blake::Blake3_256,
+        merkle::{Forest, InOrderIndex, MmrPeaks},
+    },
+    utils::SliceReader,
+};
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{
+    InputNoteRecord, NoteFilter, OutputNoteRecord, PartialBlockchainFilter, Store, StoreError,
+    TransactionFilter,
+};
+use crate::{Client, ClientError, sync::NoteTagRecord, transaction::TransactionRecord};
+
+// STORE SNAPSHOT
+// ================================================================================================
+
+/// The version of the snapshot format written by this version of the client.
+pub const SNAPSHOT_VERSION: u8 = 1;
+
+/// The bytes every serialized snapshot starts with.
+const SNAPSHOT_MAGIC: [u8; 4] = *b"MCSS";
+
+/// A snapshot of the data tracked by a [`Store`]: accounts with all their stored states, input and
+/// output notes, transactions, note tags, block headers and the authentication nodes of the
+/// partial blockchain.
+///
+/// Snapshots don't depend on the store implementation, so a snapshot exported from a `WebStore`
+/// can be imported into a `SqliteStore` and vice versa. The serialized snapshot starts with the
+/// version of its format and ends with a checksum of its contents, which are verified when it's
+/// deserialized.
+///
+/// The secret keys of the accounts aren't part of the snapshot, as they are kept in the keystore.
+/// Neither are the pending transactions nor the cached foreign account code.
+#[derive(Debug, Clone)]
+pub struct StoreSnapshot {
+    sync_height: BlockNumber,
+    accounts: Vec<AccountSnapshot>,
+    input_notes: Vec<InputNoteRecord>,
+    output_notes: Vec<OutputNoteRecord>,
+    transactions: Vec<TransactionRecord>,
+    note_tags: Vec<NoteTagRecord>,
+    block_headers: Vec<BlockHeaderSnapshot>,
+    partial_blockchain_nodes: Vec<(InOrderIndex, Word)>,
+}
+
+impl StoreSnapshot {
+    /// Reads the snapshot of the data tracked by the store.
+    pub(crate) async fn from_store<S: Store + ?Sized>(store: &S) -> Result<Self, StoreError> {
+        let sync_height = store.get_sync_height().await?;
+
+        let mut accounts = Vec::new();
+        for account_id in store.get_account_ids().await? {
+            accounts.push(AccountSnapshot::from_store(store, account_id).await?);
+        }
+
+        // Besides the blocks with relevant notes, the store keeps the genesis and latest blocks
+        let tracked_blocks: BTreeSet<BlockNumber> = store
+            .get_tracked_block_headers()
+            .await?
+            .iter()
+            .map(BlockHeader::block_num)
+            .collect();
+        let mut block_nums = tracked_blocks.clone();
+        block_nums.extend([BlockNumber::GENESIS, sync_height]);
+
+        let mut block_headers = Vec::new();
+        for (header, _) in store.get_block_headers(&block_nums).await? {
+            let peaks = store.get_partial_blockchain_peaks_by_block_num(header.block_num()).await?;
+            block_headers.push(BlockHeaderSnapshot {
+                has_client_notes: tracked_blocks.contains(&header.block_num()),
+                partial_blockchain_peaks: peaks.peaks().to_vec(),
+                header,
+            });
+        }
+
+        Ok(Self {
+            sync_height,
+            accounts,
+            input_notes: store.get_input_notes(NoteFilter::All).await?,
+            output_notes: store.get_output_notes(NoteFilter::All).await?,
+            transactions: store.get_transactions(TransactionFilter::All).await?,
+            note_tags: store.get_note_tags().await?,
+            block_headers,
+            partial_blockchain_nodes: store
+                .get_partial_blockchain_nodes(PartialBlockchainFilter::All)
+                .await?
+                .into_iter()
+                .collect(),
+        })
+    }
+
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the block number the store was synced to.
+    pub fn sync_height(&self) -> BlockNumber {
+        self.sync_height
+    }
+
+    /// Returns the tracked accounts.
+    pub fn accounts(&self) -> &[AccountSnapshot] {
+        &self.accounts
+    }
+
+    /// Returns the input notes.
+    pub fn input_notes(&self) -> &[InputNoteRecord] {
+        &self.input_notes
+    }
+
+    /// Returns the output notes.
+    pub fn output_notes(&self) -> &[OutputNoteRecord] {
+        &self.output_notes
+    }
+
+    /// Returns the transactions.
+    pub fn transactions(&self) -> &[TransactionRecord] {
+        &self.transactions
+    }
+
+    /// Returns the tracked note tags.
+    pub fn note_tags(&self) -> &[NoteTagRecord] {
+        &self.note_tags
+    }
+
+    /// Returns the stored block headers.
+    pub fn block_headers(&self) -> &[BlockHeaderSnapshot] {
+        &self.block_headers
+    }
+
+    /// Returns the authentication nodes of the partial blockchain.
+    pub fn partial_blockchain_nodes(&self) -> &[(InOrderIndex, Word)] {
+        &self.partial_blockchain_nodes
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that the contents of the snapshot are consistent with each other, so that they can
+    /// be imported into a store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StoreError::InvalidSnapshot`] if:
+    /// - An account has no states, states of another account or states whose nonces decrease, or is
+    ///   present more than once. Consecutive states may share a nonce, as the store keeps every
+    ///   state of the account.
+    /// - A note or transaction is present more than once.
+    /// - The peaks of a block header don't match its block number.
+    /// - The header of the block the store was synced to is missing.
+    pub fn validate(&self) -> Result<(), StoreError> {
+        let mut account_ids = BTreeSet::new();
+        for account in &self.accounts {
+            account.validate()?;
+            if !account_ids.insert(account.id()) {
+                return Err(StoreError::InvalidSnapshot(format!(
+                    "account {} is duplicated",
+                    account.id()
+                )));
+            }
+        }
+
+        check_unique("input note", self.input_notes.iter().map(InputNoteRecord::id))?;
+        check_unique("output note", self.output_notes.iter().map(OutputNoteRecord::id))?;
+        check_unique("transaction", self.transactions.iter().map(|tx| tx.id))?;
+
+        for block_header in &self.block_headers {
+            block_header.partial_blockchain_peaks().map_err(|err| {
+                StoreError::InvalidSnapshot(format!(
+                    "invalid peaks for block {}: {err}",
+                    block_header.header.block_num()
+                ))
+            })?;
+        }
+
+        if self.sync_height != BlockNumber::GENESIS
+            && !self
+                .block_headers
+                .iter()
+                .any(|block| block.header.block_num() == self.sync_height)
+        {
+            return Err(StoreError::InvalidSnapshot(format!(
+                "missing header of the sync height block {}",
+                self.sync_height
+            )));
+        }
+
+        Ok(())
+    }
+
+    // SERIALIZATION HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    fn write_contents<W: ByteWriter>(&self, target: &mut W) {
+        self.sync_height.write_into(target);
+        self.accounts.write_into(target);
+        self.input_notes.write_into(target);
+        self.output_notes.write_into(target);
+        self.transactions.write_into(target);
+        self.note_tags.write_into(target);
+        self.block_headers.write_into(target);
+
+        target.write_usize(self.partial_blockchain_nodes.len());
+        for (index, node) in &self.partial_blockchain_nodes {
+            target.write_usize(index.inner());
+            node.write_into(target);
+        }
+    }
+
+    fn read_contents<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let sync_height = BlockNumber::read_from(source)?;
+        let accounts = Vec::<AccountSnapshot>::read_from(source)?;
+        let input_notes = Vec::<InputNoteRecord>::read_from(source)?;
+        let output_notes = Vec::<OutputNoteRecord>::read_from(source)?;
+        let transactions = Vec::<TransactionRecord>::read_from(source)?;
+        let note_tags = Vec::<NoteTagRecord>::read_from(source)?;
+        let block_headers = Vec::<BlockHeaderSnapshot>::read_from(source)?;
+
+        let num_nodes = source.read_usize()?;
+        let mut partial_blockchain_nodes = Vec::with_capacity(num_nodes);
+        for _ in 0..num_nodes {
+            let index = NonZeroUsize::new(source.read_usize()?).ok_or_else(|| {
+                DeserializationError::InvalidValue("Invalid partial blockchain node index".into())
+            })?;
+            partial_blockchain_nodes.push((InOrderIndex::new(index), Word::read_from(source)?));
+        }
+
+        Ok(Self {
+            sync_height,
+            accounts,
+            input_notes,
+            output_notes,
+            transactions,
+            note_tags,
+            block_headers,
+            partial_blockchain_nodes,
+        })
+    }
+}
+
+impl Serializable for StoreSnapshot {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        let mut contents = Vec::new();
+        self.write_contents(&mut contents);
+
+        target.write_bytes(&SNAPSHOT_MAGIC);
+        target.write_u8(SNAPSHOT_VERSION);
+        contents.write_into(target);
+        target.write_bytes(&*Blake3_256::hash(&contents));
+    }
+}
+
+impl Deserializable for StoreSnapshot {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        if source.read_array::<4>()? != SNAPSHOT_MAGIC {
+            return Err(DeserializationError::InvalidValue(
+                "The data isn't a store snapshot".to_string(),
+            ));
+        }
+
+        let version = source.read_u8()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Unsupported store snapshot version {version}, expected version {SNAPSHOT_VERSION}"
+            )));
+        }
+
+        let contents = Vec::<u8>::read_from(source)?;
+        let checksum = source.read_array::<32>()?;
+        if checksum != *Blake3_256::hash(&contents) {
+            return Err(DeserializationError::InvalidValue(
+                "The store snapshot checksum doesn't match its contents".to_string(),
+            ));
+        }
+
+        let mut reader = SliceReader::new(&contents);
+        let snapshot = Self::read_contents(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok(snapshot)
+    }
+}
+
+// ACCOUNT SNAPSHOT
+// ================================================================================================
+
+/// The stored states of an account, from oldest to newest, along with its status.
+#[derive(Debug, Clone)]
+pub struct AccountSnapshot {
+    states: Vec<Account>,
+    seed: Option<Word>,
+    locked: bool,
+}
+
+impl AccountSnapshot {
+    async fn from_store<S: Store + ?Sized>(
+        store: &S,
+        account_id: AccountId,
+    ) -> Result<Self, StoreError> {
+        let mut states = Vec::new();
+        let mut seed = None;
+        let mut locked = false;
+        // The states are fetched by commitment, as several of them may share a nonce
+        for header in store.get_account_history(account_id).await? {
+            let record = store
+                .get_account_by_commitment(header.commitment())
+                .await?
+                .ok_or(StoreError::AccountDataNotFound(account_id))?;
+
+            seed = seed.or(record.seed().copied());
+            locked = record.is_locked();
+            states.push(record.into());
+        }
+
+        Ok(Self { states, seed, locked })
+    }
+
+    /// Returns the ID of the account.
+    pub fn id(&self) -> AccountId {
+        self.states[0].id()
+    }
+
+    /// Returns the stored states of the account, from oldest to newest.
+    pub fn states(&self) -> &[Account] {
+        &self.states
+    }
+
+    /// Returns the seed used to create the account, if it's still stored.
+    pub fn seed(&self) -> Option<Word> {
+        self.seed
+    }
+
+    /// Returns whether the account is locked.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    fn validate(&self) -> Result<(), StoreError> {
+        let Some(first_state) = self.states.first() else {
+            return Err(StoreError::InvalidSnapshot("account without states".to_string()));
+        };
+
+        for (previous, state) in self.states.iter().zip(self.states.iter().skip(1)) {
+            if state.id() != first_state.id() {
+                return Err(StoreError::InvalidSnapshot(format!(
+                    "account {} has states of account {}",
+                    first_state.id(),
+                    state.id()
+                )));
+            }
+
+            if state.nonce().as_int() < previous.nonce().as_int() {
+                return Err(StoreError::InvalidSnapshot(format!(
+                    "the states of account {} aren't sorted by nonce",
+                    first_state.id()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Serializable for AccountSnapshot {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.states.write_into(target);
+        self.seed.write_into(target);
+        self.locked.write_into(target);
+    }
+}
+
+impl Deserializable for AccountSnapshot {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let states = Vec::<Account>::read_from(source)?;
+        let seed = Option::<Word>::read_from(source)?;
+        let locked = bool::read_from(source)?;
+
+        Ok(Self { states, seed, locked })
+    }
+}
+
+// BLOCK HEADER SNAPSHOT
+// ================================================================================================
+
+/// A stored block header, along with the peaks of the partial blockchain at the block.
+#[derive(Debug, Clone)]
+pub struct BlockHeaderSnapshot {
+    header: BlockHeader,
+    partial_blockchain_peaks: Vec<Word>,
+    has_client_notes: bool,
+}
+
+impl BlockHeaderSnapshot {
+    /// Returns the block header.
+    pub fn header(&self) -> &BlockHeader {
+        &self.header
+    }
+
+    /// Returns the peaks of the partial blockchain at the block.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of peaks doesn't match the block number.
+    pub fn partial_blockchain_peaks(&self) -> Result<MmrPeaks, StoreError> {
+        Ok(MmrPeaks::new(
+            Forest::new(self.header.block_num().as_usize()),
+            self.partial_blockchain_peaks.clone(),
+        )?)
+    }
+
+    /// Returns whether the block contains notes relevant to the client.
+    pub fn has_client_notes(&self) -> bool {
+        self.has_client_notes
+    }
+}
+
+impl Serializable for BlockHeaderSnapshot {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.header.write_into(target);
+        self.partial_blockchain_peaks.write_into(target);
+        self.has_client_notes.write_into(target);
+    }
+}
+
+impl Deserializable for BlockHeaderSnapshot {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let header = BlockHeader::read_from(source)?;
+        let partial_blockchain_peaks = Vec::<Word>::read_from(source)?;
+        let has_client_notes = bool::read_from(source)?;
+
+        Ok(Self {
+            header,
+            partial_blockchain_peaks,
+            has_client_notes,
+        })
+    }
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+/// Store snapshot methods
+impl Client {
+    /// Returns a snapshot of the data tracked by the client, which can be serialized to back up the
+    /// client and restored with [`Client::import_snapshot`], possibly into another kind of store.
+    ///
+    /// The secret keys of the accounts aren't part of the snapshot and need to be backed up from
+    /// the keystore.
+    pub async fn export_snapshot(&self) -> Result<StoreSnapshot, ClientError> {
+        Ok(self.store.export_snapshot().await?)
+    }
+
+    /// Restores a snapshot exported by [`Client::export_snapshot`] into the store, which must be
+    /// empty.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`StoreError::StoreNotEmpty`] if the store tracks accounts, notes or transactions,
+    ///   or was synced.
+    /// - Returns [`StoreError::InvalidSnapshot`] if the contents of the snapshot aren't consistent
+    ///   (see [`StoreSnapshot::validate`]).
+    /// - Returns [`StoreError::SnapshotGenesisMismatch`] if the snapshot holds a genesis block
+    ///   header different from the one in the store, as it was taken on another network.
+    pub async fn import_snapshot(&mut self, snapshot: &StoreSnapshot) -> Result<(), ClientError> {
+        snapshot.validate()?;
+
+        if !self.store_is_empty().await? {
+            return Err(StoreError::StoreNotEmpty.into());
+        }
+
+        // The store may already hold the genesis block header of the network the client connects
+        // to, which the snapshot must have been taken on
+        let snapshot_genesis = snapshot
+            .block_headers()
+            .iter()
+            .find(|block| block.header().block_num() == BlockNumber::GENESIS);
+        if let Some(snapshot_genesis) = snapshot_genesis
+            && let Some((store_genesis, _)) =
+                self.store.get_block_header_by_num(BlockNumber::GENESIS).await?
+            && snapshot_genesis.header().commitment() != store_genesis.commitment()
+        {
+            return Err(StoreError::SnapshotGenesisMismatch(
+                snapshot_genesis.header().commitment(),
+                store_genesis.commitment(),
+            )
+            .into());
+        }
+
+        Ok(self.store.import_snapshot(snapshot).await?)
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    async fn store_is_empty(&self) -> Result<bool, ClientError> {
+        Ok(self.store.get_sync_height().await? == BlockNumber::GENESIS
+            && self.store.get_account_ids().await?.is_empty()
+            && self.store.get_input_notes(NoteFilter::All).await?.is_empty()
+            && self.store.get_output_notes(NoteFilter::All).await?.is_empty()
+            && self.store.get_transactions(TransactionFilter::All).await?.is_empty())
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns an error if an ID is present more than once.
+fn check_unique<T: Ord + core::fmt::Display>(
+    kind: &str,
+    ids: impl Iterator<Item = T>,
+) -> Result<(), StoreError> {
+    let mut seen = BTreeSet::new();
+    for id in ids {
+        if seen.contains(&id) {
+            return Err(StoreError::InvalidSnapshot(format!("{kind} {id} is duplicated")));
+        }
+        seen.insert(id);
+    }
+
+    Ok(())
+}
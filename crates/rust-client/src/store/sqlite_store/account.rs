@@ -4,21 +4,36 @@ use alloc::{
     string::{String, ToString},
     vec::Vec,
 };
-use std::{collections::BTreeMap, rc::Rc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
 
 use miden_objects::{
-    AccountError, Felt, Word,
-    account::{Account, AccountCode, AccountHeader, AccountId, AccountStorage},
-    asset::{Asset, AssetVault},
+    AccountError, AssetVaultError, EMPTY_WORD, Felt, Word,
+    account::{
+        Account, AccountCode, AccountHeader, AccountId, AccountStorage, AccountType, StorageMap,
+        StorageSlot,
+    },
+    asset::{Asset, AssetVault, FungibleAsset},
+    crypto::merkle::SmtProof,
 };
 use miden_tx::utils::{Deserializable, Serializable};
-use rusqlite::{Connection, Transaction, named_params, params, types::Value};
+use rusqlite::{Connection, OptionalExtension, Transaction, named_params, params, types::Value};
+use rusqlite_migration::{HookError, HookResult};
 
-use super::{SqliteStore, column_value_as_u64, u64_to_value};
+use super::{
+    SqliteStore, column_value_as_u64,
+    note::{remove_input_notes_tx, remove_output_notes_tx},
+    transaction::{remove_pending_transactions_tx, remove_transactions_tx},
+    u64_to_value,
+};
 use crate::{
+    account::AccountRemoval,
     insert_sql,
     store::{AccountRecord, AccountStatus, StoreError},
     subst,
+    sync::NoteTagSource,
 };
 
 // TYPES
@@ -26,13 +41,14 @@ use crate::{
 type SerializedAccountData = (String, String, String, String, Value, bool, String);
 type SerializedAccountsParts = (String, u64, String, String, String, Option<Vec<u8>>, bool);
 
-type SerializedAccountVaultData = (String, Vec<u8>);
-
 type SerializedAccountCodeData = (String, Vec<u8>);
 
-type SerializedAccountStorageData = (String, Vec<u8>);
+type SerializedFullAccountParts = (String, u64, Option<Vec<u8>>, Vec<u8>, bool, i64);
 
-type SerializedFullAccountParts = (String, u64, Option<Vec<u8>>, Vec<u8>, Vec<u8>, Vec<u8>, bool);
+/// Type of the value slots in the `account_storage_slots` table.
+const VALUE_SLOT_TYPE: u8 = 0;
+/// Type of the map slots in the `account_storage_slots` table.
+const MAP_SLOT_TYPE: u8 = 1;
 
 impl SqliteStore {
     // ACCOUNTS
@@ -56,7 +72,9 @@ impl SqliteStore {
     ) -> Result<Vec<(AccountHeader, AccountStatus)>, StoreError> {
         const QUERY: &str = "SELECT a.id, a.nonce, a.vault_root, a.storage_root, a.code_root, a.account_seed, a.locked \
             FROM accounts a \
-            WHERE a.nonce = (SELECT MAX(b.nonce) FROM accounts b WHERE b.id = a.id)";
+            WHERE a.state_id = ( \
+                SELECT b.state_id FROM accounts b WHERE b.id = a.id \
+                ORDER BY b.nonce DESC, b.state_id DESC LIMIT 1)";
 
         conn.prepare(QUERY)?
             .query_map([], parse_accounts_columns)
@@ -71,7 +89,7 @@ impl SqliteStore {
     ) -> Result<Option<(AccountHeader, AccountStatus)>, StoreError> {
         const QUERY: &str = "SELECT id, nonce, vault_root, storage_root, code_root, account_seed, locked \
             FROM accounts WHERE id = ? \
-            ORDER BY nonce DESC \
+            ORDER BY nonce DESC, state_id DESC \
             LIMIT 1";
         conn.prepare(QUERY)?
             .query_map(params![account_id.to_hex()], parse_accounts_columns)?
@@ -102,20 +120,149 @@ impl SqliteStore {
         conn: &mut Connection,
         account_id: AccountId,
     ) -> Result<Option<AccountRecord>, StoreError> {
-        const QUERY: &str = "SELECT accounts.id, accounts.nonce, accounts.account_seed, account_code.code, account_storage.slots, account_vaults.assets, accounts.locked \
+        const QUERY: &str = "SELECT accounts.id, accounts.nonce, accounts.account_seed, account_code.code, accounts.locked, accounts.state_id \
                             FROM accounts \
                             JOIN account_code ON accounts.code_root = account_code.root \
-                            JOIN account_storage ON accounts.storage_root = account_storage.root \
-                            JOIN account_vaults ON accounts.vault_root = account_vaults.root \
                             WHERE accounts.id = ? \
-                            ORDER BY accounts.nonce DESC \
+                            ORDER BY accounts.nonce DESC, accounts.state_id DESC \
                             LIMIT 1";
 
-        conn.prepare(QUERY)?
+        let account_parts = conn
+            .prepare(QUERY)?
             .query_map(params![account_id.to_hex()], parse_account_columns)?
-            .map(|result| Ok(result?).and_then(parse_account))
             .next()
-            .transpose()
+            .transpose()?;
+
+        account_parts.map(|parts| parse_account(conn, parts)).transpose()
+    }
+
+    pub(crate) fn get_account_history(
+        conn: &mut Connection,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountHeader>, StoreError> {
+        const QUERY: &str = "SELECT id, nonce, vault_root, storage_root, code_root, account_seed, locked \
+            FROM accounts WHERE id = ? \
+            ORDER BY nonce ASC, state_id ASC";
+
+        conn.prepare(QUERY)?
+            .query_map(params![account_id.to_hex()], parse_accounts_columns)?
+            .map(|result| Ok(parse_accounts(result?)?.0))
+            .collect()
+    }
+
+    pub(crate) fn get_account_at_nonce(
+        conn: &mut Connection,
+        account_id: AccountId,
+        nonce: Felt,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        const QUERY: &str = "SELECT accounts.id, accounts.nonce, accounts.account_seed, account_code.code, accounts.locked, accounts.state_id \
+                            FROM accounts \
+                            JOIN account_code ON accounts.code_root = account_code.root \
+                            WHERE accounts.id = ? AND accounts.nonce = ? \
+                            ORDER BY accounts.state_id DESC \
+                            LIMIT 1";
+
+        let account_parts = conn
+            .prepare(QUERY)?
+            .query_map(
+                params![account_id.to_hex(), u64_to_value(nonce.as_int())],
+                parse_account_columns,
+            )?
+            .next()
+            .transpose()?;
+
+        account_parts.map(|parts| parse_account(conn, parts)).transpose()
+    }
+
+    pub(crate) fn get_account_by_commitment(
+        conn: &mut Connection,
+        account_commitment: Word,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        const QUERY: &str = "SELECT accounts.id, accounts.nonce, accounts.account_seed, account_code.code, accounts.locked, accounts.state_id \
+                            FROM accounts \
+                            JOIN account_code ON accounts.code_root = account_code.root \
+                            WHERE accounts.account_commitment = ?";
+
+        let account_parts = conn
+            .prepare(QUERY)?
+            .query_map(params![account_commitment.to_string()], parse_account_columns)?
+            .next()
+            .transpose()?;
+
+        account_parts.map(|parts| parse_account(conn, parts)).transpose()
+    }
+
+    // PARTIAL ACCOUNT DATA
+    // --------------------------------------------------------------------------------------------
+
+    pub(crate) fn get_account_storage_item(
+        conn: &mut Connection,
+        account_id: AccountId,
+        index: u8,
+    ) -> Result<Word, StoreError> {
+        let state_id = query_latest_state_id(conn, account_id)?;
+
+        query_storage_slot(conn, &account_id.to_hex(), index, state_id)?
+            .map(|(_, slot_value)| slot_value)
+            .ok_or(StoreError::AccountStorageSlotNotFound(account_id, index))
+    }
+
+    pub(crate) fn get_account_map_item(
+        conn: &mut Connection,
+        account_id: AccountId,
+        index: u8,
+        key: Word,
+    ) -> Result<(Word, SmtProof), StoreError> {
+        let state_id = query_latest_state_id(conn, account_id)?;
+
+        let (slot_type, _) = query_storage_slot(conn, &account_id.to_hex(), index, state_id)?
+            .ok_or(StoreError::AccountStorageSlotNotFound(account_id, index))?;
+        if slot_type != MAP_SLOT_TYPE {
+            return Err(StoreError::AccountStorageSlotNotMap(account_id, index));
+        }
+
+        // Only the entries of the requested map are loaded, as they're needed to build the proof
+        let entries = query_storage_map_entries(conn, &account_id.to_hex(), index, state_id)?;
+        let map = StorageMap::with_entries(entries.into_iter())
+            .map_err(|err| StoreError::ParsingError(err.to_string()))?;
+
+        Ok((map.get(&key), map.open(&key)))
+    }
+
+    pub(crate) fn get_account_balance(
+        conn: &mut Connection,
+        account_id: AccountId,
+        faucet_id: AccountId,
+    ) -> Result<u64, StoreError> {
+        const QUERY: &str = "SELECT asset FROM account_assets \
+            WHERE account_id = ? AND vault_key = ? AND state_id <= ? \
+            ORDER BY state_id DESC \
+            LIMIT 1";
+
+        if faucet_id.account_type() != AccountType::FungibleFaucet {
+            return Err(AssetVaultError::NotAFungibleFaucetId(faucet_id).into());
+        }
+
+        let state_id = query_latest_state_id(conn, account_id)?;
+        let vault_key = Asset::from(
+            FungibleAsset::new(faucet_id, 0).expect("faucet ID is a fungible faucet ID"),
+        )
+        .vault_key();
+
+        // The asset is NULL if it was removed from the vault
+        let asset: Option<Vec<u8>> = conn
+            .query_row(
+                QUERY,
+                params![account_id.to_hex(), vault_key.to_string(), state_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+
+        match asset.map(|asset| Asset::read_from_bytes(&asset)).transpose()? {
+            Some(Asset::Fungible(asset)) => Ok(asset.amount()),
+            _ => Ok(0),
+        }
     }
 
     pub(crate) fn insert_account(
@@ -126,9 +273,7 @@ impl SqliteStore {
         let tx = conn.transaction()?;
 
         insert_account_code(&tx, account.code())?;
-        insert_account_storage(&tx, account.storage())?;
-        insert_account_asset_vault(&tx, account.vault())?;
-        insert_account_record(&tx, account, account_seed)?;
+        insert_account_state(&tx, account, account_seed)?;
 
         Ok(tx.commit()?)
     }
@@ -163,6 +308,42 @@ impl SqliteStore {
         Ok(tx.commit()?)
     }
 
+    pub(crate) fn remove_account(
+        conn: &mut Connection,
+        removal: &AccountRemoval,
+    ) -> Result<(), StoreError> {
+        let account_id = removal.account_id();
+        const QUERIES: [&str; 3] = [
+            "DELETE FROM account_storage_slots WHERE account_id = ?",
+            "DELETE FROM account_storage_map_entries WHERE account_id = ?",
+            "DELETE FROM account_assets WHERE account_id = ?",
+        ];
+
+        let tx = conn.transaction()?;
+
+        let removed_states =
+            tx.execute("DELETE FROM accounts WHERE id = ?", params![account_id.to_hex()])?;
+        if removed_states == 0 {
+            return Err(StoreError::AccountDataNotFound(account_id));
+        }
+
+        for query in QUERIES {
+            tx.execute(query, params![account_id.to_hex()])?;
+        }
+
+        tx.execute(
+            "DELETE FROM tags WHERE source = ?",
+            params![NoteTagSource::Account(account_id).to_bytes()],
+        )?;
+
+        remove_pending_transactions_tx(&tx, removal.pending_transaction_ids())?;
+        remove_transactions_tx(&tx, removal.transaction_ids())?;
+        remove_input_notes_tx(&tx, removal.input_note_ids())?;
+        remove_output_notes_tx(&tx, removal.output_note_ids())?;
+
+        Ok(tx.commit()?)
+    }
+
     pub fn upsert_foreign_account_code(
         conn: &mut Connection,
         account_id: AccountId,
@@ -217,20 +398,19 @@ impl SqliteStore {
 ///
 /// Because the Client retrieves the account by account ID before applying the delta, we don't
 /// need to check that it exists here. This inserts a new row into the accounts table.
-/// We can later identify the proper account state by looking at the nonce.
+/// We can later identify the proper account state by looking at its commitment.
 pub(crate) fn update_account(
     tx: &Transaction<'_>,
     new_account_state: &Account,
 ) -> Result<(), StoreError> {
-    insert_account_storage(tx, new_account_state.storage())?;
-    insert_account_asset_vault(tx, new_account_state.vault())?;
-    insert_account_record(tx, new_account_state, None)
+    insert_account_state(tx, new_account_state, None)
 }
 
-pub(super) fn insert_account_record(
+fn insert_account_record(
     tx: &Transaction<'_>,
     account: &Account,
     account_seed: Option<Word>,
+    state_id: i64,
 ) -> Result<(), StoreError> {
     let (id, code_root, storage_root, vault_root, nonce, committed, commitment) =
         serialize_account(account);
@@ -247,7 +427,8 @@ pub(super) fn insert_account_record(
             committed,
             account_seed,
             account_commitment,
-            locked
+            locked,
+            state_id
         } | REPLACE
     );
 
@@ -263,41 +444,407 @@ pub(super) fn insert_account_record(
             account_seed,
             commitment,
             false,
+            state_id,
         ],
     )?;
     Ok(())
 }
 
 /// Inserts an [`AccountCode`].
-fn insert_account_code(tx: &Transaction<'_>, account_code: &AccountCode) -> Result<(), StoreError> {
+pub(super) fn insert_account_code(
+    tx: &Transaction<'_>,
+    account_code: &AccountCode,
+) -> Result<(), StoreError> {
     let (code_root, code) = serialize_account_code(account_code);
     const QUERY: &str = insert_sql!(account_code { root, code } | IGNORE);
     tx.execute(QUERY, params![code_root, code])?;
     Ok(())
 }
 
-/// Inserts an [`AccountStorage`].
-pub(super) fn insert_account_storage(
+// ACCOUNT STATES
+// ================================================================================================
+
+/// Inserts an account state, along with the storage slots, storage map entries and assets that
+/// changed since the previous state of the account.
+///
+/// The account states are identified by a `state_id` that increases in the order they're stored,
+/// as several states of an account may share a nonce. The rows of each state reference the state
+/// that introduced them, and the rest are shared with the previous states: the state `s` is made of
+/// the latest row of each slot, map entry and asset with a `state_id` lower or equal to `s`.
+///
+/// If the state was already stored, only its record is replaced.
+pub(super) fn insert_account_state(
     tx: &Transaction<'_>,
-    account_storage: &AccountStorage,
+    account: &Account,
+    account_seed: Option<Word>,
 ) -> Result<(), StoreError> {
-    let (storage_root, storage_slots) = serialize_account_storage(account_storage);
-    const QUERY: &str = insert_sql!(account_storage { root, slots } | IGNORE);
-    tx.execute(QUERY, params![storage_root, storage_slots])?;
+    const STATE_QUERY: &str = "SELECT state_id FROM accounts WHERE account_commitment = ?";
+    const NEXT_STATE_QUERY: &str = "SELECT COALESCE(MAX(state_id), 0) + 1 FROM accounts";
+
+    let stored_state_id: Option<i64> = tx
+        .query_row(STATE_QUERY, params![account.commitment().to_string()], |row| row.get(0))
+        .optional()?;
+
+    let state_id = match stored_state_id {
+        Some(state_id) => state_id,
+        None => {
+            let state_id = tx.query_row(NEXT_STATE_QUERY, [], |row| row.get(0))?;
+            insert_account_state_rows(
+                tx,
+                &account.id().to_hex(),
+                state_id,
+                account.storage(),
+                account.vault(),
+            )?;
+            state_id
+        },
+    };
+
+    insert_account_record(tx, account, account_seed, state_id)
+}
+
+/// Inserts the storage slots, storage map entries and assets of the account state with the
+/// specified ID that changed since the previous state of the account.
+fn insert_account_state_rows(
+    tx: &Transaction<'_>,
+    account_id: &str,
+    state_id: i64,
+    storage: &AccountStorage,
+    vault: &AssetVault,
+) -> Result<(), StoreError> {
+    insert_account_storage_rows(tx, account_id, state_id, storage)?;
+    insert_account_asset_rows(tx, account_id, state_id, vault)
+}
+
+/// Inserts the storage slots and map entries that changed since the previous account state.
+fn insert_account_storage_rows(
+    tx: &Transaction<'_>,
+    account_id: &str,
+    state_id: i64,
+    storage: &AccountStorage,
+) -> Result<(), StoreError> {
+    const SLOT_QUERY: &str = insert_sql!(account_storage_slots {
+        account_id,
+        state_id,
+        slot_index,
+        slot_type,
+        slot_value
+    });
+    const ENTRY_QUERY: &str = insert_sql!(account_storage_map_entries {
+        account_id,
+        state_id,
+        slot_index,
+        key,
+        value
+    });
+
+    let previous_slots = query_storage_slots(tx, account_id, state_id - 1)?;
+
+    for (index, slot) in storage.slots().iter().enumerate() {
+        let index = u8::try_from(index).expect("accounts have at most 255 storage slots");
+        let (slot_type, slot_value) = match slot {
+            StorageSlot::Value(value) => (VALUE_SLOT_TYPE, *value),
+            StorageSlot::Map(map) => (MAP_SLOT_TYPE, map.root()),
+        };
+
+        if previous_slots.get(&index) == Some(&(slot_type, slot_value)) {
+            continue;
+        }
+
+        tx.execute(
+            SLOT_QUERY,
+            params![account_id, state_id, index, slot_type, slot_value.to_string()],
+        )?;
+
+        let StorageSlot::Map(map) = slot else {
+            continue;
+        };
+
+        let previous_entries = query_storage_map_entries(tx, account_id, index, state_id - 1)?;
+
+        for (key, value) in map.entries() {
+            if previous_entries.get(key) != Some(value) {
+                tx.execute(
+                    ENTRY_QUERY,
+                    params![account_id, state_id, index, key.to_string(), value.to_string()],
+                )?;
+            }
+        }
+
+        // Entries that were removed are set to the empty word
+        for key in previous_entries.keys().filter(|key| map.get(key) == EMPTY_WORD) {
+            tx.execute(
+                ENTRY_QUERY,
+                params![account_id, state_id, index, key.to_string(), EMPTY_WORD.to_string()],
+            )?;
+        }
+    }
+
     Ok(())
 }
 
-/// Inserts an [`AssetVault`].
-pub(super) fn insert_account_asset_vault(
+/// Inserts the assets that changed since the previous account state.
+fn insert_account_asset_rows(
     tx: &Transaction<'_>,
-    asset_vault: &AssetVault,
+    account_id: &str,
+    state_id: i64,
+    vault: &AssetVault,
 ) -> Result<(), StoreError> {
-    let (vault_root, assets) = serialize_account_asset_vault(asset_vault);
-    const QUERY: &str = insert_sql!(account_vaults { root, assets } | IGNORE);
-    tx.execute(QUERY, params![vault_root, assets])?;
+    const QUERY: &str = insert_sql!(account_assets { account_id, state_id, vault_key, asset });
+
+    let previous_assets = query_assets(tx, account_id, state_id - 1)?;
+
+    let mut vault_keys = BTreeSet::new();
+    for asset in vault.assets() {
+        let vault_key = asset.vault_key().to_string();
+        if previous_assets.get(&vault_key) != Some(&asset) {
+            tx.execute(QUERY, params![account_id, state_id, vault_key, Some(asset.to_bytes())])?;
+        }
+        vault_keys.insert(vault_key);
+    }
+
+    // Assets that were removed from the vault are set to NULL
+    for vault_key in previous_assets.keys().filter(|key| !vault_keys.contains(*key)) {
+        tx.execute(QUERY, params![account_id, state_id, vault_key, None::<Vec<u8>>])?;
+    }
+
+    Ok(())
+}
+
+/// Removes the storage slots, storage map entries and assets written by the account state with the
+/// specified ID.
+///
+/// The next state of the account may share some of these rows, so the ones it doesn't override are
+/// moved to it instead of being removed.
+fn remove_account_state_rows(
+    tx: &Transaction<'_>,
+    account_id: &str,
+    state_id: i64,
+) -> Result<(), StoreError> {
+    const NEXT_STATE_QUERY: &str =
+        "SELECT MIN(state_id) FROM accounts WHERE id = ? AND state_id > ?";
+    const MOVE_QUERIES: [&str; 3] = [
+        "UPDATE account_storage_slots SET state_id = :next_state_id \
+            WHERE account_id = :account_id AND state_id = :state_id AND NOT EXISTS ( \
+                SELECT 1 FROM account_storage_slots s \
+                WHERE s.account_id = :account_id AND s.state_id = :next_state_id \
+                AND s.slot_index = account_storage_slots.slot_index)",
+        "UPDATE account_storage_map_entries SET state_id = :next_state_id \
+            WHERE account_id = :account_id AND state_id = :state_id AND NOT EXISTS ( \
+                SELECT 1 FROM account_storage_map_entries e \
+                WHERE e.account_id = :account_id AND e.state_id = :next_state_id \
+                AND e.slot_index = account_storage_map_entries.slot_index \
+                AND e.key = account_storage_map_entries.key)",
+        "UPDATE account_assets SET state_id = :next_state_id \
+            WHERE account_id = :account_id AND state_id = :state_id AND NOT EXISTS ( \
+                SELECT 1 FROM account_assets a \
+                WHERE a.account_id = :account_id AND a.state_id = :next_state_id \
+                AND a.vault_key = account_assets.vault_key)",
+    ];
+    const REMOVE_QUERIES: [&str; 3] = [
+        "DELETE FROM account_storage_slots WHERE account_id = ? AND state_id = ?",
+        "DELETE FROM account_storage_map_entries WHERE account_id = ? AND state_id = ?",
+        "DELETE FROM account_assets WHERE account_id = ? AND state_id = ?",
+    ];
+
+    let next_state_id: Option<i64> =
+        tx.query_row(NEXT_STATE_QUERY, params![account_id, state_id], |row| row.get(0))?;
+
+    if let Some(next_state_id) = next_state_id {
+        for query in MOVE_QUERIES {
+            tx.execute(
+                query,
+                named_params! {
+                    ":account_id": account_id,
+                    ":state_id": state_id,
+                    ":next_state_id": next_state_id,
+                },
+            )?;
+        }
+    }
+
+    for query in REMOVE_QUERIES {
+        tx.execute(query, params![account_id, state_id])?;
+    }
     Ok(())
 }
 
+/// Returns the ID of the latest state of the account.
+fn query_latest_state_id(conn: &Connection, account_id: AccountId) -> Result<i64, StoreError> {
+    const QUERY: &str = "SELECT state_id FROM accounts WHERE id = ? \
+        ORDER BY nonce DESC, state_id DESC \
+        LIMIT 1";
+
+    conn.query_row(QUERY, params![account_id.to_hex()], |row| row.get(0))
+        .optional()?
+        .ok_or(StoreError::AccountDataNotFound(account_id))
+}
+
+/// Rebuilds the storage and vault of the account state with the specified ID.
+fn query_account_state(
+    conn: &Connection,
+    account_id: &str,
+    state_id: i64,
+) -> Result<(AccountStorage, AssetVault), StoreError> {
+    let mut slots = Vec::new();
+    for (index, (slot_type, slot_value)) in query_storage_slots(conn, account_id, state_id)? {
+        let slot = if slot_type == MAP_SLOT_TYPE {
+            let entries = query_storage_map_entries(conn, account_id, index, state_id)?;
+            let map = StorageMap::with_entries(entries.into_iter())
+                .map_err(|err| StoreError::ParsingError(err.to_string()))?;
+            debug_assert_eq!(map.root(), slot_value, "storage map root mismatch");
+
+            StorageSlot::Map(map)
+        } else {
+            StorageSlot::Value(slot_value)
+        };
+        slots.push(slot);
+    }
+
+    let assets: Vec<Asset> = query_assets(conn, account_id, state_id)?.into_values().collect();
+
+    Ok((AccountStorage::new(slots)?, AssetVault::new(&assets)?))
+}
+
+/// Returns the type and value of each storage slot of the account state with the specified ID,
+/// by slot index.
+fn query_storage_slots(
+    conn: &Connection,
+    account_id: &str,
+    state_id: i64,
+) -> Result<BTreeMap<u8, (u8, Word)>, StoreError> {
+    const QUERY: &str = "SELECT slot_index, slot_type, slot_value FROM account_storage_slots s \
+        WHERE account_id = :account_id AND state_id = ( \
+            SELECT MAX(state_id) FROM account_storage_slots \
+            WHERE account_id = s.account_id AND slot_index = s.slot_index AND state_id <= :state_id)";
+
+    conn.prepare(QUERY)?
+        .query_map(named_params! { ":account_id": account_id, ":state_id": state_id }, |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .map(|result| {
+            let (index, slot_type, slot_value): (u8, u8, String) = result?;
+            Ok((index, (slot_type, Word::try_from(&slot_value)?)))
+        })
+        .collect()
+}
+
+/// Returns the type and value of the storage slot with the specified index in the account state
+/// with the specified ID, or `None` if the state has no such slot.
+fn query_storage_slot(
+    conn: &Connection,
+    account_id: &str,
+    slot_index: u8,
+    state_id: i64,
+) -> Result<Option<(u8, Word)>, StoreError> {
+    const QUERY: &str = "SELECT slot_type, slot_value FROM account_storage_slots \
+        WHERE account_id = ? AND slot_index = ? AND state_id <= ? \
+        ORDER BY state_id DESC \
+        LIMIT 1";
+
+    conn.query_row(QUERY, params![account_id, slot_index, state_id], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })
+    .optional()?
+    .map(|(slot_type, slot_value): (u8, String)| Ok((slot_type, Word::try_from(&slot_value)?)))
+    .transpose()
+}
+
+/// Returns the entries of the storage map in the specified slot of the account state with the
+/// specified ID.
+fn query_storage_map_entries(
+    conn: &Connection,
+    account_id: &str,
+    slot_index: u8,
+    state_id: i64,
+) -> Result<BTreeMap<Word, Word>, StoreError> {
+    const QUERY: &str = "SELECT key, value FROM account_storage_map_entries e \
+        WHERE account_id = :account_id AND slot_index = :slot_index AND value != :empty AND state_id = ( \
+            SELECT MAX(state_id) FROM account_storage_map_entries \
+            WHERE account_id = e.account_id AND slot_index = e.slot_index AND key = e.key \
+            AND state_id <= :state_id)";
+
+    conn.prepare(QUERY)?
+        .query_map(
+            named_params! {
+                ":account_id": account_id,
+                ":slot_index": slot_index,
+                ":empty": EMPTY_WORD.to_string(),
+                ":state_id": state_id,
+            },
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?
+        .map(|result| {
+            let (key, value): (String, String) = result?;
+            Ok((Word::try_from(&key)?, Word::try_from(&value)?))
+        })
+        .collect()
+}
+
+/// Returns the assets in the vault of the account state with the specified ID, by vault key.
+fn query_assets(
+    conn: &Connection,
+    account_id: &str,
+    state_id: i64,
+) -> Result<BTreeMap<String, Asset>, StoreError> {
+    const QUERY: &str = "SELECT vault_key, asset FROM account_assets a \
+        WHERE account_id = :account_id AND asset IS NOT NULL AND state_id = ( \
+            SELECT MAX(state_id) FROM account_assets \
+            WHERE account_id = a.account_id AND vault_key = a.vault_key AND state_id <= :state_id)";
+
+    conn.prepare(QUERY)?
+        .query_map(named_params! { ":account_id": account_id, ":state_id": state_id }, |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .map(|result| {
+            let (vault_key, asset): (String, Vec<u8>) = result?;
+            Ok((vault_key, Asset::read_from_bytes(&asset)?))
+        })
+        .collect()
+}
+
+/// Converts the storage and vault blobs of the stored account states into the rows of the
+/// normalized tables, and drops the blob tables.
+///
+/// This is the hook of the migration that creates the normalized tables, as the blobs can't be
+/// deserialized from SQL.
+pub(super) fn migrate_account_state_blobs(tx: &Transaction<'_>) -> HookResult {
+    const QUERY: &str = "SELECT accounts.id, accounts.state_id, account_storage.slots, account_vaults.assets \
+        FROM accounts \
+        JOIN account_storage ON accounts.storage_root = account_storage.root \
+        JOIN account_vaults ON accounts.vault_root = account_vaults.root \
+        ORDER BY accounts.state_id";
+
+    let account_states = tx
+        .prepare(QUERY)?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+        .collect::<Result<Vec<(String, i64, Vec<u8>, Vec<u8>)>, _>>()?;
+
+    // The states are converted in the order they were stored, so that each one only writes what
+    // changed since the previous one
+    for (account_id, state_id, storage, assets) in account_states {
+        convert_account_state_blobs(tx, &account_id, state_id, &storage, &assets)
+            .map_err(|err| HookError::Hook(err.to_string()))?;
+    }
+
+    tx.execute_batch("DROP TABLE account_storage; DROP TABLE account_vaults;")?;
+    Ok(())
+}
+
+/// Deserializes the storage and vault blobs of an account state and inserts their rows.
+fn convert_account_state_blobs(
+    tx: &Transaction<'_>,
+    account_id: &str,
+    state_id: i64,
+    storage: &[u8],
+    assets: &[u8],
+) -> Result<(), StoreError> {
+    let storage = AccountStorage::read_from_bytes(storage)?;
+    let assets = Vec::<Asset>::read_from_bytes(assets)?;
+
+    insert_account_state_rows(tx, account_id, state_id, &storage, &AssetVault::new(&assets)?)
+}
+
 /// Locks the account if the mismatched digest doesn't belong to a previous account state (stale
 /// data).
 pub(super) fn lock_account_on_unexpected_commitment(
@@ -359,20 +906,21 @@ pub(super) fn parse_accounts(
     ))
 }
 
-/// Parse an account from the provided parts.
+/// Parse an account from the provided parts, rebuilding its storage and vault from the rows of the
+/// account state.
 pub(super) fn parse_account(
+    conn: &Connection,
     serialized_account_parts: SerializedFullAccountParts,
 ) -> Result<AccountRecord, StoreError> {
-    let (id, nonce, account_seed, code, storage, assets, locked) = serialized_account_parts;
+    let (id, nonce, account_seed, code, locked, state_id) = serialized_account_parts;
     let account_seed = account_seed.map(|seed| Word::read_from_bytes(&seed)).transpose()?;
     let account_id: AccountId =
         AccountId::from_hex(&id).expect("Conversion from stored AccountID should not panic");
     let account_code = AccountCode::from_bytes(&code)?;
-    let account_storage = AccountStorage::read_from_bytes(&storage)?;
-    let account_assets: Vec<Asset> = Vec::<Asset>::read_from_bytes(&assets)?;
+    let (account_storage, account_vault) = query_account_state(conn, &id, state_id)?;
     let account = Account::from_parts(
         account_id,
-        AssetVault::new(&account_assets)?,
+        account_vault,
         account_storage,
         account_code,
         Felt::new(nonce),
@@ -409,21 +957,6 @@ fn serialize_account_code(account_code: &AccountCode) -> SerializedAccountCodeDa
     (commitment, code)
 }
 
-/// Serialize the provided `account_storage` into database compatible types.
-fn serialize_account_storage(account_storage: &AccountStorage) -> SerializedAccountStorageData {
-    let commitment = account_storage.commitment().to_string();
-    let storage = account_storage.to_bytes();
-
-    (commitment, storage)
-}
-
-/// Serialize the provided `asset_vault` into database compatible types.
-fn serialize_account_asset_vault(asset_vault: &AssetVault) -> SerializedAccountVaultData {
-    let commitment = asset_vault.root().to_string();
-    let assets = asset_vault.assets().collect::<Vec<Asset>>().to_bytes();
-    (commitment, assets)
-}
-
 /// Parse accounts parts from the provided row into native types.
 pub(super) fn parse_account_columns(
     row: &rusqlite::Row<'_>,
@@ -432,11 +965,10 @@ pub(super) fn parse_account_columns(
     let nonce: u64 = column_value_as_u64(row, 1)?;
     let account_seed: Option<Vec<u8>> = row.get(2)?;
     let code: Vec<u8> = row.get(3)?;
-    let storage: Vec<u8> = row.get(4)?;
-    let assets: Vec<u8> = row.get(5)?;
-    let locked: bool = row.get(6)?;
+    let locked: bool = row.get(4)?;
+    let state_id: i64 = row.get(5)?;
 
-    Ok((id, nonce, account_seed, code, storage, assets, locked))
+    Ok((id, nonce, account_seed, code, locked, state_id))
 }
 
 /// Removes account states with the specified hashes from the database.
@@ -450,9 +982,20 @@ pub(crate) fn undo_account_state(
     tx: &Transaction<'_>,
     account_hashes: &[Word],
 ) -> Result<(), StoreError> {
+    const STATE_QUERY: &str = "SELECT id, state_id FROM accounts WHERE account_commitment = ?";
     const QUERY: &str = "DELETE FROM accounts WHERE account_commitment = ?";
-    for account_id in account_hashes {
-        tx.execute(QUERY, params![account_id.to_hex()])?;
+    for account_hash in account_hashes {
+        let account_state: Option<(String, i64)> = tx
+            .query_row(STATE_QUERY, params![account_hash.to_hex()], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .optional()?;
+
+        if let Some((account_id, state_id)) = account_state {
+            remove_account_state_rows(tx, &account_id, state_id)?;
+        }
+
+        tx.execute(QUERY, params![account_hash.to_hex()])?;
     }
     Ok(())
 }
@@ -461,13 +1004,30 @@ pub(crate) fn undo_account_state(
 mod tests {
     use miden_lib::account::auth::RpoFalcon512;
     use miden_objects::{
-        EMPTY_WORD,
-        account::{AccountCode, AccountComponent},
+        EMPTY_WORD, Felt, Word,
+        account::{
+            AccountCode, AccountComponent, AccountDelta, AccountStorageDelta, AccountVaultDelta,
+            StorageSlot,
+        },
+        asset::Asset,
         crypto::dsa::rpo_falcon512::PublicKey,
         testing::account_component::BASIC_WALLET_CODE,
     };
+    use rusqlite::Connection;
 
-    use crate::store::sqlite_store::{account::insert_account_code, tests::create_test_store};
+    use crate::store::{
+        Store, StoreError,
+        sqlite_store::{
+            SqliteStore,
+            account::{insert_account_code, undo_account_state},
+            tests::{create_test_account, create_test_store},
+        },
+    };
+
+    fn count_rows(conn: &Connection, table: &str) -> usize {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| row.get(0))
+            .unwrap()
+    }
 
     #[tokio::test]
     async fn account_code_insertion_no_duplicates() {
@@ -510,4 +1070,211 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn account_states_share_unchanged_rows() {
+        let store = create_test_store().await;
+        let account = create_test_account();
+        let map_index = account
+            .storage()
+            .slots()
+            .iter()
+            .position(|slot| matches!(slot, StorageSlot::Map(_)))
+            .map(|index| u8::try_from(index).unwrap())
+            .unwrap();
+
+        // Add an entry to the storage map and remove another one
+        let mut storage_delta = AccountStorageDelta::default();
+        storage_delta.set_map_item(
+            map_index,
+            Word::from([Felt::new(100); 4]),
+            Word::from([Felt::new(1); 4]),
+        );
+        storage_delta.set_map_item(map_index, Word::from([Felt::new(0); 4]), EMPTY_WORD);
+        let delta = AccountDelta::new(
+            account.id(),
+            storage_delta,
+            AccountVaultDelta::default(),
+            Felt::new(1),
+        )
+        .unwrap();
+
+        let mut updated_account = account.clone();
+        updated_account.apply_delta(&delta).unwrap();
+
+        store
+            .interact_with_connection(move |conn| {
+                SqliteStore::insert_account(conn, &account, None)?;
+                let slot_rows = count_rows(conn, "account_storage_slots");
+                let entry_rows = count_rows(conn, "account_storage_map_entries");
+                let asset_rows = count_rows(conn, "account_assets");
+
+                SqliteStore::update_account(conn, &updated_account)?;
+
+                // Only the map slot and its two modified entries are written for the new state
+                assert_eq!(count_rows(conn, "account_storage_slots"), slot_rows + 1);
+                assert_eq!(count_rows(conn, "account_storage_map_entries"), entry_rows + 2);
+                assert_eq!(count_rows(conn, "account_assets"), asset_rows);
+
+                // Both states are rebuilt from the shared rows
+                let latest = SqliteStore::get_account(conn, account.id())?.unwrap();
+                assert_eq!(latest.account().commitment(), updated_account.commitment());
+
+                let previous =
+                    SqliteStore::get_account_at_nonce(conn, account.id(), account.nonce())?
+                        .unwrap();
+                assert_eq!(previous.account().commitment(), account.commitment());
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn account_states_with_the_same_nonce_are_kept_apart() {
+        let store = create_test_store().await;
+        let account = create_test_account();
+        let slot_index = |is_map: bool| {
+            account
+                .storage()
+                .slots()
+                .iter()
+                .position(|slot| matches!(slot, StorageSlot::Map(_)) == is_map)
+                .map(|index| u8::try_from(index).unwrap())
+                .unwrap()
+        };
+        let (map_index, value_index) = (slot_index(true), slot_index(false));
+
+        // Both states set the same value slot, but a different entry of the storage map
+        let updated_account = |map_key: u64| {
+            let mut storage_delta = AccountStorageDelta::default();
+            storage_delta.set_item(value_index, Word::from([Felt::new(9); 4]));
+            storage_delta.set_map_item(
+                map_index,
+                Word::from([Felt::new(map_key); 4]),
+                Word::from([Felt::new(7); 4]),
+            );
+            let delta = AccountDelta::new(
+                account.id(),
+                storage_delta,
+                AccountVaultDelta::default(),
+                Felt::new(1),
+            )
+            .unwrap();
+
+            let mut updated_account = account.clone();
+            updated_account.apply_delta(&delta).unwrap();
+            updated_account
+        };
+        let first_state = updated_account(100);
+        let second_state = updated_account(200);
+        assert_eq!(first_state.nonce(), second_state.nonce());
+
+        store
+            .interact_with_connection(move |conn| {
+                SqliteStore::insert_account(conn, &account, None)?;
+                SqliteStore::update_account(conn, &first_state)?;
+                SqliteStore::update_account(conn, &second_state)?;
+
+                for state in [&account, &first_state, &second_state] {
+                    let stored =
+                        SqliteStore::get_account_by_commitment(conn, state.commitment())?.unwrap();
+                    assert_eq!(stored.account().commitment(), state.commitment());
+                }
+
+                // Discarding the first state keeps the rows the second one shares with it
+                let tx = conn.transaction()?;
+                undo_account_state(&tx, &[first_state.commitment()])?;
+                tx.commit()?;
+
+                assert!(
+                    SqliteStore::get_account_by_commitment(conn, first_state.commitment())?
+                        .is_none()
+                );
+                for state in [&account, &second_state] {
+                    let stored =
+                        SqliteStore::get_account_by_commitment(conn, state.commitment())?.unwrap();
+                    assert_eq!(stored.account().commitment(), state.commitment());
+                }
+
+                let latest = SqliteStore::get_account(conn, account.id())?.unwrap();
+                assert_eq!(latest.account().commitment(), second_state.commitment());
+
+                Ok(())
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn partial_account_data_matches_account() {
+        let store = create_test_store().await;
+        let account = create_test_account();
+        let map_index = account
+            .storage()
+            .slots()
+            .iter()
+            .position(|slot| matches!(slot, StorageSlot::Map(_)))
+            .map(|index| u8::try_from(index).unwrap())
+            .unwrap();
+        let value_index = account
+            .storage()
+            .slots()
+            .iter()
+            .position(|slot| matches!(slot, StorageSlot::Value(_)))
+            .map(|index| u8::try_from(index).unwrap())
+            .unwrap();
+        let Asset::Fungible(asset) = account.vault().assets().next().unwrap() else {
+            panic!("test account holds a fungible asset");
+        };
+
+        let mut storage_delta = AccountStorageDelta::default();
+        storage_delta.set_map_item(
+            map_index,
+            Word::from([Felt::new(1); 4]),
+            Word::from([Felt::new(5); 4]),
+        );
+        let delta = AccountDelta::new(
+            account.id(),
+            storage_delta,
+            AccountVaultDelta::default(),
+            Felt::new(1),
+        )
+        .unwrap();
+        let mut updated_account = account.clone();
+        updated_account.apply_delta(&delta).unwrap();
+
+        store.insert_account(&account, None).await.unwrap();
+        store.update_account(&updated_account).await.unwrap();
+
+        let storage = updated_account.storage();
+        for index in [value_index, map_index] {
+            assert_eq!(
+                store.get_account_storage_item(account.id(), index).await.unwrap(),
+                storage.get_item(index).unwrap()
+            );
+        }
+
+        for key in [Word::from([Felt::new(1); 4]), Word::from([Felt::new(100); 4])] {
+            let (value, proof) =
+                store.get_account_map_item(account.id(), map_index, key).await.unwrap();
+            assert_eq!(value, storage.get_map_item(map_index, key).unwrap());
+            assert_eq!(proof.compute_root(), storage.get_item(map_index).unwrap());
+        }
+
+        assert_eq!(
+            store.get_account_balance(account.id(), asset.faucet_id()).await.unwrap(),
+            asset.amount()
+        );
+
+        assert!(matches!(
+            store.get_account_map_item(account.id(), value_index, EMPTY_WORD).await,
+            Err(StoreError::AccountStorageSlotNotMap(..))
+        ));
+        assert!(matches!(
+            store.get_account_storage_item(account.id(), u8::MAX).await,
+            Err(StoreError::AccountStorageSlotNotFound(..))
+        ));
+    }
 }
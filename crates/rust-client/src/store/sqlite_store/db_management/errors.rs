@@ -20,6 +20,12 @@ pub enum SqliteStoreError {
     MissingSettingsTable,
     #[error("Migration hashes mismatch")]
     MigrationHashMismatch,
+    #[error(
+        "Database schema version {version} is newer than the latest version supported by the client ({latest})"
+    )]
+    UnsupportedSchemaVersion { version: usize, latest: usize },
+    #[error("Failed to back up the database: {0}")]
+    BackupError(String),
     #[error("Failed to decode hex string: {0}")]
     HexDecodeError(String),
 }
@@ -1,4 +1,6 @@
 use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
     string::{String, ToString},
     sync::LazyLock,
     vec::Vec,
@@ -6,9 +8,14 @@ use std::{
 
 use miden_objects::crypto::hash::blake::{Blake3_160, Blake3Digest};
 use rusqlite::{Connection, OptionalExtension, Result, ToSql, Transaction, params, types::FromSql};
-use rusqlite_migration::{M, Migrations, SchemaVersion};
+use rusqlite_migration::{HookResult, M, Migrations, SchemaVersion};
+use tracing::info;
 
 use super::errors::SqliteStoreError;
+use crate::store::sqlite_store::{
+    account::migrate_account_state_blobs, note::migrate_note_filter_data,
+    transaction::migrate_transaction_filter_data,
+};
 
 // MACROS
 // ================================================================================================
@@ -57,48 +64,101 @@ macro_rules! insert_sql {
 // ================================================================================================
 
 type Hash = Blake3Digest<20>;
+type MigrationHook = fn(&Transaction<'_>) -> HookResult;
 
-const MIGRATION_SCRIPTS: [&str; 1] = [include_str!("../store.sql")];
-static MIGRATION_HASHES: LazyLock<Vec<Hash>> = LazyLock::new(compute_migration_hashes);
-static MIGRATIONS: LazyLock<Migrations> = LazyLock::new(prepare_migrations);
-
-fn up(s: &'static str) -> M<'static> {
-    M::up(s).foreign_key_check()
+/// A step of the store schema history: a SQL script, optionally followed by a hook that migrates
+/// the data that can only be transformed from Rust (such as serialized objects).
+struct MigrationStep {
+    sql: &'static str,
+    hook: Option<MigrationHook>,
 }
 
-const DB_MIGRATION_HASH_FIELD: &str = "db-migration-hash";
-const DB_SCHEMA_VERSION_FIELD: &str = "db-schema-version";
+impl MigrationStep {
+    const fn new(sql: &'static str) -> Self {
+        Self { sql, hook: None }
+    }
 
-/// Applies the migrations to the database.
-pub fn apply_migrations(conn: &mut Connection) -> Result<(), SqliteStoreError> {
-    let version_before = MIGRATIONS.current_version(conn)?;
+    const fn with_hook(sql: &'static str, hook: MigrationHook) -> Self {
+        Self { sql, hook: Some(hook) }
+    }
 
-    if let SchemaVersion::Inside(ver) = version_before {
-        if !table_exists(&conn.transaction()?, "settings")? {
-            return Err(SqliteStoreError::MissingSettingsTable);
+    fn to_migration(&self) -> M<'static> {
+        match self.hook {
+            Some(hook) => M::up_with_hook(self.sql, hook),
+            None => M::up(self.sql),
         }
+        .foreign_key_check()
+    }
+}
 
-        let last_schema_version: usize = get_settings_value(conn, DB_SCHEMA_VERSION_FIELD)?
-            .ok_or_else(|| {
-                SqliteStoreError::DatabaseError("Schema version not found".to_string())
-            })?;
-
-        let current_schema_version = schema_version(conn)?;
+/// The migration steps, in the order they are applied. The schema version of a store is the number
+/// of steps applied to it.
+///
+/// This list is append-only. Every released client records the hash of the steps it applied, so
+/// the steps of a released schema must never be modified or reordered: schema changes are made by
+/// adding a script to the `migrations` directory (and a hook, if the stored data needs to be
+/// transformed) at the end of the list.
+///
+/// The schemas of the released versions are kept in the `fixtures` directory, and the tests check
+/// that they are still the first steps of this list and that they get upgraded.
+const MIGRATION_STEPS: [MigrationStep; 5] = [
+    MigrationStep::new(include_str!("../store.sql")),
+    // The stored account blobs can only be converted from Rust
+    MigrationStep::with_hook(
+        include_str!("../migrations/normalize_account_state.sql"),
+        migrate_account_state_blobs,
+    ),
+    // The transaction details can only be deserialized from Rust
+    MigrationStep::with_hook(
+        include_str!("../migrations/transaction_filters.sql"),
+        migrate_transaction_filter_data,
+    ),
+    // The note metadata, states and assets can only be deserialized from Rust
+    MigrationStep::with_hook(
+        include_str!("../migrations/note_filters.sql"),
+        migrate_note_filter_data,
+    ),
+    MigrationStep::new(include_str!("../migrations/pending_transactions.sql")),
+];
+static MIGRATION_HASHES: LazyLock<Vec<Hash>> = LazyLock::new(compute_migration_hashes);
+static MIGRATIONS: LazyLock<Migrations> =
+    LazyLock::new(|| prepare_migrations(MIGRATION_STEPS.len()));
 
-        if last_schema_version != current_schema_version {
-            return Err(SqliteStoreError::SchemaVersionMismatch);
-        }
+const DB_MIGRATION_HASH_FIELD: &str = "db-migration-hash";
+const DB_SCHEMA_VERSION_FIELD: &str = "db-schema-version";
 
-        let expected_hash = &*MIGRATION_HASHES[ver.get() - 1];
-        let actual_hash =
-            hex::decode(get_settings_value::<String>(conn, DB_MIGRATION_HASH_FIELD)?.ok_or_else(
-                || SqliteStoreError::DatabaseError("Migration hash not found".to_string()),
-            )?)
-            .map_err(|e| SqliteStoreError::HexDecodeError(e.to_string()))?;
+/// Applies the pending migrations to the database stored at `database_path`.
+///
+/// Before migrating a database created by an older version of the client, a copy of it is written
+/// to the path returned by [`backup_path`], so the user data can be recovered if the migration
+/// doesn't go as expected. The migrations themselves run in a single transaction.
+///
+/// # Errors
+///
+/// Returns an error if the database was created by a newer version of the client, if its recorded
+/// schema version or migration hash don't match the ones of the client, or if the backup or any
+/// of the migrations fail.
+pub fn apply_migrations(
+    conn: &mut Connection,
+    database_path: &Path,
+) -> Result<(), SqliteStoreError> {
+    let version_before = MIGRATIONS.current_version(conn)?;
 
-        if actual_hash != expected_hash {
-            return Err(SqliteStoreError::MigrationHashMismatch);
-        }
+    match version_before {
+        SchemaVersion::NoneSet => {},
+        SchemaVersion::Inside(ver) => {
+            verify_settings(conn, ver.get())?;
+
+            if ver.get() < MIGRATION_STEPS.len() {
+                backup_database(conn, &backup_path(database_path, ver.get()))?;
+            }
+        },
+        SchemaVersion::Outside(ver) => {
+            return Err(SqliteStoreError::UnsupportedSchemaVersion {
+                version: ver.get(),
+                latest: MIGRATION_STEPS.len(),
+            });
+        },
     }
 
     MIGRATIONS.to_latest(conn)?;
@@ -116,16 +176,76 @@ pub fn apply_migrations(conn: &mut Connection) -> Result<(), SqliteStoreError> {
     Ok(())
 }
 
-fn prepare_migrations() -> Migrations<'static> {
-    Migrations::new(MIGRATION_SCRIPTS.map(up).to_vec())
+/// Returns the path of the backup written before migrating the database at `database_path` from
+/// the specified schema version, which is the database path followed by `.v{version}.bak`.
+pub fn backup_path(database_path: &Path, version: usize) -> PathBuf {
+    let mut path = OsString::from(database_path);
+    path.push(format!(".v{version}.bak"));
+    PathBuf::from(path)
+}
+
+/// Checks that the schema version and migration hash recorded in the settings of a database at
+/// the specified migration version match the ones expected by the client.
+fn verify_settings(conn: &mut Connection, version: usize) -> Result<(), SqliteStoreError> {
+    if !table_exists(&conn.transaction()?, "settings")? {
+        return Err(SqliteStoreError::MissingSettingsTable);
+    }
+
+    let last_schema_version: usize = get_settings_value(conn, DB_SCHEMA_VERSION_FIELD)?
+        .ok_or_else(|| SqliteStoreError::DatabaseError("Schema version not found".to_string()))?;
+
+    let current_schema_version = schema_version(conn)?;
+
+    if last_schema_version != current_schema_version {
+        return Err(SqliteStoreError::SchemaVersionMismatch);
+    }
+
+    let expected_hash = &*MIGRATION_HASHES[version - 1];
+    let actual_hash =
+        hex::decode(get_settings_value::<String>(conn, DB_MIGRATION_HASH_FIELD)?.ok_or_else(
+            || SqliteStoreError::DatabaseError("Migration hash not found".to_string()),
+        )?)
+        .map_err(|e| SqliteStoreError::HexDecodeError(e.to_string()))?;
+
+    if actual_hash != expected_hash {
+        return Err(SqliteStoreError::MigrationHashMismatch);
+    }
+
+    Ok(())
+}
+
+/// Writes a copy of the database to `backup_path`.
+///
+/// If a backup already exists at the path, it's kept as is: it was written by a previous attempt
+/// to migrate the database, which left the database unchanged as migrations are transactional.
+fn backup_database(conn: &Connection, backup_path: &Path) -> Result<(), SqliteStoreError> {
+    if backup_path.exists() {
+        return Ok(());
+    }
+
+    let path = backup_path.to_str().ok_or_else(|| {
+        SqliteStoreError::BackupError(format!("Invalid backup path {}", backup_path.display()))
+    })?;
+
+    conn.execute("VACUUM INTO ?", params![path])
+        .map_err(|err| SqliteStoreError::BackupError(err.to_string()))?;
+
+    info!("Backed up the store to {path} before migrating it");
+
+    Ok(())
+}
+
+/// Returns the migrations of the first `version` steps.
+fn prepare_migrations(version: usize) -> Migrations<'static> {
+    Migrations::new(MIGRATION_STEPS[..version].iter().map(MigrationStep::to_migration).collect())
 }
 
 fn compute_migration_hashes() -> Vec<Hash> {
     let mut accumulator = Hash::default();
-    MIGRATION_SCRIPTS
+    MIGRATION_STEPS
         .iter()
-        .map(|sql| {
-            let script_hash = Blake3_160::hash(preprocess_sql(sql).as_bytes());
+        .map(|step| {
+            let script_hash = Blake3_160::hash(preprocess_sql(step.sql).as_bytes());
             accumulator = Blake3_160::merge(&[accumulator, script_hash]);
             accumulator
         })
@@ -175,3 +295,151 @@ pub fn schema_version(connection: &mut Connection) -> rusqlite::Result<usize> {
         .transaction()?
         .query_row("SELECT * FROM pragma_schema_version", [], |row| row.get(0))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    use miden_objects::{account::Account, asset::Asset, utils::Serializable};
+    use rusqlite::{Connection, params};
+    use rusqlite_migration::{M, Migrations, SchemaVersion};
+
+    use super::{
+        DB_MIGRATION_HASH_FIELD, DB_SCHEMA_VERSION_FIELD, MIGRATION_HASHES, MIGRATION_STEPS,
+        MIGRATIONS, SqliteStoreError, apply_migrations, backup_path, prepare_migrations,
+        schema_version, set_settings_value, table_exists,
+    };
+    use crate::{
+        store::sqlite_store::{SqliteStore, tests::create_test_account, u64_to_value},
+        tests::create_test_store_path,
+    };
+
+    /// Stores the account state as blobs, as the initial schema did.
+    fn insert_initial_schema_account(conn: &Connection, account: &Account) {
+        conn.execute(
+            "INSERT INTO account_code (root, code) VALUES (?, ?)",
+            params![account.code().commitment().to_string(), account.code().to_bytes()],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO account_storage (root, slots) VALUES (?, ?)",
+            params![account.storage().commitment().to_string(), account.storage().to_bytes()],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO account_vaults (root, assets) VALUES (?, ?)",
+            params![
+                account.vault().root().to_string(),
+                account.vault().assets().collect::<Vec<Asset>>().to_bytes()
+            ],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO accounts (id, code_root, storage_root, vault_root, nonce, committed, account_seed, account_commitment, locked) \
+            VALUES (?, ?, ?, ?, ?, ?, NULL, ?, false)",
+            params![
+                account.id().to_hex(),
+                account.code().commitment().to_string(),
+                account.storage().commitment().to_string(),
+                account.vault().root().to_string(),
+                u64_to_value(account.nonce().as_int()),
+                account.is_public(),
+                account.commitment().to_string()
+            ],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn account_state_blobs_are_migrated() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        prepare_migrations(1).to_latest(&mut conn).unwrap();
+
+        let account = create_test_account();
+        insert_initial_schema_account(&conn, &account);
+
+        MIGRATIONS.to_latest(&mut conn).unwrap();
+
+        let record = SqliteStore::get_account(&mut conn, account.id()).unwrap().unwrap();
+        assert_eq!(record.account().commitment(), account.commitment());
+        assert!(!table_exists(&conn.transaction().unwrap(), "account_storage").unwrap());
+        assert!(!table_exists(&conn.transaction().unwrap(), "account_vaults").unwrap());
+    }
+
+    /// The schema created by the 0.10 release, which is the first step of the migrations.
+    const SCHEMA_V0_10: &str = include_str!("fixtures/store_v0.10.0.sql");
+
+    /// The migration hash recorded by the 0.10 release in the databases it created.
+    const MIGRATION_HASH_V0_10: &str = "c4a6c5e502ef2fa4ec58af77b56f2ac33d235be7";
+
+    #[test]
+    fn released_schemas_are_unchanged() {
+        assert_eq!(MIGRATION_STEPS[0].sql, SCHEMA_V0_10);
+        assert_eq!(hex::encode(&*MIGRATION_HASHES[0]), MIGRATION_HASH_V0_10);
+    }
+
+    #[test]
+    fn released_schemas_are_upgraded_with_a_backup() {
+        let account = create_test_account();
+
+        // Create the database as the 0.10 release did
+        let path = create_test_store_path();
+        let mut conn = Connection::open(&path).unwrap();
+        Migrations::new(vec![M::up(SCHEMA_V0_10).foreign_key_check()])
+            .to_latest(&mut conn)
+            .unwrap();
+        set_settings_value(&conn, DB_MIGRATION_HASH_FIELD, &MIGRATION_HASH_V0_10).unwrap();
+        let schema = schema_version(&mut conn).unwrap();
+        set_settings_value(&conn, DB_SCHEMA_VERSION_FIELD, &schema).unwrap();
+        insert_initial_schema_account(&conn, &account);
+
+        apply_migrations(&mut conn, &path).unwrap();
+
+        assert_eq!(
+            MIGRATIONS.current_version(&conn).unwrap(),
+            SchemaVersion::Inside(MIGRATION_STEPS.len().try_into().unwrap())
+        );
+        let record = SqliteStore::get_account(&mut conn, account.id()).unwrap().unwrap();
+        assert_eq!(record.account().commitment(), account.commitment());
+
+        // The backup holds the database as it was before the migration
+        let backup = backup_path(&path, 1);
+        let backup_conn = Connection::open(&backup).unwrap();
+        assert_eq!(
+            prepare_migrations(1).current_version(&backup_conn).unwrap(),
+            SchemaVersion::Inside(1usize.try_into().unwrap())
+        );
+        let backup_commitment: String = backup_conn
+            .query_row("SELECT account_commitment FROM accounts", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(backup_commitment, account.commitment().to_string());
+
+        // Opening the migrated database again doesn't write another backup
+        apply_migrations(&mut conn, &path).unwrap();
+        assert!(!backup_path(&path, MIGRATION_STEPS.len()).exists());
+
+        fs::remove_file(backup).unwrap();
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn newer_schemas_are_rejected() {
+        let path = create_test_store_path();
+        let mut conn = Connection::open(&path).unwrap();
+        apply_migrations(&mut conn, &path).unwrap();
+
+        let newer_version = MIGRATION_STEPS.len() + 1;
+        conn.pragma_update(None, "user_version", newer_version).unwrap();
+
+        assert!(matches!(
+            apply_migrations(&mut conn, &path),
+            Err(SqliteStoreError::UnsupportedSchemaVersion { version, .. }) if version == newer_version
+        ));
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,68 @@
+-- Normalizes the storage and vault of the account states. Instead of storing the whole storage and
+-- vault of every state as blobs, each state only stores the storage slots, storage map entries and
+-- assets that changed since the previous state of the account, sharing the rest with it.
+--
+-- The rows reference the account state that introduced them through its `state_id`, which orders
+-- the states in the order they were stored. Account states can't be identified by their nonce, as
+-- several states of an account can share it.
+--
+-- The stored blobs are converted into rows, and the `account_storage` and `account_vaults` tables
+-- dropped, by the hook that runs after this script (see `migrate_account_state_blobs`).
+
+-- Create account_storage_slots table
+CREATE TABLE account_storage_slots (
+    account_id TEXT NOT NULL,               -- ID of the account
+    state_id INTEGER NOT NULL,              -- First account state with this slot value
+    slot_index UNSIGNED INT NOT NULL,       -- Index of the slot in the account storage
+    slot_type UNSIGNED INT NOT NULL,        -- 0 for value slots, 1 for map slots
+    slot_value TEXT NOT NULL,               -- Value of the slot, or root of the storage map
+    PRIMARY KEY (account_id, slot_index, state_id)
+);
+
+-- Create account_storage_map_entries table
+CREATE TABLE account_storage_map_entries (
+    account_id TEXT NOT NULL,               -- ID of the account
+    state_id INTEGER NOT NULL,              -- First account state with this entry value
+    slot_index UNSIGNED INT NOT NULL,       -- Index of the map slot in the account storage
+    key TEXT NOT NULL,                      -- Key of the entry
+    value TEXT NOT NULL,                    -- Value of the entry, the empty word if it was removed
+    PRIMARY KEY (account_id, slot_index, key, state_id)
+);
+
+-- Create account_assets table
+CREATE TABLE account_assets (
+    account_id TEXT NOT NULL,               -- ID of the account
+    state_id INTEGER NOT NULL,              -- First account state with this asset
+    vault_key TEXT NOT NULL,                -- Key of the asset in the vault
+    asset BLOB NULL,                        -- Serialized asset, NULL if it was removed from the vault
+    PRIMARY KEY (account_id, vault_key, state_id)
+);
+
+-- Recreate the accounts table without the foreign keys to the blob tables, and with the ID of
+-- each state
+CREATE TABLE accounts_normalized (
+    account_commitment TEXT NOT NULL UNIQUE,    -- Account state commitment
+    state_id INTEGER NOT NULL UNIQUE,           -- Order in which the account states were stored
+    id UNSIGNED BIG INT NOT NULL,               -- Account ID.
+    code_root TEXT NOT NULL,                    -- Root of the account_code
+    storage_root TEXT NOT NULL,                 -- Root of the account storage Merkle tree.
+    vault_root TEXT NOT NULL,                   -- Root of the account vault Merkle tree.
+    nonce BIGINT NOT NULL,                      -- Account nonce.
+    committed BOOLEAN NOT NULL,                 -- True if recorded, false if not.
+    account_seed BLOB NULL,                     -- Account seed used to generate the ID. Expected to be NULL for non-new accounts
+    locked BOOLEAN NOT NULL,                    -- True if the account is locked, false if not.
+    PRIMARY KEY (account_commitment),
+    FOREIGN KEY (code_root) REFERENCES account_code(root)
+
+    CONSTRAINT check_seed_nonzero CHECK (NOT (nonce = 0 AND account_seed IS NULL))
+);
+
+INSERT INTO accounts_normalized
+    (account_commitment, state_id, id, code_root, storage_root, vault_root, nonce, committed, account_seed, locked)
+SELECT account_commitment, ROW_NUMBER() OVER (ORDER BY nonce, rowid), id, code_root, storage_root, vault_root, nonce, committed, account_seed, locked
+FROM accounts;
+
+DROP TABLE accounts;
+ALTER TABLE accounts_normalized RENAME TO accounts;
+
+CREATE UNIQUE INDEX idx_account_commitment ON accounts(account_commitment);
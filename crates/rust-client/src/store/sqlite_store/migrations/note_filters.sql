@@ -0,0 +1,36 @@
+-- Adds the data needed to filter notes by sender, tag, consumer and target accounts, script,
+-- inclusion block and the faucets of their assets, which is otherwise only available in the
+-- serialized notes.
+--
+-- The data of the stored notes is extracted by the hook that runs after this script (see
+-- `migrate_note_filter_data`).
+
+-- Add the filterable columns of input notes
+ALTER TABLE input_notes ADD COLUMN sender TEXT NULL;                  -- ID of the account that sent the note, if the metadata is known
+ALTER TABLE input_notes ADD COLUMN tag UNSIGNED INT NULL;             -- tag of the note, if the metadata is known
+ALTER TABLE input_notes ADD COLUMN consumer_account_id TEXT NULL;     -- ID of the account that consumed the note locally
+ALTER TABLE input_notes ADD COLUMN target_account_id TEXT NULL;       -- ID of the target account of P2ID and P2IDE notes
+ALTER TABLE input_notes ADD COLUMN block_num UNSIGNED INT NULL;       -- number of the block the note was included in
+
+CREATE INDEX idx_input_notes_sender ON input_notes(sender);
+CREATE INDEX idx_input_notes_consumer_account_id ON input_notes(consumer_account_id);
+CREATE INDEX idx_input_notes_target_account_id ON input_notes(target_account_id);
+
+-- Add the filterable columns of output notes
+ALTER TABLE output_notes ADD COLUMN sender TEXT NULL;                 -- ID of the account that sent the note
+ALTER TABLE output_notes ADD COLUMN tag UNSIGNED INT NULL;            -- tag of the note
+ALTER TABLE output_notes ADD COLUMN target_account_id TEXT NULL;      -- ID of the target account of P2ID and P2IDE notes, if the recipient is known
+ALTER TABLE output_notes ADD COLUMN script_root TEXT NULL;            -- root of the note script, if the recipient is known
+ALTER TABLE output_notes ADD COLUMN block_num UNSIGNED INT NULL;      -- number of the block the note was included in
+
+CREATE INDEX idx_output_notes_sender ON output_notes(sender);
+CREATE INDEX idx_output_notes_target_account_id ON output_notes(target_account_id);
+
+-- Create note_faucets table, used for both input and output notes
+CREATE TABLE note_faucets (
+    note_id TEXT NOT NULL,                  -- ID of the note
+    faucet_id_prefix TEXT NOT NULL,         -- ID prefix of the faucet that issued an asset held by the note
+    PRIMARY KEY (note_id, faucet_id_prefix)
+);
+
+CREATE INDEX idx_note_faucets_faucet_id_prefix ON note_faucets(faucet_id_prefix);
@@ -0,0 +1,9 @@
+-- Adds the table used to persist transactions between the execute, prove and submit steps.
+
+-- Create pending_transactions table
+CREATE TABLE pending_transactions (
+    id TEXT NOT NULL,                                -- Transaction ID
+    tx_result BLOB NOT NULL,                         -- Serialized transaction execution result
+    stage BLOB NOT NULL,                             -- Serialized stage reached in the execute, prove and submit pipeline
+    PRIMARY KEY (id)
+);
@@ -0,0 +1,26 @@
+-- Adds the data needed to filter transactions by the account that executed them and by the notes
+-- they consumed or created, which is otherwise only available in the serialized details.
+--
+-- The data of the stored transactions is extracted from their details by the hook that runs after
+-- this script (see `migrate_transaction_filter_data`).
+
+-- Add the ID of the account that executed the transaction
+ALTER TABLE transactions ADD COLUMN account_id TEXT NULL;
+
+CREATE INDEX idx_transactions_account_id ON transactions(account_id);
+
+-- Create transaction_input_notes table
+CREATE TABLE transaction_input_notes (
+    transaction_id TEXT NOT NULL,           -- ID of the transaction
+    nullifier TEXT NOT NULL,                -- Nullifier of a note consumed by the transaction
+    PRIMARY KEY (transaction_id, nullifier)
+);
+
+-- Create transaction_output_notes table
+CREATE TABLE transaction_output_notes (
+    transaction_id TEXT NOT NULL,           -- ID of the transaction
+    note_id TEXT NOT NULL,                  -- ID of a note created by the transaction
+    PRIMARY KEY (transaction_id, note_id)
+);
+
+CREATE INDEX idx_transaction_output_notes_note_id ON transaction_output_notes(note_id);
@@ -9,30 +9,35 @@ use alloc::{
     collections::{BTreeMap, BTreeSet},
     vec::Vec,
 };
-use std::{path::PathBuf, string::ToString};
+use std::{
+    path::PathBuf,
+    string::{String, ToString},
+};
 
 use db_management::{
     pool_manager::{Pool, SqlitePoolManager},
-    utils::apply_migrations,
+    utils::{apply_migrations, get_settings_value, set_settings_value},
 };
 use miden_objects::{
-    Word,
+    Felt, Word,
     account::{Account, AccountCode, AccountHeader, AccountId},
     block::{BlockHeader, BlockNumber},
-    crypto::merkle::{InOrderIndex, MmrPeaks},
+    crypto::merkle::{InOrderIndex, MmrPeaks, SmtProof},
     note::{NoteTag, Nullifier},
+    transaction::TransactionId,
 };
 use rusqlite::{Connection, types::Value};
 use tonic::async_trait;
 
 use super::{
-    AccountRecord, AccountStatus, BlockRelevance, InputNoteRecord, NoteFilter, OutputNoteRecord,
-    PartialBlockchainFilter, Store, TransactionFilter,
+    AccountRecord, AccountStatus, BlockRelevance, InputNoteRecord, NoteFilter, NoteSort,
+    OutputNoteRecord, Page, PartialBlockchainFilter, Store, StoreSnapshot, TransactionFilter,
 };
 use crate::{
+    account::AccountRemoval,
     store::StoreError,
     sync::{NoteTagRecord, StateSyncUpdate},
-    transaction::{TransactionRecord, TransactionStoreUpdate},
+    transaction::{PendingTransactionRecord, TransactionRecord, TransactionStoreUpdate},
 };
 
 mod account;
@@ -40,16 +45,20 @@ mod chain_data;
 mod db_management;
 mod errors;
 mod note;
+mod snapshot;
 mod sync;
 mod transaction;
 
+pub use db_management::utils::backup_path;
+
 // SQLITE STORE
 // ================================================================================================
 
 /// Represents a pool of connections with an `SQLite` database. The pool is used to interact
 /// concurrently with the underlying database in a safe and efficient manner.
 ///
-/// Current table definitions can be found at `store.sql` migration file.
+/// Table definitions can be found at the `store.sql` migration file, along with the changes made by
+/// the later scripts in the `migrations` directory.
 pub struct SqliteStore {
     pub(crate) pool: Pool,
 }
@@ -59,6 +68,9 @@ impl SqliteStore {
     // --------------------------------------------------------------------------------------------
 
     /// Returns a new instance of [Store] instantiated with the specified configuration options.
+    ///
+    /// If the database was created by an older version of the client, it's migrated to the latest
+    /// schema after writing a backup of it next to the database file (see [`backup_path`]).
     pub async fn new(database_filepath: PathBuf) -> Result<Self, StoreError> {
         let sqlite_pool_manager = SqlitePoolManager::new(database_filepath.clone());
         let pool = Pool::builder(sqlite_pool_manager)
@@ -67,9 +79,9 @@ impl SqliteStore {
 
         let conn = pool.get().await.map_err(|e| StoreError::DatabaseError(e.to_string()))?;
 
-        let _ = conn
-            .interact(apply_migrations)
+        conn.interact(move |conn| apply_migrations(conn, &database_filepath))
             .await
+            .map_err(|e| StoreError::DatabaseError(e.to_string()))?
             .map_err(|e| StoreError::DatabaseError(e.to_string()))?;
 
         Ok(SqliteStore { pool })
@@ -94,6 +106,39 @@ impl SqliteStore {
             .await
             .map_err(|err| StoreError::DatabaseError(err.to_string()))?
     }
+
+    // GARBAGE COLLECTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Removes the account code, account state rows, note scripts, note faucets and transaction
+    /// scripts that are no longer referenced by any stored account, note or transaction. Returns
+    /// the number of removed rows.
+    fn garbage_collect(conn: &mut Connection) -> Result<usize, StoreError> {
+        const QUERIES: [&str; 7] = [
+            "DELETE FROM account_code WHERE root NOT IN (SELECT code_root FROM accounts) \
+                AND root NOT IN (SELECT code_root FROM foreign_account_code)",
+            "DELETE FROM account_storage_slots WHERE account_id NOT IN (SELECT id FROM accounts)",
+            "DELETE FROM account_storage_map_entries \
+                WHERE account_id NOT IN (SELECT id FROM accounts)",
+            "DELETE FROM account_assets WHERE account_id NOT IN (SELECT id FROM accounts)",
+            "DELETE FROM notes_scripts WHERE script_root NOT IN (SELECT script_root FROM input_notes)",
+            "DELETE FROM note_faucets WHERE note_id NOT IN (SELECT note_id FROM input_notes) \
+                AND note_id NOT IN (SELECT note_id FROM output_notes)",
+            "DELETE FROM transaction_scripts WHERE script_root NOT IN \
+                (SELECT script_root FROM transactions WHERE script_root IS NOT NULL)",
+        ];
+
+        let tx = conn.transaction()?;
+
+        let mut removed_rows = 0;
+        for query in QUERIES {
+            removed_rows += tx.execute(query, [])?;
+        }
+
+        tx.commit()?;
+
+        Ok(removed_rows)
+    }
 }
 
 // SQLite implementation of the Store trait
@@ -146,11 +191,52 @@ impl Store for SqliteStore {
         .await
     }
 
+    async fn get_transactions_page(
+        &self,
+        transaction_filter: TransactionFilter,
+        page: Page,
+    ) -> Result<Vec<TransactionRecord>, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_transactions_page(conn, &transaction_filter, page)
+        })
+        .await
+    }
+
     async fn apply_transaction(&self, tx_update: TransactionStoreUpdate) -> Result<(), StoreError> {
         self.interact_with_connection(move |conn| SqliteStore::apply_transaction(conn, &tx_update))
             .await
     }
 
+    async fn get_pending_transactions(&self) -> Result<Vec<PendingTransactionRecord>, StoreError> {
+        self.interact_with_connection(SqliteStore::get_pending_transactions).await
+    }
+
+    async fn get_pending_transaction(
+        &self,
+        tx_id: TransactionId,
+    ) -> Result<Option<PendingTransactionRecord>, StoreError> {
+        self.interact_with_connection(move |conn| SqliteStore::get_pending_transaction(conn, tx_id))
+            .await
+    }
+
+    async fn upsert_pending_transaction(
+        &self,
+        record: &PendingTransactionRecord,
+    ) -> Result<(), StoreError> {
+        let record = record.clone();
+        self.interact_with_connection(move |conn| {
+            SqliteStore::upsert_pending_transaction(conn, &record)
+        })
+        .await
+    }
+
+    async fn remove_pending_transaction(&self, tx_id: TransactionId) -> Result<bool, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::remove_pending_transaction(conn, tx_id)
+        })
+        .await
+    }
+
     async fn get_input_notes(
         &self,
         filter: NoteFilter,
@@ -167,6 +253,30 @@ impl Store for SqliteStore {
             .await
     }
 
+    async fn get_input_notes_page(
+        &self,
+        filter: NoteFilter,
+        sort: NoteSort,
+        page: Page,
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_input_notes_page(conn, &filter, sort, page)
+        })
+        .await
+    }
+
+    async fn get_output_notes_page(
+        &self,
+        filter: NoteFilter,
+        sort: NoteSort,
+        page: Page,
+    ) -> Result<Vec<OutputNoteRecord>, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_output_notes_page(conn, &filter, sort, page)
+        })
+        .await
+    }
+
     async fn upsert_input_notes(&self, notes: &[InputNoteRecord]) -> Result<(), StoreError> {
         let notes = notes.to_vec();
         self.interact_with_connection(move |conn| SqliteStore::upsert_input_notes(conn, &notes))
@@ -296,6 +406,71 @@ impl Store for SqliteStore {
             .await
     }
 
+    async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountHeader>, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_account_history(conn, account_id)
+        })
+        .await
+    }
+
+    async fn get_account_at_nonce(
+        &self,
+        account_id: AccountId,
+        nonce: Felt,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_account_at_nonce(conn, account_id, nonce)
+        })
+        .await
+    }
+
+    async fn get_account_by_commitment(
+        &self,
+        account_commitment: Word,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_account_by_commitment(conn, account_commitment)
+        })
+        .await
+    }
+
+    async fn get_account_storage_item(
+        &self,
+        account_id: AccountId,
+        index: u8,
+    ) -> Result<Word, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_account_storage_item(conn, account_id, index)
+        })
+        .await
+    }
+
+    async fn get_account_map_item(
+        &self,
+        account_id: AccountId,
+        index: u8,
+        key: Word,
+    ) -> Result<(Word, SmtProof), StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_account_map_item(conn, account_id, index, key)
+        })
+        .await
+    }
+
+    async fn get_account_balance(
+        &self,
+        account_id: AccountId,
+        faucet_id: AccountId,
+    ) -> Result<u64, StoreError> {
+        self.interact_with_connection(move |conn| {
+            SqliteStore::get_account_balance(conn, account_id, faucet_id)
+        })
+        .await
+    }
+
     async fn upsert_foreign_account_code(
         &self,
         account_id: AccountId,
@@ -321,6 +496,32 @@ impl Store for SqliteStore {
         self.interact_with_connection(SqliteStore::get_unspent_input_note_nullifiers)
             .await
     }
+
+    async fn remove_account(&self, removal: &AccountRemoval) -> Result<(), StoreError> {
+        let removal = removal.clone();
+        self.interact_with_connection(move |conn| SqliteStore::remove_account(conn, &removal))
+            .await
+    }
+
+    async fn garbage_collect(&self) -> Result<usize, StoreError> {
+        self.interact_with_connection(SqliteStore::garbage_collect).await
+    }
+
+    async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError> {
+        self.interact_with_connection(move |conn| Ok(get_settings_value(conn, &name)?))
+            .await
+    }
+
+    async fn set_setting(&self, name: String, value: Vec<u8>) -> Result<(), StoreError> {
+        self.interact_with_connection(move |conn| Ok(set_settings_value(conn, &name, &value)?))
+            .await
+    }
+
+    async fn import_snapshot(&self, snapshot: &StoreSnapshot) -> Result<(), StoreError> {
+        let snapshot = snapshot.clone();
+        self.interact_with_connection(move |conn| SqliteStore::import_snapshot(conn, &snapshot))
+            .await
+    }
 }
 
 // UTILS
@@ -361,6 +562,15 @@ pub fn u64_to_value(v: u64) -> Value {
 pub mod tests {
     use std::boxed::Box;
 
+    use miden_lib::{account::auth::RpoFalcon512, transaction::TransactionKernel};
+    use miden_objects::{
+        EMPTY_WORD, Felt, Word,
+        account::{Account, AccountBuilder, AccountComponent, StorageMap, StorageSlot},
+        asset::FungibleAsset,
+        crypto::dsa::rpo_falcon512::PublicKey,
+        testing::account_component::BASIC_WALLET_CODE,
+    };
+
     use super::SqliteStore;
     use crate::{store::Store, tests::create_test_store_path};
 
@@ -386,7 +596,46 @@ pub mod tests {
         tokio::task::spawn(async move { dyn_trait_send_fut(client).await });
     }
 
+    #[tokio::test]
+    async fn settings_are_kept_when_reopening_the_store() {
+        let path = create_test_store_path();
+        let store = SqliteStore::new(path.clone()).await.unwrap();
+        assert_eq!(store.get_setting("setting".into()).await.unwrap(), None);
+
+        store.set_setting("setting".into(), vec![1, 2]).await.unwrap();
+        store.set_setting("setting".into(), vec![3]).await.unwrap();
+        drop(store);
+
+        let store = SqliteStore::new(path).await.unwrap();
+        assert_eq!(store.get_setting("setting".into()).await.unwrap(), Some(vec![3]));
+    }
+
     pub(crate) async fn create_test_store() -> SqliteStore {
         SqliteStore::new(create_test_store_path()).await.unwrap()
     }
+
+    /// Returns an existing account with a value slot, a storage map with 10 entries and a fungible
+    /// asset.
+    pub(crate) fn create_test_account() -> Account {
+        let storage_map = StorageMap::with_entries(
+            (0..10u32)
+                .map(|index| (Word::from([Felt::from(index); 4]), Word::from([Felt::new(1); 4]))),
+        )
+        .unwrap();
+
+        let component = AccountComponent::compile(
+            BASIC_WALLET_CODE,
+            TransactionKernel::assembler(),
+            vec![StorageSlot::Value(Word::from([Felt::new(1); 4])), StorageSlot::Map(storage_map)],
+        )
+        .unwrap()
+        .with_supports_all_types();
+
+        AccountBuilder::new([0; 32])
+            .with_auth_component(RpoFalcon512::new(PublicKey::new(EMPTY_WORD)))
+            .with_component(component)
+            .with_assets([FungibleAsset::mock(100)])
+            .build_existing()
+            .unwrap()
+    }
 }
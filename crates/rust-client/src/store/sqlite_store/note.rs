@@ -11,20 +11,23 @@ use miden_objects::{
     block::BlockNumber,
     crypto::utils::{Deserializable, Serializable},
     note::{
-        NoteAssets, NoteDetails, NoteInputs, NoteMetadata, NoteRecipient, NoteScript, Nullifier,
+        NoteAssets, NoteDetails, NoteId, NoteInputs, NoteMetadata, NoteRecipient, NoteScript,
+        Nullifier,
     },
 };
 use rusqlite::{Connection, Transaction, params, params_from_iter, types::Value};
+use rusqlite_migration::{HookError, HookResult};
 
 use super::{SqliteStore, chain_data::set_block_header_has_client_notes};
 use crate::{
     insert_sql,
-    note::NoteUpdateTracker,
+    note::{NoteUpdateTracker, well_known_note_target},
     store::{
-        NoteFilter, StoreError,
+        NoteFilter, NoteSort, NoteSortKey, Page, StoreError,
         note_record::{InputNoteRecord, InputNoteState, OutputNoteRecord, OutputNoteState},
     },
     subst,
+    sync::NoteTagSource,
 };
 
 // TYPES
@@ -42,6 +45,11 @@ struct SerializedInputNoteData {
     pub state_discriminant: u8,
     pub state: Vec<u8>,
     pub created_at: u64,
+    pub sender: Option<String>,
+    pub tag: Option<u32>,
+    pub consumer_account_id: Option<String>,
+    pub target_account_id: Option<String>,
+    pub block_num: Option<u32>,
 }
 
 /// Represents an `OutputNoteRecord` serialized to be stored in the database.
@@ -54,6 +62,11 @@ struct SerializedOutputNoteData {
     pub expected_height: u32,
     pub state_discriminant: u8,
     pub state: Vec<u8>,
+    pub sender: String,
+    pub tag: u32,
+    pub target_account_id: Option<String>,
+    pub script_root: Option<String>,
+    pub block_num: Option<u32>,
 }
 
 /// Represents the parts retrieved from the database to build an `InputNoteRecord`.
@@ -100,7 +113,15 @@ impl NoteFilter {
     /// of parameters to be used in it.
     fn output_notes_condition(&self) -> (String, NoteQueryParams) {
         let mut params = Vec::new();
-        let condition = match self {
+        let condition = self.output_notes_condition_with(&mut params);
+
+        (condition, params)
+    }
+
+    /// Returns a [String] containing the output notes query conditions for this Filter, and pushes
+    /// the parameters used in them to `params`.
+    fn output_notes_condition_with(&self, params: &mut NoteQueryParams) -> String {
+        match self {
             NoteFilter::All => "1 = 1".to_string(),
             NoteFilter::Committed => {
                 format!(
@@ -150,9 +171,45 @@ impl NoteFilter {
                     OutputNoteState::STATE_COMMITTED_FULL,
                 )
             },
-        };
-
-        (condition, params)
+            // There is no consumer account or creation time for output notes
+            NoteFilter::Consumer(_) | NoteFilter::CreatedBetween { .. } => "1 = 0".to_string(),
+            NoteFilter::Target(account_id) => {
+                params.push(Rc::new(vec![Value::Text(account_id.to_hex())]));
+                "note.target_account_id IN rarray(?)".to_string()
+            },
+            NoteFilter::Sender(account_id) => {
+                params.push(Rc::new(vec![Value::Text(account_id.to_hex())]));
+                "note.sender IN rarray(?)".to_string()
+            },
+            NoteFilter::Tag(tag) => {
+                params.push(Rc::new(vec![Value::Integer(tag.as_u32().into())]));
+                "note.tag IN rarray(?)".to_string()
+            },
+            NoteFilter::Faucet(faucet_id) => {
+                params.push(Rc::new(vec![Value::Text(faucet_id.prefix().to_hex())]));
+                "note.note_id IN (SELECT note_id FROM note_faucets WHERE faucet_id_prefix IN rarray(?))"
+                    .to_string()
+            },
+            NoteFilter::ScriptRoot(script_root) => {
+                params.push(Rc::new(vec![Value::Text(script_root.to_hex())]));
+                "note.script_root IN rarray(?)".to_string()
+            },
+            NoteFilter::BlockRange { from, to } => {
+                format!("note.block_num BETWEEN {} AND {}", from.as_u32(), to.as_u32())
+            },
+            NoteFilter::And(filters) => {
+                let conditions = filters
+                    .iter()
+                    .map(|filter| format!("({})", filter.output_notes_condition_with(params)))
+                    .collect::<Vec<_>>();
+
+                if conditions.is_empty() {
+                    "1 = 1".to_string()
+                } else {
+                    conditions.join(" AND ")
+                }
+            },
+        }
     }
 
     /// Returns a [String] containing the full input notes query conditions for this Filter and a
@@ -179,7 +236,15 @@ impl NoteFilter {
     /// of parameters to be used in it.
     fn input_notes_condition(&self) -> (String, NoteQueryParams) {
         let mut params = Vec::new();
-        let condition = match self {
+        let condition = self.input_notes_condition_with(&mut params);
+
+        (condition, params)
+    }
+
+    /// Returns a [String] containing the input notes query conditions for this Filter, and pushes
+    /// the parameters used in them to `params`.
+    fn input_notes_condition_with(&self, params: &mut NoteQueryParams) -> String {
+        match self {
             NoteFilter::All => "(1 = 1)".to_string(),
             NoteFilter::Committed => {
                 format!("(state_discriminant = {})", InputNoteState::STATE_COMMITTED)
@@ -238,12 +303,94 @@ impl NoteFilter {
                     InputNoteState::STATE_COMMITTED
                 )
             },
-        };
+            NoteFilter::Consumer(account_id) => {
+                params.push(Rc::new(vec![Value::Text(account_id.to_hex())]));
+                "(note.consumer_account_id IN rarray(?))".to_string()
+            },
+            NoteFilter::Target(account_id) => {
+                params.push(Rc::new(vec![Value::Text(account_id.to_hex())]));
+                "(note.target_account_id IN rarray(?))".to_string()
+            },
+            NoteFilter::Sender(account_id) => {
+                params.push(Rc::new(vec![Value::Text(account_id.to_hex())]));
+                "(note.sender IN rarray(?))".to_string()
+            },
+            NoteFilter::Tag(tag) => {
+                params.push(Rc::new(vec![Value::Integer(tag.as_u32().into())]));
+                "(note.tag IN rarray(?))".to_string()
+            },
+            NoteFilter::Faucet(faucet_id) => {
+                params.push(Rc::new(vec![Value::Text(faucet_id.prefix().to_hex())]));
+                "(note.note_id IN (SELECT note_id FROM note_faucets WHERE faucet_id_prefix IN rarray(?)))"
+                    .to_string()
+            },
+            NoteFilter::ScriptRoot(script_root) => {
+                params.push(Rc::new(vec![Value::Text(script_root.to_hex())]));
+                "(note.script_root IN rarray(?))".to_string()
+            },
+            NoteFilter::CreatedBetween { from, to } => {
+                format!("(note.created_at BETWEEN {from} AND {to})")
+            },
+            NoteFilter::BlockRange { from, to } => {
+                format!("(note.block_num BETWEEN {} AND {})", from.as_u32(), to.as_u32())
+            },
+            NoteFilter::And(filters) => {
+                let conditions = filters
+                    .iter()
+                    .map(|filter| filter.input_notes_condition_with(params))
+                    .collect::<Vec<_>>();
+
+                if conditions.is_empty() {
+                    "(1 = 1)".to_string()
+                } else {
+                    format!("({})", conditions.join(" AND "))
+                }
+            },
+        }
+    }
+}
 
-        (condition, params)
+impl NoteSort {
+    /// Returns the `ORDER BY` clause of paginated input notes queries.
+    fn input_notes_order(self) -> String {
+        let direction = self.direction();
+        match self.key() {
+            NoteSortKey::Id => format!("note.note_id {direction}"),
+            NoteSortKey::BlockNum => format!(
+                "note.block_num IS NULL {direction}, note.block_num {direction}, note.note_id {direction}"
+            ),
+            NoteSortKey::CreatedAt => {
+                format!("note.created_at {direction}, note.note_id {direction}")
+            },
+        }
+    }
+
+    /// Returns the `ORDER BY` clause of paginated output notes queries.
+    fn output_notes_order(self) -> String {
+        let direction = self.direction();
+        match self.key() {
+            // Output notes don't track their creation time
+            NoteSortKey::Id | NoteSortKey::CreatedAt => format!("note.note_id {direction}"),
+            NoteSortKey::BlockNum => format!(
+                "note.block_num IS NULL {direction}, note.block_num {direction}, note.note_id {direction}"
+            ),
+        }
+    }
+
+    fn direction(self) -> &'static str {
+        if self.is_descending() { "DESC" } else { "ASC" }
     }
 }
 
+/// Returns the `LIMIT` and `OFFSET` clauses for the page.
+fn page_clause(page: Page) -> String {
+    format!(
+        "LIMIT {} OFFSET {}",
+        i64::try_from(page.limit()).unwrap_or(i64::MAX),
+        i64::try_from(page.offset()).unwrap_or(i64::MAX)
+    )
+}
+
 // NOTES STORE METHODS
 // --------------------------------------------------------------------------------------------
 
@@ -279,6 +426,38 @@ impl SqliteStore {
         Ok(notes)
     }
 
+    /// Retrieves a page of the input notes from the database, sorted as specified by `sort`.
+    pub(crate) fn get_input_notes_page(
+        conn: &mut Connection,
+        filter: &NoteFilter,
+        sort: NoteSort,
+        page: Page,
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        let (query, params) = filter.to_query_input_notes();
+        let query = format!("{query} ORDER BY {} {}", sort.input_notes_order(), page_clause(page));
+
+        conn.prepare(&query)?
+            .query_map(params_from_iter(params), parse_input_note_columns)?
+            .map(|result| Ok(result?).and_then(parse_input_note))
+            .collect::<Result<Vec<InputNoteRecord>, _>>()
+    }
+
+    /// Retrieves a page of the output notes from the database, sorted as specified by `sort`.
+    pub(crate) fn get_output_notes_page(
+        conn: &mut Connection,
+        filter: &NoteFilter,
+        sort: NoteSort,
+        page: Page,
+    ) -> Result<Vec<OutputNoteRecord>, StoreError> {
+        let (query, params) = filter.to_query_output_notes();
+        let query = format!("{query} ORDER BY {} {}", sort.output_notes_order(), page_clause(page));
+
+        conn.prepare(&query)?
+            .query_map(params_from_iter(params), parse_output_note_columns)?
+            .map(|result| Ok(result?).and_then(parse_output_note))
+            .collect::<Result<Vec<OutputNoteRecord>, _>>()
+    }
+
     pub(crate) fn upsert_input_notes(
         conn: &mut Connection,
         notes: &[InputNoteRecord],
@@ -326,6 +505,41 @@ impl SqliteStore {
 // HELPERS
 // ================================================================================================
 
+/// Removes the input notes with the specified IDs, along with the tags that were tracked for them.
+pub(super) fn remove_input_notes_tx(
+    tx: &Transaction<'_>,
+    note_ids: &[NoteId],
+) -> Result<(), StoreError> {
+    let ids = note_ids
+        .iter()
+        .map(|note_id| Value::Text(note_id.as_word().to_string()))
+        .collect::<Vec<_>>();
+    tx.execute("DELETE FROM input_notes WHERE note_id IN rarray(?)", [Rc::new(ids)])?;
+
+    for note_id in note_ids {
+        tx.execute(
+            "DELETE FROM tags WHERE source = ?",
+            params![NoteTagSource::Note(*note_id).to_bytes()],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Removes the output notes with the specified IDs.
+pub(super) fn remove_output_notes_tx(
+    tx: &Transaction<'_>,
+    note_ids: &[NoteId],
+) -> Result<(), StoreError> {
+    let ids = note_ids
+        .iter()
+        .map(|note_id| Value::Text(note_id.as_word().to_string()))
+        .collect::<Vec<_>>();
+    tx.execute("DELETE FROM output_notes WHERE note_id IN rarray(?)", [Rc::new(ids)])?;
+
+    Ok(())
+}
+
 /// Inserts the provided input note into the database, if the note already exists, it will be
 /// replaced.
 pub(super) fn upsert_input_note_tx(
@@ -343,6 +557,11 @@ pub(super) fn upsert_input_note_tx(
         state_discriminant,
         state,
         created_at,
+        sender,
+        tag,
+        consumer_account_id,
+        target_account_id,
+        block_num,
     } = serialize_input_note(note);
 
     const SCRIPT_QUERY: &str =
@@ -360,6 +579,11 @@ pub(super) fn upsert_input_note_tx(
             state_discriminant,
             state,
             created_at,
+            sender,
+            tag,
+            consumer_account_id,
+            target_account_id,
+            block_num,
         } | REPLACE
     );
 
@@ -375,10 +599,16 @@ pub(super) fn upsert_input_note_tx(
             state_discriminant,
             state,
             created_at,
+            sender,
+            tag,
+            consumer_account_id,
+            target_account_id,
+            block_num,
         ],
     )
-    .map_err(|err| StoreError::QueryError(err.to_string()))
-    .map(|_| ())
+    .map_err(|err| StoreError::QueryError(err.to_string()))?;
+
+    insert_note_faucets(tx, &id, note.assets())
 }
 
 /// Inserts the provided input note into the database.
@@ -395,7 +625,12 @@ pub fn upsert_output_note_tx(
             nullifier,
             expected_height,
             state_discriminant,
-            state
+            state,
+            sender,
+            tag,
+            target_account_id,
+            script_root,
+            block_num,
         } | REPLACE
     );
 
@@ -408,6 +643,11 @@ pub fn upsert_output_note_tx(
         expected_height,
         state_discriminant,
         state,
+        sender,
+        tag,
+        target_account_id,
+        script_root,
+        block_num,
     } = serialize_output_note(note);
 
     tx.execute(
@@ -421,9 +661,92 @@ pub fn upsert_output_note_tx(
             expected_height,
             state_discriminant,
             state,
+            sender,
+            tag,
+            target_account_id,
+            script_root,
+            block_num,
         ],
     )?;
 
+    insert_note_faucets(tx, &id, note.assets())
+}
+
+/// Inserts the ID prefixes of the faucets that issued the assets held by the note, which are used
+/// to filter notes by faucet.
+fn insert_note_faucets(
+    tx: &Transaction<'_>,
+    note_id: &str,
+    assets: &NoteAssets,
+) -> Result<(), StoreError> {
+    const QUERY: &str = insert_sql!(note_faucets { note_id, faucet_id_prefix } | IGNORE);
+
+    for asset in assets.iter() {
+        tx.execute(QUERY, params![note_id, asset.faucet_id_prefix().to_hex()])?;
+    }
+
+    Ok(())
+}
+
+/// Fills the filterable columns of the stored notes and the faucets of their assets, which are
+/// only available in their serialized data.
+pub(super) fn migrate_note_filter_data(tx: &Transaction<'_>) -> HookResult {
+    let (query, params) = NoteFilter::All.to_query_input_notes();
+    let input_notes = tx
+        .prepare(&query)?
+        .query_map(params_from_iter(params), parse_input_note_columns)?
+        .map(|result| Ok(result?).and_then(parse_input_note))
+        .collect::<Result<Vec<InputNoteRecord>, StoreError>>()
+        .map_err(|err| HookError::Hook(err.to_string()))?;
+
+    for note in input_notes {
+        let SerializedInputNoteData {
+            id,
+            sender,
+            tag,
+            consumer_account_id,
+            target_account_id,
+            block_num,
+            ..
+        } = serialize_input_note(&note);
+
+        tx.execute(
+            "UPDATE input_notes SET sender = ?, tag = ?, consumer_account_id = ?, \
+                target_account_id = ?, block_num = ? WHERE note_id = ?",
+            params![sender, tag, consumer_account_id, target_account_id, block_num, id],
+        )?;
+        insert_note_faucets(tx, &id, note.assets())
+            .map_err(|err| HookError::Hook(err.to_string()))?;
+    }
+
+    let (query, params) = NoteFilter::All.to_query_output_notes();
+    let output_notes = tx
+        .prepare(&query)?
+        .query_map(params_from_iter(params), parse_output_note_columns)?
+        .map(|result| Ok(result?).and_then(parse_output_note))
+        .collect::<Result<Vec<OutputNoteRecord>, StoreError>>()
+        .map_err(|err| HookError::Hook(err.to_string()))?;
+
+    for note in output_notes {
+        let SerializedOutputNoteData {
+            id,
+            sender,
+            tag,
+            target_account_id,
+            script_root,
+            block_num,
+            ..
+        } = serialize_output_note(&note);
+
+        tx.execute(
+            "UPDATE output_notes SET sender = ?, tag = ?, target_account_id = ?, \
+                script_root = ?, block_num = ? WHERE note_id = ?",
+            params![sender, tag, target_account_id, script_root, block_num, id],
+        )?;
+        insert_note_faucets(tx, &id, note.assets())
+            .map_err(|err| HookError::Hook(err.to_string()))?;
+    }
+
     Ok(())
 }
 
@@ -494,6 +817,12 @@ fn serialize_input_note(note: &InputNoteRecord) -> SerializedInputNoteData {
     let state_discriminant = note.state().discriminant();
     let state = note.state().to_bytes();
 
+    let sender = note.metadata().map(|metadata| metadata.sender().to_hex());
+    let tag = note.metadata().map(|metadata| metadata.tag().as_u32());
+    let consumer_account_id = note.consumer_account().map(|account_id| account_id.to_hex());
+    let target_account_id = well_known_note_target(recipient).map(|account_id| account_id.to_hex());
+    let block_num = note.inclusion_proof().map(|proof| proof.location().block_num().as_u32());
+
     SerializedInputNoteData {
         id,
         assets,
@@ -505,6 +834,11 @@ fn serialize_input_note(note: &InputNoteRecord) -> SerializedInputNoteData {
         state_discriminant,
         state,
         created_at,
+        sender,
+        tag,
+        consumer_account_id,
+        target_account_id,
+        block_num,
     }
 }
 
@@ -565,6 +899,13 @@ fn serialize_output_note(note: &OutputNoteRecord) -> SerializedOutputNoteData {
     let state_discriminant = note.state().discriminant();
     let state = note.state().to_bytes();
 
+    let target_account_id = note
+        .recipient()
+        .and_then(well_known_note_target)
+        .map(|account_id| account_id.to_hex());
+    let script_root = note.recipient().map(|recipient| recipient.script().root().to_hex());
+    let block_num = note.inclusion_proof().map(|proof| proof.location().block_num().as_u32());
+
     SerializedOutputNoteData {
         id,
         assets,
@@ -574,6 +915,11 @@ fn serialize_output_note(note: &OutputNoteRecord) -> SerializedOutputNoteData {
         expected_height: note.expected_height().as_u32(),
         state_discriminant,
         state,
+        sender: note.metadata().sender().to_hex(),
+        tag: note.metadata().tag().as_u32(),
+        target_account_id,
+        script_root,
+        block_num,
     }
 }
 
@@ -591,3 +937,149 @@ pub(crate) fn apply_note_updates_tx(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use miden_lib::note::{create_p2id_note, well_known_note::WellKnownNote};
+    use miden_objects::{
+        Felt, Word,
+        account::AccountId,
+        asset::FungibleAsset,
+        block::BlockNumber,
+        crypto::rand::RpoRandomCoin,
+        note::{Note, NoteId, NoteType},
+        testing::account_id::{
+            ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+            ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        },
+    };
+
+    use super::upsert_output_note_tx;
+    use crate::store::{
+        InputNoteRecord, NoteFilter, NoteSort, NoteSortKey, OutputNoteRecord, Page, Store,
+        sqlite_store::tests::create_test_store,
+    };
+
+    fn p2id_note(
+        sender: AccountId,
+        target: AccountId,
+        faucet_id: AccountId,
+        rng: &mut RpoRandomCoin,
+    ) -> Note {
+        let asset = FungibleAsset::new(faucet_id, 10).unwrap();
+        create_p2id_note(sender, target, vec![asset.into()], NoteType::Private, Felt::ZERO, rng)
+            .unwrap()
+    }
+
+    fn sorted_ids(mut note_ids: Vec<NoteId>) -> Vec<NoteId> {
+        note_ids.sort_by_key(NoteId::to_hex);
+        note_ids
+    }
+
+    #[tokio::test]
+    async fn note_filters_sorting_and_pagination() {
+        let store = create_test_store().await;
+        let account_a =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE).unwrap();
+        let account_b =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let faucet_a = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+        let faucet_b = AccountId::try_from(ACCOUNT_ID_PRIVATE_FUNGIBLE_FAUCET).unwrap();
+
+        let mut rng = RpoRandomCoin::new(Word::default());
+        let note_a = p2id_note(account_a, account_b, faucet_a, &mut rng);
+        let note_b = p2id_note(account_b, account_a, faucet_b, &mut rng);
+
+        let input_notes = [(&note_a, 100), (&note_b, 200)]
+            .into_iter()
+            .map(|(note, created_at)| {
+                let record = InputNoteRecord::from(note.clone());
+                InputNoteRecord::new(note.clone().into(), Some(created_at), record.state().clone())
+            })
+            .collect::<Vec<_>>();
+        store.upsert_input_notes(&input_notes).await.unwrap();
+
+        let output_notes = [&note_a, &note_b]
+            .map(|note| OutputNoteRecord::from_full_note(note.clone(), BlockNumber::from(0)));
+        store
+            .interact_with_connection(move |conn| {
+                let tx = conn.transaction()?;
+                for note in &output_notes {
+                    upsert_output_note_tx(&tx, note)?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let query = |filter: NoteFilter| {
+            let store = &store;
+            async move {
+                let input_notes = store
+                    .get_input_notes_page(filter.clone(), NoteSort::default(), Page::first(10))
+                    .await
+                    .unwrap()
+                    .iter()
+                    .map(InputNoteRecord::id)
+                    .collect::<Vec<_>>();
+                let output_notes = store
+                    .get_output_notes_page(filter, NoteSort::default(), Page::first(10))
+                    .await
+                    .unwrap()
+                    .iter()
+                    .map(OutputNoteRecord::id)
+                    .collect::<Vec<_>>();
+
+                (input_notes, output_notes)
+            }
+        };
+
+        let only_a = vec![note_a.id()];
+        let both = sorted_ids(vec![note_a.id(), note_b.id()]);
+
+        assert_eq!(query(NoteFilter::Sender(account_a)).await, (only_a.clone(), only_a.clone()));
+        assert_eq!(query(NoteFilter::Target(account_b)).await, (only_a.clone(), only_a.clone()));
+        assert_eq!(query(NoteFilter::Faucet(faucet_a)).await, (only_a.clone(), only_a.clone()));
+        assert_eq!(
+            query(NoteFilter::ScriptRoot(WellKnownNote::P2ID.script_root())).await,
+            (both.clone(), both.clone())
+        );
+        assert_eq!(
+            query(NoteFilter::CreatedBetween { from: 50, to: 150 }).await,
+            (only_a.clone(), vec![])
+        );
+        assert_eq!(query(NoteFilter::Consumer(account_a)).await, (vec![], vec![]));
+        assert_eq!(
+            query(NoteFilter::BlockRange { from: 0.into(), to: 10.into() }).await,
+            (vec![], vec![])
+        );
+        assert_eq!(
+            query(NoteFilter::And(vec![
+                NoteFilter::Tag(note_a.metadata().tag()),
+                NoteFilter::Faucet(faucet_a),
+            ]))
+            .await,
+            (only_a.clone(), only_a.clone())
+        );
+        assert_eq!(
+            query(NoteFilter::And(vec![
+                NoteFilter::Sender(account_a),
+                NoteFilter::Faucet(faucet_b)
+            ]))
+            .await,
+            (vec![], vec![])
+        );
+
+        let sort = NoteSort::descending(NoteSortKey::CreatedAt);
+        let page = Page::first(1);
+        let first_page = store.get_input_notes_page(NoteFilter::All, sort, page).await.unwrap();
+        let second_page = store
+            .get_input_notes_page(NoteFilter::All, sort, page.next_page())
+            .await
+            .unwrap();
+        assert_eq!(first_page.iter().map(InputNoteRecord::id).collect::<Vec<_>>(), [note_b.id()]);
+        assert_eq!(second_page.iter().map(InputNoteRecord::id).collect::<Vec<_>>(), [note_a.id()]);
+    }
+}
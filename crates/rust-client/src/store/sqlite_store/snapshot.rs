@@ -0,0 +1,203 @@
+#![allow(clippy::items_after_statements)]
+
+use rusqlite::{Connection, params};
+
+use super::{
+    SqliteStore,
+    account::{insert_account_code, insert_account_state, update_account},
+    note::{upsert_input_note_tx, upsert_output_note_tx},
+    sync::add_note_tag_tx,
+    transaction::upsert_transaction_record,
+};
+use crate::store::{StoreError, StoreSnapshot};
+
+impl SqliteStore {
+    /// Inserts the data of the snapshot in a single transaction.
+    pub(crate) fn import_snapshot(
+        conn: &mut Connection,
+        snapshot: &StoreSnapshot,
+    ) -> Result<(), StoreError> {
+        let tx = conn.transaction()?;
+
+        for account in snapshot.accounts() {
+            let Some((first_state, next_states)) = account.states().split_first() else {
+                return Err(StoreError::InvalidSnapshot("account without states".into()));
+            };
+
+            insert_account_code(&tx, first_state.code())?;
+            insert_account_state(&tx, first_state, account.seed())?;
+
+            for state in next_states {
+                update_account(&tx, state)?;
+            }
+
+            if account.is_locked() {
+                const LOCK_QUERY: &str = "UPDATE accounts SET locked = true WHERE id = ?";
+                tx.execute(LOCK_QUERY, params![account.id().to_hex()])?;
+            }
+        }
+
+        for note in snapshot.input_notes() {
+            upsert_input_note_tx(&tx, note)?;
+        }
+
+        for note in snapshot.output_notes() {
+            upsert_output_note_tx(&tx, note)?;
+        }
+
+        for transaction in snapshot.transactions() {
+            upsert_transaction_record(&tx, transaction)?;
+        }
+
+        for tag in snapshot.note_tags() {
+            add_note_tag_tx(&tx, tag)?;
+        }
+
+        for block in snapshot.block_headers() {
+            Self::insert_block_header_tx(
+                &tx,
+                block.header(),
+                &block.partial_blockchain_peaks()?,
+                block.has_client_notes(),
+            )?;
+        }
+
+        Self::insert_partial_blockchain_nodes_tx(&tx, snapshot.partial_blockchain_nodes())?;
+
+        const BLOCK_NUMBER_QUERY: &str = "UPDATE state_sync SET block_num = ?";
+        tx.execute(BLOCK_NUMBER_QUERY, params![i64::from(snapshot.sync_height().as_u32())])?;
+
+        Ok(tx.commit()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_lib::{note::create_p2id_note, transaction::TransactionKernel};
+    use miden_objects::{
+        Felt, Word,
+        account::{AccountDelta, AccountId, AccountStorageDelta, AccountVaultDelta},
+        asset::FungibleAsset,
+        block::BlockHeader,
+        crypto::{
+            merkle::{InOrderIndex, Mmr},
+            rand::RpoRandomCoin,
+        },
+        note::{NoteTag, NoteType},
+        testing::account_id::ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
+        transaction::{OutputNotes, TransactionId},
+        utils::{Deserializable, Serializable},
+    };
+
+    use super::SqliteStore;
+    use crate::{
+        store::{
+            InputNoteRecord, OutputNoteRecord, Store, StoreSnapshot,
+            sqlite_store::{
+                note::upsert_output_note_tx,
+                tests::{create_test_account, create_test_store},
+                transaction::upsert_transaction_record,
+            },
+        },
+        sync::NoteTagRecord,
+        transaction::{TransactionDetails, TransactionRecord, TransactionStatus},
+    };
+
+    #[tokio::test]
+    async fn snapshots_are_restored() {
+        let store = create_test_store().await;
+
+        // Two states of an account
+        let account = create_test_account();
+        let delta = AccountDelta::new(
+            account.id(),
+            AccountStorageDelta::default(),
+            AccountVaultDelta::default(),
+            Felt::new(1),
+        )
+        .unwrap();
+        let mut updated_account = account.clone();
+        updated_account.apply_delta(&delta).unwrap();
+        store.insert_account(&account, None).await.unwrap();
+        store.update_account(&updated_account).await.unwrap();
+
+        // A note received and sent by the account, a transaction and a tag
+        let faucet_id = AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap();
+        let note = create_p2id_note(
+            account.id(),
+            account.id(),
+            vec![FungibleAsset::new(faucet_id, 10).unwrap().into()],
+            NoteType::Private,
+            Felt::ZERO,
+            &mut RpoRandomCoin::new(Word::default()),
+        )
+        .unwrap();
+        store.upsert_input_notes(&[InputNoteRecord::from(note.clone())]).await.unwrap();
+        store
+            .add_note_tag(NoteTagRecord::with_account_source(NoteTag::from(1), account.id()))
+            .await
+            .unwrap();
+
+        let output_note = OutputNoteRecord::from_full_note(note, 1.into());
+        let transaction = TransactionRecord::new(
+            TransactionId::from(Word::from([Felt::new(1); 4])),
+            TransactionDetails {
+                account_id: account.id(),
+                init_account_state: account.commitment(),
+                final_account_state: updated_account.commitment(),
+                input_note_nullifiers: vec![],
+                output_notes: OutputNotes::new(vec![]).unwrap(),
+                block_num: 1.into(),
+                submission_height: 1.into(),
+                expiration_block_num: 10.into(),
+            },
+            None,
+            TransactionStatus::Committed(1.into()),
+        );
+
+        // The genesis block and the block the store is synced to
+        let genesis = BlockHeader::mock(0, None, None, &[], TransactionKernel::kernel_commitment());
+        let block = BlockHeader::mock(1, None, None, &[], TransactionKernel::kernel_commitment());
+        let mut mmr = Mmr::new();
+        mmr.add(genesis.commitment());
+        let nodes = vec![(InOrderIndex::from_leaf_pos(0), genesis.commitment())];
+
+        store
+            .interact_with_connection(move |conn| {
+                let tx = conn.transaction()?;
+                upsert_output_note_tx(&tx, &output_note)?;
+                upsert_transaction_record(&tx, &transaction)?;
+                SqliteStore::insert_block_header_tx(&tx, &genesis, &Mmr::new().peaks(), false)?;
+                SqliteStore::insert_block_header_tx(&tx, &block, &mmr.peaks(), true)?;
+                tx.execute("UPDATE state_sync SET block_num = 1", [])?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+        store.insert_partial_blockchain_nodes(&nodes).await.unwrap();
+
+        let snapshot = store.export_snapshot().await.unwrap();
+        snapshot.validate().unwrap();
+        let bytes = snapshot.to_bytes();
+
+        // Corrupted snapshots are rejected
+        let mut corrupted = bytes.clone();
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 1;
+        assert!(StoreSnapshot::read_from_bytes(&corrupted).is_err());
+
+        let restored_store = create_test_store().await;
+        restored_store
+            .import_snapshot(&StoreSnapshot::read_from_bytes(&bytes).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(restored_store.export_snapshot().await.unwrap().to_bytes(), bytes);
+        assert_eq!(restored_store.get_sync_height().await.unwrap(), 1.into());
+        let restored_account = restored_store.get_account(account.id()).await.unwrap().unwrap();
+        assert_eq!(restored_account.account().commitment(), updated_account.commitment());
+        assert_eq!(restored_store.get_account_history(account.id()).await.unwrap().len(), 2);
+        assert_eq!(restored_store.get_tracked_block_headers().await.unwrap(), vec![block]);
+    }
+}
@@ -10,26 +10,28 @@ use miden_objects::{
     Word,
     block::BlockNumber,
     crypto::utils::{Deserializable, Serializable},
-    transaction::{ToInputNoteCommitments, TransactionScript},
+    transaction::{ToInputNoteCommitments, TransactionId, TransactionScript},
 };
-use rusqlite::{Connection, Transaction, params, types::Value};
+use rusqlite::{Connection, Transaction, params, params_from_iter, types::Value};
+use rusqlite_migration::{HookError, HookResult};
 
 use super::{
     SqliteStore, account::update_account, note::apply_note_updates_tx, sync::add_note_tag_tx,
 };
 use crate::{
     insert_sql,
-    store::{StoreError, TransactionFilter},
+    store::{Page, StoreError, TransactionFilter},
     subst,
     transaction::{
-        DiscardCause, TransactionDetails, TransactionRecord, TransactionStatus,
-        TransactionStoreUpdate,
+        DiscardCause, PendingTransactionRecord, PendingTransactionStage, TransactionDetails,
+        TransactionRecord, TransactionResult, TransactionStatus, TransactionStoreUpdate,
     },
 };
 
 pub(crate) const UPSERT_TRANSACTION_QUERY: &str = insert_sql!(
     transactions {
         id,
+        account_id,
         details,
         script_root,
         block_num,
@@ -38,30 +40,91 @@ pub(crate) const UPSERT_TRANSACTION_QUERY: &str = insert_sql!(
     } | REPLACE
 );
 
+const INSERT_TRANSACTION_INPUT_NOTE_QUERY: &str =
+    insert_sql!(transaction_input_notes { transaction_id, nullifier } | IGNORE);
+
+const INSERT_TRANSACTION_OUTPUT_NOTE_QUERY: &str =
+    insert_sql!(transaction_output_notes { transaction_id, note_id } | IGNORE);
+
 pub(crate) const INSERT_TRANSACTION_SCRIPT_QUERY: &str =
     insert_sql!(transaction_scripts { script_root, script } | IGNORE);
 
+const UPSERT_PENDING_TRANSACTION_QUERY: &str =
+    insert_sql!(pending_transactions { id, tx_result, stage } | REPLACE);
+
 // TRANSACTIONS FILTERS
 // ================================================================================================
 
+type TransactionQueryParams = Vec<Rc<Vec<Value>>>;
 impl TransactionFilter {
-    /// Returns a [String] containing the query for this Filter.
-    pub fn to_query(&self) -> String {
+    /// Returns a [String] containing the query for this Filter and a vector of parameters to be
+    /// used in it.
+    fn to_query(&self) -> (String, TransactionQueryParams) {
         const QUERY: &str = "SELECT tx.id, script.script, tx.details, tx.commit_height, tx.discard_cause \
             FROM transactions AS tx LEFT JOIN transaction_scripts AS script ON tx.script_root = script.script_root";
+
+        let mut params = Vec::new();
+        let condition = self.condition(&mut params);
+
+        (format!("{QUERY} WHERE {condition}"), params)
+    }
+
+    /// Returns a [String] containing the query conditions for this Filter, and pushes the
+    /// parameters used in them to `params`. Every parameter is bound with SQLite's array
+    /// parameter binding.
+    fn condition(&self, params: &mut TransactionQueryParams) -> String {
         match self {
-            TransactionFilter::All => QUERY.to_string(),
-            TransactionFilter::Uncommitted => format!("{QUERY} WHERE tx.commit_height IS NULL"),
-            TransactionFilter::Ids(_) => {
-                // Use SQLite's array parameter binding
-                format!("{QUERY} WHERE tx.id IN rarray(?)")
+            TransactionFilter::All => "(1 = 1)".to_string(),
+            TransactionFilter::Uncommitted => "(tx.commit_height IS NULL)".to_string(),
+            TransactionFilter::Ids(ids) => {
+                params.push(Rc::new(ids.iter().map(|id| Value::Text(id.to_string())).collect()));
+                "(tx.id IN rarray(?))".to_string()
             },
             TransactionFilter::ExpiredBefore(block_num) => {
                 format!(
-                    "{QUERY} WHERE tx.block_num < {} AND tx.discard_cause IS NULL AND tx.commit_height IS NULL",
+                    "(tx.block_num < {} AND tx.discard_cause IS NULL AND tx.commit_height IS NULL)",
                     block_num.as_u32()
                 )
             },
+            TransactionFilter::InFlight => {
+                "(tx.commit_height IS NULL AND tx.discard_cause IS NULL)".to_string()
+            },
+            TransactionFilter::Committed => "(tx.commit_height IS NOT NULL)".to_string(),
+            TransactionFilter::Discarded(None) => "(tx.discard_cause IS NOT NULL)".to_string(),
+            TransactionFilter::Discarded(Some(cause)) => {
+                params.push(Rc::new(vec![Value::Blob(cause.to_bytes())]));
+                "(tx.discard_cause IN rarray(?))".to_string()
+            },
+            TransactionFilter::Account(account_id) => {
+                params.push(Rc::new(vec![Value::Text(account_id.to_hex())]));
+                "(tx.account_id IN rarray(?))".to_string()
+            },
+            TransactionFilter::BlockRange { from, to } => {
+                format!("(tx.block_num BETWEEN {} AND {})", from.as_u32(), to.as_u32())
+            },
+            TransactionFilter::Note(note_id) => {
+                let note_ids = Rc::new(vec![Value::Text(note_id.as_word().to_string())]);
+                params.push(note_ids.clone());
+                params.push(note_ids);
+                "(tx.id IN (SELECT transaction_id FROM transaction_output_notes WHERE note_id IN rarray(?)) \
+                    OR tx.id IN (SELECT transaction_id FROM transaction_input_notes WHERE nullifier IN \
+                    (SELECT nullifier FROM input_notes WHERE note_id IN rarray(?))))"
+                    .to_string()
+            },
+            TransactionFilter::ScriptRoot(script_root) => {
+                params.push(Rc::new(vec![Value::Blob(script_root.to_bytes())]));
+                "(tx.script_root IN rarray(?))".to_string()
+            },
+            TransactionFilter::And(filters) => {
+                let conditions =
+                    filters.iter().map(|filter| filter.condition(params)).collect::<Vec<_>>();
+
+                if conditions.is_empty() {
+                    "(1 = 1)".to_string()
+                } else {
+                    format!("({})", conditions.join(" AND "))
+                }
+            },
         }
     }
 }
@@ -72,6 +135,8 @@ impl TransactionFilter {
 struct SerializedTransactionData {
     /// Transaction ID
     id: String,
+    /// ID of the account that executed the transaction
+    account_id: String,
     /// Script root
     script_root: Option<Vec<u8>>,
     /// Transaction script
@@ -105,26 +170,32 @@ impl SqliteStore {
         conn: &mut Connection,
         filter: &TransactionFilter,
     ) -> Result<Vec<TransactionRecord>, StoreError> {
-        match filter {
-            TransactionFilter::Ids(ids) => {
-                // Convert transaction IDs to strings for the array parameter
-                let id_strings =
-                    ids.iter().map(|id| Value::Text(id.to_string())).collect::<Vec<_>>();
-
-                // Create a prepared statement and bind the array parameter
-                conn.prepare(&filter.to_query())?
-                    .query_map(params![Rc::new(id_strings)], parse_transaction_columns)?
-                    .map(|result| Ok(result?).and_then(parse_transaction))
-                    .collect::<Result<Vec<TransactionRecord>, _>>()
-            },
-            _ => {
-                // For other filters, no parameters are needed
-                conn.prepare(&filter.to_query())?
-                    .query_map([], parse_transaction_columns)?
-                    .map(|result| Ok(result?).and_then(parse_transaction))
-                    .collect::<Result<Vec<TransactionRecord>, _>>()
-            },
-        }
+        let (query, params) = filter.to_query();
+
+        conn.prepare(&query)?
+            .query_map(params_from_iter(params), parse_transaction_columns)?
+            .map(|result| Ok(result?).and_then(parse_transaction))
+            .collect::<Result<Vec<TransactionRecord>, _>>()
+    }
+
+    /// Retrieves a page of the tracked transactions, filtered by [`TransactionFilter`] and sorted
+    /// by execution block number and ID.
+    pub fn get_transactions_page(
+        conn: &mut Connection,
+        filter: &TransactionFilter,
+        page: Page,
+    ) -> Result<Vec<TransactionRecord>, StoreError> {
+        let (query, params) = filter.to_query();
+        let query = format!(
+            "{query} ORDER BY tx.block_num, tx.id LIMIT {} OFFSET {}",
+            i64::try_from(page.limit()).unwrap_or(i64::MAX),
+            i64::try_from(page.offset()).unwrap_or(i64::MAX)
+        );
+
+        conn.prepare(&query)?
+            .query_map(params_from_iter(params), parse_transaction_columns)?
+            .map(|result| Ok(result?).and_then(parse_transaction))
+            .collect::<Result<Vec<TransactionRecord>, _>>()
     }
 
     /// Inserts a transaction and updates the current state based on the `tx_result` changes.
@@ -176,10 +247,96 @@ impl SqliteStore {
             add_note_tag_tx(&tx, tag_record)?;
         }
 
+        // The transaction is no longer pending
+        tx.execute(
+            "DELETE FROM pending_transactions WHERE id = ?",
+            params![executed_transaction.id().to_hex()],
+        )?;
+
         tx.commit()?;
 
         Ok(())
     }
+
+    // PENDING TRANSACTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Retrieves all the pending transactions.
+    pub fn get_pending_transactions(
+        conn: &mut Connection,
+    ) -> Result<Vec<PendingTransactionRecord>, StoreError> {
+        conn.prepare("SELECT tx_result, stage FROM pending_transactions")?
+            .query_map([], parse_pending_transaction_columns)?
+            .map(|result| Ok(result?).and_then(parse_pending_transaction))
+            .collect()
+    }
+
+    /// Retrieves the pending transaction with the specified ID, if any.
+    pub fn get_pending_transaction(
+        conn: &mut Connection,
+        tx_id: TransactionId,
+    ) -> Result<Option<PendingTransactionRecord>, StoreError> {
+        conn.prepare("SELECT tx_result, stage FROM pending_transactions WHERE id = ?")?
+            .query_map(params![tx_id.to_hex()], parse_pending_transaction_columns)?
+            .map(|result| Ok(result?).and_then(parse_pending_transaction))
+            .next()
+            .transpose()
+    }
+
+    /// Inserts a pending transaction, replacing the existing one with the same ID.
+    pub fn upsert_pending_transaction(
+        conn: &mut Connection,
+        record: &PendingTransactionRecord,
+    ) -> Result<(), StoreError> {
+        conn.execute(
+            UPSERT_PENDING_TRANSACTION_QUERY,
+            params![record.id().to_hex(), record.tx_result().to_bytes(), record.stage().to_bytes()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Removes the pending transaction with the specified ID. Returns `true` if it existed.
+    pub fn remove_pending_transaction(
+        conn: &mut Connection,
+        tx_id: TransactionId,
+    ) -> Result<bool, StoreError> {
+        let removed =
+            conn.execute("DELETE FROM pending_transactions WHERE id = ?", params![tx_id.to_hex()])?;
+
+        Ok(removed > 0)
+    }
+}
+
+/// Removes the transactions with the specified IDs, along with the links to their notes.
+pub(super) fn remove_transactions_tx(
+    tx: &Transaction<'_>,
+    tx_ids: &[TransactionId],
+) -> Result<(), StoreError> {
+    let ids = Rc::new(tx_ids.iter().map(|id| Value::Text(id.to_hex())).collect::<Vec<_>>());
+
+    tx.execute("DELETE FROM transactions WHERE id IN rarray(?)", params![ids.clone()])?;
+    tx.execute(
+        "DELETE FROM transaction_input_notes WHERE transaction_id IN rarray(?)",
+        params![ids.clone()],
+    )?;
+    tx.execute(
+        "DELETE FROM transaction_output_notes WHERE transaction_id IN rarray(?)",
+        params![ids],
+    )?;
+
+    Ok(())
+}
+
+/// Removes the pending transactions with the specified IDs.
+pub(super) fn remove_pending_transactions_tx(
+    tx: &Transaction<'_>,
+    tx_ids: &[TransactionId],
+) -> Result<(), StoreError> {
+    let ids = tx_ids.iter().map(|id| Value::Text(id.to_hex())).collect::<Vec<_>>();
+    tx.execute("DELETE FROM pending_transactions WHERE id IN rarray(?)", [Rc::new(ids)])?;
+
+    Ok(())
 }
 
 /// Updates the transaction record in the database, inserting it if it doesn't exist.
@@ -189,6 +346,7 @@ pub(crate) fn upsert_transaction_record(
 ) -> Result<(), StoreError> {
     let SerializedTransactionData {
         id,
+        account_id,
         script_root,
         tx_script,
         details,
@@ -203,9 +361,56 @@ pub(crate) fn upsert_transaction_record(
 
     tx.execute(
         UPSERT_TRANSACTION_QUERY,
-        params![id, details, script_root, block_num, commit_height, discard_cause],
+        params![id, account_id, details, script_root, block_num, commit_height, discard_cause],
     )?;
 
+    insert_transaction_notes(tx, &id, &transaction.details)
+}
+
+/// Inserts the nullifiers of the notes consumed by the transaction and the IDs of the notes it
+/// created, which are used to filter transactions by note.
+fn insert_transaction_notes(
+    tx: &Transaction<'_>,
+    transaction_id: &str,
+    details: &TransactionDetails,
+) -> Result<(), StoreError> {
+    for nullifier in &details.input_note_nullifiers {
+        tx.execute(
+            INSERT_TRANSACTION_INPUT_NOTE_QUERY,
+            params![transaction_id, nullifier.to_hex()],
+        )?;
+    }
+
+    for note in details.output_notes.iter() {
+        tx.execute(
+            INSERT_TRANSACTION_OUTPUT_NOTE_QUERY,
+            params![transaction_id, note.id().as_word().to_string()],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Fills the account ID of the stored transactions and the notes they consumed or created, which
+/// are only available in their serialized details.
+pub(super) fn migrate_transaction_filter_data(tx: &Transaction<'_>) -> HookResult {
+    let transactions = tx
+        .prepare("SELECT id, details FROM transactions")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<(String, Vec<u8>)>, _>>()?;
+
+    for (id, details) in transactions {
+        let details = TransactionDetails::read_from_bytes(&details)
+            .map_err(|err| HookError::Hook(err.to_string()))?;
+
+        tx.execute(
+            "UPDATE transactions SET account_id = ? WHERE id = ?",
+            params![details.account_id.to_hex(), id],
+        )?;
+        insert_transaction_notes(tx, &id, &details)
+            .map_err(|err| HookError::Hook(err.to_string()))?;
+    }
+
     Ok(())
 }
 
@@ -224,6 +429,7 @@ fn serialize_transaction_data(transaction_record: &TransactionRecord) -> Seriali
 
     SerializedTransactionData {
         id: transaction_id,
+        account_id: transaction_record.details.account_id.to_hex(),
         script_root,
         tx_script,
         details: transaction_record.details.to_bytes(),
@@ -284,3 +490,157 @@ fn parse_transaction(
         status,
     })
 }
+
+fn parse_pending_transaction_columns(
+    row: &rusqlite::Row<'_>,
+) -> Result<(Vec<u8>, Vec<u8>), rusqlite::Error> {
+    let tx_result: Vec<u8> = row.get(0)?;
+    let stage: Vec<u8> = row.get(1)?;
+
+    Ok((tx_result, stage))
+}
+
+/// Parse a pending transaction from the provided parts.
+fn parse_pending_transaction(
+    (tx_result, stage): (Vec<u8>, Vec<u8>),
+) -> Result<PendingTransactionRecord, StoreError> {
+    Ok(PendingTransactionRecord::new(
+        TransactionResult::read_from_bytes(&tx_result)?,
+        PendingTransactionStage::read_from_bytes(&stage)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::{
+        Felt, Word,
+        account::AccountId,
+        block::BlockNumber,
+        note::{NoteExecutionHint, NoteHeader, NoteId, NoteMetadata, NoteTag, NoteType},
+        testing::account_id::{
+            ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        },
+        transaction::{OutputNote, OutputNotes, TransactionId},
+    };
+
+    use super::upsert_transaction_record;
+    use crate::{
+        store::{Page, Store, TransactionFilter, sqlite_store::tests::create_test_store},
+        transaction::{DiscardCause, TransactionDetails, TransactionRecord, TransactionStatus},
+    };
+
+    fn transaction_record(
+        index: u32,
+        account_id: AccountId,
+        output_notes: Vec<OutputNote>,
+        status: TransactionStatus,
+    ) -> TransactionRecord {
+        let details = TransactionDetails {
+            account_id,
+            init_account_state: Word::default(),
+            final_account_state: Word::default(),
+            input_note_nullifiers: vec![],
+            output_notes: OutputNotes::new(output_notes).unwrap(),
+            block_num: BlockNumber::from(index),
+            submission_height: BlockNumber::from(index),
+            expiration_block_num: BlockNumber::from(index + 10),
+        };
+
+        TransactionRecord::new(
+            TransactionId::from(Word::from([Felt::from(index); 4])),
+            details,
+            None,
+            status,
+        )
+    }
+
+    #[tokio::test]
+    async fn transaction_filters_and_pagination() {
+        let store = create_test_store().await;
+        let account_a =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE).unwrap();
+        let account_b =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+
+        let note_id = NoteId::new(Word::from([Felt::new(7); 4]), Word::default());
+        let metadata = NoteMetadata::new(
+            account_a,
+            NoteType::Private,
+            NoteTag::from(0),
+            NoteExecutionHint::None,
+            Felt::default(),
+        )
+        .unwrap();
+        let output_note = OutputNote::Header(NoteHeader::new(note_id, metadata));
+
+        let pending =
+            transaction_record(1, account_a, vec![output_note], TransactionStatus::Pending);
+        let committed =
+            transaction_record(2, account_a, vec![], TransactionStatus::Committed(5.into()));
+        let discarded = transaction_record(
+            3,
+            account_b,
+            vec![],
+            TransactionStatus::Discarded(DiscardCause::Expired),
+        );
+        let ids = [pending.id, committed.id, discarded.id];
+
+        store
+            .interact_with_connection(move |conn| {
+                let tx = conn.transaction()?;
+                for record in [pending, committed, discarded] {
+                    upsert_transaction_record(&tx, &record)?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        let query = |filter: TransactionFilter| {
+            let store = &store;
+            async move {
+                store
+                    .get_transactions_page(filter, Page::first(usize::MAX))
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|record| record.id)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(query(TransactionFilter::Account(account_a)).await, ids[..2]);
+        assert_eq!(query(TransactionFilter::InFlight).await, ids[..1]);
+        assert_eq!(query(TransactionFilter::Committed).await, ids[1..2]);
+        assert_eq!(query(TransactionFilter::Discarded(None)).await, ids[2..]);
+        assert_eq!(
+            query(TransactionFilter::Discarded(Some(DiscardCause::Expired))).await,
+            ids[2..]
+        );
+        assert!(query(TransactionFilter::Discarded(Some(DiscardCause::Stale))).await.is_empty());
+        assert_eq!(query(TransactionFilter::Note(note_id)).await, ids[..1]);
+        assert_eq!(
+            query(TransactionFilter::BlockRange { from: 2.into(), to: 3.into() }).await,
+            ids[1..]
+        );
+        assert_eq!(
+            query(TransactionFilter::And(vec![
+                TransactionFilter::Account(account_a),
+                TransactionFilter::BlockRange { from: 2.into(), to: 3.into() },
+            ]))
+            .await,
+            ids[1..2]
+        );
+
+        let page = Page::first(2);
+        let first_page = store.get_transactions_page(TransactionFilter::All, page).await.unwrap();
+        let second_page = store
+            .get_transactions_page(TransactionFilter::All, page.next_page())
+            .await
+            .unwrap();
+        assert_eq!(first_page.iter().map(|record| record.id).collect::<Vec<_>>(), ids[..2]);
+        assert_eq!(second_page.iter().map(|record| record.id).collect::<Vec<_>>(), ids[2..]);
+    }
+}
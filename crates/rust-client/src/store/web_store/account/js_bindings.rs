@@ -20,6 +20,9 @@ extern "C" {
     #[wasm_bindgen(js_name = getAccountHeaderByCommitment)]
     pub fn idxdb_get_account_header_by_commitment(account_commitment: String) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = getAccountHistory)]
+    pub fn idxdb_get_account_history(account_id: String) -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = getAccountCode)]
     pub fn idxdb_get_account_code(code_root: String) -> js_sys::Promise;
 
@@ -32,6 +35,9 @@ extern "C" {
     #[wasm_bindgen(js_name = getAccountAuthByPubKey)]
     pub fn idxdb_get_account_auth_by_pub_key(pub_key: String) -> JsValue;
 
+    #[wasm_bindgen(js_name = getAllAccountAuthSecretKeys)]
+    pub fn idxdb_get_all_account_auth_secret_keys() -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = fetchAndCacheAccountAuthByPubKey)]
     pub fn idxdb_fetch_and_cache_account_auth_by_pub_key(pub_key: String) -> js_sys::Promise;
 
@@ -85,6 +91,18 @@ extern "C" {
     // DELETES
     // ================================================================================================
 
+    #[wasm_bindgen(js_name = removeAccountAuth)]
+    pub fn idxdb_remove_account_auth(pub_key: String) -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = undoAccountStates)]
     pub fn idxdb_undo_account_states(account_hashes: Vec<String>) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = removeAccount)]
+    pub fn idxdb_remove_account(
+        account_id: String,
+        pending_transaction_ids: Vec<String>,
+        transaction_ids: Vec<String>,
+        input_note_ids: Vec<String>,
+        output_note_ids: Vec<String>,
+    ) -> js_sys::Promise;
 }
@@ -5,7 +5,7 @@ use alloc::{
 };
 
 use miden_objects::{
-    AccountIdError, Word,
+    AccountIdError, Felt, Word,
     account::{Account, AccountCode, AccountHeader, AccountId, AccountStorage},
     asset::{Asset, AssetVault},
 };
@@ -14,15 +14,18 @@ use serde_wasm_bindgen::from_value;
 use wasm_bindgen_futures::JsFuture;
 
 use super::WebStore;
-use crate::store::{AccountRecord, AccountStatus, StoreError};
+use crate::{
+    account::AccountRemoval,
+    store::{AccountRecord, AccountStatus, StoreError},
+};
 
 mod js_bindings;
 use js_bindings::{
     idxdb_fetch_and_cache_account_auth_by_pub_key, idxdb_get_account_asset_vault,
     idxdb_get_account_code, idxdb_get_account_header, idxdb_get_account_header_by_commitment,
-    idxdb_get_account_headers, idxdb_get_account_ids, idxdb_get_account_storage,
-    idxdb_get_foreign_account_code, idxdb_lock_account, idxdb_undo_account_states,
-    idxdb_upsert_foreign_account_code,
+    idxdb_get_account_headers, idxdb_get_account_history, idxdb_get_account_ids,
+    idxdb_get_account_storage, idxdb_get_foreign_account_code, idxdb_lock_account,
+    idxdb_remove_account, idxdb_undo_account_states, idxdb_upsert_foreign_account_code,
 };
 
 mod models;
@@ -124,6 +127,62 @@ impl WebStore {
         account_header
     }
 
+    pub(crate) async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountHeader>, StoreError> {
+        Ok(self
+            .fetch_account_history(account_id)
+            .await?
+            .into_iter()
+            .map(|(account_header, _status)| account_header)
+            .collect())
+    }
+
+    pub(crate) async fn get_account_at_nonce(
+        &self,
+        account_id: AccountId,
+        nonce: Felt,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        let account_state = self
+            .fetch_account_history(account_id)
+            .await?
+            .into_iter()
+            .find(|(account_header, _status)| account_header.nonce() == nonce);
+
+        match account_state {
+            None => Ok(None),
+            Some((account_header, status)) => {
+                Ok(Some(self.build_account_record(account_header, status).await?))
+            },
+        }
+    }
+
+    pub(crate) async fn get_account_by_commitment(
+        &self,
+        account_commitment: Word,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        let promise = idxdb_get_account_header_by_commitment(account_commitment.to_string());
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!(
+                "failed to fetch account header by commitment: {js_error:?}",
+            ))
+        })?;
+
+        let account_header_idxdb: Option<AccountRecordIdxdbObject> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        match account_header_idxdb {
+            None => Ok(None),
+            Some(account_header_idxdb) => {
+                let (account_header, status) =
+                    parse_account_record_idxdb_object(account_header_idxdb)?;
+
+                Ok(Some(self.build_account_record(account_header, status).await?))
+            },
+        }
+    }
+
     pub(crate) async fn get_account(
         &self,
         account_id: AccountId,
@@ -132,6 +191,36 @@ impl WebStore {
             None => return Ok(None),
             Some((account_header, status)) => (account_header, status),
         };
+
+        Ok(Some(self.build_account_record(account_header, status).await?))
+    }
+
+    /// Fetches the headers of every stored state of the account, ordered by nonce from oldest to
+    /// newest.
+    async fn fetch_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<(AccountHeader, AccountStatus)>, StoreError> {
+        let promise = idxdb_get_account_history(account_id.to_string());
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to fetch account history: {js_error:?}",))
+        })?;
+
+        let account_headers_idxdb: Vec<AccountRecordIdxdbObject> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        account_headers_idxdb
+            .into_iter()
+            .map(parse_account_record_idxdb_object)
+            .collect::<Result<Vec<_>, StoreError>>()
+    }
+
+    /// Builds the full account for the provided header, fetching its code, storage and vault.
+    async fn build_account_record(
+        &self,
+        account_header: AccountHeader,
+        status: AccountStatus,
+    ) -> Result<AccountRecord, StoreError> {
         let account_code = self.get_account_code(account_header.code_commitment()).await?;
 
         let account_storage = self.get_account_storage(account_header.storage_commitment()).await?;
@@ -146,7 +235,7 @@ impl WebStore {
             account_header.nonce(),
         );
 
-        Ok(Some(AccountRecord::new(account, status)))
+        Ok(AccountRecord::new(account, status))
     }
 
     pub(super) async fn get_account_code(&self, root: Word) -> Result<AccountCode, StoreError> {
@@ -332,6 +421,28 @@ impl WebStore {
         Ok(())
     }
 
+    pub(crate) async fn remove_account(&self, removal: &AccountRemoval) -> Result<(), StoreError> {
+        let account_id = removal.account_id();
+        let promise = idxdb_remove_account(
+            account_id.to_string(),
+            removal.pending_transaction_ids().iter().map(|tx_id| tx_id.to_hex()).collect(),
+            removal.transaction_ids().iter().map(|tx_id| tx_id.to_hex()).collect(),
+            removal.input_note_ids().iter().map(|note_id| note_id.to_hex()).collect(),
+            removal.output_note_ids().iter().map(|note_id| note_id.to_hex()).collect(),
+        );
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to remove account: {js_error:?}",))
+        })?;
+        let removed_states: usize = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        if removed_states == 0 {
+            return Err(StoreError::AccountDataNotFound(account_id));
+        }
+
+        Ok(())
+    }
+
     /// Locks the account if the mismatched digest doesn't belong to a previous account state (stale
     /// data).
     pub(crate) async fn lock_account_on_unexpected_commitment(
@@ -348,6 +459,11 @@ impl WebStore {
             }
         }
 
+        self.lock_account(account_id).await
+    }
+
+    /// Locks the account, rendering it unusable.
+    pub(crate) async fn lock_account(&self, account_id: &AccountId) -> Result<(), StoreError> {
         let account_id_str = account_id.to_string();
         let promise = idxdb_lock_account(account_id_str);
         JsFuture::from(promise).await.map_err(|js_error| {
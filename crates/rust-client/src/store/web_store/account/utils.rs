@@ -16,9 +16,10 @@ use wasm_bindgen_futures::JsFuture;
 
 use super::{
     js_bindings::{
-        idxdb_get_account_auth_by_pub_key, idxdb_insert_account_asset_vault,
+        idxdb_fetch_and_cache_account_auth_by_pub_key, idxdb_get_account_auth_by_pub_key,
+        idxdb_get_all_account_auth_secret_keys, idxdb_insert_account_asset_vault,
         idxdb_insert_account_auth, idxdb_insert_account_code, idxdb_insert_account_record,
-        idxdb_insert_account_storage,
+        idxdb_insert_account_storage, idxdb_remove_account_auth,
     },
     models::{AccountAuthIdxdbObject, AccountRecordIdxdbObject},
 };
@@ -62,6 +63,28 @@ pub async fn insert_account_auth(pub_key: String, secret_key: String) -> Result<
     Ok(())
 }
 
+pub async fn fetch_account_auth_by_pub_key(pub_key: String) -> Result<Option<String>, JsValue> {
+    let promise = idxdb_fetch_and_cache_account_auth_by_pub_key(pub_key);
+    let js_value = JsFuture::from(promise).await?;
+    let account_auth_idxdb: Option<AccountAuthIdxdbObject> = from_value(js_value)?;
+
+    Ok(account_auth_idxdb.map(|account_auth| account_auth.secret_key))
+}
+
+pub async fn get_all_account_auth_secret_keys() -> Result<Vec<String>, JsValue> {
+    let promise = idxdb_get_all_account_auth_secret_keys();
+    let js_value = JsFuture::from(promise).await?;
+
+    Ok(from_value(js_value)?)
+}
+
+pub async fn remove_account_auth(pub_key: String) -> Result<(), JsValue> {
+    let promise = idxdb_remove_account_auth(pub_key);
+    JsFuture::from(promise).await?;
+
+    Ok(())
+}
+
 pub fn get_account_auth_by_pub_key(pub_key: String) -> Result<String, StoreError> {
     let js_value = idxdb_get_account_auth_by_pub_key(pub_key.clone());
     let account_auth_idxdb: Option<AccountAuthIdxdbObject> = from_value(js_value)
@@ -1,5 +1,10 @@
-use super::WebStore;
-use crate::store::StoreError;
+use super::{
+    WebStore,
+    account::utils::update_account,
+    note::utils::{upsert_input_note_tx, upsert_output_note_tx},
+    transaction::utils::upsert_transaction_record,
+};
+use crate::store::{StoreError, StoreSnapshot};
 
 mod js_bindings;
 use js_bindings::idxdb_force_import_store;
@@ -14,4 +19,45 @@ impl WebStore {
             .map_err(|err| StoreError::DatabaseError(format!("Failed to import store: {err:?}")))?;
         Ok(())
     }
+
+    /// Inserts the data of the snapshot.
+    ///
+    /// Unlike in the `SqliteStore`, the data isn't inserted in a single transaction: if the import
+    /// fails, the partially imported data needs to be cleared before trying again.
+    pub(crate) async fn import_snapshot(&self, snapshot: &StoreSnapshot) -> Result<(), StoreError> {
+        for account in snapshot.accounts() {
+            let Some((first_state, next_states)) = account.states().split_first() else {
+                return Err(StoreError::InvalidSnapshot("account without states".into()));
+            };
+
+            self.insert_account(first_state, account.seed()).await?;
+            for state in next_states {
+                update_account(state).await.map_err(|err| {
+                    StoreError::DatabaseError(format!("failed to update account: {err:?}"))
+                })?;
+            }
+
+            if account.is_locked() {
+                self.lock_account(&account.id()).await?;
+            }
+        }
+
+        for note in snapshot.input_notes() {
+            upsert_input_note_tx(note).await?;
+        }
+
+        for note in snapshot.output_notes() {
+            upsert_output_note_tx(note).await?;
+        }
+
+        for transaction in snapshot.transactions() {
+            upsert_transaction_record(transaction).await?;
+        }
+
+        for tag in snapshot.note_tags() {
+            self.add_note_tag(*tag).await?;
+        }
+
+        self.import_snapshot_chain_data(snapshot).await
+    }
 }
@@ -1,10 +1,16 @@
 import {
+  db,
   accountCodes,
   accountStorages,
   accountVaults,
   accountAuths,
   accounts,
   foreignAccountCode,
+  inputNotes,
+  outputNotes,
+  pendingTransactions,
+  tags,
+  transactions,
 } from "./schema.js";
 
 // GET FUNCTIONS
@@ -168,6 +174,49 @@ export async function getAccountHeaderByCommitment(accountCommitment) {
   }
 }
 
+export async function getAccountHistory(accountId) {
+  try {
+    const allMatchingRecords = await accounts
+      .where("id")
+      .equals(accountId)
+      .toArray();
+
+    // Sort from the oldest state to the newest one
+    const sortedRecords = allMatchingRecords.sort((a, b) => {
+      const bigIntA = BigInt(a.nonce);
+      const bigIntB = BigInt(b.nonce);
+      return bigIntA < bigIntB ? -1 : bigIntA > bigIntB ? 1 : 0;
+    });
+
+    return await Promise.all(
+      sortedRecords.map(async (record) => {
+        let accountSeedBase64 = null;
+        if (record.accountSeed) {
+          let accountSeedArrayBuffer = await record.accountSeed.arrayBuffer();
+          let accountSeedArray = new Uint8Array(accountSeedArrayBuffer);
+          accountSeedBase64 = uint8ArrayToBase64(accountSeedArray);
+        }
+
+        return {
+          id: record.id,
+          nonce: record.nonce,
+          vaultRoot: record.vaultRoot,
+          storageRoot: record.storageRoot,
+          codeRoot: record.codeRoot,
+          accountSeed: accountSeedBase64,
+          locked: record.locked,
+        };
+      })
+    );
+  } catch (error) {
+    console.error(
+      `Error fetching account history for ID ${accountId}:`,
+      error.toString()
+    );
+    throw error;
+  }
+}
+
 export async function getAccountCode(codeRoot) {
   try {
     // Fetch all records matching the given root
@@ -285,6 +334,16 @@ export function getAccountAuthByPubKey(pubKey) {
   return data;
 }
 
+export async function getAllAccountAuthSecretKeys() {
+  try {
+    const allRecords = await accountAuths.toArray();
+    return allRecords.map((record) => record.secretKey);
+  } catch (error) {
+    console.error("Failed to retrieve account auths: ", error.toString());
+    throw error;
+  }
+}
+
 var ACCOUNT_AUTH_MAP = new Map();
 export async function fetchAndCacheAccountAuthByPubKey(pubKey) {
   try {
@@ -428,8 +487,8 @@ export async function insertAccountAuth(pubKey, secretKey) {
       secretKey: secretKey,
     };
 
-    // Perform the insert using Dexie
-    await accountAuths.add(data);
+    // Perform the upsert using Dexie, so re-adding a key overwrites it
+    await accountAuths.put(data);
   } catch (error) {
     console.error(
       `Error inserting auth for account: ${accountId}:`,
@@ -525,6 +584,63 @@ export async function undoAccountStates(accountCommitments) {
   }
 }
 
+// Removes the account along with its pending transactions and the specified
+// transactions and notes, in a single database transaction. Returns the number
+// of removed account states.
+export async function removeAccount(
+  accountId,
+  pendingTransactionIds,
+  transactionIds,
+  inputNoteIds,
+  outputNoteIds
+) {
+  return db.transaction(
+    "rw",
+    [
+      accounts,
+      inputNotes,
+      outputNotes,
+      pendingTransactions,
+      tags,
+      transactions,
+    ],
+    async (tx) => {
+      try {
+        const removedStates = await tx.accounts
+          .where("id")
+          .equals(accountId)
+          .delete();
+        if (removedStates === 0) {
+          return removedStates;
+        }
+
+        await tx.tags
+          .filter(
+            (tag) =>
+              tag.sourceAccountId === accountId ||
+              inputNoteIds.includes(tag.sourceNoteId)
+          )
+          .delete();
+        await tx.pendingTransactions
+          .where("id")
+          .anyOf(pendingTransactionIds)
+          .delete();
+        await tx.transactions.where("id").anyOf(transactionIds).delete();
+        await tx.inputNotes.where("noteId").anyOf(inputNoteIds).delete();
+        await tx.outputNotes.where("noteId").anyOf(outputNoteIds).delete();
+
+        return removedStates;
+      } catch (error) {
+        console.error(
+          `Error removing account: ${accountId}:`,
+          error.toString()
+        );
+        throw error;
+      }
+    }
+  );
+}
+
 function uint8ArrayToBase64(bytes) {
   const binary = bytes.reduce(
     (acc, byte) => acc + String.fromCharCode(byte),
@@ -532,3 +648,16 @@ function uint8ArrayToBase64(bytes) {
   );
   return btoa(binary);
 }
+
+export async function removeAccountAuth(pubKey) {
+  try {
+    await accountAuths.where("pubKey").equals(pubKey).delete();
+    ACCOUNT_AUTH_MAP.delete(pubKey);
+  } catch (error) {
+    console.error(
+      `Error removing auth for public key: ${pubKey}:`,
+      error.toString()
+    );
+    throw error;
+  }
+}
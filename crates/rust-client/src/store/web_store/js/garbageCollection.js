@@ -0,0 +1,78 @@
+import {
+  db,
+  accountCodes,
+  accountStorages,
+  accountVaults,
+  accounts,
+  foreignAccountCode,
+  inputNotes,
+  notesScripts,
+  transactions,
+  transactionScripts,
+} from "./schema.js";
+
+// Removes the account code, storages, vaults, note scripts and transaction scripts that are no
+// longer referenced by any account, note or transaction. Returns the number of removed records.
+export async function garbageCollect() {
+  return db.transaction(
+    "rw",
+    [
+      accountCodes,
+      accountStorages,
+      accountVaults,
+      accounts,
+      foreignAccountCode,
+      inputNotes,
+      notesScripts,
+      transactions,
+      transactionScripts,
+    ],
+    async (tx) => {
+      try {
+        const accountRecords = await tx.accounts.toArray();
+        const foreignCodeRecords = await tx.foreignAccountCode.toArray();
+        const inputNoteRecords = await tx.inputNotes.toArray();
+        const transactionRecords = await tx.transactions.toArray();
+
+        const codeRoots = new Set([
+          ...accountRecords.map((account) => account.codeRoot),
+          ...foreignCodeRecords.map((code) => code.codeRoot),
+        ]);
+        const storageRoots = new Set(
+          accountRecords.map((account) => account.storageRoot)
+        );
+        const vaultRoots = new Set(
+          accountRecords.map((account) => account.vaultRoot)
+        );
+        const noteScriptRoots = new Set(
+          inputNoteRecords.map((note) => note.noteScriptRoot)
+        );
+        const transactionScriptRoots = new Set(
+          transactionRecords.map((transaction) => transaction.scriptRoot)
+        );
+
+        let removedRecords = 0;
+        removedRecords += await tx.accountCodes
+          .filter((code) => !codeRoots.has(code.root))
+          .delete();
+        removedRecords += await tx.accountStorages
+          .filter((storage) => !storageRoots.has(storage.root))
+          .delete();
+        removedRecords += await tx.accountVaults
+          .filter((vault) => !vaultRoots.has(vault.root))
+          .delete();
+        removedRecords += await tx.notesScripts
+          .filter((script) => !noteScriptRoots.has(script.scriptRoot))
+          .delete();
+        removedRecords += await tx.transactionScripts
+          .filter((script) => !transactionScriptRoots.has(script.scriptRoot))
+          .delete();
+
+        return removedRecords;
+      } catch (err) {
+        console.error("Failed to collect garbage: ", err.toString());
+        throw err;
+      }
+    }
+  );
+}
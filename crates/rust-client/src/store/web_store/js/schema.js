@@ -21,6 +21,7 @@ const Table = {
   AccountAuth: "accountAuth",
   Accounts: "accounts",
   Transactions: "transactions",
+  PendingTransactions: "pendingTransactions",
   TransactionScripts: "transactionScripts",
   InputNotes: "inputNotes",
   OutputNotes: "outputNotes",
@@ -30,6 +31,7 @@ const Table = {
   PartialBlockchainNodes: "partialBlockchainNodes",
   Tags: "tags",
   ForeignAccountCode: "foreignAccountCode",
+  Settings: "settings",
 };
 
 const db = new Dexie(DATABASE_NAME);
@@ -62,6 +64,16 @@ db.version(1).stores({
   [Table.ForeignAccountCode]: indexes("accountId"),
 });
 
+// Schema changes are added as new versions, which Dexie applies to existing databases when they
+// are opened. Only the tables that change need to be listed.
+db.version(2).stores({
+  [Table.PendingTransactions]: indexes("id"),
+});
+
+db.version(3).stores({
+  [Table.Settings]: indexes("name"),
+});
+
 function indexes(...items) {
   return items.join(",");
 }
@@ -77,6 +89,7 @@ const accountVaults = db.table(Table.AccountVaults);
 const accountAuths = db.table(Table.AccountAuth);
 const accounts = db.table(Table.Accounts);
 const transactions = db.table(Table.Transactions);
+const pendingTransactions = db.table(Table.PendingTransactions);
 const transactionScripts = db.table(Table.TransactionScripts);
 const inputNotes = db.table(Table.InputNotes);
 const outputNotes = db.table(Table.OutputNotes);
@@ -86,6 +99,7 @@ const blockHeaders = db.table(Table.BlockHeaders);
 const partialBlockchainNodes = db.table(Table.PartialBlockchainNodes);
 const tags = db.table(Table.Tags);
 const foreignAccountCode = db.table(Table.ForeignAccountCode);
+const settings = db.table(Table.Settings);
 
 export {
   db,
@@ -95,6 +109,7 @@ export {
   accountAuths,
   accounts,
   transactions,
+  pendingTransactions,
   transactionScripts,
   inputNotes,
   outputNotes,
@@ -104,4 +119,5 @@ export {
   partialBlockchainNodes,
   tags,
   foreignAccountCode,
+  settings,
 };
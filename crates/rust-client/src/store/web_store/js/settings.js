@@ -0,0 +1,22 @@
+import { settings } from "./schema.js";
+
+// Returns the value of the setting with the specified name, or null if it isn't set.
+export async function getSetting(name) {
+  try {
+    const record = await settings.get(name);
+    return record ? record.value : null;
+  } catch (error) {
+    console.error("Error fetching setting:", error.toString());
+    throw error;
+  }
+}
+
+// Sets the value of the setting with the specified name, replacing the previous one.
+export async function setSetting(name, value) {
+  try {
+    await settings.put({ name: name, value: new Uint8Array(value) });
+  } catch (error) {
+    console.error("Error setting setting:", error.toString());
+    throw error;
+  }
+}
@@ -1,4 +1,8 @@
-import { transactions, transactionScripts } from "./schema.js";
+import {
+  transactions,
+  pendingTransactions,
+  transactionScripts,
+} from "./schema.js";
 
 const IDS_FILTER_PREFIX = "Ids:";
 export async function getTransactions(filter) {
@@ -178,6 +182,61 @@ export async function upsertTransactionRecord(
   }
 }
 
+export async function getPendingTransactions(transactionId) {
+  try {
+    const records =
+      transactionId === undefined || transactionId === null
+        ? await pendingTransactions.toArray()
+        : await pendingTransactions
+            .where("id")
+            .equals(transactionId)
+            .toArray();
+
+    return await Promise.all(
+      records.map(async (record) => {
+        let txResultArrayBuffer = await record.txResult.arrayBuffer();
+        let stageArrayBuffer = await record.stage.arrayBuffer();
+
+        return {
+          id: record.id,
+          txResult: uint8ArrayToBase64(new Uint8Array(txResultArrayBuffer)),
+          stage: uint8ArrayToBase64(new Uint8Array(stageArrayBuffer)),
+        };
+      })
+    );
+  } catch (err) {
+    console.error("Failed to get pending transactions: ", err.toString());
+    throw err;
+  }
+}
+
+export async function upsertPendingTransaction(transactionId, txResult, stage) {
+  try {
+    const data = {
+      id: transactionId,
+      txResult: new Blob([new Uint8Array(txResult)]),
+      stage: new Blob([new Uint8Array(stage)]),
+    };
+
+    await pendingTransactions.put(data);
+  } catch (err) {
+    console.error("Failed to insert pending transaction: ", err.toString());
+    throw err;
+  }
+}
+
+export async function removePendingTransaction(transactionId) {
+  try {
+    return await pendingTransactions
+      .where("id")
+      .equals(transactionId)
+      .delete();
+  } catch (err) {
+    console.error("Failed to remove pending transaction: ", err.toString());
+    throw err;
+  }
+}
+
 function uint8ArrayToBase64(bytes) {
   const binary = bytes.reduce(
     (acc, byte) => acc + String.fromCharCode(byte),
@@ -10,15 +10,17 @@
 use alloc::{
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
+    string::String,
     vec::Vec,
 };
 
 use miden_objects::{
-    Word,
+    Felt, Word,
     account::{Account, AccountCode, AccountHeader, AccountId},
     block::{BlockHeader, BlockNumber},
     crypto::merkle::{InOrderIndex, MmrPeaks},
     note::Nullifier,
+    transaction::TransactionId,
 };
 use tonic::async_trait;
 use wasm_bindgen::prelude::*;
@@ -26,11 +28,12 @@ use wasm_bindgen_futures::{JsFuture, js_sys, wasm_bindgen};
 
 use super::{
     AccountRecord, AccountStatus, BlockRelevance, InputNoteRecord, NoteFilter, OutputNoteRecord,
-    PartialBlockchainFilter, Store, StoreError, TransactionFilter,
+    PartialBlockchainFilter, Store, StoreError, StoreSnapshot, TransactionFilter,
 };
 use crate::{
+    account::AccountRemoval,
     sync::{NoteTagRecord, StateSyncUpdate},
-    transaction::{TransactionRecord, TransactionStoreUpdate},
+    transaction::{PendingTransactionRecord, TransactionRecord, TransactionStoreUpdate},
 };
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -51,6 +54,23 @@ extern "C" {
     fn setup_indexed_db() -> js_sys::Promise;
 }
 
+// Garbage collection
+#[wasm_bindgen(module = "/src/store/web_store/js/garbageCollection.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = garbageCollect)]
+    fn idxdb_garbage_collect() -> js_sys::Promise;
+}
+
+// Settings
+#[wasm_bindgen(module = "/src/store/web_store/js/settings.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = getSetting)]
+    fn idxdb_get_setting(name: String) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = setSetting)]
+    fn idxdb_set_setting(name: String, value: Vec<u8>) -> js_sys::Promise;
+}
+
 pub struct WebStore {}
 
 impl WebStore {
@@ -58,6 +78,33 @@ impl WebStore {
         JsFuture::from(setup_indexed_db()).await?;
         Ok(WebStore {})
     }
+
+    pub(crate) async fn garbage_collect(&self) -> Result<usize, StoreError> {
+        let js_value = JsFuture::from(idxdb_garbage_collect()).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to collect garbage: {js_error:?}"))
+        })?;
+
+        serde_wasm_bindgen::from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))
+    }
+
+    pub(crate) async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError> {
+        let js_value = JsFuture::from(idxdb_get_setting(name)).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to get setting: {js_error:?}"))
+        })?;
+
+        if js_value.is_null() || js_value.is_undefined() {
+            return Ok(None);
+        }
+        Ok(Some(js_sys::Uint8Array::new(&js_value).to_vec()))
+    }
+
+    pub(crate) async fn set_setting(&self, name: String, value: Vec<u8>) -> Result<(), StoreError> {
+        JsFuture::from(idxdb_set_setting(name, value)).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to set setting: {js_error:?}"))
+        })?;
+        Ok(())
+    }
 }
 
 #[async_trait(?Send)]
@@ -103,6 +150,28 @@ impl Store for WebStore {
         self.apply_transaction(tx_update).await
     }
 
+    async fn get_pending_transactions(&self) -> Result<Vec<PendingTransactionRecord>, StoreError> {
+        self.get_pending_transactions().await
+    }
+
+    async fn get_pending_transaction(
+        &self,
+        tx_id: TransactionId,
+    ) -> Result<Option<PendingTransactionRecord>, StoreError> {
+        self.get_pending_transaction(tx_id).await
+    }
+
+    async fn upsert_pending_transaction(
+        &self,
+        record: &PendingTransactionRecord,
+    ) -> Result<(), StoreError> {
+        self.upsert_pending_transaction(record).await
+    }
+
+    async fn remove_pending_transaction(&self, tx_id: TransactionId) -> Result<bool, StoreError> {
+        self.remove_pending_transaction(tx_id).await
+    }
+
     // NOTES
     // --------------------------------------------------------------------------------------------
     async fn get_input_notes(
@@ -187,6 +256,10 @@ impl Store for WebStore {
         self.update_account(new_account_state).await
     }
 
+    async fn remove_account(&self, removal: &AccountRemoval) -> Result<(), StoreError> {
+        self.remove_account(removal).await
+    }
+
     async fn get_account_ids(&self) -> Result<Vec<AccountId>, StoreError> {
         self.get_account_ids().await
     }
@@ -216,6 +289,28 @@ impl Store for WebStore {
         self.get_account(account_id).await
     }
 
+    async fn get_account_history(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<AccountHeader>, StoreError> {
+        self.get_account_history(account_id).await
+    }
+
+    async fn get_account_at_nonce(
+        &self,
+        account_id: AccountId,
+        nonce: Felt,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        self.get_account_at_nonce(account_id, nonce).await
+    }
+
+    async fn get_account_by_commitment(
+        &self,
+        account_commitment: Word,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        self.get_account_by_commitment(account_commitment).await
+    }
+
     async fn upsert_foreign_account_code(
         &self,
         account_id: AccountId,
@@ -234,4 +329,26 @@ impl Store for WebStore {
     async fn get_unspent_input_note_nullifiers(&self) -> Result<Vec<Nullifier>, StoreError> {
         self.get_unspent_input_note_nullifiers().await
     }
+
+    // GARBAGE COLLECTION
+    // --------------------------------------------------------------------------------------------
+
+    async fn garbage_collect(&self) -> Result<usize, StoreError> {
+        self.garbage_collect().await
+    }
+
+    // SETTINGS
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError> {
+        self.get_setting(name).await
+    }
+
+    async fn set_setting(&self, name: String, value: Vec<u8>) -> Result<(), StoreError> {
+        self.set_setting(name, value).await
+    }
+
+    async fn import_snapshot(&self, snapshot: &StoreSnapshot) -> Result<(), StoreError> {
+        self.import_snapshot(snapshot).await
+    }
 }
@@ -39,10 +39,13 @@ impl WebStore {
         let input_notes_idxdb: Vec<InputNoteIdxdbObject> = from_value(js_value)
             .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
 
-        input_notes_idxdb
+        let notes = input_notes_idxdb
             .into_iter()
             .map(parse_input_note_idxdb_object) // Simplified closure
-            .collect::<Result<Vec<_>, _>>() // Collect results into a single Result
+            .collect::<Result<Vec<_>, _>>()?; // Collect results into a single Result
+
+        // Filters that can't be expressed as an IndexedDB query are applied here
+        Ok(notes.into_iter().filter(|note| filter.matches_input_note(note)).collect())
     }
 
     pub(crate) async fn get_output_notes(
@@ -57,10 +60,13 @@ impl WebStore {
         let output_notes_idxdb: Vec<OutputNoteIdxdbObject> = from_value(js_value)
             .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
 
-        output_notes_idxdb
+        let notes = output_notes_idxdb
             .into_iter()
             .map(parse_output_note_idxdb_object) // Simplified closure
-            .collect::<Result<Vec<_>, _>>() // Collect results into a single Result
+            .collect::<Result<Vec<_>, _>>()?; // Collect results into a single Result
+
+        // Filters that can't be expressed as an IndexedDB query are applied here
+        Ok(notes.into_iter().filter(|note| filter.matches_output_note(note)).collect())
     }
 
     pub(crate) async fn get_unspent_input_note_nullifiers(
@@ -102,9 +108,28 @@ impl NoteFilter {
             | NoteFilter::Expected
             | NoteFilter::Processing
             | NoteFilter::Unspent
-            | NoteFilter::Unverified => {
+            | NoteFilter::Unverified
+            | NoteFilter::Consumer(_)
+            | NoteFilter::Target(_)
+            | NoteFilter::Sender(_)
+            | NoteFilter::Tag(_)
+            | NoteFilter::Faucet(_)
+            | NoteFilter::ScriptRoot(_)
+            | NoteFilter::CreatedBetween { .. }
+            | NoteFilter::BlockRange { .. }
+            | NoteFilter::And(_) => {
                 let states: Vec<u8> = match self {
-                    NoteFilter::All => vec![],
+                    // The notes are filtered by `NoteFilter::matches_input_note` once fetched
+                    NoteFilter::All
+                    | NoteFilter::Consumer(_)
+                    | NoteFilter::Target(_)
+                    | NoteFilter::Sender(_)
+                    | NoteFilter::Tag(_)
+                    | NoteFilter::Faucet(_)
+                    | NoteFilter::ScriptRoot(_)
+                    | NoteFilter::CreatedBetween { .. }
+                    | NoteFilter::BlockRange { .. }
+                    | NoteFilter::And(_) => vec![],
                     NoteFilter::Consumed => vec![
                         InputNoteState::STATE_CONSUMED_AUTHENTICATED_LOCAL,
                         InputNoteState::STATE_CONSUMED_UNAUTHENTICATED_LOCAL,
@@ -158,9 +183,24 @@ impl NoteFilter {
             | NoteFilter::Consumed
             | NoteFilter::Committed
             | NoteFilter::Expected
-            | NoteFilter::Unspent => {
+            | NoteFilter::Unspent
+            | NoteFilter::Target(_)
+            | NoteFilter::Sender(_)
+            | NoteFilter::Tag(_)
+            | NoteFilter::Faucet(_)
+            | NoteFilter::ScriptRoot(_)
+            | NoteFilter::BlockRange { .. }
+            | NoteFilter::And(_) => {
                 let states = match self {
-                    NoteFilter::All => vec![],
+                    // The notes are filtered by `NoteFilter::matches_output_note` once fetched
+                    NoteFilter::All
+                    | NoteFilter::Target(_)
+                    | NoteFilter::Sender(_)
+                    | NoteFilter::Tag(_)
+                    | NoteFilter::Faucet(_)
+                    | NoteFilter::ScriptRoot(_)
+                    | NoteFilter::BlockRange { .. }
+                    | NoteFilter::And(_) => vec![],
                     NoteFilter::Consumed => vec![OutputNoteState::STATE_CONSUMED],
                     NoteFilter::Committed => vec![
                         OutputNoteState::STATE_COMMITTED_FULL,
@@ -179,9 +219,10 @@ impl NoteFilter {
 
                 idxdb_get_output_notes(states)
             },
-            NoteFilter::Processing | NoteFilter::Unverified => {
-                Promise::resolve(&JsValue::from(Array::new()))
-            },
+            NoteFilter::Processing
+            | NoteFilter::Unverified
+            | NoteFilter::Consumer(_)
+            | NoteFilter::CreatedBetween { .. } => Promise::resolve(&JsValue::from(Array::new())),
             NoteFilter::List(ids) => {
                 let note_ids_as_str: Vec<String> =
                     ids.iter().map(|id| id.as_word().to_string()).collect();
@@ -20,7 +20,7 @@ use super::{
     transaction::utils::upsert_transaction_record,
 };
 use crate::{
-    store::StoreError,
+    store::{StoreError, StoreSnapshot},
     sync::{NoteTagRecord, NoteTagSource, StateSyncUpdate},
 };
 
@@ -117,6 +117,50 @@ impl WebStore {
         Ok(removed_tags)
     }
 
+    /// Sets the sync height and inserts the block headers and partial blockchain nodes of the
+    /// snapshot in a single transaction.
+    pub(super) async fn import_snapshot_chain_data(
+        &self,
+        snapshot: &StoreSnapshot,
+    ) -> Result<(), StoreError> {
+        let mut block_headers_as_bytes = vec![];
+        let mut peaks_as_bytes = vec![];
+        let mut block_nums_as_str = vec![];
+        let mut block_has_relevant_notes = vec![];
+
+        for block in snapshot.block_headers() {
+            block_headers_as_bytes.push(block.header().to_bytes());
+            peaks_as_bytes.push(block.partial_blockchain_peaks()?.peaks().to_vec().to_bytes());
+            block_nums_as_str.push(block.header().block_num().to_string());
+            block_has_relevant_notes.push(u8::from(block.has_client_notes()));
+        }
+
+        let mut serialized_node_ids = Vec::new();
+        let mut serialized_nodes = Vec::new();
+        for (id, node) in snapshot.partial_blockchain_nodes() {
+            let SerializedPartialBlockchainNodeData { id, node } =
+                serialize_partial_blockchain_node(*id, *node)?;
+            serialized_node_ids.push(id);
+            serialized_nodes.push(node);
+        }
+
+        let promise = idxdb_apply_state_sync(
+            snapshot.sync_height().to_string(),
+            flatten_nested_u8_vec(block_headers_as_bytes),
+            block_nums_as_str,
+            flatten_nested_u8_vec(peaks_as_bytes),
+            block_has_relevant_notes,
+            serialized_node_ids,
+            serialized_nodes,
+            vec![],
+        );
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to import chain data: {js_error:?}"))
+        })?;
+
+        Ok(())
+    }
+
     pub(super) async fn apply_state_sync(
         &self,
         state_sync_update: StateSyncUpdate,
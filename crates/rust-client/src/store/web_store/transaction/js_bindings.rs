@@ -28,4 +28,20 @@ extern "C" {
         committed: Option<String>,
         discard_cause: Option<Vec<u8>>,
     ) -> js_sys::Promise;
+
+    // PENDING TRANSACTIONS
+    // ================================================================================================
+
+    #[wasm_bindgen(js_name = getPendingTransactions)]
+    pub fn idxdb_get_pending_transactions(transaction_id: Option<String>) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = upsertPendingTransaction)]
+    pub fn idxdb_upsert_pending_transaction(
+        transaction_id: String,
+        tx_result: Vec<u8>,
+        stage: Vec<u8>,
+    ) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = removePendingTransaction)]
+    pub fn idxdb_remove_pending_transaction(transaction_id: String) -> js_sys::Promise;
 }
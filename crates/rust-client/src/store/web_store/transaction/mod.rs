@@ -1,38 +1,48 @@
 use alloc::{
+    collections::BTreeMap,
     string::{String, ToString},
     vec::Vec,
 };
 
-use miden_objects::{Word, block::BlockNumber, transaction::TransactionScript};
-use miden_tx::utils::Deserializable;
+use miden_objects::{
+    Word,
+    block::BlockNumber,
+    transaction::{TransactionId, TransactionScript},
+};
+use miden_tx::utils::{Deserializable, Serializable};
 use serde_wasm_bindgen::from_value;
 use wasm_bindgen_futures::JsFuture;
 
 use super::{WebStore, account::utils::update_account, note::utils::apply_note_updates_tx};
 use crate::{
-    store::{StoreError, TransactionFilter},
+    store::{NoteFilter, StoreError, TransactionFilter},
     transaction::{
-        DiscardCause, TransactionDetails, TransactionRecord, TransactionStatus,
-        TransactionStoreUpdate,
+        DiscardCause, PendingTransactionRecord, PendingTransactionStage, TransactionDetails,
+        TransactionRecord, TransactionResult, TransactionStatus, TransactionStoreUpdate,
     },
 };
 
 mod js_bindings;
-use js_bindings::idxdb_get_transactions;
+use js_bindings::{
+    idxdb_get_pending_transactions, idxdb_get_transactions, idxdb_remove_pending_transaction,
+    idxdb_upsert_pending_transaction,
+};
 
 mod models;
-use models::TransactionIdxdbObject;
+use models::{PendingTransactionIdxdbObject, TransactionIdxdbObject};
 
 pub mod utils;
 use utils::insert_proven_transaction_data;
 
+// TRANSACTIONS
+// ================================================================================================
+
 impl WebStore {
     pub async fn get_transactions(
         &self,
         filter: TransactionFilter,
     ) -> Result<Vec<TransactionRecord>, StoreError> {
-        let filter_as_str = match filter {
-            TransactionFilter::All => "All",
+        let filter_as_str = match &filter {
             TransactionFilter::Uncommitted => "Uncommitted",
             TransactionFilter::Ids(ids) => &{
                 let ids_str =
@@ -42,6 +52,8 @@ impl WebStore {
             TransactionFilter::ExpiredBefore(block_number) => {
                 &format!("ExpiredPending:{block_number}")
             },
+            // The rest of the filters are applied to the deserialized transactions
+            _ => "All",
         };
 
         let promise = idxdb_get_transactions(filter_as_str.to_string());
@@ -84,7 +96,22 @@ impl WebStore {
             })
             .collect();
 
-        transaction_records
+        // Consumed notes are matched by nullifier, which is only known for tracked input notes
+        let note_ids = filter.note_ids();
+        let nullifiers = if note_ids.is_empty() {
+            BTreeMap::new()
+        } else {
+            self.get_input_notes(NoteFilter::List(note_ids))
+                .await?
+                .into_iter()
+                .map(|note| (note.id(), note.nullifier()))
+                .collect()
+        };
+
+        Ok(transaction_records?
+            .into_iter()
+            .filter(|record| filter.matches(record, &nullifiers))
+            .collect())
     }
 
     pub async fn apply_transaction(
@@ -110,6 +137,73 @@ impl WebStore {
             self.add_note_tag(*tag_record).await?;
         }
 
+        // The transaction is no longer pending
+        self.remove_pending_transaction(tx_update.executed_transaction().id()).await?;
+
         Ok(())
     }
+
+    pub async fn get_pending_transactions(
+        &self,
+    ) -> Result<Vec<PendingTransactionRecord>, StoreError> {
+        fetch_pending_transactions(None).await
+    }
+
+    pub async fn get_pending_transaction(
+        &self,
+        tx_id: TransactionId,
+    ) -> Result<Option<PendingTransactionRecord>, StoreError> {
+        Ok(fetch_pending_transactions(Some(tx_id.to_hex())).await?.pop())
+    }
+
+    pub async fn upsert_pending_transaction(
+        &self,
+        record: &PendingTransactionRecord,
+    ) -> Result<(), StoreError> {
+        let promise = idxdb_upsert_pending_transaction(
+            record.id().to_hex(),
+            record.tx_result().to_bytes(),
+            record.stage().to_bytes(),
+        );
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to insert pending transaction: {js_error:?}"))
+        })?;
+
+        Ok(())
+    }
+
+    pub async fn remove_pending_transaction(
+        &self,
+        tx_id: TransactionId,
+    ) -> Result<bool, StoreError> {
+        let promise = idxdb_remove_pending_transaction(tx_id.to_hex());
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to remove pending transaction: {js_error:?}"))
+        })?;
+        let removed: usize = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        Ok(removed > 0)
+    }
+}
+
+async fn fetch_pending_transactions(
+    tx_id: Option<String>,
+) -> Result<Vec<PendingTransactionRecord>, StoreError> {
+    let promise = idxdb_get_pending_transactions(tx_id);
+    let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+        StoreError::DatabaseError(format!("failed to get pending transactions: {js_error:?}"))
+    })?;
+    let records_idxdb: Vec<PendingTransactionIdxdbObject> = from_value(js_value)
+        .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+    records_idxdb
+        .into_iter()
+        .map(|record_idxdb| {
+            Ok(PendingTransactionRecord::new(
+                TransactionResult::read_from_bytes(&record_idxdb.tx_result)?,
+                PendingTransactionStage::read_from_bytes(&record_idxdb.stage)?,
+            ))
+        })
+        .collect()
 }
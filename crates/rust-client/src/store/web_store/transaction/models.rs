@@ -19,6 +19,16 @@ pub struct TransactionIdxdbObject {
     pub discard_cause: Option<Vec<u8>>,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTransactionIdxdbObject {
+    pub id: String,
+    #[serde(deserialize_with = "base64_to_vec_u8_required", default)]
+    pub tx_result: Vec<u8>,
+    #[serde(deserialize_with = "base64_to_vec_u8_required", default)]
+    pub stage: Vec<u8>,
+}
+
 fn base64_to_vec_u8_required<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
@@ -0,0 +1,349 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    future::{Future, poll_fn},
+    pin::pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::Poll,
+    time::Duration,
+};
+
+use futures::{future, lock::Mutex, task::AtomicWaker};
+use tracing::{info, warn};
+
+use super::SyncSummary;
+use crate::{Client, ClientError};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The default time between two consecutive sync attempts.
+const DEFAULT_SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The default maximum time to wait before retrying a sync after consecutive failures.
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+// AUTO SYNC CONFIG
+// ================================================================================================
+
+/// Determines when the [`AutoSync`] driver syncs the client's state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoSyncTrigger {
+    /// Sync every time the interval elapses.
+    Interval,
+    /// Every time the interval elapses, query the node for the chain tip and only sync if there
+    /// are new blocks past the client's sync height.
+    NewBlocks,
+}
+
+/// Configuration for the [`AutoSync`] driver.
+#[derive(Debug, Clone)]
+pub struct AutoSyncConfig {
+    /// Time to wait between two consecutive sync attempts.
+    interval: Duration,
+    /// Determines when the client's state gets synced.
+    trigger: AutoSyncTrigger,
+    /// Maximum time to wait before retrying after consecutive failures. The waiting time starts
+    /// at `interval` and is doubled after every consecutive failure.
+    max_backoff: Duration,
+}
+
+impl AutoSyncConfig {
+    /// Creates a new [`AutoSyncConfig`] that syncs with the provided interval and trigger.
+    pub fn new(interval: Duration, trigger: AutoSyncTrigger) -> Self {
+        Self {
+            interval,
+            trigger,
+            max_backoff: DEFAULT_MAX_BACKOFF.max(interval),
+        }
+    }
+
+    /// Sets the maximum time to wait before retrying after consecutive failures.
+    #[must_use]
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Returns the time to wait between two consecutive sync attempts.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the trigger that determines when the client's state gets synced.
+    pub fn trigger(&self) -> AutoSyncTrigger {
+        self.trigger
+    }
+
+    /// Returns the time to wait before the next attempt after `consecutive_failures` consecutive
+    /// failures.
+    pub fn backoff(&self, consecutive_failures: u32) -> Duration {
+        let multiplier = 2u32.saturating_pow(consecutive_failures);
+        self.interval
+            .saturating_mul(multiplier)
+            .min(self.max_backoff.max(self.interval))
+    }
+}
+
+impl Default for AutoSyncConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_SYNC_INTERVAL, AutoSyncTrigger::Interval)
+    }
+}
+
+// AUTO SYNC HANDLE
+// ================================================================================================
+
+/// A handle that can be used to stop a running [`AutoSync`] driver.
+///
+/// The handle can be cloned and shared. Once cancelled, a driver that is waiting for its next
+/// attempt stops right away, and a driver that is syncing stops once the current sync finishes.
+#[derive(Debug, Clone, Default)]
+pub struct AutoSyncHandle {
+    state: Arc<CancellationState>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    /// Whether the driver was requested to stop.
+    cancelled: AtomicBool,
+    /// Wakes up the driver while it waits for its next attempt.
+    waker: AtomicWaker,
+}
+
+impl AutoSyncHandle {
+    /// Requests the driver to stop.
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::SeqCst);
+        self.state.waker.wake();
+    }
+
+    /// Returns `true` if the driver was requested to stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns a future that completes once the driver is requested to stop.
+    async fn cancelled(&self) {
+        poll_fn(|cx| {
+            if self.is_cancelled() {
+                return Poll::Ready(());
+            }
+
+            self.state.waker.register(cx.waker());
+            if self.is_cancelled() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+}
+
+// AUTO SYNC LISTENER
+// ================================================================================================
+
+/// A listener that gets notified of the results of the syncs performed by the [`AutoSync`]
+/// driver.
+///
+/// This trait is implemented for any `Fn(&SyncSummary)` closure.
+pub trait AutoSyncListener {
+    /// Called after every successful sync with the resulting [`SyncSummary`].
+    fn on_sync_summary(&self, summary: &SyncSummary);
+
+    /// Called after every failed sync attempt. The driver keeps running and retries with a
+    /// backoff.
+    fn on_sync_error(&self, _error: &ClientError) {}
+}
+
+impl<F> AutoSyncListener for F
+where
+    F: Fn(&SyncSummary),
+{
+    fn on_sync_summary(&self, summary: &SyncSummary) {
+        self(summary);
+    }
+}
+
+// AUTO SYNC
+// ================================================================================================
+
+/// Drives the periodic synchronization of a [`Client`] with the network.
+///
+/// The driver works on a client shared through a [`Mutex`], which is only locked while a sync is
+/// in progress, so the client can keep being used by the rest of the application in between.
+///
+/// `AutoSync::spawn_tokio` (on `std` targets with the `tonic` feature) and `AutoSync::spawn_wasm`
+/// (on `wasm32` targets with the `idxdb` feature) run the driver as a background task. On other
+/// runtimes, [`AutoSync::run`] can be awaited with any timer function.
+///
+/// Each sync is performed through [`Client::sync_state`], so the configured sync chunk size and
+/// sync listeners are used as well.
+///
+/// ```rust,ignore
+/// let client = Rc::new(Mutex::new(client));
+///
+/// let mut auto_sync = AutoSync::new(AutoSyncConfig::default());
+/// let handle = auto_sync.handle();
+/// auto_sync.add_listener(Arc::new(|summary: &SyncSummary| {
+///     println!("Synced to block {}", summary.block_num);
+/// }));
+///
+/// // Must be called from within a `tokio::task::LocalSet`.
+/// let task = auto_sync.spawn_tokio(client.clone());
+///
+/// // The client can still be used while the driver runs.
+/// let accounts = client.lock().await.get_account_headers().await?;
+///
+/// handle.cancel();
+/// task.await?;
+/// ```
+pub struct AutoSync {
+    /// The configuration of the driver.
+    config: AutoSyncConfig,
+    /// Handle used to stop the driver.
+    handle: AutoSyncHandle,
+    /// Listeners that get notified of the results of each sync.
+    listeners: Vec<Arc<dyn AutoSyncListener>>,
+}
+
+impl AutoSync {
+    /// Creates a new [`AutoSync`] driver with the provided configuration.
+    pub fn new(config: AutoSyncConfig) -> Self {
+        Self {
+            config,
+            handle: AutoSyncHandle::default(),
+            listeners: Vec::new(),
+        }
+    }
+
+    /// Returns a handle that can be used to stop the driver.
+    pub fn handle(&self) -> AutoSyncHandle {
+        self.handle.clone()
+    }
+
+    /// Registers a listener that will be notified of the results of each sync.
+    pub fn add_listener(&mut self, listener: Arc<dyn AutoSyncListener>) {
+        self.listeners.push(listener);
+    }
+
+    /// Runs the driver until it gets cancelled through its [`AutoSyncHandle`].
+    ///
+    /// An initial sync is performed right away. After that, the driver waits for the configured
+    /// interval between attempts, using the provided `sleep` function. If a sync fails, the error
+    /// is reported to the listeners and the next attempt is delayed with an exponential backoff.
+    ///
+    /// The client is only locked while a sync is in progress.
+    pub async fn run<S, F>(&self, client: &Mutex<Client>, sleep: S)
+    where
+        S: Fn(Duration) -> F,
+        F: Future<Output = ()>,
+    {
+        let mut consecutive_failures = 0;
+
+        while !self.handle.is_cancelled() {
+            let result = self.tick(&mut *client.lock().await).await;
+            match result {
+                Ok(summary) => {
+                    consecutive_failures = 0;
+                    if let Some(summary) = summary {
+                        self.notify_summary(&summary);
+                    }
+                },
+                Err(err) => {
+                    warn!("Auto sync failed: {err}");
+                    consecutive_failures += 1;
+                    self.notify_error(&err);
+                },
+            }
+
+            let wait = pin!(sleep(self.config.backoff(consecutive_failures)));
+            let cancelled = pin!(self.handle.cancelled());
+            future::select(wait, cancelled).await;
+        }
+
+        info!("Auto sync stopped");
+    }
+
+    /// Spawns the driver as a background task on the current tokio [`LocalSet`], using
+    /// `tokio::time::sleep` to wait between attempts.
+    ///
+    /// The client is not `Send`, so this must be called from within a [`LocalSet`]. The returned
+    /// handle completes once the driver is cancelled.
+    ///
+    /// [`LocalSet`]: tokio::task::LocalSet
+    #[cfg(feature = "tonic")]
+    pub fn spawn_tokio(self, client: alloc::rc::Rc<Mutex<Client>>) -> tokio::task::JoinHandle<()> {
+        tokio::task::spawn_local(async move { self.run(&client, tokio::time::sleep).await })
+    }
+
+    /// Spawns the driver as a background task on the browser's event loop, using the global
+    /// `setTimeout` to wait between attempts. This works both in windows and in web workers.
+    ///
+    /// # Panics
+    ///
+    /// The driver panics if the JavaScript environment doesn't provide `setTimeout`.
+    #[cfg(all(target_arch = "wasm32", feature = "idxdb"))]
+    pub fn spawn_wasm(self, client: alloc::rc::Rc<Mutex<Client>>) {
+        wasm_bindgen_futures::spawn_local(async move { self.run(&client, wasm_sleep).await });
+    }
+
+    /// Performs a single sync attempt according to the configured trigger. Returns `None` if the
+    /// sync was skipped because there were no new blocks.
+    async fn tick(&self, client: &mut Client) -> Result<Option<SyncSummary>, ClientError> {
+        if self.config.trigger == AutoSyncTrigger::NewBlocks {
+            let (chain_tip, _) = client.rpc_api.get_block_header_by_number(None, false).await?;
+            if chain_tip.block_num() <= client.get_sync_height().await? {
+                return Ok(None);
+            }
+        }
+
+        client.sync_state().await.map(Some)
+    }
+
+    fn notify_summary(&self, summary: &SyncSummary) {
+        for listener in &self.listeners {
+            listener.on_sync_summary(summary);
+        }
+    }
+
+    fn notify_error(&self, error: &ClientError) {
+        for listener in &self.listeners {
+            listener.on_sync_error(error);
+        }
+    }
+}
+
+/// Returns a future that completes after `duration`, based on the global `setTimeout`, which is
+/// available both in windows and in web workers.
+#[cfg(all(target_arch = "wasm32", feature = "idxdb"))]
+async fn wasm_sleep(duration: Duration) {
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::{
+        JsFuture,
+        js_sys::{Function, Promise, Reflect, global},
+    };
+
+    let millis = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+    let promise = Promise::new(&mut |resolve, reject| {
+        let global = global();
+        match Reflect::get(&global, &JsValue::from_str("setTimeout")) {
+            Ok(set_timeout) if set_timeout.is_function() => {
+                let set_timeout: Function = set_timeout.unchecked_into();
+                if let Err(err) = set_timeout.call2(&global, &resolve, &JsValue::from(millis)) {
+                    let _ = reject.call1(&JsValue::NULL, &err);
+                }
+            },
+            _ => {
+                let _ =
+                    reject.call1(&JsValue::NULL, &JsValue::from_str("setTimeout is not available"));
+            },
+        }
+    });
+
+    // Without a timer the driver can't wait between attempts, so it stops instead of retrying in
+    // a busy loop
+    JsFuture::from(promise)
+        .await
+        .expect("the global scope should provide setTimeout");
+}
@@ -0,0 +1,75 @@
+use miden_objects::{
+    account::AccountId,
+    block::BlockNumber,
+    crypto::merkle::MmrPeaks,
+    note::{NoteId, Nullifier},
+    transaction::TransactionId,
+};
+
+use crate::transaction::DiscardCause;
+
+// SYNC EVENT
+// ================================================================================================
+
+/// Describes a change detected by the [`StateSync`](super::StateSync) component while syncing
+/// with the node.
+///
+/// Events are emitted as soon as the change is processed, before the sync update is applied to
+/// the store. If the sync fails, the emitted changes won't be persisted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncEvent {
+    /// A new sync step started. The request to the node is made from the provided block number.
+    StepStarted { block_num: BlockNumber },
+    /// A sync step finished. The client has processed the changes up to `block_num`, which may be
+    /// lower than the current `chain_tip`.
+    StepCompleted {
+        block_num: BlockNumber,
+        chain_tip: BlockNumber,
+    },
+    /// A relevant note was committed to the chain in the specified block.
+    NoteCommitted { note_id: NoteId, block_num: BlockNumber },
+    /// A nullifier of a tracked note was found in the chain, meaning that the note was consumed.
+    NullifierConsumed {
+        nullifier: Nullifier,
+        block_num: BlockNumber,
+    },
+    /// A tracked public account was updated on chain.
+    AccountUpdated { account_id: AccountId },
+    /// A tracked private account's commitment doesn't match the one on chain. The account may be
+    /// locked once the update is applied.
+    AccountLocked { account_id: AccountId },
+    /// A tracked transaction was committed in the specified block.
+    TransactionCommitted {
+        transaction_id: TransactionId,
+        block_num: BlockNumber,
+    },
+    /// A tracked transaction was discarded.
+    TransactionDiscarded {
+        transaction_id: TransactionId,
+        cause: DiscardCause,
+    },
+    /// The partial MMR was advanced to the specified block, resulting in the new peaks.
+    MmrPeaksAdvanced { block_num: BlockNumber, peaks: MmrPeaks },
+}
+
+// SYNC LISTENER
+// ================================================================================================
+
+/// A listener that gets notified of the [`SyncEvent`]s emitted while syncing the client's state.
+///
+/// Listeners are registered through [`Client::add_sync_listener`](crate::Client::add_sync_listener)
+/// and are called synchronously from within the sync process, so they should return quickly. This
+/// trait is implemented for any `Fn(&SyncEvent)` closure.
+pub trait SyncListener {
+    /// Called every time a new [`SyncEvent`] is emitted.
+    fn on_sync_event(&self, event: &SyncEvent);
+}
+
+impl<F> SyncListener for F
+where
+    F: Fn(&SyncEvent),
+{
+    fn on_sync_event(&self, event: &SyncEvent) {
+        self(event);
+    }
+}
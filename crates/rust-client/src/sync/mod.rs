@@ -50,12 +50,17 @@
 //!
 //! The `sync_state` method loops internally until the client is fully synced to the network tip.
 //!
+//! To keep the client synced in the background, an `AutoSync` driver can be used. It calls
+//! `sync_state` periodically on a shared client, retries with an exponential backoff on errors
+//! and publishes the resulting [`SyncSummary`] values to its listeners until it gets cancelled
+//! through its `AutoSyncHandle`.
+//!
 //! For more advanced usage, refer to the individual functions (such as
 //! `committed_note_updates` and `consumed_note_updates`) to understand how the sync data is
 //! processed and applied to the local store.
 
-use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
-use core::cmp::max;
+use alloc::{boxed::Box, collections::BTreeSet, sync::Arc, vec::Vec};
+use core::{cmp::max, num::NonZeroUsize};
 
 use miden_objects::{
     account::AccountId,
@@ -64,14 +69,23 @@ use miden_objects::{
     transaction::{PartialBlockchain, TransactionId},
 };
 use miden_tx::utils::{Deserializable, DeserializationError, Serializable};
+use tracing::warn;
 
 use crate::{
     Client, ClientError,
-    note::NoteScreener,
+    note_transport::NoteTransportError,
     store::{NoteFilter, TransactionFilter},
 };
+#[cfg(any(feature = "std", feature = "idxdb"))]
+mod auto_sync;
+#[cfg(any(feature = "std", feature = "idxdb"))]
+pub use auto_sync::{AutoSync, AutoSyncConfig, AutoSyncHandle, AutoSyncListener, AutoSyncTrigger};
+
 mod block_header;
 
+mod events;
+pub use events::{SyncEvent, SyncListener};
+
 mod tag;
 pub use tag::{NoteTagRecord, NoteTagSource};
 
@@ -112,12 +126,77 @@ impl Client {
     ///    state.
     /// 7. The MMR is updated with the new peaks and authentication nodes.
     /// 8. All updates are applied to the store to be persisted.
+    ///
+    /// If a note transport is set (see [`Client::set_note_transport`]), the private notes sent to
+    /// the client are fetched and imported before the sync starts.
+    ///
+    /// If a sync chunk size was configured (see [`Client::set_sync_chunk_size`]), the updates are
+    /// applied to the store every time the specified number of steps is processed. If the sync is
+    /// interrupted, the progress made up to the last applied chunk is kept and the next sync
+    /// resumes from it.
     pub async fn sync_state(&mut self) -> Result<SyncSummary, ClientError> {
         _ = self.ensure_genesis_in_place().await?;
 
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
-        let state_sync = StateSync::new(
+        // Private notes are imported first so that the sync picks up their inclusion. The note
+        // transport being unreachable shouldn't prevent syncing with the node.
+        match self.fetch_private_notes().await {
+            Ok(_) | Err(ClientError::NoteTransportError(NoteTransportError::NotConfigured)) => {},
+            Err(err) => warn!("Failed to fetch private notes from the note transport: {err}"),
+        }
+
+        let mut sync_summary = SyncSummary::new_empty(self.store.get_sync_height().await?);
+        loop {
+            let (chunk_summary, reached_chain_tip) =
+                self.sync_state_chunk(self.sync_chunk_size).await?;
+            sync_summary.combine_with(chunk_summary);
+
+            if reached_chain_tip {
+                break;
+            }
+        }
+
+        // Remove irrelevant block headers
+        self.store.prune_irrelevant_blocks().await?;
+
+        Ok(sync_summary)
+    }
+
+    /// Returns the number of sync steps after which the sync updates are applied to the store.
+    /// If `None`, the updates are applied once the chain tip is reached.
+    pub fn sync_chunk_size(&self) -> Option<NonZeroUsize> {
+        self.sync_chunk_size
+    }
+
+    /// Sets the number of sync steps after which the sync updates are applied to the store. Each
+    /// step corresponds to a single sync request to the node, which returns the next block that
+    /// contains relevant changes for the client.
+    ///
+    /// If `None`, all updates are kept in memory and applied once the chain tip is reached.
+    pub fn set_sync_chunk_size(&mut self, sync_chunk_size: Option<NonZeroUsize>) {
+        self.sync_chunk_size = sync_chunk_size;
+    }
+
+    /// Registers a listener that will be notified of the [`SyncEvent`]s emitted while syncing the
+    /// client's state. Listeners are kept for the lifetime of the client and are notified during
+    /// every subsequent call to [`Client::sync_state`].
+    pub fn add_sync_listener(&mut self, listener: Arc<dyn SyncListener>) {
+        self.sync_listeners.push(listener);
+    }
+
+    /// Syncs the client's state for at most `max_steps` steps and applies the resulting updates to
+    /// the store. Returns the [`SyncSummary`] of the applied updates and whether the chain tip was
+    /// reached.
+    ///
+    /// The sync starts from the current sync height of the store, so consecutive calls resume
+    /// the sync from the last applied chunk.
+    async fn sync_state_chunk(
+        &self,
+        max_steps: Option<NonZeroUsize>,
+    ) -> Result<(SyncSummary, bool), ClientError> {
+        let note_screener = self.note_screener();
+        let mut state_sync = StateSync::new(
             self.rpc_api.clone(),
+            self.store.clone(),
             Box::new({
                 let store_clone = self.store.clone();
                 move |committed_note, public_note, note_screener, note_tags| {
@@ -133,6 +212,9 @@ impl Client {
             self.tx_graceful_blocks,
             note_screener,
         );
+        for listener in &self.sync_listeners {
+            state_sync.add_listener(listener.clone());
+        }
 
         // Get current state of the client
         let accounts = self
@@ -170,14 +252,15 @@ impl Client {
             .map(|(header, _has_notes)| header);
 
         // Get the sync update from the network
-        let state_sync_update = state_sync
-            .sync_state(
+        let (state_sync_update, reached_chain_tip) = state_sync
+            .sync_state_chunk(
                 PartialBlockchain::new(current_partial_mmr, block_headers)?,
                 accounts,
                 note_tags,
                 unspent_input_notes,
                 unspent_output_notes,
                 uncommitted_transactions,
+                max_steps,
             )
             .await?;
 
@@ -189,10 +272,7 @@ impl Client {
             .await
             .map_err(ClientError::StoreError)?;
 
-        // Remove irrelevant block headers
-        self.store.prune_irrelevant_blocks().await?;
-
-        Ok(sync_summary)
+        Ok((sync_summary, reached_chain_tip))
     }
 }
 
@@ -4,20 +4,21 @@ use alloc::{
     sync::Arc,
     vec::Vec,
 };
-use core::{future::Future, pin::Pin};
+use core::{future::Future, num::NonZeroUsize, pin::Pin};
 
 use miden_objects::{
     Word,
     account::{Account, AccountHeader, AccountId},
     block::{BlockHeader, BlockNumber},
     crypto::merkle::{InOrderIndex, MmrDelta, MmrPeaks, PartialMmr},
-    note::{NoteId, NoteTag},
-    transaction::PartialBlockchain,
+    note::{NoteId, NoteTag, Nullifier},
+    transaction::{PartialBlockchain, TransactionId},
 };
 use tracing::info;
 
 use super::{
-    AccountUpdates, BlockUpdates, StateSyncUpdate, state_sync_update::TransactionUpdateTracker,
+    AccountUpdates, BlockUpdates, StateSyncUpdate, SyncEvent, SyncListener,
+    state_sync_update::TransactionUpdateTracker,
 };
 use crate::{
     ClientError,
@@ -27,7 +28,7 @@ use crate::{
         domain::{note::CommittedNote, transaction::TransactionInclusion},
     },
     store::{InputNoteRecord, NoteFilter, OutputNoteRecord, Store, StoreError},
-    transaction::TransactionRecord,
+    transaction::{TransactionRecord, TransactionStatus},
 };
 
 // SYNC CALLBACKS
@@ -66,6 +67,9 @@ pub type OnNoteReceived = Box<
 pub struct StateSync {
     /// The RPC client used to communicate with the node.
     rpc_api: Arc<dyn NodeRpcClient + Send>,
+    /// The client's store, used to load the local state of the public accounts that need to be
+    /// updated.
+    store: Arc<dyn Store>,
     /// Callback to be executed when a new note inclusion is received.
     on_note_received: OnNoteReceived,
     /// The number of blocks that are considered old enough to discard pending transactions. If
@@ -73,6 +77,8 @@ pub struct StateSync {
     tx_graceful_blocks: Option<u32>,
     /// The note screener used to check the relevance of notes.
     note_screener: Arc<NoteScreener>,
+    /// Listeners that get notified of the changes detected during the sync.
+    listeners: Vec<Arc<dyn SyncListener>>,
 }
 
 impl StateSync {
@@ -81,24 +87,33 @@ impl StateSync {
     /// # Arguments
     ///
     /// * `rpc_api` - The RPC client used to communicate with the node.
+    /// * `store` - The client's store, used to load the public accounts that need to be updated.
     /// * `on_note_received` - A callback to be executed when a new note inclusion is received.
     /// * `tx_graceful_blocks` - The number of blocks that are considered old enough to discard.
     /// * `note_screener` - The note screener used to check the relevance of notes.
     pub fn new(
         rpc_api: Arc<dyn NodeRpcClient + Send>,
+        store: Arc<dyn Store>,
         on_note_received: OnNoteReceived,
         tx_graceful_blocks: Option<u32>,
         note_screener: NoteScreener,
     ) -> Self {
         Self {
             rpc_api,
+            store,
             on_note_received,
             tx_graceful_blocks,
             #[allow(clippy::arc_with_non_send_sync)]
             note_screener: Arc::new(note_screener),
+            listeners: Vec::new(),
         }
     }
 
+    /// Registers a listener that will be notified of the [`SyncEvent`]s emitted during the sync.
+    pub fn add_listener(&mut self, listener: Arc<dyn SyncListener>) {
+        self.listeners.push(listener);
+    }
+
     /// Syncs the state of the client with the chain tip of the node, returning the updates that
     /// should be applied to the store.
     ///
@@ -133,6 +148,40 @@ impl StateSync {
         unspent_output_notes: Vec<OutputNoteRecord>,
         uncommitted_transactions: Vec<TransactionRecord>,
     ) -> Result<StateSyncUpdate, ClientError> {
+        let (state_sync_update, _) = self
+            .sync_state_chunk(
+                current_partial_blockchain,
+                accounts,
+                note_tags,
+                unspent_input_notes,
+                unspent_output_notes,
+                uncommitted_transactions,
+                None,
+            )
+            .await?;
+
+        Ok(state_sync_update)
+    }
+
+    /// Syncs the state of the client with the node for at most `max_steps` sync steps, returning
+    /// the updates that should be applied to the store along with a flag that indicates whether
+    /// the chain tip was reached. If `max_steps` is `None`, the sync continues until the chain tip
+    /// is reached.
+    ///
+    /// The process is the same as the one described in [`StateSync::sync_state`], but the
+    /// returned update only covers the blocks processed during this chunk. The last block of the
+    /// chunk is always included in the block updates so that, once the update is applied to the
+    /// store, a new chunk can resume the sync from it.
+    pub async fn sync_state_chunk(
+        &self,
+        current_partial_blockchain: PartialBlockchain,
+        accounts: Vec<AccountHeader>,
+        note_tags: BTreeSet<NoteTag>,
+        unspent_input_notes: Vec<InputNoteRecord>,
+        unspent_output_notes: Vec<OutputNoteRecord>,
+        uncommitted_transactions: Vec<TransactionRecord>,
+        max_steps: Option<NonZeroUsize>,
+    ) -> Result<(StateSyncUpdate, bool), ClientError> {
         let block_num =
             current_partial_blockchain.chain_length().checked_sub(1).unwrap_or_default();
 
@@ -145,23 +194,32 @@ impl StateSync {
 
         let mut partial_mmr = current_partial_blockchain.mmr().clone();
         let note_tags = Arc::new(note_tags);
-        loop {
+        let mut steps = 0;
+        let reached_chain_tip = loop {
+            steps += 1;
+            let is_last_step = max_steps.is_some_and(|max_steps| steps >= max_steps.get());
+
             if !self
                 .sync_state_step(
                     &mut state_sync_update,
                     &mut partial_mmr,
                     &accounts,
                     note_tags.clone(),
+                    is_last_step,
                 )
                 .await?
             {
-                break;
+                break true;
             }
-        }
+
+            if is_last_step {
+                break false;
+            }
+        };
 
         self.sync_nullifiers(&mut state_sync_update, block_num).await?;
 
-        Ok(state_sync_update)
+        Ok((state_sync_update, reached_chain_tip))
     }
 
     /// Executes a single step of the state sync process, returning `true` if the client should
@@ -174,6 +232,9 @@ impl StateSync {
     /// The `sync_state_update` field of the struct will be updated with the new changes from this
     /// step.
     ///
+    /// If `track_block` is `true`, the new block header is stored even if it doesn't contain
+    /// relevant notes. This is used to persist the last block of a sync chunk.
+    ///
     /// This function returns whether the state sync process must continue, depending on whether
     /// the chain tip was reached already.
     async fn sync_state_step(
@@ -182,9 +243,12 @@ impl StateSync {
         current_partial_mmr: &mut PartialMmr,
         accounts: &[AccountHeader],
         note_tags: Arc<BTreeSet<NoteTag>>,
+        track_block: bool,
     ) -> Result<bool, ClientError> {
         let account_ids: Vec<AccountId> = accounts.iter().map(AccountHeader::id).collect();
 
+        self.emit(&SyncEvent::StepStarted { block_num: state_sync_update.block_num });
+
         let response = self
             .rpc_api
             .sync_state(state_sync_update.block_num, &account_ids, note_tags.as_ref())
@@ -195,6 +259,7 @@ impl StateSync {
             return Ok(false);
         }
 
+        let previous_block_num = state_sync_update.block_num;
         let new_block_num = response.block_header.block_num();
         state_sync_update.block_num = new_block_num;
 
@@ -202,6 +267,8 @@ impl StateSync {
             &mut state_sync_update.account_updates,
             accounts,
             &response.account_commitment_updates,
+            previous_block_num,
+            new_block_num,
         )
         .await?;
 
@@ -227,9 +294,14 @@ impl StateSync {
             response.mmr_delta,
         )?;
 
+        self.emit(&SyncEvent::MmrPeaksAdvanced {
+            block_num: new_block_num,
+            peaks: new_mmr_peaks.clone(),
+        });
+
         let mut new_blocks = vec![];
-        if found_relevant_note || response.chain_tip == new_block_num {
-            // Only track relevant blocks or the chain tip
+        if found_relevant_note || track_block || response.chain_tip == new_block_num {
+            // Only track relevant blocks, the chain tip or the last block of a sync chunk
             new_blocks.push((response.block_header, found_relevant_note, new_mmr_peaks));
         }
 
@@ -237,6 +309,11 @@ impl StateSync {
             .block_updates
             .extend(BlockUpdates::new(new_blocks, new_authentication_nodes));
 
+        self.emit(&SyncEvent::StepCompleted {
+            block_num: new_block_num,
+            chain_tip: response.chain_tip,
+        });
+
         if response.chain_tip == new_block_num {
             Ok(false)
         } else {
@@ -247,11 +324,19 @@ impl StateSync {
     // HELPERS
     // --------------------------------------------------------------------------------------------
 
+    /// Notifies all registered listeners of the provided event.
+    fn emit(&self, event: &SyncEvent) {
+        for listener in &self.listeners {
+            listener.on_sync_event(event);
+        }
+    }
+
     /// Compares the state of tracked accounts with the updates received from the node. The method
     /// updates the `state_sync_update` field with the details of the accounts that need to be
     /// updated.
     ///
-    /// The account updates might include:
+    /// The accounts are synced from `from_block` to `to_block`. The account updates might
+    /// include:
     /// * Public accounts that have been updated in the node.
     /// * Network accounts that have been updated in the node and are being tracked by the client.
     /// * Private accounts that have been marked as mismatched because the current commitment
@@ -262,12 +347,20 @@ impl StateSync {
         account_updates: &mut AccountUpdates,
         accounts: &[AccountHeader],
         account_commitment_updates: &[(AccountId, Word)],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
     ) -> Result<(), ClientError> {
         let (public_accounts, private_accounts): (Vec<_>, Vec<_>) =
             accounts.iter().partition(|account_header| !account_header.id().is_private());
 
         let updated_public_accounts = self
-            .get_updated_public_accounts(account_commitment_updates, &public_accounts)
+            .get_updated_public_accounts(
+                account_updates,
+                account_commitment_updates,
+                &public_accounts,
+                from_block,
+                to_block,
+            )
             .await?;
 
         let mismatched_private_accounts = account_commitment_updates
@@ -280,33 +373,63 @@ impl StateSync {
             .copied()
             .collect::<Vec<_>>();
 
+        for account in &updated_public_accounts {
+            self.emit(&SyncEvent::AccountUpdated { account_id: account.id() });
+        }
+        for (account_id, _) in &mismatched_private_accounts {
+            self.emit(&SyncEvent::AccountLocked { account_id: *account_id });
+        }
+
         account_updates
             .extend(AccountUpdates::new(updated_public_accounts, mismatched_private_accounts));
 
         Ok(())
     }
 
-    /// Queries the node for the latest state of the public accounts that don't match the current
-    /// state of the client.
+    /// Queries the node for the latest state of the public accounts whose commitment in the node
+    /// doesn't match the current state of the client.
+    ///
+    /// Only the accounts included in the `account_commitment_updates` received from the node are
+    /// loaded from the store and requested. Accounts updated in a previous step of the sync aren't
+    /// stored yet, so their latest state is taken from `account_updates`.
     async fn get_updated_public_accounts(
         &self,
-        account_updates: &[(AccountId, Word)],
+        account_updates: &AccountUpdates,
+        account_commitment_updates: &[(AccountId, Word)],
         current_public_accounts: &[&AccountHeader],
+        from_block: BlockNumber,
+        to_block: BlockNumber,
     ) -> Result<Vec<Account>, ClientError> {
         let mut mismatched_public_accounts = vec![];
 
-        for (id, commitment) in account_updates {
+        for (id, commitment) in account_commitment_updates {
             // check if this updated account state is tracked by the client
-            if let Some(account) = current_public_accounts
+            let Some(header) = current_public_accounts.iter().find(|acc| *id == acc.id()) else {
+                continue;
+            };
+
+            let updated_account = account_updates
+                .updated_public_accounts()
                 .iter()
-                .find(|acc| *id == acc.id() && *commitment != acc.commitment())
-            {
-                mismatched_public_accounts.push(*account);
-            }
+                .rev()
+                .find(|account| account.id() == *id);
+
+            let local_account = match updated_account {
+                Some(account) => account.clone(),
+                None if header.commitment() == *commitment => continue,
+                None => self
+                    .store
+                    .get_account(*id)
+                    .await?
+                    .ok_or(StoreError::AccountDataNotFound(*id))?
+                    .into(),
+            };
+
+            mismatched_public_accounts.push((local_account, *commitment));
         }
 
         self.rpc_api
-            .get_updated_public_accounts(&mismatched_public_accounts)
+            .get_updated_public_accounts(mismatched_public_accounts, from_block, to_block)
             .await
             .map_err(ClientError::RpcError)
     }
@@ -356,6 +479,11 @@ impl StateSync {
                     public_note,
                     block_header,
                 )?;
+
+                self.emit(&SyncEvent::NoteCommitted {
+                    note_id: *committed_note.note_id(),
+                    block_num: block_header.block_num(),
+                });
             }
         }
 
@@ -396,11 +524,9 @@ impl StateSync {
         // response.block_header.block_num())
 
         // Check for new nullifiers for input notes that were updated
-        let nullifiers_tags: Vec<u16> = state_sync_update
-            .note_updates
-            .unspent_nullifiers()
-            .map(|nullifier| nullifier.prefix())
-            .collect();
+        let unspent_nullifiers: Vec<Nullifier> =
+            state_sync_update.note_updates.unspent_nullifiers().collect();
+        let nullifiers_tags: Vec<u16> = unspent_nullifiers.iter().map(Nullifier::prefix).collect();
 
         let mut new_nullifiers = self
             .rpc_api
@@ -411,7 +537,17 @@ impl StateSync {
         // changes between the sync_state and the check_nullifier calls)
         new_nullifiers.retain(|update| update.block_num <= state_sync_update.block_num.as_u32());
 
+        let previously_updated_transactions: BTreeSet<TransactionId> =
+            state_sync_update.transaction_updates.updated_transaction_ids().collect();
+
         for nullifier_update in new_nullifiers {
+            if unspent_nullifiers.contains(&nullifier_update.nullifier) {
+                self.emit(&SyncEvent::NullifierConsumed {
+                    nullifier: nullifier_update.nullifier,
+                    block_num: nullifier_update.block_num.into(),
+                });
+            }
+
             state_sync_update.note_updates.apply_nullifiers_state_transitions(
                 &nullifier_update,
                 state_sync_update.transaction_updates.committed_transactions(),
@@ -425,6 +561,11 @@ impl StateSync {
                 .apply_input_note_nullified(nullifier_update.nullifier);
         }
 
+        self.emit_transaction_events(
+            &state_sync_update.transaction_updates,
+            &previously_updated_transactions,
+        );
+
         Ok(())
     }
 
@@ -440,11 +581,46 @@ impl StateSync {
         new_sync_height: BlockNumber,
         transaction_inclusions: &[TransactionInclusion],
     ) {
+        let previously_updated_transactions: BTreeSet<TransactionId> =
+            transaction_updates.updated_transaction_ids().collect();
+
         for transaction_inclusion in transaction_inclusions {
             transaction_updates.apply_transaction_inclusion(transaction_inclusion);
         }
 
         transaction_updates.apply_sync_height_update(new_sync_height, self.tx_graceful_blocks);
+
+        self.emit_transaction_events(transaction_updates, &previously_updated_transactions);
+    }
+
+    /// Emits the events for the transactions in `transaction_updates` that were committed or
+    /// discarded and weren't part of `previously_updated_transactions`.
+    fn emit_transaction_events(
+        &self,
+        transaction_updates: &TransactionUpdateTracker,
+        previously_updated_transactions: &BTreeSet<TransactionId>,
+    ) {
+        if self.listeners.is_empty() {
+            return;
+        }
+
+        for transaction in transaction_updates
+            .committed_transactions()
+            .chain(transaction_updates.discarded_transactions())
+            .filter(|transaction| !previously_updated_transactions.contains(&transaction.id))
+        {
+            let event = match transaction.status {
+                TransactionStatus::Committed(block_num) => SyncEvent::TransactionCommitted {
+                    transaction_id: transaction.id,
+                    block_num,
+                },
+                TransactionStatus::Discarded(cause) => {
+                    SyncEvent::TransactionDiscarded { transaction_id: transaction.id, cause }
+                },
+                TransactionStatus::Pending => continue,
+            };
+            self.emit(&event);
+        }
     }
 }
 
@@ -98,6 +98,22 @@ impl NoteTagRecord {
     }
 }
 
+impl Serializable for NoteTagRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.tag.write_into(target);
+        self.source.write_into(target);
+    }
+}
+
+impl Deserializable for NoteTagRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let tag = NoteTag::read_from(source)?;
+        let source = NoteTagSource::read_from(source)?;
+
+        Ok(Self { tag, source })
+    }
+}
+
 impl Serializable for NoteTagSource {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         match self {
@@ -32,7 +32,7 @@ use crate::{
     auth::AuthSecretKey,
     builder::ClientBuilder,
     crypto::FeltRng,
-    keystore::FilesystemKeyStore,
+    keystore::{FilesystemKeyStore, KeyStore},
     note::{Note, create_p2id_note},
     rpc::{Endpoint, RpcError, TonicRpcClient},
     store::{NoteFilter, TransactionFilter, sqlite_store::SqliteStore},
@@ -145,7 +145,7 @@ pub fn create_test_store_path() -> std::path::PathBuf {
 pub async fn insert_new_wallet(
     client: &mut Client,
     storage_mode: AccountStorageMode,
-    keystore: &TestClientKeyStore,
+    keystore: &dyn KeyStore,
 ) -> Result<(Account, Word, SecretKey), ClientError> {
     let mut init_seed = [0u8; 32];
     client.rng().fill_bytes(&mut init_seed);
@@ -157,13 +157,13 @@ pub async fn insert_new_wallet(
 pub async fn insert_new_wallet_with_seed(
     client: &mut Client,
     storage_mode: AccountStorageMode,
-    keystore: &TestClientKeyStore,
+    keystore: &dyn KeyStore,
     init_seed: [u8; 32],
 ) -> Result<(Account, Word, SecretKey), ClientError> {
     let key_pair = SecretKey::with_rng(client.rng());
     let pub_key = key_pair.public_key();
 
-    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone())).unwrap();
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone())).await.unwrap();
 
     let (account, seed) = AccountBuilder::new(init_seed)
         .account_type(AccountType::RegularAccountImmutableCode)
@@ -182,12 +182,12 @@ pub async fn insert_new_wallet_with_seed(
 pub async fn insert_new_fungible_faucet(
     client: &mut Client,
     storage_mode: AccountStorageMode,
-    keystore: &TestClientKeyStore,
+    keystore: &dyn KeyStore,
 ) -> Result<(Account, Word, SecretKey), ClientError> {
     let key_pair = SecretKey::with_rng(client.rng());
     let pub_key = key_pair.public_key();
 
-    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone())).unwrap();
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone())).await.unwrap();
 
     // we need to use an initial seed to create the wallet account
     let mut init_seed = [0u8; 32];
@@ -353,7 +353,7 @@ pub const TRANSFER_AMOUNT: u64 = 59;
 pub async fn setup_two_wallets_and_faucet(
     client: &mut TestClient,
     accounts_storage_mode: AccountStorageMode,
-    keystore: &TestClientKeyStore,
+    keystore: &dyn KeyStore,
 ) -> (Account, Account, Account) {
     // Ensure clean state
     assert!(client.get_account_headers().await.unwrap().is_empty());
@@ -384,7 +384,7 @@ pub async fn setup_two_wallets_and_faucet(
 pub async fn setup_wallet_and_faucet(
     client: &mut TestClient,
     accounts_storage_mode: AccountStorageMode,
-    keystore: &TestClientKeyStore,
+    keystore: &dyn KeyStore,
 ) -> (Account, Account) {
     let (faucet_account, ..) = insert_new_fungible_faucet(client, accounts_storage_mode, keystore)
         .await
@@ -1,9 +1,9 @@
-use alloc::{collections::BTreeSet, sync::Arc, vec::Vec};
+use alloc::{collections::BTreeSet, format, string::ToString, sync::Arc, vec::Vec};
 
 use miden_lib::transaction::TransactionKernel;
 use miden_objects::{
     Felt, Word,
-    account::{AccountCode, AccountDelta, AccountId},
+    account::{Account, AccountCode, AccountDelta, AccountId},
     block::{BlockHeader, BlockNumber, ProvenBlock},
     crypto::{
         merkle::{Forest, MerklePath, Mmr, MmrProof, SmtProof},
@@ -18,16 +18,18 @@ use miden_tx::utils::sync::RwLock;
 
 use crate::{
     Client,
+    note_transport::{EncryptedNote, NoteTransport, NoteTransportError},
     rpc::{
         NodeRpcClient, RpcError,
         domain::{
-            account::{AccountProofs, FetchedAccount},
+            account::{AccountProofs, AccountUpdateSummary, FetchedAccount},
             note::{CommittedNote, FetchedNote, NoteSyncInfo},
             nullifier::NullifierUpdate,
             sync::StateSyncInfo,
         },
         generated::{
-            note::NoteSyncRecord, responses::SyncStateResponse, transaction::TransactionSummary,
+            account::AccountSummary, note::NoteSyncRecord, responses::SyncStateResponse,
+            transaction::TransactionSummary,
         },
     },
     transaction::ForeignAccount,
@@ -42,6 +44,9 @@ pub type MockClient = Client;
 #[derive(Clone)]
 pub struct MockRpcApi {
     committed_transactions: Arc<RwLock<Vec<TransactionSummary>>>, /* TODO: Should this be tracked by the mock_chain? */
+    /// Public account states committed with [`MockRpcApi::commit_account_update`], along with the
+    /// block in which they were committed and the delta from the previous state.
+    account_updates: Arc<RwLock<Vec<(BlockNumber, Account, AccountDelta)>>>,
     pub mock_chain: Arc<RwLock<MockChain>>,
 }
 impl Default for MockRpcApi {
@@ -55,6 +60,7 @@ impl MockRpcApi {
         let mock_chain = MockChain::new();
         let api = Self {
             committed_transactions: Arc::new(RwLock::new(vec![])),
+            account_updates: Arc::new(RwLock::new(vec![])),
             mock_chain: Arc::new(RwLock::new(mock_chain)),
         };
 
@@ -101,6 +107,19 @@ impl MockRpcApi {
         mock_chain.prove_next_block().unwrap();
     }
 
+    /// Commits a new state of a public account in a new block, along with the delta from its
+    /// previous state. Returns the number of the block.
+    ///
+    /// The account is then reported by [`NodeRpcClient::sync_state`] and its state can be fetched
+    /// with [`NodeRpcClient::get_account_details`] and [`NodeRpcClient::get_account_state_delta`].
+    pub fn commit_account_update(&self, account: Account, delta: AccountDelta) -> BlockNumber {
+        self.seal_block(vec![], vec![]);
+        let block_num = self.get_chain_tip_block_num();
+        self.account_updates.write().push((block_num, account, delta));
+
+        block_num
+    }
+
     /// Returns the current MMR of the blockchain.
     pub fn get_mmr(&self) -> Mmr {
         self.mock_chain.read().blockchain().as_mmr().clone()
@@ -120,6 +139,7 @@ impl MockRpcApi {
     fn get_sync_state_request(
         &self,
         request_block_num: BlockNumber,
+        account_ids: &[AccountId],
         note_tags: &BTreeSet<NoteTag>,
     ) -> Result<SyncStateResponse, RpcError> {
         // Determine the next block number to sync
@@ -165,11 +185,30 @@ impl MockRpcApi {
             .cloned()
             .collect::<Vec<_>>();
 
+        // Report the latest state of the requested accounts updated up to the next block
+        let mut accounts: Vec<AccountSummary> = vec![];
+        for (block_num, account, _) in self.account_updates.read().iter() {
+            if !account_ids.contains(&account.id())
+                || *block_num <= request_block_num
+                || *block_num > next_block_num
+            {
+                continue;
+            }
+
+            let summary = AccountSummary {
+                account_id: Some(account.id().into()),
+                account_commitment: Some(account.commitment().into()),
+                block_num: block_num.as_u32(),
+            };
+            accounts.retain(|other| other.account_id != summary.account_id);
+            accounts.push(summary);
+        }
+
         Ok(SyncStateResponse {
             chain_tip: self.get_chain_tip_block_num().as_u32(),
             block_header: Some(next_block.into()),
             mmr_delta: Some(mmr_delta.try_into()?),
-            accounts: vec![],
+            accounts,
             transactions,
             notes,
         })
@@ -225,7 +264,7 @@ impl NodeRpcClient for MockRpcApi {
         block_num: BlockNumber,
         note_tags: &BTreeSet<NoteTag>,
     ) -> Result<NoteSyncInfo, RpcError> {
-        let response = self.get_sync_state_request(block_num, note_tags)?;
+        let response = self.get_sync_state_request(block_num, &[], note_tags)?;
 
         let response = NoteSyncInfo {
             chain_tip: response.chain_tip,
@@ -253,10 +292,10 @@ impl NodeRpcClient for MockRpcApi {
     async fn sync_state(
         &self,
         block_num: BlockNumber,
-        _account_ids: &[AccountId],
+        account_ids: &[AccountId],
         note_tags: &BTreeSet<NoteTag>,
     ) -> Result<StateSyncInfo, RpcError> {
-        let response = self.get_sync_state_request(block_num, note_tags)?;
+        let response = self.get_sync_state_request(block_num, account_ids, note_tags)?;
 
         Ok(response.try_into().unwrap())
     }
@@ -327,11 +366,16 @@ impl NodeRpcClient for MockRpcApi {
         Ok(self.get_chain_tip_block_num())
     }
 
-    async fn get_account_details(
-        &self,
-        _account_id: AccountId,
-    ) -> Result<FetchedAccount, RpcError> {
-        unimplemented!("shouldn't be used for now")
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        let account_updates = self.account_updates.read();
+        let (block_num, account, _) = account_updates
+            .iter()
+            .rev()
+            .find(|(_, account, _)| account.id() == account_id)
+            .ok_or(RpcError::ExpectedDataMissing(format!("account {account_id}")))?;
+
+        let summary = AccountUpdateSummary::new(account.commitment(), block_num.as_u32());
+        Ok(FetchedAccount::Public(account.clone(), summary))
     }
 
     async fn get_account_proofs(
@@ -371,11 +415,29 @@ impl NodeRpcClient for MockRpcApi {
 
     async fn get_account_state_delta(
         &self,
-        _account_id: AccountId,
-        _from_block: BlockNumber,
-        _to_block: BlockNumber,
+        account_id: AccountId,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
     ) -> Result<AccountDelta, RpcError> {
-        unimplemented!("shouldn't be used for now")
+        let mut deltas = self
+            .account_updates
+            .read()
+            .iter()
+            .filter(|(block_num, account, _)| {
+                account.id() == account_id && *block_num > from_block && *block_num <= to_block
+            })
+            .map(|(_, _, delta)| delta.clone())
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        let mut delta = deltas.next().ok_or(RpcError::ExpectedDataMissing(format!(
+            "delta of account {account_id} between blocks {from_block} and {to_block}"
+        )))?;
+        for other in deltas {
+            delta.merge(other).map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
+        }
+
+        Ok(delta)
     }
 
     async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
@@ -391,3 +453,45 @@ impl NodeRpcClient for MockRpcApi {
         Ok(block)
     }
 }
+
+// MOCK NOTE TRANSPORT
+// ================================================================================================
+
+/// An in-memory [`NoteTransport`] that behaves like a note relay, intended to be shared between
+/// the clients of a test.
+#[derive(Clone, Default)]
+pub struct MockNoteTransport {
+    notes: Arc<RwLock<Vec<EncryptedNote>>>,
+}
+
+impl MockNoteTransport {
+    /// Returns all the notes sent through the transport.
+    pub fn sent_notes(&self) -> Vec<EncryptedNote> {
+        self.notes.read().clone()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl NoteTransport for MockNoteTransport {
+    async fn send_note(&self, note: EncryptedNote) -> Result<(), NoteTransportError> {
+        self.notes.write().push(note);
+        Ok(())
+    }
+
+    async fn fetch_notes(
+        &self,
+        tags: &[NoteTag],
+        cursor: u64,
+    ) -> Result<(Vec<EncryptedNote>, u64), NoteTransportError> {
+        let notes = self.notes.read();
+        let start = usize::try_from(cursor).unwrap_or(usize::MAX).min(notes.len());
+        let fetched_notes = notes[start..]
+            .iter()
+            .filter(|note| tags.contains(&note.tag()))
+            .cloned()
+            .collect();
+
+        Ok((fetched_notes, notes.len() as u64))
+    }
+}
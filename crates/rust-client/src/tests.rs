@@ -1,6 +1,23 @@
 use alloc::vec::Vec;
-use std::{boxed::Box, collections::BTreeSet, env::temp_dir, println, sync::Arc};
+use core::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use std::{
+    boxed::Box,
+    collections::BTreeSet,
+    env::temp_dir,
+    println,
+    rc::Rc,
+    string::ToString,
+    sync::{Arc, Mutex},
+};
 
+use chacha20poly1305::{
+    KeyInit, XChaCha20Poly1305, XNonce,
+    aead::{Aead, Payload},
+};
 // TESTS
 // ================================================================================================
 use miden_lib::{
@@ -17,14 +34,16 @@ use miden_objects::{
         Account, AccountBuilder, AccountCode, AccountHeader, AccountId, AccountStorageMode,
         AccountType, AuthSecretKey,
     },
-    asset::{Asset, FungibleAsset, TokenSymbol},
+    asset::{Asset, AssetVault, FungibleAsset, TokenSymbol},
+    block::{BlockHeader, BlockNumber},
     crypto::{
         dsa::rpo_falcon512::{PublicKey, SecretKey},
+        merkle::Mmr,
         rand::{FeltRng, RpoRandomCoin},
     },
     note::{
-        Note, NoteAssets, NoteExecutionHint, NoteFile, NoteInputs, NoteMetadata, NoteRecipient,
-        NoteTag, NoteType,
+        Note, NoteAssets, NoteExecutionHint, NoteFile, NoteHeader, NoteId, NoteInputs,
+        NoteMetadata, NoteRecipient, NoteTag, NoteType,
     },
     testing::account_id::{
         ACCOUNT_ID_PRIVATE_SENDER, ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1,
@@ -37,6 +56,7 @@ use miden_objects::{
 };
 use miden_tx::{
     TransactionExecutorError,
+    auth::TransactionAuthenticator,
     utils::{Deserializable, Serializable},
 };
 use rand::{Rng, RngCore, rngs::StdRng};
@@ -44,15 +64,21 @@ use uuid::Uuid;
 
 use crate::{
     Client, ClientError, DebugMode,
+    account::{AccountStateSelector, RelatedData},
     builder::ClientBuilder,
-    keystore::FilesystemKeyStore,
-    note::NoteRelevance,
+    keystore::{
+        EncryptedFilesystemKeyStore, ExternalSignerKeyStore, FilesystemKeyStore, KeyStore,
+        KeyStoreError, SignerRequest, SignerResponse, SignerTransport, decrypt_exported_keys,
+        encrypt_exported_keys,
+    },
+    note::{NoteDecryptionKey, NoteRelevance, NoteRelevanceChecker, NoteScreenerError},
+    note_transport::{EncryptedNote, NoteChannelKey, NoteTransport},
     rpc::NodeRpcClient,
     store::{
-        InputNoteRecord, InputNoteState, NoteFilter, TransactionFilter,
+        InputNoteRecord, InputNoteState, NoteFilter, StoreError, StoreSnapshot, TransactionFilter,
         input_note_states::ConsumedAuthenticatedLocalNoteState, sqlite_store::SqliteStore,
     },
-    sync::NoteTagSource,
+    sync::{AutoSync, AutoSyncConfig, AutoSyncTrigger, NoteTagSource, SyncEvent, SyncSummary},
     testing::{
         common::{
             ACCOUNT_ID_REGULAR, MINT_AMOUNT, RECALL_HEIGHT_DELTA, TRANSFER_AMOUNT,
@@ -60,11 +86,13 @@ use crate::{
             execute_failing_tx, execute_tx, execute_tx_and_sync, mint_and_consume, mint_note,
             setup_two_wallets_and_faucet, setup_wallet_and_faucet, wait_for_tx,
         },
-        mock::{MockClient, MockRpcApi},
+        mock::{MockClient, MockNoteTransport, MockRpcApi},
     },
     transaction::{
-        DiscardCause, PaymentNoteDescription, SwapTransactionData, TransactionRequestBuilder,
-        TransactionRequestError, TransactionStatus,
+        DiscardCause, LocalTransactionProver, OfflineTransaction, OfflineTransactionExecutor,
+        PaymentNoteDescription, PendingTransactionRecord, PendingTransactionStage,
+        SwapTransactionData, TransactionChain, TransactionRequestBuilder, TransactionRequestError,
+        TransactionStatus,
     },
 };
 
@@ -72,6 +100,64 @@ use crate::{
 /// stale.
 const TX_GRACEFUL_BLOCKS: u32 = 20;
 
+// STORE TESTS
+// ================================================================================================
+
+/// Defines a `sqlite` and a `memory` test for each of the specified functions, which receive the
+/// [`TestStore`] to create their client with.
+macro_rules! store_tests {
+    ($($test:ident),* $(,)?) => {
+        mod store_tests {
+            $(
+                mod $test {
+                    use crate::tests::TestStore;
+
+                    #[tokio::test]
+                    async fn sqlite() {
+                        crate::tests::$test(TestStore::Sqlite).await;
+                    }
+
+                    #[tokio::test]
+                    async fn memory() {
+                        crate::tests::$test(TestStore::Memory).await;
+                    }
+                }
+            )*
+        }
+    };
+}
+
+store_tests!(
+    input_notes_round_trip,
+    get_input_note,
+    insert_basic_account,
+    insert_faucet_account,
+    insert_same_account_twice_fails,
+    account_code,
+    get_account_by_id,
+    sync_state,
+    sync_state_mmr,
+    sync_state_tags,
+    tags,
+    mint_transaction,
+    pending_transaction_pipeline,
+    transaction_chain,
+    account_history_and_diff,
+    account_removal_and_garbage_collection,
+    import_note_validation,
+    import_processing_note_returns_error,
+    added_notes,
+    p2id_transfer,
+    p2ide_transfer_consumed_by_target,
+    get_consumable_notes,
+    account_balances,
+    store_snapshot_restore,
+    store_snapshot_states_sharing_nonce,
+    get_output_notes,
+    subsequent_discarded_transactions,
+    input_note_checks,
+);
+
 // HELPERS
 // ================================================================================================
 
@@ -103,7 +189,24 @@ pub async fn create_test_client_builder() -> (ClientBuilder, MockRpcApi, Filesys
 }
 
 pub async fn create_test_client() -> (MockClient, MockRpcApi, FilesystemKeyStore<StdRng>) {
-    let (builder, rpc_api, keystore) = create_test_client_builder().await;
+    create_test_client_with_store(TestStore::Sqlite).await
+}
+
+/// Store backends the client tests are run against, see `store_tests!`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestStore {
+    Sqlite,
+    Memory,
+}
+
+pub async fn create_test_client_with_store(
+    store: TestStore,
+) -> (MockClient, MockRpcApi, FilesystemKeyStore<StdRng>) {
+    let (mut builder, rpc_api, keystore) = create_test_client_builder().await;
+    if store == TestStore::Memory {
+        builder = builder.in_memory_store();
+    }
+
     let mut client = builder.build().await.unwrap();
     client.ensure_genesis_in_place().await.unwrap();
 
@@ -124,7 +227,7 @@ async fn insert_new_wallet(
     let key_pair = SecretKey::with_rng(&mut client.rng);
     let pub_key = key_pair.public_key();
 
-    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).unwrap();
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).await.unwrap();
 
     let mut init_seed = [0u8; 32];
     client.rng.fill_bytes(&mut init_seed);
@@ -150,7 +253,7 @@ async fn insert_new_fungible_faucet(
     let key_pair = SecretKey::with_rng(&mut client.rng);
     let pub_key = key_pair.public_key();
 
-    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).unwrap();
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair)).await.unwrap();
 
     // we need to use an initial seed to create the wallet account
     let mut init_seed = [0u8; 32];
@@ -175,10 +278,9 @@ async fn insert_new_fungible_faucet(
 // TESTS
 // ================================================================================================
 
-#[tokio::test]
-async fn input_notes_round_trip() {
+async fn input_notes_round_trip(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, rpc_api, keystore) = create_test_client().await;
+    let (mut client, rpc_api, keystore) = create_test_client_with_store(store).await;
 
     insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
         .await
@@ -217,10 +319,9 @@ async fn input_notes_round_trip() {
     }
 }
 
-#[tokio::test]
-async fn get_input_note() {
+async fn get_input_note(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, rpc_api, _) = create_test_client().await;
+    let (mut client, rpc_api, _) = create_test_client_with_store(store).await;
     // Get note from mocked RPC backend since any note works here
     let original_note = rpc_api.get_available_notes()[0].note().unwrap().clone();
 
@@ -242,10 +343,9 @@ async fn get_input_note() {
     assert_eq!(recorded_note.id(), retrieved_note.id());
 }
 
-#[tokio::test]
-async fn insert_basic_account() {
+async fn insert_basic_account(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (mut client, _rpc_api, keystore) = create_test_client_with_store(store).await;
 
     // Insert Account
     let account_insert_result =
@@ -273,10 +373,9 @@ async fn insert_basic_account() {
     assert_eq!(account_seed, fetched_account_seed.unwrap());
 }
 
-#[tokio::test]
-async fn insert_faucet_account() {
+async fn insert_faucet_account(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (mut client, _rpc_api, keystore) = create_test_client_with_store(store).await;
 
     // Insert Account
     let account_insert_result =
@@ -304,10 +403,9 @@ async fn insert_faucet_account() {
     assert_eq!(account_seed, fetched_account_seed.unwrap());
 }
 
-#[tokio::test]
-async fn insert_same_account_twice_fails() {
+async fn insert_same_account_twice_fails(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, _rpc_api, _) = create_test_client().await;
+    let (mut client, _rpc_api, _) = create_test_client_with_store(store).await;
 
     let account = Account::mock(
         ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2,
@@ -320,10 +418,9 @@ async fn insert_same_account_twice_fails() {
     assert!(client.add_account(&account, Some(Word::default()), false).await.is_err());
 }
 
-#[tokio::test]
-async fn account_code() {
+async fn account_code(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, _rpc_api, _) = create_test_client().await;
+    let (mut client, _rpc_api, _) = create_test_client_with_store(store).await;
 
     let account = Account::mock(
         ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
@@ -344,10 +441,9 @@ async fn account_code() {
     assert_eq!(*account.code(), *retrieved_acc.account().code());
 }
 
-#[tokio::test]
-async fn get_account_by_id() {
+async fn get_account_by_id(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, _rpc_api, _) = create_test_client().await;
+    let (mut client, _rpc_api, _) = create_test_client_with_store(store).await;
 
     let account = Account::mock(
         ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
@@ -370,10 +466,9 @@ async fn get_account_by_id() {
     assert!(client.get_account_header_by_id(invalid_id).await.unwrap().is_none());
 }
 
-#[tokio::test]
-async fn sync_state() {
+async fn sync_state(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, rpc_api, _) = create_test_client().await;
+    let (mut client, rpc_api, _) = create_test_client_with_store(store).await;
 
     // Import first mockchain note as expected
     let expected_notes = rpc_api
@@ -417,10 +512,9 @@ async fn sync_state() {
     assert_eq!(client.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
 }
 
-#[tokio::test]
-async fn sync_state_mmr() {
+async fn sync_state_mmr(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, rpc_api, keystore) = create_test_client().await;
+    let (mut client, rpc_api, keystore) = create_test_client_with_store(store).await;
     // Import note and create wallet so that synced notes do not get discarded (due to being
     // irrelevant)
     insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
@@ -491,10 +585,202 @@ async fn sync_state_mmr() {
 }
 
 #[tokio::test]
-async fn sync_state_tags() {
-    // generate test client with a random store name
+async fn sync_state_in_chunks() {
+    let (builder, rpc_api, keystore) = create_test_client_builder().await;
+    let mut client = builder.sync_chunk_size(NonZeroUsize::new(1).unwrap()).build().await.unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+
+    insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    let notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+
+    for note in &notes {
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    // Each relevant block is applied to the store in its own chunk
+    let sync_details = client.sync_state().await.unwrap();
+
+    assert_eq!(sync_details.block_num, rpc_api.get_chain_tip_block_num());
+    assert_eq!(client.get_sync_height().await.unwrap(), rpc_api.get_chain_tip_block_num());
+    assert_eq!(client.get_input_notes(NoteFilter::Committed).await.unwrap().len(), 1);
+    assert_eq!(client.get_input_notes(NoteFilter::Consumed).await.unwrap().len(), 1);
+    assert_eq!(sync_details.consumed_notes.len(), 1);
+
+    // The partial MMR rebuilt from the store should be the same as the one from a single chunk
+    // sync
+    let partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    assert_eq!(partial_mmr.forest().num_leaves(), 6);
+    assert!(partial_mmr.open(1).unwrap().is_some());
+    assert!(partial_mmr.open(4).unwrap().is_some());
+
+    let mmr_proof = partial_mmr.open(4).unwrap().unwrap();
+    let (block_4, _) = rpc_api.get_block_header_by_number(Some(4.into()), false).await.unwrap();
+    partial_mmr.peaks().verify(block_4.commitment(), mmr_proof).unwrap();
+
+    // Intermediate chunk blocks without relevant notes are pruned after the sync
+    assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 2);
+
+    // A new sync resumes from the last applied chunk
+    let sync_details = client.sync_state().await.unwrap();
+    assert!(sync_details.is_empty());
+}
+
+#[tokio::test]
+async fn sync_state_events() {
     let (mut client, rpc_api, _) = create_test_client().await;
 
+    let expected_notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+
+    for note in &expected_notes {
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    client.add_sync_listener(Arc::new({
+        let events = events.clone();
+        move |event: &SyncEvent| events.lock().unwrap().push(event.clone())
+    }));
+
+    client.sync_state().await.unwrap();
+
+    let events = events.lock().unwrap();
+    assert!(
+        matches!(events.first(), Some(SyncEvent::StepStarted { block_num }) if block_num.as_u32() == 0)
+    );
+    assert!(matches!(
+        events.iter().rev().find(|event| matches!(event, SyncEvent::StepCompleted { .. })),
+        Some(SyncEvent::StepCompleted { block_num, chain_tip })
+            if *block_num == rpc_api.get_chain_tip_block_num() && block_num == chain_tip
+    ));
+
+    let committed_notes = events
+        .iter()
+        .filter_map(|event| match event {
+            SyncEvent::NoteCommitted { note_id, .. } => Some(*note_id),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+    assert_eq!(committed_notes, expected_notes.iter().map(Note::id).collect());
+
+    let consumed_nullifiers = events
+        .iter()
+        .filter_map(|event| match event {
+            SyncEvent::NullifierConsumed { nullifier, .. } => Some(*nullifier),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(consumed_nullifiers.len(), 1);
+    assert!(expected_notes.iter().any(|note| note.nullifier() == consumed_nullifiers[0]));
+}
+
+#[tokio::test]
+async fn auto_sync_new_blocks() {
+    let (client, rpc_api, _) = create_test_client().await;
+    let client = futures::lock::Mutex::new(client);
+
+    let mut auto_sync =
+        AutoSync::new(AutoSyncConfig::new(Duration::from_millis(1), AutoSyncTrigger::NewBlocks));
+
+    let summaries = Arc::new(Mutex::new(Vec::new()));
+    auto_sync.add_listener(Arc::new({
+        let summaries = summaries.clone();
+        move |summary: &SyncSummary| summaries.lock().unwrap().push(summary.block_num)
+    }));
+
+    // Cancel the driver after a few iterations
+    let handle = auto_sync.handle();
+    let iterations = AtomicUsize::new(0);
+    auto_sync
+        .run(&client, |_| {
+            if iterations.fetch_add(1, Ordering::SeqCst) == 2 {
+                handle.cancel();
+            }
+            async {}
+        })
+        .await;
+
+    assert!(auto_sync.handle().is_cancelled());
+    assert_eq!(iterations.load(Ordering::SeqCst), 3);
+
+    // Only the first iteration should sync as no new blocks are produced afterwards
+    assert_eq!(*summaries.lock().unwrap(), vec![rpc_api.get_chain_tip_block_num()]);
+    assert_eq!(
+        client.lock().await.get_sync_height().await.unwrap(),
+        rpc_api.get_chain_tip_block_num()
+    );
+}
+
+#[tokio::test]
+async fn auto_sync_spawned_task_is_cancelled_while_waiting() {
+    let (client, rpc_api, _) = create_test_client().await;
+    let client = Rc::new(futures::lock::Mutex::new(client));
+
+    // The interval is long enough that the driver is waiting when it gets cancelled
+    let auto_sync =
+        AutoSync::new(AutoSyncConfig::new(Duration::from_secs(3600), AutoSyncTrigger::Interval));
+    let handle = auto_sync.handle();
+
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let task = auto_sync.spawn_tokio(client.clone());
+
+            // The client can be used while the driver runs
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(
+                client.lock().await.get_sync_height().await.unwrap(),
+                rpc_api.get_chain_tip_block_num()
+            );
+
+            handle.cancel();
+            tokio::time::timeout(Duration::from_secs(5), task)
+                .await
+                .expect("the driver should stop while waiting")
+                .unwrap();
+        })
+        .await;
+}
+
+#[test]
+fn auto_sync_backoff() {
+    let config = AutoSyncConfig::new(Duration::from_secs(1), AutoSyncTrigger::Interval)
+        .max_backoff(Duration::from_secs(5));
+
+    assert_eq!(config.backoff(0), Duration::from_secs(1));
+    assert_eq!(config.backoff(1), Duration::from_secs(2));
+    assert_eq!(config.backoff(2), Duration::from_secs(4));
+    assert_eq!(config.backoff(3), Duration::from_secs(5));
+    assert_eq!(config.backoff(u32::MAX), Duration::from_secs(5));
+}
+
+async fn sync_state_tags(store: TestStore) {
+    // generate test client with a random store name
+    let (mut client, rpc_api, _) = create_test_client_with_store(store).await;
+
     // Import first mockchain note as expected
     let expected_notes = rpc_api.get_available_notes();
     for tag in expected_notes.iter().map(|n| n.metadata().tag()) {
@@ -520,10 +806,9 @@ async fn sync_state_tags() {
     assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 1);
 }
 
-#[tokio::test]
-async fn tags() {
+async fn tags(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, _rpc_api, _) = create_test_client().await;
+    let (mut client, _rpc_api, _) = create_test_client_with_store(store).await;
 
     // Assert that the store gets created with the tag 0 (used for notes consumable by any account)
     assert!(client.get_note_tags().await.unwrap().is_empty());
@@ -557,10 +842,9 @@ async fn tags() {
     assert_eq!(client.get_note_tags().await.unwrap(), vec![tag_2]);
 }
 
-#[tokio::test]
-async fn mint_transaction() {
+async fn mint_transaction(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (mut client, _rpc_api, keystore) = create_test_client_with_store(store).await;
 
     // Faucet account generation
     let (faucet, _seed) =
@@ -570,25 +854,327 @@ async fn mint_transaction() {
 
     client.sync_state().await.unwrap();
 
-    // Test submitting a mint transaction
-    let transaction_request = TransactionRequestBuilder::new()
-        .build_mint_fungible_asset(
-            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
-            AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
-            miden_objects::note::NoteType::Private,
-            client.rng(),
-        )
-        .unwrap();
+    // Test submitting a mint transaction
+    let transaction_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), 5u64).unwrap(),
+            AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1).unwrap(),
+            miden_objects::note::NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+
+    assert_eq!(transaction.executed_transaction().account_delta().nonce_delta(), ONE);
+}
+
+async fn pending_transaction_pipeline(store: TestStore) {
+    let (mut client, _rpc_api, keystore) = create_test_client_with_store(store).await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let build_mint_request = |client: &mut MockClient| {
+        TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+                wallet.id(),
+                NoteType::Private,
+                client.rng(),
+            )
+            .unwrap()
+    };
+
+    // Pending transactions can be discarded before being submitted
+    let tx_request = build_mint_request(&mut client);
+    let tx_result = client.new_transaction(faucet.id(), tx_request).await.unwrap();
+    let discarded_tx_id = client.store_pending_transaction(tx_result).await.unwrap();
+    assert!(client.discard_pending_transaction(discarded_tx_id).await.unwrap());
+    assert!(!client.discard_pending_transaction(discarded_tx_id).await.unwrap());
+
+    let tx_request = build_mint_request(&mut client);
+    let tx_result = client.new_transaction(faucet.id(), tx_request).await.unwrap();
+    let tx_id = client.store_pending_transaction(tx_result).await.unwrap();
+
+    let record = client.get_pending_transaction(tx_id).await.unwrap().unwrap();
+    assert_eq!(record.stage(), &PendingTransactionStage::Executed);
+
+    // The proof is persisted, and the record can be moved to another client
+    let proven_transaction = client.prove_pending_transaction(tx_id).await.unwrap();
+    let record = client.get_pending_transaction(tx_id).await.unwrap().unwrap();
+    assert_eq!(record.proven_transaction(), Some(&proven_transaction));
+    assert_eq!(PendingTransactionRecord::read_from_bytes(&record.to_bytes()).unwrap(), record);
+
+    client.submit_pending_transaction(tx_id).await.unwrap();
+    assert!(client.get_pending_transactions().await.unwrap().is_empty());
+    assert!(matches!(
+        client.submit_pending_transaction(tx_id).await,
+        Err(ClientError::PendingTransactionNotFound(_))
+    ));
+
+    wait_for_tx(&mut client, tx_id).await;
+}
+
+#[tokio::test]
+async fn offline_transaction_signing() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let tx_request = TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(
+            FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+            wallet.id(),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+
+    let offline_tx = client.export_offline_transaction(faucet.id(), tx_request).await.unwrap();
+    assert_eq!(offline_tx.account_id(), faucet.id());
+
+    // The transaction is moved to the offline machine and signed there without a client
+    let offline_tx = OfflineTransaction::read_from_bytes(&offline_tx.to_bytes()).unwrap();
+    let executor = OfflineTransactionExecutor::new(Arc::new(keystore.clone()));
+    let record = executor
+        .execute_and_prove(offline_tx, Arc::new(LocalTransactionProver::default()))
+        .await
+        .unwrap();
+    assert!(record.proven_transaction().is_some());
+
+    // The signed transaction is brought back and submitted by the online client
+    let record = PendingTransactionRecord::read_from_bytes(&record.to_bytes()).unwrap();
+    let tx_id = client.import_pending_transaction(record).await.unwrap();
+    client.submit_pending_transaction(tx_id).await.unwrap();
+    assert!(client.get_pending_transactions().await.unwrap().is_empty());
+
+    wait_for_tx(&mut client, tx_id).await;
+}
+
+#[tokio::test]
+async fn import_pending_transaction_validation() {
+    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let mut tx_results = Vec::new();
+    for _ in 0..2 {
+        let tx_request = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+                wallet.id(),
+                NoteType::Private,
+                client.rng(),
+            )
+            .unwrap();
+        tx_results.push(client.new_transaction(faucet.id(), tx_request).await.unwrap());
+    }
+    let other_tx_result = tx_results.pop().unwrap();
+    let tx_result = tx_results.pop().unwrap();
+
+    let tx_id = client.store_pending_transaction(tx_result.clone()).await.unwrap();
+    let proven_transaction = client.prove_pending_transaction(tx_id).await.unwrap();
+
+    // A record can't hold the proof of a different transaction
+    let mismatched_record = PendingTransactionRecord::new(
+        other_tx_result,
+        PendingTransactionStage::Proven(proven_transaction.clone()),
+    );
+    assert!(matches!(
+        client.import_pending_transaction(mismatched_record).await,
+        Err(ClientError::PendingTransactionProofMismatch(..))
+    ));
+
+    // Once submitted, a transaction can't be moved back to an earlier stage
+    let submitted_record = PendingTransactionRecord::new(
+        tx_result.clone(),
+        PendingTransactionStage::Submitted(BlockNumber::from(1u32)),
+    );
+    client.import_pending_transaction(submitted_record.clone()).await.unwrap();
+
+    let proven_record = PendingTransactionRecord::new(
+        tx_result,
+        PendingTransactionStage::Proven(proven_transaction),
+    );
+    assert!(matches!(
+        client.import_pending_transaction(proven_record).await,
+        Err(ClientError::PendingTransactionAlreadySubmitted(_))
+    ));
+    assert_eq!(client.get_pending_transaction(tx_id).await.unwrap(), Some(submitted_record));
+}
+
+async fn transaction_chain(store: TestStore) {
+    let (mut client, _rpc_api, keystore) = create_test_client_with_store(store).await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    let mut chain: TransactionChain = client.new_transaction_chain(faucet.id()).await.unwrap();
+    let initial_nonce = chain.account().nonce();
+
+    // Each transaction is executed against the state produced by the previous one, without
+    // touching the store
+    for _ in 0..3 {
+        let tx_request = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+                wallet.id(),
+                NoteType::Private,
+                client.rng(),
+            )
+            .unwrap();
+        client.add_to_transaction_chain(&mut chain, tx_request).await.unwrap();
+    }
+
+    assert_eq!(chain.len(), 3);
+    assert_eq!(chain.account().nonce(), initial_nonce + Felt::new(3));
+
+    let stored_faucet = client.get_account(faucet.id()).await.unwrap().unwrap();
+    assert_eq!(stored_faucet.account().nonce(), initial_nonce);
+
+    let tx_ids = chain.transaction_ids();
+    let final_commitment = chain.account().commitment();
+    client.submit_transaction_chain(chain).await.unwrap();
+
+    for tx_id in tx_ids {
+        wait_for_tx(&mut client, tx_id).await;
+    }
+
+    let stored_faucet = client.get_account(faucet.id()).await.unwrap().unwrap();
+    assert_eq!(stored_faucet.account().commitment(), final_commitment);
+}
+
+async fn account_history_and_diff(store: TestStore) {
+    let (mut client, _rpc_api, keystore) = create_test_client_with_store(store).await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    client.sync_state().await.unwrap();
+
+    let history = client.get_account_history(wallet.id()).await.unwrap();
+    assert!(history.len() >= 2);
+    assert!(
+        history
+            .windows(2)
+            .all(|states| states[0].nonce().as_int() < states[1].nonce().as_int())
+    );
+
+    let first_state = history.first().unwrap();
+    let latest_state = history.last().unwrap();
+    assert_eq!(first_state.commitment(), wallet.commitment());
+
+    let record = client
+        .get_account_at_nonce(wallet.id(), first_state.nonce())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.account().commitment(), wallet.commitment());
+
+    let record = client
+        .get_account_by_commitment(latest_state.commitment())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.account().nonce(), latest_state.nonce());
+
+    let delta = client
+        .get_account_state_diff(
+            wallet.id(),
+            AccountStateSelector::Nonce(first_state.nonce()),
+            AccountStateSelector::Nonce(latest_state.nonce()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        delta.vault().fungible().amount(&faucet.id()),
+        Some(MINT_AMOUNT.try_into().unwrap())
+    );
+    assert_eq!(delta.nonce_delta(), latest_state.nonce() - first_state.nonce());
+
+    // Selecting the states by commitment gives the same delta
+    let delta_by_commitment = client
+        .get_account_state_diff(
+            wallet.id(),
+            AccountStateSelector::Commitment(first_state.commitment()),
+            AccountStateSelector::Commitment(latest_state.commitment()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(delta_by_commitment, delta);
+
+    assert!(matches!(
+        client
+            .get_account_state_diff(
+                wallet.id(),
+                AccountStateSelector::Nonce(latest_state.nonce()),
+                AccountStateSelector::Nonce(first_state.nonce()),
+            )
+            .await,
+        Err(ClientError::InvalidAccountStateRange { .. })
+    ));
+    assert!(matches!(
+        client
+            .get_account_state_diff(
+                faucet.id(),
+                AccountStateSelector::Commitment(first_state.commitment()),
+                AccountStateSelector::Commitment(latest_state.commitment()),
+            )
+            .await,
+        Err(ClientError::AccountStateNotFound(..))
+    ));
+}
+
+async fn account_removal_and_garbage_collection(store: TestStore) {
+    let (mut client, _rpc_api, keystore) = create_test_client_with_store(store).await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore).await;
+
+    mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+    client.sync_state().await.unwrap();
+    let input_notes = client.get_input_notes(NoteFilter::All).await.unwrap();
+
+    client.remove_account(wallet.id(), RelatedData::Remove).await.unwrap();
+
+    assert!(client.get_account(wallet.id()).await.unwrap().is_none());
+    assert!(client.get_account_history(wallet.id()).await.unwrap().is_empty());
+    assert!(
+        !client
+            .get_note_tags()
+            .await
+            .unwrap()
+            .into_iter()
+            .any(|tag| tag.source == NoteTagSource::Account(wallet.id()))
+    );
+
+    // The transactions of the wallet are removed, but the minted note is kept as the faucet
+    // created it
+    let transactions = client.get_transactions(TransactionFilter::All).await.unwrap();
+    assert!(!transactions.is_empty());
+    assert!(transactions.iter().all(|tx| tx.details.account_id == faucet.id()));
+    assert_eq!(client.get_input_notes(NoteFilter::All).await.unwrap().len(), input_notes.len());
+
+    assert!(matches!(
+        client.remove_account(wallet.id(), RelatedData::Remove).await,
+        Err(ClientError::AccountDataNotFound(_))
+    ));
+
+    // The code of the wallet is no longer used by any account, the memory store drops it along
+    // with the account
+    let removed_entries = client.garbage_collect().await.unwrap();
+    assert!(removed_entries > 0 || store == TestStore::Memory);
+    assert_eq!(client.garbage_collect().await.unwrap(), 0);
 
-    let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+    // Removing the faucet removes the notes it created
+    client.remove_account(faucet.id(), RelatedData::Remove).await.unwrap();
 
-    assert_eq!(transaction.executed_transaction().account_delta().nonce_delta(), ONE);
+    assert!(client.get_account_headers().await.unwrap().is_empty());
+    assert!(client.get_transactions(TransactionFilter::All).await.unwrap().is_empty());
+    assert!(client.get_output_notes(NoteFilter::All).await.unwrap().is_empty());
 }
 
-#[tokio::test]
-async fn import_note_validation() {
+async fn import_note_validation(store: TestStore) {
     // generate test client
-    let (mut client, rpc_api, _) = create_test_client().await;
+    let (mut client, rpc_api, _) = create_test_client_with_store(store).await;
 
     // generate test data
     let expected_note = rpc_api.get_available_notes()[0].clone();
@@ -655,10 +1241,9 @@ async fn transaction_request_expiration() {
     assert_eq!(tx_outputs.expiration_block_num, current_height + 5);
 }
 
-#[tokio::test]
-async fn import_processing_note_returns_error() {
+async fn import_processing_note_returns_error(store: TestStore) {
     // generate test client with a random store name
-    let (mut client, _rpc_api, keystore) = create_test_client().await;
+    let (mut client, _rpc_api, keystore) = create_test_client_with_store(store).await;
     client.sync_state().await.unwrap();
 
     let (account, _seed) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
@@ -875,9 +1460,8 @@ async fn real_note_roundtrip() {
     assert!(matches!(note.state(), &InputNoteState::ConsumedAuthenticatedLocal(_)));
 }
 
-#[tokio::test]
-async fn added_notes() {
-    let (mut client, _, authenticator) = create_test_client().await;
+async fn added_notes(store: TestStore) {
+    let (mut client, _, authenticator) = create_test_client_with_store(store).await;
 
     let faucet_account_header =
         insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &authenticator)
@@ -905,9 +1489,8 @@ async fn added_notes() {
     assert!(notes.is_empty());
 }
 
-#[tokio::test]
-async fn p2id_transfer() {
-    let (mut client, _, authenticator) = create_test_client().await;
+async fn p2id_transfer(store: TestStore) {
+    let (mut client, _, authenticator) = create_test_client_with_store(store).await;
 
     let (first_regular_account, second_regular_account, faucet_account_header) =
         setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
@@ -1047,9 +1630,8 @@ async fn p2id_transfer_failing_not_enough_balance() {
     .await;
 }
 
-#[tokio::test]
-async fn p2ide_transfer_consumed_by_target() {
-    let (mut client, _, authenticator) = create_test_client().await;
+async fn p2ide_transfer_consumed_by_target(store: TestStore) {
+    let (mut client, _, authenticator) = create_test_client_with_store(store).await;
 
     let (first_regular_account, second_regular_account, faucet_account_header) =
         setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
@@ -1319,9 +1901,8 @@ async fn p2ide_timelocked() {
     assert_eq!(target_account.vault().get_balance(faucet_account_id).unwrap(), TRANSFER_AMOUNT);
 }
 
-#[tokio::test]
-async fn get_consumable_notes() {
-    let (mut client, _, authenticator) = create_test_client().await;
+async fn get_consumable_notes(store: TestStore) {
+    let (mut client, _, authenticator) = create_test_client_with_store(store).await;
 
     let (first_regular_account, second_regular_account, faucet_account_header) =
         setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
@@ -1389,9 +1970,460 @@ async fn get_consumable_notes() {
     assert_eq!(to_account_relevance, NoteRelevance::Now);
 }
 
+async fn account_balances(store: TestStore) {
+    let (mut client, _, authenticator) = create_test_client_with_store(store).await;
+
+    let (from_account, to_account, faucet_account) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await;
+    let from_account_id = from_account.id();
+    let to_account_id = to_account.id();
+    let faucet_id = faucet_account.id();
+
+    assert!(client.get_balances(from_account_id).await.unwrap().is_empty());
+
+    let untracked_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+    assert!(matches!(
+        client.get_balance(untracked_account_id, faucet_id).await,
+        Err(ClientError::AccountDataNotFound(_))
+    ));
+    assert!(matches!(
+        client.get_balances(untracked_account_id).await,
+        Err(ClientError::AccountDataNotFound(_))
+    ));
+
+    // The minted note is pending until it gets consumed
+    let note = mint_note(&mut client, from_account_id, faucet_id, NoteType::Private).await;
+    let balance = client.get_balance(from_account_id, faucet_id).await.unwrap();
+    assert_eq!(balance.amount(), 0);
+    assert_eq!(balance.pending_incoming(), MINT_AMOUNT);
+    assert_eq!(client.get_balances(from_account_id).await.unwrap(), vec![balance]);
+    assert_eq!(
+        client.get_balance(to_account_id, faucet_id).await.unwrap().pending_incoming(),
+        0
+    );
+
+    consume_notes(&mut client, from_account_id, &[note]).await;
+    let balance = client.get_balance(from_account_id, faucet_id).await.unwrap();
+    assert_eq!(balance.amount(), MINT_AMOUNT);
+    assert_eq!(balance.pending_incoming(), 0);
+
+    // The transferred amount is pending until the note gets committed
+    let asset = FungibleAsset::new(faucet_id, TRANSFER_AMOUNT).unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(
+                vec![Asset::Fungible(asset)],
+                from_account_id,
+                to_account_id,
+            ),
+            NoteType::Private,
+            client.rng(),
+        )
+        .unwrap();
+    let tx_id = execute_tx(&mut client, from_account_id, tx_request).await;
+
+    let balance = client.get_balance(from_account_id, faucet_id).await.unwrap();
+    assert_eq!(balance.amount(), MINT_AMOUNT - TRANSFER_AMOUNT);
+    assert_eq!(balance.pending_outgoing(), TRANSFER_AMOUNT);
+
+    wait_for_tx(&mut client, tx_id).await;
+
+    assert_eq!(
+        client.get_balance(from_account_id, faucet_id).await.unwrap().pending_outgoing(),
+        0
+    );
+    let balances = client.get_balances(to_account_id).await.unwrap();
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].faucet_id(), faucet_id);
+    assert_eq!(balances[0].amount(), 0);
+    assert_eq!(balances[0].pending_incoming(), TRANSFER_AMOUNT);
+}
+
+async fn store_snapshot_restore(store: TestStore) {
+    let (mut client, _, authenticator) = create_test_client_with_store(store).await;
+
+    let (from_account, _, faucet_account) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await;
+    let note =
+        mint_note(&mut client, from_account.id(), faucet_account.id(), NoteType::Private).await;
+    consume_notes(&mut client, from_account.id(), &[note]).await;
+
+    let bytes = client.export_snapshot().await.unwrap().to_bytes();
+    let snapshot = StoreSnapshot::read_from_bytes(&bytes).unwrap();
+
+    let (mut restored_client, ..) = create_test_client().await;
+    restored_client.import_snapshot(&snapshot).await.unwrap();
+
+    assert_eq!(
+        restored_client.get_sync_height().await.unwrap(),
+        client.get_sync_height().await.unwrap()
+    );
+    let account = restored_client.get_account(from_account.id()).await.unwrap().unwrap();
+    assert_eq!(
+        account.account().commitment(),
+        client
+            .get_account(from_account.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .account()
+            .commitment()
+    );
+    assert_eq!(
+        restored_client.get_transactions(TransactionFilter::All).await.unwrap().len(),
+        client.get_transactions(TransactionFilter::All).await.unwrap().len()
+    );
+
+    // Snapshots are only restored into empty stores
+    assert!(matches!(
+        restored_client.import_snapshot(&snapshot).await,
+        Err(ClientError::StoreError(StoreError::StoreNotEmpty))
+    ));
+}
+
+async fn store_snapshot_states_sharing_nonce(store: TestStore) {
+    let (mut client, _, authenticator) = create_test_client_with_store(store).await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator).await;
+
+    // Consecutive states of an account can share a nonce
+    let asset = FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap();
+    let updated_wallet = Account::from_parts(
+        wallet.id(),
+        AssetVault::new(&[asset.into()]).unwrap(),
+        wallet.storage().clone(),
+        wallet.code().clone(),
+        wallet.nonce(),
+    );
+    client.test_store().update_account(&updated_wallet).await.unwrap();
+
+    let history = client.get_account_history(wallet.id()).await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].nonce(), history[1].nonce());
+
+    let snapshot = client.export_snapshot().await.unwrap();
+    snapshot.validate().unwrap();
+    let snapshot = StoreSnapshot::read_from_bytes(&snapshot.to_bytes()).unwrap();
+
+    let (mut restored_client, ..) = create_test_client_with_store(store).await;
+    restored_client.import_snapshot(&snapshot).await.unwrap();
+    assert_eq!(restored_client.get_account_history(wallet.id()).await.unwrap(), history);
+}
+
+#[tokio::test]
+async fn store_snapshot_genesis_mismatch() {
+    let (client, ..) = create_test_client().await;
+    let snapshot = client.export_snapshot().await.unwrap();
+
+    // The store of the other client holds the genesis block of a different network
+    let (builder, ..) = create_test_client_builder().await;
+    let mut other_client = builder.build().await.unwrap();
+    let other_genesis = BlockHeader::mock(
+        0,
+        None,
+        Some(Word::from([ONE; 4])),
+        &[],
+        TransactionKernel::kernel_commitment(),
+    );
+    other_client
+        .test_store()
+        .insert_block_header(&other_genesis, Mmr::new().peaks(), false)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        other_client.import_snapshot(&snapshot).await,
+        Err(ClientError::StoreError(StoreError::SnapshotGenesisMismatch(..)))
+    ));
+}
+
+#[tokio::test]
+async fn memory_store_client() {
+    let (builder, _, authenticator) = create_test_client_builder().await;
+    let mut client = builder.in_memory_store().build().await.unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+
+    let (from_account, _, faucet_account) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await;
+    let note =
+        mint_note(&mut client, from_account.id(), faucet_account.id(), NoteType::Private).await;
+    consume_notes(&mut client, from_account.id(), &[note.clone()]).await;
+    assert_account_has_single_asset(&client, from_account.id(), faucet_account.id(), MINT_AMOUNT)
+        .await;
+
+    let input_note = client.get_input_note(note.id()).await.unwrap().unwrap();
+    assert!(matches!(input_note.state(), InputNoteState::ConsumedAuthenticatedLocal { .. }));
+
+    // The state of the in-memory store can be moved to a SQLite store
+    let snapshot = client.export_snapshot().await.unwrap();
+    let (mut sqlite_client, ..) = create_test_client().await;
+    sqlite_client.import_snapshot(&snapshot).await.unwrap();
+    assert_eq!(
+        sqlite_client
+            .get_account(from_account.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .account()
+            .commitment(),
+        client
+            .get_account(from_account.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .account()
+            .commitment()
+    );
+}
+
+#[tokio::test]
+async fn sync_updated_public_accounts() {
+    let (mut client, rpc_api, keystore) = create_test_client().await;
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Public, &keystore).await;
+
+    // Commit two updates of the faucet to the node, along with their deltas
+    let initial_state: Account = client.get_account(faucet.id()).await.unwrap().unwrap().into();
+    let mut states = vec![];
+    let mut block_nums = vec![];
+    for _ in 0..2 {
+        let transaction_request = TransactionRequestBuilder::new()
+            .build_mint_fungible_asset(
+                FungibleAsset::new(faucet.id(), MINT_AMOUNT).unwrap(),
+                wallet.id(),
+                NoteType::Private,
+                client.rng(),
+            )
+            .unwrap();
+        let transaction = client.new_transaction(faucet.id(), transaction_request).await.unwrap();
+        let delta = transaction.account_delta().clone();
+        client.submit_transaction(transaction).await.unwrap();
+
+        let state: Account = client.get_account(faucet.id()).await.unwrap().unwrap().into();
+        block_nums.push(rpc_api.commit_account_update(state.clone(), delta));
+        states.push(state);
+    }
+
+    // The delta is applied to the local state when it results in the commitment in the node. The
+    // whole account would be the latest state instead.
+    let updated_accounts = rpc_api
+        .get_updated_public_accounts(
+            vec![(initial_state.clone(), states[0].commitment())],
+            BlockNumber::GENESIS,
+            block_nums[0],
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated_accounts.len(), 1);
+    assert_eq!(updated_accounts[0].commitment(), states[0].commitment());
+
+    // Otherwise the whole account is fetched
+    let updated_accounts = rpc_api
+        .get_updated_public_accounts(
+            vec![(initial_state, states[1].commitment())],
+            block_nums[0],
+            block_nums[1],
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated_accounts.len(), 1);
+    assert_eq!(updated_accounts[0].commitment(), states[1].commitment());
+
+    // Accounts that match the commitment in the node aren't requested. The node has no state for
+    // the wallet, so requesting it would fail.
+    let wallet_state: Account = client.get_account(wallet.id()).await.unwrap().unwrap().into();
+    let wallet_commitment = wallet_state.commitment();
+    assert!(
+        rpc_api
+            .get_updated_public_accounts(
+                vec![(wallet_state, wallet_commitment)],
+                BlockNumber::GENESIS,
+                block_nums[1],
+            )
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // The updates are reported while syncing, and the local state is kept as it's up to date
+    client.sync_state().await.unwrap();
+    let faucet_record = client.get_account(faucet.id()).await.unwrap().unwrap();
+    assert_eq!(faucet_record.account().commitment(), states[1].commitment());
+}
+
+#[tokio::test]
+async fn private_note_transport() {
+    let (mut sender, rpc_api, sender_keystore) = create_test_client().await;
+    let (builder, _, recipient_keystore) = create_test_client_builder().await;
+    let mut recipient = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    recipient.ensure_genesis_in_place().await.unwrap();
+    let (builder, ..) = create_test_client_builder().await;
+    let mut other_client = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    other_client.ensure_genesis_in_place().await.unwrap();
+
+    let transport = Arc::new(MockNoteTransport::default());
+    sender.set_note_transport(transport.clone());
+    recipient.set_note_transport(transport.clone());
+    other_client.set_note_transport(transport.clone());
+
+    let (faucet, _) =
+        insert_new_fungible_faucet(&mut sender, AccountStorageMode::Private, &sender_keystore)
+            .await
+            .unwrap();
+    let (wallet, _) =
+        insert_new_wallet(&mut recipient, AccountStorageMode::Private, &recipient_keystore)
+            .await
+            .unwrap();
+
+    // The sender and the recipient share the channel key for the recipient's tag, while another
+    // client tracks the same tag with a different key
+    let tag = NoteTag::from_account_id(wallet.id());
+    let key = NoteChannelKey::with_rng(&mut rand::rng());
+    sender.add_note_channel(tag, key.clone());
+    recipient.add_note_channel(tag, key);
+    other_client.add_note_tag(tag).await.unwrap();
+    other_client.add_note_channel(tag, NoteChannelKey::with_rng(&mut rand::rng()));
+
+    // Private notes are sent when the transaction is submitted
+    let note = mint_note(&mut sender, wallet.id(), faucet.id(), NoteType::Private).await;
+    assert_eq!(transport.sent_notes().len(), 1);
+    assert_eq!(transport.sent_notes()[0].id(), note.id());
+
+    // And fetched while syncing
+    recipient.sync_state().await.unwrap();
+    let input_note = recipient.get_input_note(note.id()).await.unwrap().unwrap();
+    assert!(matches!(input_note.state(), InputNoteState::Committed { .. }));
+    assert!(recipient.fetch_private_notes().await.unwrap().is_empty());
+
+    other_client.sync_state().await.unwrap();
+    assert!(other_client.get_input_notes(NoteFilter::All).await.unwrap().is_empty());
+
+    // Tags that get a channel key after a fetch still receive the notes sent to them before
+    let (other_wallet, _) =
+        insert_new_wallet(&mut recipient, AccountStorageMode::Private, &recipient_keystore)
+            .await
+            .unwrap();
+    let other_tag = NoteTag::from_account_id(other_wallet.id());
+    let other_key = NoteChannelKey::with_rng(&mut rand::rng());
+    sender.add_note_channel(other_tag, other_key.clone());
+
+    let other_note =
+        mint_note(&mut sender, other_wallet.id(), faucet.id(), NoteType::Private).await;
+    assert!(recipient.fetch_private_notes().await.unwrap().is_empty());
+
+    recipient.add_note_channel(other_tag, other_key);
+    assert_eq!(recipient.fetch_private_notes().await.unwrap(), vec![other_note.id()]);
+    assert!(recipient.fetch_private_notes().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn private_note_transport_skips_undecodable_notes() {
+    let (mut sender, rpc_api, sender_keystore) = create_test_client().await;
+    let (builder, _, recipient_keystore) = create_test_client_builder().await;
+    let mut recipient = builder.rpc(Arc::new(rpc_api.clone())).build().await.unwrap();
+    recipient.ensure_genesis_in_place().await.unwrap();
+
+    let transport = Arc::new(MockNoteTransport::default());
+    sender.set_note_transport(transport.clone());
+    recipient.set_note_transport(transport.clone());
+
+    let (faucet, _) =
+        insert_new_fungible_faucet(&mut sender, AccountStorageMode::Private, &sender_keystore)
+            .await
+            .unwrap();
+    let (wallet, _) =
+        insert_new_wallet(&mut recipient, AccountStorageMode::Private, &recipient_keystore)
+            .await
+            .unwrap();
+
+    let tag = NoteTag::from_account_id(wallet.id());
+    let key_bytes: [u8; 32] = rand::rng().random();
+    sender.add_note_channel(tag, NoteChannelKey::new(key_bytes));
+    recipient.add_note_channel(tag, NoteChannelKey::new(key_bytes));
+
+    // A note encrypted with the channel key whose contents aren't a note file
+    let note_header = NoteHeader::new(
+        NoteId::new(Word::from([ONE; 4]), Word::default()),
+        NoteMetadata::new(faucet.id(), NoteType::Private, tag, NoteExecutionHint::always(), ZERO)
+            .unwrap(),
+    );
+    let nonce = [0u8; 24];
+    let ciphertext = XChaCha20Poly1305::new((&key_bytes).into())
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &[1, 2, 3],
+                aad: &note_header.to_bytes(),
+            },
+        )
+        .unwrap();
+    let undecodable_note =
+        EncryptedNote::new(note_header, [nonce.as_slice(), &ciphertext].concat());
+    transport.send_note(undecodable_note.clone()).await.unwrap();
+
+    // The undecodable note is skipped without preventing the valid note from being imported
+    let note = mint_note(&mut sender, wallet.id(), faucet.id(), NoteType::Private).await;
+    assert_eq!(recipient.fetch_private_notes().await.unwrap(), vec![note.id()]);
+    assert!(recipient.get_input_note(undecodable_note.id()).await.unwrap().is_none());
+
+    // And the cursors are persisted, so neither note is fetched again
+    assert!(recipient.fetch_private_notes().await.unwrap().is_empty());
+}
+
 #[tokio::test]
-async fn get_output_notes() {
+async fn note_relevance_checker() {
+    /// Marks notes as consumable after a fixed block only by the specified account.
+    struct FixedAccountChecker(AccountId);
+
+    #[async_trait::async_trait]
+    impl NoteRelevanceChecker for FixedAccountChecker {
+        async fn check_relevance(
+            &self,
+            _note: &Note,
+            account: &Account,
+        ) -> Result<Option<NoteRelevance>, NoteScreenerError> {
+            Ok((account.id() == self.0).then_some(NoteRelevance::After(42)))
+        }
+    }
+
     let (mut client, _, authenticator) = create_test_client().await;
+
+    let (first_regular_account, second_regular_account, faucet_account_header) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await;
+
+    let from_account_id = first_regular_account.id();
+    let to_account_id = second_regular_account.id();
+
+    let note =
+        mint_note(&mut client, from_account_id, faucet_account_header.id(), NoteType::Private)
+            .await;
+
+    // By default, the minted P2ID note is only consumable by its target
+    let consumability = client
+        .get_note_consumability(client.get_input_note(note.id()).await.unwrap().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(consumability, vec![(from_account_id, NoteRelevance::Now)]);
+
+    // The registered checker takes precedence over the default checks
+    client.register_note_relevance_checker(
+        WellKnownNote::P2ID.script_root(),
+        Arc::new(FixedAccountChecker(to_account_id)),
+    );
+
+    let consumable_notes = client.get_consumable_notes(None).await.unwrap();
+    assert_eq!(consumable_notes.len(), 1);
+    assert_eq!(consumable_notes[0].1, vec![(to_account_id, NoteRelevance::After(42))]);
+    assert!(client.get_consumable_notes(Some(from_account_id)).await.unwrap().is_empty());
+}
+
+async fn get_output_notes(store: TestStore) {
+    let (mut client, _, authenticator) = create_test_client_with_store(store).await;
     let _ = client.sync_state().await.unwrap();
     let (first_regular_account, faucet_account_header) =
         setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator).await;
@@ -1531,9 +2563,8 @@ async fn account_rollback() {
     );
 }
 
-#[tokio::test]
-async fn subsequent_discarded_transactions() {
-    let (mut client, rpc_api, keystore) = create_test_client().await;
+async fn subsequent_discarded_transactions(store: TestStore) {
+    let (mut client, rpc_api, keystore) = create_test_client_with_store(store).await;
 
     let (regular_account, faucet_account_header) =
         setup_wallet_and_faucet(&mut client, AccountStorageMode::Public, &keystore).await;
@@ -1655,9 +2686,8 @@ async fn missing_recipient_digest() {
     }
 }
 
-#[tokio::test]
-async fn input_note_checks() {
-    let (mut client, _, authenticator) = create_test_client().await;
+async fn input_note_checks(store: TestStore) {
+    let (mut client, _, authenticator) = create_test_client_with_store(store).await;
 
     let (wallet, faucet) =
         setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator).await;
@@ -1803,3 +2833,228 @@ async fn swap_chain_test() {
         1
     );
 }
+
+#[tokio::test]
+async fn encrypted_keystore_lifecycle() {
+    let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+
+    // Plaintext keys get migrated in place
+    let plaintext_keystore = FilesystemKeyStore::new(keys_directory.clone()).unwrap();
+    let plaintext_key = SecretKey::new();
+    let plaintext_pub_key = Word::from(plaintext_key.public_key());
+    plaintext_keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(plaintext_key))
+        .await
+        .unwrap();
+
+    let keystore = EncryptedFilesystemKeyStore::new(keys_directory.clone()).unwrap();
+    assert!(!keystore.is_initialized());
+    assert!(matches!(keystore.get_key(plaintext_pub_key).await, Err(KeyStoreError::Locked)));
+
+    assert!(matches!(keystore.unlock("passphrase"), Err(KeyStoreError::NotInitialized)));
+    keystore.initialize("passphrase").unwrap();
+    assert!(keystore.is_initialized());
+    assert!(keystore.is_unlocked());
+    assert!(matches!(keystore.initialize("other"), Err(KeyStoreError::AlreadyInitialized)));
+    assert_eq!(keystore.migrate_plaintext_keys(&keys_directory).unwrap(), 1);
+    assert_eq!(keystore.migrate_plaintext_keys(&keys_directory).unwrap(), 0);
+    assert!(plaintext_keystore.get_key(plaintext_pub_key).await.is_err());
+
+    let secret_key = SecretKey::new();
+    let pub_key = Word::from(secret_key.public_key());
+    keystore
+        .add_key(&AuthSecretKey::RpoFalcon512(secret_key.clone()))
+        .await
+        .unwrap();
+
+    // Keys can't be read while locked or with the wrong passphrase
+    keystore.lock();
+    assert!(!keystore.is_unlocked());
+    assert!(matches!(keystore.get_key(pub_key).await, Err(KeyStoreError::Locked)));
+    assert!(matches!(keystore.unlock("wrong"), Err(KeyStoreError::InvalidPassphrase)));
+
+    // Rotating the passphrase keeps the keys readable
+    keystore.change_passphrase("passphrase", "new passphrase").unwrap();
+    keystore.lock();
+    assert!(matches!(keystore.unlock("passphrase"), Err(KeyStoreError::InvalidPassphrase)));
+    keystore.unlock("new passphrase").unwrap();
+
+    let AuthSecretKey::RpoFalcon512(stored_key) = keystore.get_key(pub_key).await.unwrap().unwrap();
+    assert_eq!(stored_key.to_bytes(), secret_key.to_bytes());
+    assert!(keystore.get_key(plaintext_pub_key).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn encrypted_keystore_recovers_interrupted_passphrase_change() {
+    let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+    let keystore = EncryptedFilesystemKeyStore::new(keys_directory.clone()).unwrap();
+    keystore.initialize("passphrase").unwrap();
+
+    let secret_key = SecretKey::new();
+    let pub_key = Word::from(secret_key.public_key());
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).await.unwrap();
+
+    let key_path = std::fs::read_dir(&keys_directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.file_name().unwrap().to_str().unwrap().parse::<u64>().is_ok())
+        .unwrap();
+    let staged_key_path = std::path::PathBuf::from(format!("{}.next", key_path.display()));
+    let metadata_path = keys_directory.join("keystore.meta");
+    let staged_metadata_path = keys_directory.join("keystore.meta.next");
+
+    // A change that wasn't committed is discarded and the current passphrase keeps working
+    std::fs::write(&staged_metadata_path, b"partial").unwrap();
+    std::fs::write(&staged_key_path, b"partial").unwrap();
+
+    let keystore = EncryptedFilesystemKeyStore::new(keys_directory.clone()).unwrap();
+    assert!(!staged_metadata_path.exists());
+    assert!(!staged_key_path.exists());
+    keystore.unlock("passphrase").unwrap();
+    assert!(keystore.get_key(pub_key).await.unwrap().is_some());
+
+    // A committed change whose key files weren't all replaced is completed
+    let old_sealed_key = std::fs::read(&key_path).unwrap();
+    keystore.change_passphrase("passphrase", "new passphrase").unwrap();
+    std::fs::rename(&key_path, &staged_key_path).unwrap();
+    std::fs::write(&key_path, old_sealed_key).unwrap();
+
+    let keystore = EncryptedFilesystemKeyStore::new(keys_directory.clone()).unwrap();
+    assert!(!staged_key_path.exists());
+    assert!(metadata_path.exists());
+    keystore.unlock("new passphrase").unwrap();
+    assert!(keystore.get_key(pub_key).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn keystore_list_remove_export_import() {
+    let keystore = FilesystemKeyStore::new(temp_dir().join(Uuid::new_v4().to_string())).unwrap();
+    assert!(keystore.list_keys().await.unwrap().is_empty());
+
+    let secret_keys = [SecretKey::new(), SecretKey::new()];
+    for secret_key in &secret_keys {
+        keystore
+            .add_key(&AuthSecretKey::RpoFalcon512(secret_key.clone()))
+            .await
+            .unwrap();
+    }
+
+    let mut pub_keys: Vec<Word> = secret_keys
+        .iter()
+        .map(|secret_key| Word::from(secret_key.public_key()))
+        .collect();
+    pub_keys.sort();
+    let mut listed_keys = keystore.list_keys().await.unwrap();
+    listed_keys.sort();
+    assert_eq!(listed_keys, pub_keys);
+
+    // Exported keys can be imported into a different keystore
+    let exported_keys = keystore.export_keys().await.unwrap();
+    assert_eq!(exported_keys.len(), 2);
+
+    // Exported keys are encrypted with a passphrase
+    let encrypted_keys =
+        encrypt_exported_keys(&exported_keys, "passphrase", &mut rand::rng()).unwrap();
+    assert!(matches!(
+        decrypt_exported_keys(&encrypted_keys, "wrong"),
+        Err(KeyStoreError::InvalidPassphrase)
+    ));
+    let exported_keys = decrypt_exported_keys(&encrypted_keys, "passphrase").unwrap();
+
+    let other_keystore =
+        FilesystemKeyStore::new(temp_dir().join(Uuid::new_v4().to_string())).unwrap();
+    other_keystore.import_keys(&exported_keys).await.unwrap();
+    let mut imported_keys = other_keystore.list_keys().await.unwrap();
+    imported_keys.sort();
+    assert_eq!(imported_keys, pub_keys);
+
+    // Removing a key only affects that key, and removing it twice is not an error
+    keystore.remove_key(pub_keys[0]).await.unwrap();
+    keystore.remove_key(pub_keys[0]).await.unwrap();
+    assert_eq!(keystore.list_keys().await.unwrap(), vec![pub_keys[1]]);
+    assert!(keystore.get_key(pub_keys[0]).await.unwrap().is_none());
+    assert!(other_keystore.get_key(pub_keys[0]).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn external_signer_keystore() {
+    /// A signer transport that serves requests from a filesystem keystore in the same process,
+    /// going through the requests' serialization.
+    struct InProcessSigner(FilesystemKeyStore<StdRng>);
+
+    impl SignerTransport for InProcessSigner {
+        fn send(&self, request: &SignerRequest) -> Result<SignerResponse, KeyStoreError> {
+            let request = SignerRequest::read_from_bytes(&request.to_bytes()).unwrap();
+            let response = match request {
+                SignerRequest::ListKeys => SignerResponse::Keys(vec![]),
+                SignerRequest::Sign { pub_key, message, account_delta } => {
+                    match self.0.get_signature(pub_key, message, &account_delta) {
+                        Ok(signature) => SignerResponse::Signature(signature),
+                        Err(err) => SignerResponse::Error(err.to_string()),
+                    }
+                },
+            };
+            Ok(SignerResponse::read_from_bytes(&response.to_bytes()).unwrap())
+        }
+    }
+
+    let signer_keystore =
+        FilesystemKeyStore::new(temp_dir().join(Uuid::new_v4().to_string())).unwrap();
+    let keystore = ExternalSignerKeyStore::new(InProcessSigner(signer_keystore.clone()));
+
+    let (builder, ..) = create_test_client_builder().await;
+    let mut client = builder.keystore(Arc::new(keystore.clone())).build().await.unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+
+    // Keys are only added to the signer, the client signs the mint transaction through it
+    let (wallet, faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &signer_keystore).await;
+    mint_note(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+
+    assert_eq!(signer_keystore.list_keys().await.unwrap().len(), 2);
+    assert!(matches!(
+        keystore.add_key(&AuthSecretKey::RpoFalcon512(SecretKey::new())).await,
+        Err(KeyStoreError::UnsupportedOperation(_))
+    ));
+
+    // Secret keys can't be exported, and no note decryption key can be derived from them
+    assert!(matches!(
+        keystore.export_keys().await,
+        Err(KeyStoreError::UnsupportedOperation(_))
+    ));
+    let encryption_key =
+        NoteDecryptionKey::from_auth_secret_key(&signer_keystore.export_keys().await.unwrap()[0])
+            .encryption_key();
+    assert!(keystore.get_note_decryption_key(&encryption_key).await.unwrap().is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn unix_socket_transport_rejects_oversized_responses() {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixListener,
+    };
+
+    use crate::keystore::UnixSocketTransport;
+
+    let socket_path = temp_dir().join(format!("{}.sock", Uuid::new_v4()));
+    let listener = UnixListener::bind(&socket_path).unwrap();
+
+    // The signer announces a 4 GiB response
+    let signer = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request_len = [0u8; 4];
+        stream.read_exact(&mut request_len).unwrap();
+        let mut request = vec![0u8; u32::from_le_bytes(request_len) as usize];
+        stream.read_exact(&mut request).unwrap();
+        stream.write_all(&u32::MAX.to_le_bytes()).unwrap();
+    });
+
+    let transport = UnixSocketTransport::new(socket_path);
+    assert!(matches!(
+        transport.send(&SignerRequest::ListKeys),
+        Err(KeyStoreError::SignerError(_))
+    ));
+    signer.join().unwrap();
+}
@@ -0,0 +1,199 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+use miden_objects::{
+    Word,
+    account::{Account, AccountId},
+    note::Nullifier,
+    transaction::TransactionId,
+};
+use miden_tx::TransactionProver;
+use tracing::info;
+
+use super::{TransactionRequest, TransactionResult};
+use crate::{Client, ClientError};
+
+// TRANSACTION CHAIN
+// ================================================================================================
+
+/// A sequence of transactions executed by the same account, each one against the account state
+/// produced by the previous one.
+///
+/// The account state is kept in memory, so the transactions can be prepared without submitting
+/// them first. Once built, the chain is proven and submitted with
+/// [`Client::submit_transaction_chain`].
+#[derive(Debug, Clone)]
+pub struct TransactionChain {
+    /// State of the account after the last transaction in the chain.
+    account: Account,
+    /// Seed of the account, only needed if the account is new and no transaction was added yet.
+    account_seed: Option<Word>,
+    /// Results of the transactions in the chain, in execution order.
+    transactions: Vec<TransactionResult>,
+}
+
+impl TransactionChain {
+    /// Returns the ID of the account that executes the transactions.
+    pub fn account_id(&self) -> AccountId {
+        self.account.id()
+    }
+
+    /// Returns the state of the account after the last transaction in the chain.
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    /// Returns the results of the transactions in the chain, in execution order.
+    pub fn transactions(&self) -> &[TransactionResult] {
+        &self.transactions
+    }
+
+    /// Returns the IDs of the transactions in the chain, in execution order.
+    pub fn transaction_ids(&self) -> Vec<TransactionId> {
+        self.transactions.iter().map(|tx| tx.executed_transaction().id()).collect()
+    }
+
+    /// Returns the number of transactions in the chain.
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns `true` if no transaction was added to the chain.
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Returns `true` if a transaction in the chain already consumes the note with the provided
+    /// nullifier.
+    fn consumes(&self, nullifier: Nullifier) -> bool {
+        self.transactions.iter().any(|tx| {
+            tx.executed_transaction()
+                .input_notes()
+                .iter()
+                .any(|note| note.note().nullifier() == nullifier)
+        })
+    }
+}
+
+/// Transaction chain methods
+impl Client {
+    /// Starts a new [`TransactionChain`] for the specified account, based on its state in the
+    /// store.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked.
+    /// - Returns [`ClientError::AccountLocked`] if the account is locked.
+    pub async fn new_transaction_chain(
+        &self,
+        account_id: AccountId,
+    ) -> Result<TransactionChain, ClientError> {
+        let account_record = self.try_get_account(account_id).await?;
+
+        if account_record.is_locked() {
+            return Err(ClientError::AccountLocked(account_id));
+        }
+
+        let account_seed = account_record.seed().copied();
+
+        Ok(TransactionChain {
+            account: account_record.into(),
+            account_seed,
+            transactions: Vec::new(),
+        })
+    }
+
+    /// Executes the transaction request against the account state at the end of the chain, and
+    /// appends the transaction to it. The store isn't changed.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::NoteAlreadyConsumedInChain`] if a transaction in the chain already
+    ///   consumes one of the input notes.
+    /// - Returns the same errors as [`Client::new_transaction`] otherwise.
+    pub async fn add_to_transaction_chain(
+        &mut self,
+        chain: &mut TransactionChain,
+        transaction_request: TransactionRequest,
+    ) -> Result<TransactionId, ClientError> {
+        let account_id = chain.account_id();
+        let mut prepared_transaction = self
+            .prepare_transaction_for_account(&chain.account, transaction_request)
+            .await?;
+
+        if let Some(note) = prepared_transaction
+            .input_notes
+            .iter()
+            .find(|note| chain.consumes(note.note().nullifier()))
+        {
+            return Err(ClientError::NoteAlreadyConsumedInChain(note.id()));
+        }
+
+        prepared_transaction.data_store = prepared_transaction
+            .data_store
+            .with_account_state(chain.account.clone(), chain.account_seed);
+
+        let tx_result = self.execute_prepared_transaction(account_id, prepared_transaction).await?;
+
+        chain.account.apply_delta(tx_result.account_delta())?;
+        // The seed is only needed to execute the first transaction of a new account
+        chain.account_seed = None;
+
+        let tx_id = tx_result.executed_transaction().id();
+        chain.transactions.push(tx_result);
+
+        Ok(tx_id)
+    }
+
+    /// Proves the transactions in the chain using the client's prover, and submits them to the
+    /// network in order. See [`Client::submit_transaction_chain_with_prover`].
+    pub async fn submit_transaction_chain(
+        &mut self,
+        chain: TransactionChain,
+    ) -> Result<(), ClientError> {
+        self.submit_transaction_chain_with_prover(chain, self.tx_prover.clone()).await
+    }
+
+    /// Proves the transactions in the chain using the provided prover, and submits them to the
+    /// network in order, applying each one to the local store once it's accepted.
+    ///
+    /// All transactions are proven before the first one is submitted, so a proving failure leaves
+    /// the store untouched. If a submission fails, the chain is rolled back: the remaining
+    /// transactions are dropped and the store is left at the state produced by the transactions
+    /// that the node already accepted, as those can't be withdrawn.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::TransactionChainSubmissionFailed`] with the number of submitted
+    /// transactions if a submission fails.
+    pub async fn submit_transaction_chain_with_prover(
+        &mut self,
+        chain: TransactionChain,
+        tx_prover: Arc<dyn TransactionProver>,
+    ) -> Result<(), ClientError> {
+        let mut proven_transactions = Vec::with_capacity(chain.len());
+        for tx_result in &chain.transactions {
+            proven_transactions.push(self.prove_transaction(tx_result, tx_prover.clone()).await?);
+        }
+
+        for (submitted, (tx_result, proven_transaction)) in
+            chain.transactions.into_iter().zip(proven_transactions).enumerate()
+        {
+            let submission_height = match self.submit_proven_transaction(proven_transaction).await {
+                Ok(submission_height) => submission_height,
+                Err(err) => {
+                    info!("Submission failed, dropping the rest of the transaction chain");
+                    return Err(ClientError::TransactionChainSubmissionFailed(
+                        submitted,
+                        Box::new(err),
+                    ));
+                },
+            };
+
+            let created_notes = tx_result.created_notes().clone();
+            self.apply_transaction(submission_height, tx_result).await?;
+            self.send_created_private_notes(created_notes.iter(), submission_height).await;
+        }
+
+        Ok(())
+    }
+}
@@ -12,6 +12,12 @@
 //!   that includes execution details and relevant notes for state tracking.
 //! - Prove transactions (locally or remotely) using a [`TransactionProver`] and submit the proven
 //!   transactions to the network.
+//! - Run the prove and submit steps separately, persisting the progress in the store as a
+//!   [`PendingTransactionRecord`] so that transactions can be resumed later or on another machine.
+//! - Chain several transactions from the same account with a [`TransactionChain`], executing each
+//!   one against the state produced by the previous one before any of them is submitted.
+//! - Export an [`OfflineTransaction`] to execute and sign it with an [`OfflineTransactionExecutor`]
+//!   on a machine without network access, such as a cold wallet.
 //! - Track and update the state of transactions, including their status (e.g., `Pending`,
 //!   `Committed`, or `Discarded`).
 //!
@@ -70,7 +76,7 @@ use alloc::{
 use core::fmt::{self};
 
 use miden_objects::{
-    AssetError, Felt, Word,
+    AssetError, Felt, MAX_TX_EXECUTION_CYCLES, MIN_TX_EXECUTION_CYCLES, Word,
     account::{Account, AccountCode, AccountDelta, AccountId},
     assembly::DefaultSourceManager,
     asset::{Asset, NonFungibleAsset},
@@ -79,28 +85,32 @@ use miden_objects::{
     transaction::{AccountInputs, TransactionArgs},
 };
 use miden_tx::{
-    NoteAccountExecution, NoteConsumptionChecker, TransactionExecutor,
+    ExecutionOptions, NoteAccountExecution, NoteConsumptionChecker, TransactionExecutor,
     utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
 };
 use tracing::info;
 
 use super::Client;
 use crate::{
-    ClientError,
-    note::{NoteScreener, NoteUpdateTracker},
+    ClientError, DebugMode,
+    note::NoteUpdateTracker,
     rpc::domain::account::AccountProof,
     store::{
-        InputNoteRecord, InputNoteState, NoteFilter, OutputNoteRecord, StoreError,
+        InputNoteRecord, InputNoteState, NoteFilter, OutputNoteRecord, Page, StoreError,
         TransactionFilter, data_store::ClientDataStore, input_note_states::ExpectedNoteState,
     },
     sync::NoteTagRecord,
 };
 
+mod chain;
+mod offline;
+mod pipeline;
 mod request;
 
 // RE-EXPORTS
 // ================================================================================================
 
+pub use chain::TransactionChain;
 pub use miden_lib::{
     account::interface::{AccountComponentInterface, AccountInterface},
     transaction::TransactionKernel,
@@ -116,6 +126,8 @@ pub use miden_tx::{
     DataStoreError, LocalTransactionProver, ProvingOptions, TransactionExecutorError,
     TransactionProver, TransactionProverError, auth::TransactionAuthenticator,
 };
+pub use offline::{OfflineTransaction, OfflineTransactionExecutor};
+pub use pipeline::{PendingTransactionRecord, PendingTransactionStage};
 pub use request::{
     ForeignAccount, NoteArgs, PaymentNoteDescription, SwapTransactionData, TransactionRequest,
     TransactionRequestBuilder, TransactionRequestError, TransactionScriptTemplate,
@@ -256,6 +268,26 @@ impl TransactionRecord {
     }
 }
 
+impl Serializable for TransactionRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.id.write_into(target);
+        self.details.write_into(target);
+        self.script.write_into(target);
+        self.status.write_into(target);
+    }
+}
+
+impl Deserializable for TransactionRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let id = TransactionId::read_from(source)?;
+        let details = TransactionDetails::read_from(source)?;
+        let script = Option::<TransactionScript>::read_from(source)?;
+        let status = TransactionStatus::read_from(source)?;
+
+        Ok(Self { id, details, script, status })
+    }
+}
+
 /// Describes the details associated with a transaction.
 #[derive(Debug, Clone)]
 pub struct TransactionDetails {
@@ -392,6 +424,35 @@ impl fmt::Display for TransactionStatus {
     }
 }
 
+impl Serializable for TransactionStatus {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            TransactionStatus::Pending => target.write_u8(0),
+            TransactionStatus::Committed(block_num) => {
+                target.write_u8(1);
+                block_num.write_into(target);
+            },
+            TransactionStatus::Discarded(cause) => {
+                target.write_u8(2);
+                cause.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for TransactionStatus {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(TransactionStatus::Pending),
+            1 => Ok(TransactionStatus::Committed(BlockNumber::read_from(source)?)),
+            2 => Ok(TransactionStatus::Discarded(DiscardCause::read_from(source)?)),
+            val => Err(DeserializationError::InvalidValue(format!(
+                "Invalid transaction status: {val}"
+            ))),
+        }
+    }
+}
+
 // TRANSACTION STORE UPDATE
 // ================================================================================================
 
@@ -462,6 +523,28 @@ impl TransactionStoreUpdate {
     }
 }
 
+// PREPARED TRANSACTION
+// ================================================================================================
+
+/// Everything needed to execute a transaction request, as gathered by
+/// [`Client::prepare_transaction`].
+pub(crate) struct PreparedTransaction {
+    /// Block against which the transaction gets executed.
+    pub block_num: BlockNumber,
+    /// Notes consumed by the transaction.
+    pub input_notes: InputNotes<InputNote>,
+    /// Arguments of the transaction, including the inputs of foreign accounts.
+    pub tx_args: TransactionArgs,
+    /// Recipients of the output notes the transaction is expected to create.
+    pub output_recipients: Vec<NoteRecipient>,
+    /// Notes that might be created in the future as a result of the transaction.
+    pub future_notes: Vec<(NoteDetails, NoteTag)>,
+    /// Whether input notes that can't be consumed should be removed before execution.
+    pub ignore_invalid_input_notes: bool,
+    /// Data store with the code of the executing and foreign accounts loaded.
+    pub data_store: ClientDataStore,
+}
+
 /// Transaction management methods
 impl Client {
     // TRANSACTION DATA RETRIEVAL
@@ -475,6 +558,17 @@ impl Client {
         self.store.get_transactions(filter).await.map_err(Into::into)
     }
 
+    /// Retrieves a [`Page`] of the tracked transactions, filtered by [`TransactionFilter`].
+    /// Transactions are sorted by the number of the block they were executed against and then by
+    /// ID.
+    pub async fn get_transactions_page(
+        &self,
+        filter: TransactionFilter,
+        page: Page,
+    ) -> Result<Vec<TransactionRecord>, ClientError> {
+        self.store.get_transactions_page(filter, page).await.map_err(Into::into)
+    }
+
     // TRANSACTION
     // --------------------------------------------------------------------------------------------
 
@@ -496,8 +590,89 @@ impl Client {
         account_id: AccountId,
         transaction_request: TransactionRequest,
     ) -> Result<TransactionResult, ClientError> {
+        let prepared_transaction =
+            self.prepare_transaction(account_id, transaction_request).await?;
+        self.execute_prepared_transaction(account_id, prepared_transaction).await
+    }
+
+    /// Executes a transaction prepared with [`Client::prepare_transaction`] against the account
+    /// state served by its data store.
+    pub(crate) async fn execute_prepared_transaction(
+        &self,
+        account_id: AccountId,
+        prepared_transaction: PreparedTransaction,
+    ) -> Result<TransactionResult, ClientError> {
+        let PreparedTransaction {
+            block_num,
+            mut input_notes,
+            tx_args,
+            output_recipients,
+            future_notes,
+            ignore_invalid_input_notes,
+            data_store,
+        } = prepared_transaction;
+
+        if ignore_invalid_input_notes {
+            // Remove invalid notes
+            input_notes = get_valid_input_notes(
+                &self.build_executor(&data_store)?,
+                account_id,
+                self.store.get_sync_height().await?,
+                input_notes,
+                tx_args.clone(),
+            )
+            .await?;
+        }
+
+        // Execute the transaction and get the witness
+        let executed_transaction = self
+            .build_executor(&data_store)?
+            .execute_transaction(
+                account_id,
+                block_num,
+                input_notes,
+                tx_args,
+                Arc::new(DefaultSourceManager::default()), // TODO: Use the correct source manager
+            )
+            .await?;
+
+        validate_executed_transaction(&executed_transaction, &output_recipients)?;
+
+        TransactionResult::new(executed_transaction, future_notes)
+    }
+
+    /// Validates the transaction request and gathers everything needed to execute it against the
+    /// specified account: the input notes, the transaction arguments (including the inputs of
+    /// foreign accounts) and the reference block.
+    ///
+    /// The returned data store has the code of the executing account and the foreign accounts
+    /// loaded.
+    pub(crate) async fn prepare_transaction(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+    ) -> Result<PreparedTransaction, ClientError> {
+        let account_record = self.try_get_account(account_id).await?;
+        let account_seed = account_record.seed().copied();
+        let account: Account = account_record.into();
+
+        let mut prepared_transaction =
+            self.prepare_transaction_for_account(&account, transaction_request).await?;
+        prepared_transaction.data_store =
+            prepared_transaction.data_store.with_account_state(account, account_seed);
+
+        Ok(prepared_transaction)
+    }
+
+    /// Same as [`Client::prepare_transaction`], but validates the request against the provided
+    /// account state instead of the one in the store.
+    pub(crate) async fn prepare_transaction_for_account(
+        &mut self,
+        account: &Account,
+        transaction_request: TransactionRequest,
+    ) -> Result<PreparedTransaction, ClientError> {
         // Validates the transaction request before executing
-        self.validate_request(account_id, &transaction_request).await?;
+        self.validate_request_for_account(account, &transaction_request).await?;
 
         // Ensure authenticated notes have their inclusion proofs (a.k.a they're in a committed
         // state)
@@ -519,7 +694,7 @@ impl Client {
 
         self.store.upsert_input_notes(&unauthenticated_input_notes).await?;
 
-        let mut notes = transaction_request.build_input_notes(authenticated_note_records)?;
+        let input_notes = transaction_request.build_input_notes(authenticated_note_records)?;
 
         let output_recipients =
             transaction_request.expected_output_recipients().cloned().collect::<Vec<_>>();
@@ -528,7 +703,7 @@ impl Client {
             transaction_request.expected_future_notes().cloned().collect();
 
         let tx_script = transaction_request.build_transaction_script(
-            &self.get_account_interface(account_id).await?,
+            &AccountInterface::from(account),
             self.in_debug_mode().into(),
         )?;
 
@@ -538,7 +713,7 @@ impl Client {
         let (fpi_block_num, foreign_account_inputs) =
             self.retrieve_foreign_account_inputs(foreign_accounts).await?;
 
-        let ignore_invalid_notes = transaction_request.ignore_invalid_input_notes();
+        let ignore_invalid_input_notes = transaction_request.ignore_invalid_input_notes();
 
         let data_store = ClientDataStore::new(self.store.clone());
         for fpi_account in &foreign_account_inputs {
@@ -553,39 +728,24 @@ impl Client {
             self.store.get_sync_height().await?
         };
 
-        // TODO: Refactor this to get account code only?
-        let account_record = self
-            .store
-            .get_account(account_id)
-            .await?
-            .ok_or(ClientError::AccountDataNotFound(account_id))?;
-        let account: Account = account_record.into();
         data_store.mast_store().load_account_code(account.code());
 
-        if ignore_invalid_notes {
-            // Remove invalid notes
-            notes = self.get_valid_input_notes(account_id, notes, tx_args.clone()).await?;
-        }
-
-        // Execute the transaction and get the witness
-        let executed_transaction = self
-            .build_executor(&data_store)?
-            .execute_transaction(
-                account_id,
-                block_num,
-                notes,
-                tx_args,
-                Arc::new(DefaultSourceManager::default()), // TODO: Use the correct source manager
-            )
-            .await?;
-
-        validate_executed_transaction(&executed_transaction, &output_recipients)?;
-
-        TransactionResult::new(executed_transaction, future_notes)
+        Ok(PreparedTransaction {
+            block_num,
+            input_notes,
+            tx_args,
+            output_recipients,
+            future_notes,
+            ignore_invalid_input_notes,
+            data_store,
+        })
     }
 
     /// Proves the specified transaction using a local prover, submits it to the network, and saves
     /// the transaction into the local database for tracking.
+    ///
+    /// If a note transport is set (see [`Client::set_note_transport`]), the private notes created
+    /// by the transaction whose tag has a channel key are then sent to their recipients.
     pub async fn submit_transaction(
         &mut self,
         tx_result: TransactionResult,
@@ -602,7 +762,11 @@ impl Client {
     ) -> Result<(), ClientError> {
         let proven_transaction = self.prove_transaction(&tx_result, tx_prover).await?;
         let block_num = self.submit_proven_transaction(proven_transaction).await?;
-        self.apply_transaction(block_num, tx_result).await
+        let created_notes = tx_result.created_notes().clone();
+        self.apply_transaction(block_num, tx_result).await?;
+        self.send_created_private_notes(created_notes.iter(), block_num).await;
+
+        Ok(())
     }
 
     /// Proves the specified transaction result using the provided prover.
@@ -626,7 +790,11 @@ impl Client {
         proven_transaction: ProvenTransaction,
     ) -> Result<BlockNumber, ClientError> {
         info!("Submitting transaction to the network...");
-        let block_num = self.rpc_api.submit_proven_transaction(proven_transaction).await?;
+        let block_num = self
+            .rpc_api
+            .submit_proven_transaction(proven_transaction)
+            .await
+            .map_err(ClientError::from_submission_error)?;
         info!("Transaction submitted.");
 
         Ok(block_num)
@@ -719,12 +887,14 @@ impl Client {
             .await?
             .ok_or(ClientError::AccountDataNotFound(account_id))?;
 
+        let account_seed = account_record.seed().copied();
         let account: Account = account_record.into();
 
         let data_store = ClientDataStore::new(self.store.clone());
 
         // Ensure code is loaded on MAST store
         data_store.mast_store().load_account_code(account.code());
+        let data_store = data_store.with_account_state(account, account_seed);
 
         for fpi_account in &foreign_account_inputs {
             data_store.mast_store().load_account_code(fpi_account.code());
@@ -776,7 +946,7 @@ impl Client {
 
         // New relevant input notes
         let mut new_input_notes = vec![];
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        let note_screener = self.note_screener();
 
         for note in notes_from_output(executed_tx.output_notes()) {
             let account_relevance = note_screener.check_relevance(note).await?;
@@ -972,6 +1142,17 @@ impl Client {
         &mut self,
         account_id: AccountId,
         transaction_request: &TransactionRequest,
+    ) -> Result<(), ClientError> {
+        let account: Account = self.try_get_account(account_id).await?.into();
+        self.validate_request_for_account(&account, transaction_request).await
+    }
+
+    /// Same as [`Client::validate_request`], but validates the request against the provided
+    /// account state instead of the one in the store.
+    async fn validate_request_for_account(
+        &mut self,
+        account: &Account,
+        transaction_request: &TransactionRequest,
     ) -> Result<(), ClientError> {
         let current_chain_tip =
             self.rpc_api.get_block_header_by_number(None, false).await?.0.block_num();
@@ -985,60 +1166,14 @@ impl Client {
             }
         }
 
-        let account: Account = self.try_get_account(account_id).await?.into();
-
         if account.is_faucet() {
             // TODO(SantiagoPittella): Add faucet validations.
             Ok(())
         } else {
-            self.validate_basic_account_request(transaction_request, &account).await
+            self.validate_basic_account_request(transaction_request, account).await
         }
     }
 
-    async fn get_valid_input_notes(
-        &self,
-        account_id: AccountId,
-        mut input_notes: InputNotes<InputNote>,
-        tx_args: TransactionArgs,
-    ) -> Result<InputNotes<InputNote>, ClientError> {
-        loop {
-            let data_store = ClientDataStore::new(self.store.clone());
-
-            let execution = NoteConsumptionChecker::new(&self.build_executor(&data_store)?)
-                .check_notes_consumability(
-                    account_id,
-                    self.store.get_sync_height().await?,
-                    input_notes.clone(),
-                    tx_args.clone(),
-                    Arc::new(DefaultSourceManager::default()),
-                )
-                .await?;
-
-            if let NoteAccountExecution::Failure { failed_note_id, .. } = execution {
-                let filtered_input_notes = InputNotes::new(
-                    input_notes.into_iter().filter(|note| note.id() != failed_note_id).collect(),
-                )
-                .expect("Created from a valid input notes list");
-
-                input_notes = filtered_input_notes;
-            } else {
-                break;
-            }
-        }
-
-        Ok(input_notes)
-    }
-
-    /// Retrieves the account interface for the specified account.
-    pub(crate) async fn get_account_interface(
-        &self,
-        account_id: AccountId,
-    ) -> Result<AccountInterface, ClientError> {
-        let account: Account = self.try_get_account(account_id).await?.into();
-
-        Ok(AccountInterface::from(&account))
-    }
-
     /// Returns foreign account inputs for the required foreign accounts specified by the
     /// transaction request.
     ///
@@ -1185,6 +1320,48 @@ fn collect_assets<'a>(
     (fungible_balance_map, non_fungible_set)
 }
 
+/// Returns the executor options used by default, with the protocol's cycle limits.
+pub(crate) fn default_exec_options(debug_mode: DebugMode) -> ExecutionOptions {
+    ExecutionOptions::new(
+        Some(MAX_TX_EXECUTION_CYCLES),
+        MIN_TX_EXECUTION_CYCLES,
+        false,
+        debug_mode.into(),
+    )
+    .expect("Default executor's options should always be valid")
+}
+
+/// Removes the input notes that can't be consumed by the account, one at a time, until the rest
+/// can be consumed.
+async fn get_valid_input_notes(
+    executor: &TransactionExecutor<'_, '_>,
+    account_id: AccountId,
+    block_num: BlockNumber,
+    mut input_notes: InputNotes<InputNote>,
+    tx_args: TransactionArgs,
+) -> Result<InputNotes<InputNote>, ClientError> {
+    loop {
+        let execution = NoteConsumptionChecker::new(executor)
+            .check_notes_consumability(
+                account_id,
+                block_num,
+                input_notes.clone(),
+                tx_args.clone(),
+                Arc::new(DefaultSourceManager::default()),
+            )
+            .await?;
+
+        let NoteAccountExecution::Failure { failed_note_id, .. } = execution else {
+            return Ok(input_notes);
+        };
+
+        input_notes = InputNotes::new(
+            input_notes.into_iter().filter(|note| note.id() != failed_note_id).collect(),
+        )
+        .expect("Created from a valid input notes list");
+    }
+}
+
 /// Extracts notes from [`OutputNotes`].
 /// Used for:
 /// - Checking the relevance of notes to save them as input notes.
@@ -1251,6 +1428,7 @@ mod test {
 
     use super::PaymentNoteDescription;
     use crate::{
+        keystore::KeyStore,
         tests::create_test_client,
         transaction::{TransactionRequestBuilder, TransactionResult},
     };
@@ -1269,7 +1447,7 @@ mod test {
 
         let secret_key = SecretKey::new();
         let pub_key = secret_key.public_key();
-        keystore.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).unwrap();
+        keystore.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).await.unwrap();
 
         let wallet_component = AccountComponent::compile(
             BASIC_WALLET_CODE,
@@ -0,0 +1,301 @@
+use alloc::{boxed::Box, collections::BTreeSet, sync::Arc, vec::Vec};
+
+use miden_objects::{
+    MastForest, Word,
+    account::{Account, AccountId},
+    assembly::DefaultSourceManager,
+    block::{BlockHeader, BlockNumber},
+    note::{NoteDetails, NoteLocation, NoteRecipient, NoteTag},
+    transaction::{PartialBlockchain, TransactionArgs, TransactionInputs},
+};
+use miden_tx::{
+    DataStore, DataStoreError, ExecutionOptions, MastForestStore, TransactionExecutor,
+    TransactionMastStore, TransactionProver,
+    auth::TransactionAuthenticator,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+
+use super::{
+    PendingTransactionRecord, PendingTransactionStage, PreparedTransaction, TransactionRequest,
+    TransactionResult, default_exec_options, get_valid_input_notes, validate_executed_transaction,
+};
+use crate::{Client, ClientError, DebugMode};
+
+// OFFLINE TRANSACTION
+// ================================================================================================
+
+/// Everything needed to execute a transaction without access to the client's store or to the
+/// network: the state of the executing account, the input notes along with their inclusion proofs,
+/// the partial blockchain that authenticates them and the transaction arguments (including the
+/// inputs of foreign accounts).
+///
+/// It's created on an online machine with [`Client::export_offline_transaction`], and executed and
+/// signed on an offline machine with an [`OfflineTransactionExecutor`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OfflineTransaction {
+    /// Account state, input notes and chain data the transaction gets executed against.
+    tx_inputs: TransactionInputs,
+    /// Arguments of the transaction, including the inputs of foreign accounts.
+    tx_args: TransactionArgs,
+    /// Recipients of the output notes the transaction is expected to create.
+    output_recipients: Vec<NoteRecipient>,
+    /// Notes that might be created in the future as a result of the transaction.
+    future_notes: Vec<(NoteDetails, NoteTag)>,
+    /// Whether input notes that can't be consumed should be removed before execution.
+    ignore_invalid_input_notes: bool,
+}
+
+impl OfflineTransaction {
+    /// Returns the ID of the account that executes the transaction.
+    pub fn account_id(&self) -> AccountId {
+        self.tx_inputs.account().id()
+    }
+
+    /// Returns the account state, input notes and chain data the transaction gets executed
+    /// against.
+    pub fn tx_inputs(&self) -> &TransactionInputs {
+        &self.tx_inputs
+    }
+
+    /// Returns the arguments of the transaction.
+    pub fn tx_args(&self) -> &TransactionArgs {
+        &self.tx_args
+    }
+}
+
+impl Serializable for OfflineTransaction {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.tx_inputs.write_into(target);
+        self.tx_args.write_into(target);
+        self.output_recipients.write_into(target);
+        self.future_notes.write_into(target);
+        self.ignore_invalid_input_notes.write_into(target);
+    }
+}
+
+impl Deserializable for OfflineTransaction {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let tx_inputs = TransactionInputs::read_from(source)?;
+        let tx_args = TransactionArgs::read_from(source)?;
+        let output_recipients = Vec::<NoteRecipient>::read_from(source)?;
+        let future_notes = Vec::<(NoteDetails, NoteTag)>::read_from(source)?;
+        let ignore_invalid_input_notes = bool::read_from(source)?;
+
+        Ok(Self {
+            tx_inputs,
+            tx_args,
+            output_recipients,
+            future_notes,
+            ignore_invalid_input_notes,
+        })
+    }
+}
+
+/// Offline transaction methods
+impl Client {
+    /// Gathers everything needed to execute the transaction request against the specified account
+    /// on a machine without access to the client's store or to the network, such as a cold
+    /// wallet.
+    ///
+    /// The returned [`OfflineTransaction`] can be executed and signed with an
+    /// [`OfflineTransactionExecutor`]. The resulting [`PendingTransactionRecord`] can then be
+    /// brought back and submitted with [`Client::import_pending_transaction`] and
+    /// [`Client::submit_pending_transaction`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Client::new_transaction`] for invalid requests.
+    pub async fn export_offline_transaction(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+    ) -> Result<OfflineTransaction, ClientError> {
+        let PreparedTransaction {
+            block_num,
+            input_notes,
+            tx_args,
+            output_recipients,
+            future_notes,
+            ignore_invalid_input_notes,
+            data_store,
+        } = self.prepare_transaction(account_id, transaction_request).await?;
+
+        // The reference block goes last, the rest are the blocks that need to be authenticated
+        // for the input notes
+        let mut block_refs: BTreeSet<BlockNumber> = input_notes
+            .iter()
+            .filter_map(|note| note.location().map(NoteLocation::block_num))
+            .collect();
+        block_refs.insert(block_num);
+
+        let (account, account_seed, block_header, partial_blockchain) =
+            data_store.get_transaction_inputs(account_id, block_refs).await?;
+
+        let tx_inputs = TransactionInputs::new(
+            account,
+            account_seed,
+            block_header,
+            partial_blockchain,
+            input_notes,
+        )
+        .map_err(ClientError::TransactionInputError)?;
+
+        Ok(OfflineTransaction {
+            tx_inputs,
+            tx_args,
+            output_recipients,
+            future_notes,
+            ignore_invalid_input_notes,
+        })
+    }
+}
+
+// OFFLINE TRANSACTION EXECUTOR
+// ================================================================================================
+
+/// Executes and signs [`OfflineTransaction`]s without a store or a connection to the network.
+pub struct OfflineTransactionExecutor {
+    /// Authenticator used to sign the transactions, usually a keystore.
+    authenticator: Arc<dyn TransactionAuthenticator>,
+    /// Options used by the transaction executor.
+    exec_options: ExecutionOptions,
+}
+
+impl OfflineTransactionExecutor {
+    /// Creates a new executor that signs transactions with the provided authenticator.
+    pub fn new(authenticator: Arc<dyn TransactionAuthenticator>) -> Self {
+        Self {
+            authenticator,
+            exec_options: default_exec_options(DebugMode::Disabled),
+        }
+    }
+
+    /// Sets whether the transactions are executed in debug mode.
+    #[must_use]
+    pub fn in_debug_mode(mut self, debug: DebugMode) -> Self {
+        self.exec_options = default_exec_options(debug);
+        self
+    }
+
+    /// Executes the offline transaction, signing it with the executor's authenticator.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::MissingOutputRecipients`] if the transaction didn't create the
+    ///   expected output notes.
+    /// - Returns a [`ClientError::TransactionExecutorError`] if the execution fails.
+    pub async fn execute(
+        &self,
+        offline_tx: OfflineTransaction,
+    ) -> Result<TransactionResult, ClientError> {
+        let OfflineTransaction {
+            tx_inputs,
+            tx_args,
+            output_recipients,
+            future_notes,
+            ignore_invalid_input_notes,
+        } = offline_tx;
+
+        let account_id = tx_inputs.account().id();
+        let block_num = tx_inputs.block_header().block_num();
+        let mut input_notes = tx_inputs.input_notes().clone();
+
+        let data_store = OfflineDataStore::new(tx_inputs, &tx_args);
+        let executor = TransactionExecutor::with_options(
+            &data_store,
+            Some(self.authenticator.as_ref()),
+            self.exec_options,
+        )?;
+
+        if ignore_invalid_input_notes {
+            input_notes = get_valid_input_notes(
+                &executor,
+                account_id,
+                block_num,
+                input_notes,
+                tx_args.clone(),
+            )
+            .await?;
+        }
+
+        let executed_transaction = executor
+            .execute_transaction(
+                account_id,
+                block_num,
+                input_notes,
+                tx_args,
+                Arc::new(DefaultSourceManager::default()),
+            )
+            .await?;
+
+        validate_executed_transaction(&executed_transaction, &output_recipients)?;
+
+        TransactionResult::new(executed_transaction, future_notes)
+    }
+
+    /// Executes and proves the offline transaction, returning a [`PendingTransactionRecord`] that
+    /// can be submitted by the client that exported the transaction.
+    pub async fn execute_and_prove(
+        &self,
+        offline_tx: OfflineTransaction,
+        tx_prover: Arc<dyn TransactionProver>,
+    ) -> Result<PendingTransactionRecord, ClientError> {
+        let tx_result = self.execute(offline_tx).await?;
+        let proven_transaction =
+            tx_prover.prove(tx_result.executed_transaction().clone().into()).await?;
+
+        Ok(PendingTransactionRecord::new(
+            tx_result,
+            PendingTransactionStage::Proven(proven_transaction),
+        ))
+    }
+}
+
+// OFFLINE DATA STORE
+// ================================================================================================
+
+/// A [`DataStore`] that serves the inputs of a single [`OfflineTransaction`].
+struct OfflineDataStore {
+    /// Account state and chain data the transaction gets executed against.
+    tx_inputs: TransactionInputs,
+    /// Store used to provide MAST nodes to the transaction executor.
+    transaction_mast_store: Arc<TransactionMastStore>,
+}
+
+impl OfflineDataStore {
+    fn new(tx_inputs: TransactionInputs, tx_args: &TransactionArgs) -> Self {
+        let transaction_mast_store = Arc::new(TransactionMastStore::new());
+        transaction_mast_store.load_account_code(tx_inputs.account().code());
+        for foreign_account in tx_args.foreign_account_inputs() {
+            transaction_mast_store.load_account_code(foreign_account.code());
+        }
+
+        Self { tx_inputs, transaction_mast_store }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl DataStore for OfflineDataStore {
+    async fn get_transaction_inputs(
+        &self,
+        account_id: AccountId,
+        _block_refs: BTreeSet<BlockNumber>,
+    ) -> Result<(Account, Option<Word>, BlockHeader, PartialBlockchain), DataStoreError> {
+        if account_id != self.tx_inputs.account().id() {
+            return Err(DataStoreError::AccountNotFound(account_id));
+        }
+
+        Ok((
+            self.tx_inputs.account().clone(),
+            self.tx_inputs.account_seed(),
+            self.tx_inputs.block_header().clone(),
+            self.tx_inputs.blockchain().clone(),
+        ))
+    }
+}
+
+impl MastForestStore for OfflineDataStore {
+    fn get(&self, procedure_hash: &Word) -> Option<Arc<MastForest>> {
+        self.transaction_mast_store.get(procedure_hash)
+    }
+}
@@ -0,0 +1,332 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use miden_objects::{account::AccountId, block::BlockNumber, transaction::TransactionId};
+use miden_tx::{
+    TransactionProver,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+};
+use tracing::info;
+
+use super::{ProvenTransaction, TransactionResult};
+use crate::{Client, ClientError};
+
+// PENDING TRANSACTION RECORD
+// ================================================================================================
+
+/// The stage a pending transaction has reached in the execute, prove and submit pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingTransactionStage {
+    /// The transaction was executed but hasn't been proven yet.
+    Executed,
+    /// The transaction was proven but hasn't been submitted to the network yet.
+    Proven(ProvenTransaction),
+    /// The transaction was submitted to the network at the specified block, but hasn't been
+    /// applied to the local store yet.
+    Submitted(BlockNumber),
+}
+
+impl core::fmt::Display for PendingTransactionStage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PendingTransactionStage::Executed => write!(f, "Executed"),
+            PendingTransactionStage::Proven(_) => write!(f, "Proven"),
+            PendingTransactionStage::Submitted(block_num) => {
+                write!(f, "Submitted (Block: {block_num})")
+            },
+        }
+    }
+}
+
+impl Serializable for PendingTransactionStage {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            PendingTransactionStage::Executed => target.write_u8(0),
+            PendingTransactionStage::Proven(proven_transaction) => {
+                target.write_u8(1);
+                proven_transaction.write_into(target);
+            },
+            PendingTransactionStage::Submitted(block_num) => {
+                target.write_u8(2);
+                block_num.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for PendingTransactionStage {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(PendingTransactionStage::Executed),
+            1 => Ok(PendingTransactionStage::Proven(ProvenTransaction::read_from(source)?)),
+            2 => Ok(PendingTransactionStage::Submitted(BlockNumber::read_from(source)?)),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid pending transaction stage tag {tag}"
+            ))),
+        }
+    }
+}
+
+/// A transaction that went through part of the execute, prove and submit pipeline and was
+/// persisted in the store, so the remaining steps can be resumed later or on another machine.
+///
+/// Pending transactions are removed from the store once they're applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingTransactionRecord {
+    /// Result of the transaction execution.
+    tx_result: TransactionResult,
+    /// Stage of the pipeline reached by the transaction.
+    stage: PendingTransactionStage,
+}
+
+impl PendingTransactionRecord {
+    /// Creates a new [`PendingTransactionRecord`] instance.
+    pub fn new(tx_result: TransactionResult, stage: PendingTransactionStage) -> Self {
+        Self { tx_result, stage }
+    }
+
+    /// Returns the ID of the transaction.
+    pub fn id(&self) -> TransactionId {
+        self.tx_result.executed_transaction().id()
+    }
+
+    /// Returns the ID of the account that executed the transaction.
+    pub fn account_id(&self) -> AccountId {
+        self.tx_result.executed_transaction().account_id()
+    }
+
+    /// Returns the result of the transaction execution.
+    pub fn tx_result(&self) -> &TransactionResult {
+        &self.tx_result
+    }
+
+    /// Returns the stage of the pipeline reached by the transaction.
+    pub fn stage(&self) -> &PendingTransactionStage {
+        &self.stage
+    }
+
+    /// Returns the proven transaction, if the transaction was proven and not yet submitted.
+    pub fn proven_transaction(&self) -> Option<&ProvenTransaction> {
+        match &self.stage {
+            PendingTransactionStage::Proven(proven_transaction) => Some(proven_transaction),
+            PendingTransactionStage::Executed | PendingTransactionStage::Submitted(_) => None,
+        }
+    }
+}
+
+impl Serializable for PendingTransactionRecord {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.tx_result.write_into(target);
+        self.stage.write_into(target);
+    }
+}
+
+impl Deserializable for PendingTransactionRecord {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let tx_result = TransactionResult::read_from(source)?;
+        let stage = PendingTransactionStage::read_from(source)?;
+
+        Ok(Self { tx_result, stage })
+    }
+}
+
+// TRANSACTION PIPELINE
+// ================================================================================================
+
+/// Methods to run the steps of a transaction's pipeline separately, persisting the progress in the
+/// store after each step. If the process stops midway, the transaction can be resumed from the
+/// last completed step with its ID.
+impl Client {
+    /// Retrieves all the pending transactions tracked by the store.
+    pub async fn get_pending_transactions(
+        &self,
+    ) -> Result<Vec<PendingTransactionRecord>, ClientError> {
+        self.store.get_pending_transactions().await.map_err(Into::into)
+    }
+
+    /// Retrieves the pending transaction with the specified ID. Returns `None` if the transaction
+    /// isn't pending.
+    pub async fn get_pending_transaction(
+        &self,
+        tx_id: TransactionId,
+    ) -> Result<Option<PendingTransactionRecord>, ClientError> {
+        self.store.get_pending_transaction(tx_id).await.map_err(Into::into)
+    }
+
+    /// Persists the result of a transaction execution as a pending transaction, so it can be
+    /// proven and submitted later with [`Client::prove_pending_transaction`] and
+    /// [`Client::submit_pending_transaction`].
+    ///
+    /// Returns the ID of the transaction.
+    pub async fn store_pending_transaction(
+        &self,
+        tx_result: TransactionResult,
+    ) -> Result<TransactionId, ClientError> {
+        let record = PendingTransactionRecord::new(tx_result, PendingTransactionStage::Executed);
+        let tx_id = record.id();
+
+        self.store.upsert_pending_transaction(&record).await?;
+
+        Ok(tx_id)
+    }
+
+    /// Imports a pending transaction that was exported from another client, for example to submit
+    /// a transaction that was executed and proven on a different machine.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The account that executed the transaction isn't tracked by the client.
+    /// - The record holds a proof for a different transaction.
+    /// - The transaction was already submitted and the record would move it back to an earlier
+    ///   stage.
+    pub async fn import_pending_transaction(
+        &self,
+        record: PendingTransactionRecord,
+    ) -> Result<TransactionId, ClientError> {
+        self.try_get_account_header(record.account_id()).await?;
+
+        if let Some(proven_transaction) = record.proven_transaction()
+            && proven_transaction.id() != record.id()
+        {
+            return Err(ClientError::PendingTransactionProofMismatch(
+                record.id(),
+                proven_transaction.id(),
+            ));
+        }
+
+        if let Some(existing) = self.get_pending_transaction(record.id()).await?
+            && matches!(existing.stage(), PendingTransactionStage::Submitted(_))
+            && !matches!(record.stage(), PendingTransactionStage::Submitted(_))
+        {
+            return Err(ClientError::PendingTransactionAlreadySubmitted(record.id()));
+        }
+
+        self.store.upsert_pending_transaction(&record).await?;
+
+        Ok(record.id())
+    }
+
+    /// Removes a pending transaction from the store without submitting it. Returns `true` if the
+    /// transaction was pending.
+    ///
+    /// Transactions that were already submitted can't be discarded, as they may still get
+    /// included in a block.
+    pub async fn discard_pending_transaction(
+        &self,
+        tx_id: TransactionId,
+    ) -> Result<bool, ClientError> {
+        let Some(record) = self.get_pending_transaction(tx_id).await? else {
+            return Ok(false);
+        };
+
+        if let PendingTransactionStage::Submitted(_) = record.stage() {
+            return Err(ClientError::PendingTransactionAlreadySubmitted(tx_id));
+        }
+
+        self.store.remove_pending_transaction(tx_id).await.map_err(Into::into)
+    }
+
+    /// Proves a pending transaction using the client's prover and persists the proof in the
+    /// store. If the transaction was already proven, the stored proof is returned.
+    pub async fn prove_pending_transaction(
+        &mut self,
+        tx_id: TransactionId,
+    ) -> Result<ProvenTransaction, ClientError> {
+        self.prove_pending_transaction_with_prover(tx_id, self.tx_prover.clone()).await
+    }
+
+    /// Proves a pending transaction using the provided prover and persists the proof in the store.
+    /// If the transaction was already proven, the stored proof is returned.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ClientError::PendingTransactionNotFound`] if the transaction isn't pending.
+    /// - Returns [`ClientError::PendingTransactionAlreadySubmitted`] if the transaction was already
+    ///   submitted.
+    pub async fn prove_pending_transaction_with_prover(
+        &mut self,
+        tx_id: TransactionId,
+        tx_prover: Arc<dyn TransactionProver>,
+    ) -> Result<ProvenTransaction, ClientError> {
+        let record = self.try_get_pending_transaction(tx_id).await?;
+
+        match record.stage {
+            PendingTransactionStage::Executed => {
+                let proven_transaction =
+                    self.prove_transaction(&record.tx_result, tx_prover).await?;
+
+                let record = PendingTransactionRecord::new(
+                    record.tx_result,
+                    PendingTransactionStage::Proven(proven_transaction.clone()),
+                );
+                self.store.upsert_pending_transaction(&record).await?;
+
+                Ok(proven_transaction)
+            },
+            PendingTransactionStage::Proven(proven_transaction) => Ok(proven_transaction),
+            PendingTransactionStage::Submitted(_) => {
+                Err(ClientError::PendingTransactionAlreadySubmitted(tx_id))
+            },
+        }
+    }
+
+    /// Submits a pending transaction to the network and applies it to the local store, removing
+    /// it from the pending transactions.
+    ///
+    /// Transactions that weren't proven yet are proven first with the client's prover. If the
+    /// transaction was already submitted, it's only applied to the store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::PendingTransactionNotFound`] if the transaction isn't pending.
+    pub async fn submit_pending_transaction(
+        &mut self,
+        tx_id: TransactionId,
+    ) -> Result<(), ClientError> {
+        let record = self.try_get_pending_transaction(tx_id).await?;
+
+        let submission_height = match record.stage {
+            PendingTransactionStage::Executed | PendingTransactionStage::Proven(_) => {
+                let proven_transaction = self.prove_pending_transaction(tx_id).await?;
+                let submission_height = self.submit_proven_transaction(proven_transaction).await?;
+
+                // Persist the submission so that, if applying the transaction fails, resuming it
+                // doesn't submit it again
+                let submitted_record = PendingTransactionRecord::new(
+                    record.tx_result.clone(),
+                    PendingTransactionStage::Submitted(submission_height),
+                );
+                self.store.upsert_pending_transaction(&submitted_record).await?;
+
+                submission_height
+            },
+            PendingTransactionStage::Submitted(submission_height) => {
+                info!("Transaction was already submitted, applying it to the local store...");
+                submission_height
+            },
+        };
+
+        let created_notes = record.tx_result.created_notes().clone();
+        self.apply_transaction(submission_height, record.tx_result).await?;
+        self.send_created_private_notes(created_notes.iter(), submission_height).await;
+
+        Ok(())
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Retrieves the pending transaction with the specified ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::PendingTransactionNotFound`] if the transaction isn't pending.
+    async fn try_get_pending_transaction(
+        &self,
+        tx_id: TransactionId,
+    ) -> Result<PendingTransactionRecord, ClientError> {
+        self.get_pending_transaction(tx_id)
+            .await?
+            .ok_or(ClientError::PendingTransactionNotFound(tx_id))
+    }
+}
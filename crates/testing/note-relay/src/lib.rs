@@ -0,0 +1,184 @@
+//! A minimal relay server for delivering private notes between Miden clients in tests.
+//!
+//! The relay speaks the protocol of [`TcpRelayTransport`]: each connection carries a single
+//! [`RelayRequest`] and its [`RelayResponse`], framed as a little-endian `u32` length followed by
+//! the serialized message. Notes are kept in memory in the order they were received, and the
+//! cursor returned to clients is the number of notes held by the relay.
+//!
+//! [`TcpRelayTransport`]: miden_client::note_transport::TcpRelayTransport
+
+use std::{
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, RwLock},
+    thread::{self, JoinHandle},
+};
+
+use miden_client::{
+    note_transport::{EncryptedNote, RelayRequest, RelayResponse},
+    utils::{Deserializable, Serializable},
+};
+
+/// The port the relay listens on by default.
+pub const DEFAULT_RELAY_PORT: u16 = 57292;
+
+/// Maximum size in bytes of a request accepted by the relay.
+const MAX_REQUEST_SIZE: usize = 16 * 1024 * 1024;
+
+// NOTE RELAY
+// ================================================================================================
+
+/// An in-memory relay of encrypted private notes.
+pub struct NoteRelay {
+    listener: TcpListener,
+    notes: Arc<RwLock<Vec<EncryptedNote>>>,
+}
+
+impl NoteRelay {
+    /// Binds the relay to the provided address. Use port `0` to let the OS pick a free port.
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            notes: Arc::new(RwLock::new(Vec::new())),
+        })
+    }
+
+    /// Returns the address the relay is listening on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves requests until the listener fails. Each connection is handled in its own thread.
+    pub fn serve(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let notes = self.notes.clone();
+            thread::spawn(move || {
+                if let Err(err) = handle_connection(stream, &notes) {
+                    eprintln!("Failed to handle relay connection: {err}");
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Serves requests in a background thread.
+    pub fn spawn(self) -> JoinHandle<io::Result<()>> {
+        thread::spawn(move || self.serve())
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+fn handle_connection(mut stream: TcpStream, notes: &RwLock<Vec<EncryptedNote>>) -> io::Result<()> {
+    let mut request_len = [0u8; 4];
+    stream.read_exact(&mut request_len)?;
+    let request_len = u32::from_le_bytes(request_len) as usize;
+
+    let response = if request_len > MAX_REQUEST_SIZE {
+        RelayResponse::Error(format!("request exceeds {MAX_REQUEST_SIZE} bytes"))
+    } else {
+        let mut request = vec![0u8; request_len];
+        stream.read_exact(&mut request)?;
+
+        match RelayRequest::read_from_bytes(&request) {
+            Ok(request) => handle_request(request, notes),
+            Err(err) => RelayResponse::Error(format!("invalid request: {err}")),
+        }
+    };
+
+    let response = response.to_bytes();
+    let response_len = u32::try_from(response.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "response is too large"))?;
+    stream.write_all(&response_len.to_le_bytes())?;
+    stream.write_all(&response)
+}
+
+fn handle_request(request: RelayRequest, notes: &RwLock<Vec<EncryptedNote>>) -> RelayResponse {
+    match request {
+        RelayRequest::Send(note) => {
+            notes.write().expect("relay lock shouldn't be poisoned").push(note);
+            RelayResponse::Stored
+        },
+        RelayRequest::Fetch { tags, cursor } => {
+            let notes = notes.read().expect("relay lock shouldn't be poisoned");
+            let start = usize::try_from(cursor).unwrap_or(usize::MAX).min(notes.len());
+            let fetched_notes = notes[start..]
+                .iter()
+                .filter(|note| tags.contains(&note.tag()))
+                .cloned()
+                .collect();
+
+            RelayResponse::Notes {
+                notes: fetched_notes,
+                cursor: notes.len() as u64,
+            }
+        },
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_client::{
+        Felt, Word,
+        account::AccountId,
+        crypto::RpoRandomCoin,
+        note::{BlockNumber, NoteTag, NoteType, create_p2id_note},
+        note_transport::{NoteChannelKey, NoteTransport, NoteTransportError, TcpRelayTransport},
+        testing::account_id::{
+            ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+            ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
+        },
+    };
+
+    use super::NoteRelay;
+
+    #[tokio::test]
+    async fn notes_are_relayed_by_tag() {
+        let relay = NoteRelay::bind("127.0.0.1:0").unwrap();
+        let transport = TcpRelayTransport::new(relay.local_addr().unwrap().to_string());
+        relay.spawn();
+
+        let sender = AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+        let target =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE).unwrap();
+        let note = create_p2id_note(
+            sender,
+            target,
+            vec![],
+            NoteType::Private,
+            Felt::new(0),
+            &mut RpoRandomCoin::new(Word::default()),
+        )
+        .unwrap();
+        let tag = note.metadata().tag();
+
+        let mut rng = rand::rng();
+        let key = NoteChannelKey::with_rng(&mut rng);
+        transport
+            .send_note(key.encrypt(&note, BlockNumber::from(0), &mut rng).unwrap())
+            .await
+            .unwrap();
+
+        let (notes, cursor) = transport.fetch_notes(&[tag], 0).await.unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].id(), note.id());
+        assert!(key.decrypt(&notes[0]).is_ok());
+
+        // Notes are only returned once per cursor and only for the requested tags
+        assert!(transport.fetch_notes(&[tag], cursor).await.unwrap().0.is_empty());
+        assert!(transport.fetch_notes(&[NoteTag::from(1)], 0).await.unwrap().0.is_empty());
+
+        // Responses larger than the maximum response size are rejected
+        let transport = transport.max_response_size(16);
+        assert!(matches!(
+            transport.fetch_notes(&[tag], 0).await,
+            Err(NoteTransportError::TransportError(_))
+        ));
+    }
+}
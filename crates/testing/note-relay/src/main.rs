@@ -0,0 +1,14 @@
+use std::env;
+
+use testing_note_relay::{DEFAULT_RELAY_PORT, NoteRelay};
+
+fn main() -> anyhow::Result<()> {
+    let address = env::args().nth(1).unwrap_or_else(|| format!("127.0.0.1:{DEFAULT_RELAY_PORT}"));
+
+    let relay = NoteRelay::bind(&address)?;
+    println!("Note relay listening on {}", relay.local_addr()?);
+
+    relay.serve()?;
+
+    Ok(())
+}
@@ -56,4 +56,17 @@ impl WebClient {
 
         Ok(export)
     }
+
+    /// Returns a portable snapshot of the store, which can be restored with `importSnapshot` or by
+    /// the CLI's `restore` command. Keys in the keystore are not included.
+    #[wasm_bindgen(js_name = "exportSnapshot")]
+    pub async fn export_snapshot(&mut self) -> Result<Vec<u8>, JsValue> {
+        let client = self.get_mut_inner().ok_or(JsValue::from_str("Client not initialized"))?;
+        let snapshot = client
+            .export_snapshot()
+            .await
+            .map_err(|err| js_error_with_context(err, "failed to export snapshot"))?;
+
+        Ok(snapshot.to_bytes())
+    }
 }
@@ -1,4 +1,4 @@
-use miden_client::auth::AuthSecretKey;
+use miden_client::{auth::AuthSecretKey, keystore::KeyStore, store::StoreSnapshot};
 use miden_objects::{
     account::{AccountFile, AccountId as NativeAccountId},
     note::NoteFile,
@@ -120,4 +120,18 @@ impl WebClient {
 
         Ok(JsValue::from_str("Store imported successfully"))
     }
+
+    /// Restores a snapshot written by `exportSnapshot` or the CLI's `backup` command. The store
+    /// needs to be empty.
+    #[wasm_bindgen(js_name = "importSnapshot")]
+    pub async fn import_snapshot(&mut self, snapshot_bytes: Vec<u8>) -> Result<(), JsValue> {
+        let client = self.get_mut_inner().ok_or(JsValue::from_str("Client not initialized"))?;
+        let snapshot =
+            StoreSnapshot::read_from_bytes(&snapshot_bytes).map_err(|err| err.to_string())?;
+
+        client
+            .import_snapshot(&snapshot)
+            .await
+            .map_err(|err| js_error_with_context(err, "failed to import snapshot"))
+    }
 }
@@ -1,22 +1,90 @@
-use miden_client::store::NoteFilter as NativeNoteFilter;
+use miden_client::store::{
+    NoteFilter as NativeNoteFilter, NoteSort as NativeNoteSort, NoteSortKey as NativeNoteSortKey,
+};
 use wasm_bindgen::prelude::*;
 
-use super::note_id::NoteId;
+use super::{account_id::AccountId, note_id::NoteId, note_tag::NoteTag, word::Word};
 
 // TODO: Add nullfiier support
 
 #[derive(Clone)]
 #[wasm_bindgen]
-pub struct NoteFilter {
-    note_type: NoteFilterTypes,
-    note_ids: Option<Vec<NoteId>>,
-}
+pub struct NoteFilter(NativeNoteFilter);
 
 #[wasm_bindgen]
 impl NoteFilter {
     #[wasm_bindgen(constructor)]
     pub fn new(note_type: NoteFilterTypes, note_ids: Option<Vec<NoteId>>) -> NoteFilter {
-        NoteFilter { note_type, note_ids }
+        let filter = match note_type {
+            NoteFilterTypes::All => NativeNoteFilter::All,
+            NoteFilterTypes::Consumed => NativeNoteFilter::Consumed,
+            NoteFilterTypes::Committed => NativeNoteFilter::Committed,
+            NoteFilterTypes::Expected => NativeNoteFilter::Expected,
+            NoteFilterTypes::Processing => NativeNoteFilter::Processing,
+            NoteFilterTypes::List => {
+                let note_ids =
+                    note_ids.unwrap_or_else(|| panic!("Note IDs required for List filter"));
+                NativeNoteFilter::List(note_ids.iter().map(Into::into).collect())
+            },
+            NoteFilterTypes::Unique => {
+                let note_ids =
+                    note_ids.unwrap_or_else(|| panic!("Note ID required for Unique filter"));
+
+                assert!(note_ids.len() == 1, "Only one Note ID can be provided");
+
+                NativeNoteFilter::Unique(note_ids.first().unwrap().into())
+            },
+            NoteFilterTypes::Nullifiers => NativeNoteFilter::Nullifiers(vec![]),
+            NoteFilterTypes::Unverified => NativeNoteFilter::Unverified,
+        };
+
+        NoteFilter(filter)
+    }
+
+    /// Filters the input notes consumed, or being consumed, by a local transaction of the account.
+    pub fn consumer(account_id: &AccountId) -> NoteFilter {
+        NoteFilter(NativeNoteFilter::Consumer(account_id.into()))
+    }
+
+    /// Filters the P2ID and P2IDE notes targeting the account.
+    pub fn target(account_id: &AccountId) -> NoteFilter {
+        NoteFilter(NativeNoteFilter::Target(account_id.into()))
+    }
+
+    pub fn sender(account_id: &AccountId) -> NoteFilter {
+        NoteFilter(NativeNoteFilter::Sender(account_id.into()))
+    }
+
+    pub fn tag(tag: &NoteTag) -> NoteFilter {
+        NoteFilter(NativeNoteFilter::Tag(tag.into()))
+    }
+
+    /// Filters the notes holding assets issued by the faucet.
+    pub fn faucet(faucet_id: &AccountId) -> NoteFilter {
+        NoteFilter(NativeNoteFilter::Faucet(faucet_id.into()))
+    }
+
+    #[wasm_bindgen(js_name = "scriptRoot")]
+    pub fn script_root(script_root: &Word) -> NoteFilter {
+        NoteFilter(NativeNoteFilter::ScriptRoot(script_root.into()))
+    }
+
+    /// Filters the input notes created or imported between the timestamps (in seconds since the
+    /// Unix epoch), both ends included.
+    #[wasm_bindgen(js_name = "createdBetween")]
+    pub fn created_between(from: u64, to: u64) -> NoteFilter {
+        NoteFilter(NativeNoteFilter::CreatedBetween { from, to })
+    }
+
+    /// Filters the notes included in a block within the range, both ends included.
+    #[wasm_bindgen(js_name = "blockRange")]
+    pub fn block_range(from: u32, to: u32) -> NoteFilter {
+        NoteFilter(NativeNoteFilter::BlockRange { from: from.into(), to: to.into() })
+    }
+
+    /// Combines the filters, so that only the notes matching all of them are returned.
+    pub fn and(filters: Vec<NoteFilter>) -> NoteFilter {
+        NoteFilter(NativeNoteFilter::And(filters.into_iter().map(Into::into).collect()))
     }
 }
 
@@ -34,63 +102,59 @@ pub enum NoteFilterTypes {
     Unverified,
 }
 
+// NOTE SORTING
+// ================================================================================================
+
+#[derive(Clone, Copy)]
+#[wasm_bindgen]
+pub enum NoteSortKey {
+    Id,
+    BlockNum,
+    CreatedAt,
+}
+
+#[derive(Clone, Copy)]
+#[wasm_bindgen]
+pub struct NoteSort(NativeNoteSort);
+
+#[wasm_bindgen]
+impl NoteSort {
+    pub fn ascending(key: NoteSortKey) -> NoteSort {
+        NoteSort(NativeNoteSort::ascending(key.into()))
+    }
+
+    pub fn descending(key: NoteSortKey) -> NoteSort {
+        NoteSort(NativeNoteSort::descending(key.into()))
+    }
+}
+
 // CONVERSIONS
 // ================================================================================================
 
 impl From<NoteFilter> for NativeNoteFilter {
     fn from(filter: NoteFilter) -> Self {
-        match filter.note_type {
-            NoteFilterTypes::All => NativeNoteFilter::All,
-            NoteFilterTypes::Consumed => NativeNoteFilter::Consumed,
-            NoteFilterTypes::Committed => NativeNoteFilter::Committed,
-            NoteFilterTypes::Expected => NativeNoteFilter::Expected,
-            NoteFilterTypes::Processing => NativeNoteFilter::Processing,
-            NoteFilterTypes::List => {
-                let note_ids =
-                    filter.note_ids.unwrap_or_else(|| panic!("Note IDs required for List filter"));
-                NativeNoteFilter::List(note_ids.iter().map(Into::into).collect())
-            },
-            NoteFilterTypes::Unique => {
-                let note_ids =
-                    filter.note_ids.unwrap_or_else(|| panic!("Note ID required for Unique filter"));
-
-                assert!(note_ids.len() == 1, "Only one Note ID can be provided");
-
-                NativeNoteFilter::Unique(note_ids.first().unwrap().into())
-            },
-            NoteFilterTypes::Nullifiers => NativeNoteFilter::Nullifiers(vec![]),
-            NoteFilterTypes::Unverified => NativeNoteFilter::Unverified,
-        }
+        filter.0
     }
 }
 
 impl From<&NoteFilter> for NativeNoteFilter {
     fn from(filter: &NoteFilter) -> Self {
-        match filter.note_type {
-            NoteFilterTypes::All => NativeNoteFilter::All,
-            NoteFilterTypes::Consumed => NativeNoteFilter::Consumed,
-            NoteFilterTypes::Committed => NativeNoteFilter::Committed,
-            NoteFilterTypes::Expected => NativeNoteFilter::Expected,
-            NoteFilterTypes::Processing => NativeNoteFilter::Processing,
-            NoteFilterTypes::List => {
-                let note_ids = filter
-                    .note_ids
-                    .clone()
-                    .unwrap_or_else(|| panic!("Note IDs required for List filter"));
-                NativeNoteFilter::List(note_ids.iter().map(Into::into).collect())
-            },
-            NoteFilterTypes::Unique => {
-                let note_ids = filter
-                    .note_ids
-                    .clone()
-                    .unwrap_or_else(|| panic!("Note ID required for Unique filter"));
-
-                assert!(note_ids.len() == 1, "Only one Note ID can be provided");
+        filter.0.clone()
+    }
+}
 
-                NativeNoteFilter::Unique(note_ids.first().unwrap().into())
-            },
-            NoteFilterTypes::Nullifiers => NativeNoteFilter::Nullifiers(vec![]),
-            NoteFilterTypes::Unverified => NativeNoteFilter::Unverified,
+impl From<NoteSortKey> for NativeNoteSortKey {
+    fn from(key: NoteSortKey) -> Self {
+        match key {
+            NoteSortKey::Id => NativeNoteSortKey::Id,
+            NoteSortKey::BlockNum => NativeNoteSortKey::BlockNum,
+            NoteSortKey::CreatedAt => NativeNoteSortKey::CreatedAt,
         }
     }
 }
+
+impl From<NoteSort> for NativeNoteSort {
+    fn from(sort: NoteSort) -> Self {
+        sort.0
+    }
+}
@@ -1,6 +1,10 @@
-use miden_client::store::TransactionFilter as NativeTransactionFilter;
+use miden_client::{
+    store::TransactionFilter as NativeTransactionFilter, transaction::DiscardCause,
+};
 use wasm_bindgen::prelude::*;
 
+use super::{account_id::AccountId, note_id::NoteId, transaction_id::TransactionId, word::Word};
+
 #[derive(Clone)]
 #[wasm_bindgen]
 pub struct TransactionFilter(NativeTransactionFilter);
@@ -14,6 +18,60 @@ impl TransactionFilter {
     pub fn uncommitted() -> TransactionFilter {
         TransactionFilter(NativeTransactionFilter::Uncommitted)
     }
+
+    pub fn ids(ids: Vec<TransactionId>) -> TransactionFilter {
+        TransactionFilter(NativeTransactionFilter::Ids(ids.iter().map(Into::into).collect()))
+    }
+
+    #[wasm_bindgen(js_name = "expiredBefore")]
+    pub fn expired_before(block_num: u32) -> TransactionFilter {
+        TransactionFilter(NativeTransactionFilter::ExpiredBefore(block_num.into()))
+    }
+
+    #[wasm_bindgen(js_name = "inFlight")]
+    pub fn in_flight() -> TransactionFilter {
+        TransactionFilter(NativeTransactionFilter::InFlight)
+    }
+
+    pub fn committed() -> TransactionFilter {
+        TransactionFilter(NativeTransactionFilter::Committed)
+    }
+
+    /// Filters discarded transactions. If a cause is provided ("Expired", "InputConsumed",
+    /// "DiscardedInitialState" or "Stale"), only the transactions discarded for it are returned.
+    pub fn discarded(cause: Option<String>) -> Result<TransactionFilter, JsValue> {
+        let cause = cause
+            .map(|cause| DiscardCause::from_string(&cause))
+            .transpose()
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+        Ok(TransactionFilter(NativeTransactionFilter::Discarded(cause)))
+    }
+
+    pub fn account(account_id: &AccountId) -> TransactionFilter {
+        TransactionFilter(NativeTransactionFilter::Account(account_id.into()))
+    }
+
+    #[wasm_bindgen(js_name = "blockRange")]
+    pub fn block_range(from: u32, to: u32) -> TransactionFilter {
+        TransactionFilter(NativeTransactionFilter::BlockRange { from: from.into(), to: to.into() })
+    }
+
+    pub fn note(note_id: &NoteId) -> TransactionFilter {
+        TransactionFilter(NativeTransactionFilter::Note(note_id.into()))
+    }
+
+    #[wasm_bindgen(js_name = "scriptRoot")]
+    pub fn script_root(script_root: &Word) -> TransactionFilter {
+        TransactionFilter(NativeTransactionFilter::ScriptRoot(script_root.into()))
+    }
+
+    /// Combines the filters, so that only the transactions matching all of them are returned.
+    pub fn and(filters: Vec<TransactionFilter>) -> TransactionFilter {
+        TransactionFilter(NativeTransactionFilter::And(
+            filters.into_iter().map(Into::into).collect(),
+        ))
+    }
 }
 
 // CONVERSIONS
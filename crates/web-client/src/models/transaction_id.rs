@@ -43,3 +43,9 @@ impl From<&NativeTransactionId> for TransactionId {
         TransactionId(*native_id)
     }
 }
+
+impl From<&TransactionId> for NativeTransactionId {
+    fn from(id: &TransactionId) -> Self {
+        id.0
+    }
+}
@@ -1,4 +1,7 @@
-use miden_client::{Word, store::OutputNoteRecord};
+use miden_client::{
+    Word,
+    store::{InputNoteRecord as NativeInputNoteRecord, OutputNoteRecord, Page},
+};
 use miden_objects::note::{NoteId, NoteScript as NativeNoteScript};
 use wasm_bindgen::prelude::*;
 
@@ -6,22 +9,38 @@ use super::models::note_script::NoteScript;
 use crate::{
     WebClient, js_error_with_context,
     models::{
-        account_id::AccountId, consumable_note_record::ConsumableNoteRecord,
-        input_note_record::InputNoteRecord, note_filter::NoteFilter,
+        account_id::AccountId,
+        consumable_note_record::ConsumableNoteRecord,
+        input_note_record::InputNoteRecord,
+        note_filter::{NoteFilter, NoteSort},
     },
 };
 
 #[wasm_bindgen]
 impl WebClient {
+    /// Returns the input notes matching the filter. If a sort order, an offset or a limit is
+    /// provided, the notes are sorted (by ID unless another order is provided) and only that page
+    /// is returned.
     #[wasm_bindgen(js_name = "getInputNotes")]
     pub async fn get_input_notes(
         &mut self,
         filter: NoteFilter,
+        sort: Option<NoteSort>,
+        offset: Option<u32>,
+        limit: Option<u32>,
     ) -> Result<Vec<InputNoteRecord>, JsValue> {
         if let Some(client) = self.get_mut_inner() {
-            let result = client
-                .get_input_notes(filter.into())
-                .await
+            let result: Vec<NativeInputNoteRecord> =
+                if sort.is_none() && offset.is_none() && limit.is_none() {
+                    client.get_input_notes(filter.into()).await
+                } else {
+                    let page = Page::new(
+                        offset.unwrap_or(0).try_into().unwrap_or(usize::MAX),
+                        limit.map_or(usize::MAX, |limit| limit.try_into().unwrap_or(usize::MAX)),
+                    );
+                    let sort = sort.map(Into::into).unwrap_or_default();
+                    client.get_input_notes_page(filter.into(), sort, page).await
+                }
                 .map_err(|err| js_error_with_context(err, "failed to get input notes"))?;
             Ok(result.into_iter().map(Into::into).collect())
         } else {
@@ -49,12 +68,28 @@ impl WebClient {
         }
     }
 
+    /// Returns the IDs of the output notes matching the filter. Sorting and pagination work as in
+    /// `getInputNotes`.
     #[wasm_bindgen(js_name = "getOutputNotes")]
-    pub async fn get_output_notes(&mut self, filter: NoteFilter) -> Result<JsValue, JsValue> {
+    pub async fn get_output_notes(
+        &mut self,
+        filter: NoteFilter,
+        sort: Option<NoteSort>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<JsValue, JsValue> {
         if let Some(client) = self.get_mut_inner() {
-            let notes: Vec<OutputNoteRecord> = client
-                .get_output_notes(filter.into())
-                .await
+            let notes: Vec<OutputNoteRecord> =
+                if sort.is_none() && offset.is_none() && limit.is_none() {
+                    client.get_output_notes(filter.into()).await
+                } else {
+                    let page = Page::new(
+                        offset.unwrap_or(0).try_into().unwrap_or(usize::MAX),
+                        limit.map_or(usize::MAX, |limit| limit.try_into().unwrap_or(usize::MAX)),
+                    );
+                    let sort = sort.map(Into::into).unwrap_or_default();
+                    client.get_output_notes_page(filter.into(), sort, page).await
+                }
                 .map_err(|err| js_error_with_context(err, "failed to get output notes"))?;
             let note_ids = notes.iter().map(|note| note.id().to_string()).collect::<Vec<String>>();
 
@@ -1,5 +1,8 @@
-use miden_client::transaction::{
-    TransactionRecord as NativeTransactionRecord, TransactionScript as NativeTransactionScript,
+use miden_client::{
+    store::Page,
+    transaction::{
+        TransactionRecord as NativeTransactionRecord, TransactionScript as NativeTransactionScript,
+    },
 };
 use wasm_bindgen::prelude::*;
 
@@ -11,15 +14,26 @@ use crate::{WebClient, js_error_with_context};
 
 #[wasm_bindgen]
 impl WebClient {
+    /// Returns the transactions matching the filter. If an offset or a limit is provided, the
+    /// transactions are sorted by execution block number and ID, and only that page is returned.
     #[wasm_bindgen(js_name = "getTransactions")]
     pub async fn get_transactions(
         &mut self,
         transaction_filter: TransactionFilter,
+        offset: Option<u32>,
+        limit: Option<u32>,
     ) -> Result<Vec<TransactionRecord>, JsValue> {
         if let Some(client) = self.get_mut_inner() {
-            let transaction_records: Vec<NativeTransactionRecord> = client
-                .get_transactions(transaction_filter.into())
-                .await
+            let transaction_records: Vec<NativeTransactionRecord> =
+                if offset.is_none() && limit.is_none() {
+                    client.get_transactions(transaction_filter.into()).await
+                } else {
+                    let page = Page::new(
+                        offset.unwrap_or(0).try_into().unwrap_or(usize::MAX),
+                        limit.map_or(usize::MAX, |limit| limit.try_into().unwrap_or(usize::MAX)),
+                    );
+                    client.get_transactions_page(transaction_filter.into(), page).await
+                }
                 .map_err(|err| js_error_with_context(err, "failed to get transactions"))?;
 
             Ok(transaction_records.into_iter().map(Into::into).collect())
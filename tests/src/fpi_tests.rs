@@ -2,6 +2,7 @@ use miden_client::{
     Felt, Word,
     account::{Account, StorageSlot},
     auth::AuthSecretKey,
+    keystore::KeyStore,
     rpc::domain::account::{AccountStorageRequirements, StorageMapKey},
     testing::common::*,
     transaction::{ForeignAccount, TransactionKernel, TransactionRequestBuilder},
@@ -379,7 +380,7 @@ async fn deploy_foreign_account(
         foreign_account_with_code(storage_mode, code);
     let foreign_account_id = foreign_account.id();
 
-    keystore.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).unwrap();
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).await.unwrap();
     client.add_account(&foreign_account, Some(foreign_seed), false).await.unwrap();
 
     let deployment_tx_script = TransactionScript::compile(
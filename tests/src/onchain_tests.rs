@@ -1,6 +1,7 @@
 use miden_client::{
     account::build_wallet_id,
     auth::AuthSecretKey,
+    keystore::KeyStore,
     store::{InputNoteState, NoteFilter},
     testing::common::*,
     transaction::{PaymentNoteDescription, TransactionRequestBuilder},
@@ -166,7 +167,7 @@ async fn onchain_accounts() {
     let (_, status) = client_1.get_account_header_by_id(faucet_account_id).await.unwrap().unwrap();
     let faucet_seed = status.seed().cloned();
 
-    keystore_2.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).unwrap();
+    keystore_2.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).await.unwrap();
     client_2.add_account(&faucet_account_header, faucet_seed, false).await.unwrap();
 
     // First Mint necessary token
@@ -357,7 +358,7 @@ async fn import_account_by_id() {
             .unwrap();
     assert_eq!(built_wallet_id, first_regular_account.id());
     client_2.import_account_by_id(built_wallet_id).await.unwrap();
-    keystore_2.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).unwrap();
+    keystore_2.add_key(&AuthSecretKey::RpoFalcon512(secret_key)).await.unwrap();
 
     let original_account = client_1.get_account(first_regular_account.id()).await.unwrap().unwrap();
     let imported_account = client_2.get_account(first_regular_account.id()).await.unwrap().unwrap();